@@ -1,5 +1,19 @@
+pub(crate) mod broker;
 pub(crate) mod codec;
+pub(crate) mod config;
+pub(crate) mod coordinator;
+pub(crate) mod metadata;
+pub(crate) mod metrics;
 pub(crate) mod proto;
+pub(crate) mod quota;
+pub(crate) mod security;
+pub(crate) mod storage;
+// Wire schemas mirror the full Kafka message definitions, not every field is consumed.
+#[allow(dead_code)]
 pub(crate) mod types;
+#[allow(dead_code)]
+pub(crate) mod record;
+#[allow(dead_code)]
 pub(crate) mod request;
+#[allow(dead_code)]
 pub(crate) mod response;
@@ -0,0 +1,436 @@
+mod add_offsets_to_txn;
+mod add_partitions_to_txn;
+mod alter_client_quotas;
+mod alter_configs;
+mod broker_heartbeat;
+mod broker_registration;
+mod consumer_group_describe;
+mod consumer_group_heartbeat;
+mod create_acls;
+mod delete_acls;
+mod delete_groups;
+mod delete_records;
+mod describe_acls;
+mod describe_client_quotas;
+mod describe_configs;
+mod describe_groups;
+mod describe_producers;
+mod describe_topic_partitions;
+mod describe_transactions;
+mod end_txn;
+mod find_coordinator;
+mod heartbeat;
+mod incremental_alter_configs;
+mod init_producer_id;
+mod join_group;
+mod leave_group;
+mod list_groups;
+mod list_offsets;
+mod list_transactions;
+mod offset_commit;
+mod offset_delete;
+mod offset_fetch;
+mod offset_for_leader_epoch;
+mod request_channel;
+pub(crate) use request_channel::*;
+mod request_log;
+pub(crate) use request_log::*;
+mod sasl_authenticate;
+mod sasl_handshake;
+mod sync_group;
+mod txn_offset_commit;
+
+use crate::kafka::config::{ConfigError, DynamicConfigs, ServerConfig};
+use crate::kafka::coordinator::{
+    GroupCoordinator, TransactionCoordinator, CONSUMER_OFFSETS_TOPIC, TRANSACTION_STATE_TOPIC,
+};
+use crate::kafka::metadata::{ClusterControl, ConfigResourceType, MetadataImage, CLUSTER_METADATA_TOPIC};
+use crate::kafka::metrics::Metrics;
+use crate::kafka::proto::ApiVersionsResponse;
+use crate::kafka::quota::{ConnectionQuotas, QuotaManager};
+use crate::kafka::request::generic_request::{KafkaRequest, KafkaRequestBody};
+use crate::kafka::response::{AlterConfigsResourceResponse, KafkaResponse, KafkaResponseBody, KafkaResponseHeader};
+use crate::kafka::security::{
+    AclOperation, Authorizer, CredentialStore, KafkaPrincipal, ResourceType, SaslSession, CLUSTER_RESOURCE_NAME,
+};
+use crate::kafka::storage::LogManager;
+use crate::kafka::types::{ApiKey, ErrorCode, MessageVersion};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::error;
+
+/// The connection a request arrived on.
+#[derive(Debug, Clone)]
+pub(crate) struct RequestContext {
+    pub(crate) client_addr: SocketAddr,
+    /// The name of the listener that accepted the connection.
+    pub(crate) listener_name: String,
+    /// Who the connection is authenticated as, `None` until SASL completes.
+    pub(crate) principal: Option<KafkaPrincipal>,
+    pub(crate) sasl: SaslSession,
+    /// Set when the client exceeded its request quota: no more requests are
+    /// read from the connection until then.
+    pub(crate) muted_until: Option<Instant>,
+    /// Time the current request spent waiting on a coordinator rather than
+    /// being handled.
+    pub(crate) remote_time: Duration,
+}
+
+impl RequestContext {
+    /// The context of a new connection. Connections on SASL listeners start
+    /// unauthenticated, others as the anonymous principal.
+    pub(crate) fn new(client_addr: SocketAddr, listener_name: String, sasl_enabled: bool) -> Self {
+        let (principal, sasl) = if sasl_enabled {
+            (None, SaslSession::Handshake)
+        } else {
+            (Some(KafkaPrincipal::anonymous()), SaslSession::Complete)
+        };
+        Self { client_addr, listener_name, principal, sasl, muted_until: None, remote_time: Duration::ZERO }
+    }
+
+    /// The client address as matched against ACL hosts.
+    pub(crate) fn host(&self) -> String {
+        self.client_addr.ip().to_string()
+    }
+}
+
+/// Dispatches decoded requests to the API handlers.
+#[derive(Debug)]
+pub(crate) struct Broker {
+    config: ServerConfig,
+    log_manager: Arc<LogManager>,
+    group_coordinator: Arc<GroupCoordinator>,
+    transaction_coordinator: Arc<TransactionCoordinator>,
+    credentials: CredentialStore,
+    authorizer: Authorizer,
+    dynamic_configs: DynamicConfigs,
+    quota_manager: QuotaManager,
+    connection_quotas: Arc<ConnectionQuotas>,
+    metrics: Arc<Metrics>,
+    cluster_control: Arc<ClusterControl>,
+}
+
+impl Broker {
+    pub(crate) fn new(
+        config: ServerConfig,
+        log_manager: Arc<LogManager>,
+        group_coordinator: Arc<GroupCoordinator>,
+        transaction_coordinator: Arc<TransactionCoordinator>,
+        credentials: CredentialStore,
+        dynamic_configs: DynamicConfigs,
+        metadata: &MetadataImage,
+    ) -> Self {
+        Self {
+            authorizer: Authorizer::new(&config, metadata.acls().clone()),
+            quota_manager: QuotaManager::new(&config, metadata.client_quotas().clone()),
+            connection_quotas: Arc::new(ConnectionQuotas::new(&config)),
+            metrics: Arc::new(Metrics::default()),
+            cluster_control: Arc::new(ClusterControl::new(log_manager.clone(), metadata.brokers().clone(), &config)),
+            config,
+            log_manager,
+            group_coordinator,
+            transaction_coordinator,
+            credentials,
+            dynamic_configs,
+        }
+    }
+
+    pub(crate) fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    pub(crate) fn connection_quotas(&self) -> &Arc<ConnectionQuotas> {
+        &self.connection_quotas
+    }
+
+    pub(crate) fn cluster_control(&self) -> &Arc<ClusterControl> {
+        &self.cluster_control
+    }
+
+    pub(crate) fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    /// Whether the connection may perform `operation` on the named resource.
+    fn authorize(
+        &self,
+        context: &RequestContext,
+        operation: AclOperation,
+        resource_type: ResourceType,
+        resource_name: &str,
+    ) -> bool {
+        context.principal.as_ref().is_some_and(|principal| {
+            self.authorizer.authorize(principal, &context.host(), operation, resource_type, resource_name)
+        })
+    }
+
+    /// Whether the connection may perform `operation` on some resource of
+    /// the type.
+    fn authorize_any(&self, context: &RequestContext, operation: AclOperation, resource_type: ResourceType) -> bool {
+        context.principal.as_ref().is_some_and(|principal| {
+            self.authorizer.authorize_any(principal, &context.host(), operation, resource_type)
+        })
+    }
+
+    /// The `authorized_operations` bitfield of the named resource for the
+    /// connection.
+    fn authorized_operations(&self, context: &RequestContext, resource_type: ResourceType, resource_name: &str) -> i32 {
+        match &context.principal {
+            Some(principal) => {
+                self.authorizer.authorized_operations(principal, &context.host(), resource_type, resource_name)
+            }
+            None => 0,
+        }
+    }
+
+    /// The type of a DescribeConfigs or AlterConfigs resource the connection
+    /// may perform `operation` on: topics are authorized themselves, brokers
+    /// through the cluster.
+    fn config_resource(
+        &self,
+        context: &RequestContext,
+        resource_type: i8,
+        resource_name: &str,
+        operation: AclOperation,
+    ) -> Result<ConfigResourceType, (ErrorCode, String)> {
+        match ConfigResourceType::from_code(resource_type) {
+            Some(ConfigResourceType::Topic) => {
+                if !self.authorize(context, operation, ResourceType::Topic, resource_name) {
+                    return Err((ErrorCode::TopicAuthorizationFailed, "Topic authorization failed".to_owned()));
+                }
+                if !self.log_manager.topics().contains_key(resource_name) {
+                    let message = format!("Topic {resource_name} does not exist");
+                    return Err((ErrorCode::UnknownTopicOrPartition, message));
+                }
+                Ok(ConfigResourceType::Topic)
+            }
+            Some(ConfigResourceType::Broker) => {
+                if !self.authorize(context, operation, ResourceType::Cluster, CLUSTER_RESOURCE_NAME) {
+                    return Err((ErrorCode::ClusterAuthorizationFailed, "Cluster authorization failed".to_owned()));
+                }
+                if !resource_name.is_empty() && resource_name != self.config.node_id.to_string() {
+                    let message = format!("Unexpected broker id {resource_name}, expected {}", self.config.node_id);
+                    return Err((ErrorCode::InvalidRequest, message));
+                }
+                Ok(ConfigResourceType::Broker)
+            }
+            None => Err((ErrorCode::InvalidRequest, format!("Unsupported resource type {resource_type}"))),
+        }
+    }
+
+    /// Alters the dynamic configs of a resource. `update` changes the
+    /// current dynamic configs, which replace them if they validate, unless
+    /// `validate_only` is set. Internal topics keep the configs their
+    /// coordinators give them.
+    fn alter_resource_configs(
+        &self,
+        context: &RequestContext,
+        resource_type: i8,
+        resource_name: &str,
+        validate_only: bool,
+        update: impl FnOnce(ConfigResourceType, &mut HashMap<String, String>) -> Result<(), ConfigError>,
+    ) -> AlterConfigsResourceResponse {
+        let result = self
+            .config_resource(context, resource_type, resource_name, AclOperation::AlterConfigs)
+            .and_then(|config_resource_type| {
+                let internal = [CONSUMER_OFFSETS_TOPIC, TRANSACTION_STATE_TOPIC, CLUSTER_METADATA_TOPIC];
+                if config_resource_type == ConfigResourceType::Topic && internal.contains(&resource_name) {
+                    let message = format!("The configs of internal topic {resource_name} cannot be altered");
+                    return Err((ErrorCode::InvalidRequest, message));
+                }
+                let mut configs = self.dynamic_configs.overrides(config_resource_type, resource_name);
+                update(config_resource_type, &mut configs)
+                    .and_then(|()| self.dynamic_configs.validate(config_resource_type, &configs))
+                    .and_then(|()| {
+                        if validate_only {
+                            return Ok(());
+                        }
+                        self.dynamic_configs.replace(&self.log_manager, config_resource_type, resource_name, configs)
+                    })
+                    .map_err(|err| (err.error_code(), err.to_string()))
+            });
+        let (error_code, error_message) = match result {
+            Ok(()) => (ErrorCode::None, None),
+            Err((error_code, message)) => (error_code, Some(message)),
+        };
+        AlterConfigsResourceResponse {
+            error_code,
+            error_message: error_message.into(),
+            resource_type,
+            resource_name: resource_name.to_owned().into(),
+            ..Default::default()
+        }
+    }
+
+    /// Handles one request. Returns `None` if the request is not answered.
+    /// Clients over their request quota are told how long they are throttled
+    /// for, and muted until then.
+    pub(crate) async fn handle_request(
+        &self,
+        context: &mut RequestContext,
+        handler: &mut RequestHandler,
+        request: KafkaRequest,
+    ) -> Option<KafkaResponse> {
+        let header = request.header;
+        let version = header.message_version();
+        let start = Instant::now();
+
+        let mut body = match request.body {
+            KafkaRequestBody::ApiVersions(_) => {
+                KafkaResponseBody::ApiVersions(ApiVersionsResponse::new(header.api_version()))
+            }
+            KafkaRequestBody::SaslHandshake(body) => {
+                KafkaResponseBody::SaslHandshake(version, self.sasl_handshake(context, body))
+            }
+            KafkaRequestBody::SaslAuthenticate(body) => {
+                KafkaResponseBody::SaslAuthenticate(version, self.sasl_authenticate(context, body))
+            }
+            KafkaRequestBody::ListOffsets(body) => {
+                KafkaResponseBody::ListOffsets(version, self.list_offsets(context, version, body))
+            }
+            KafkaRequestBody::DeleteRecords(body) => {
+                KafkaResponseBody::DeleteRecords(version, self.delete_records(context, body))
+            }
+            KafkaRequestBody::OffsetForLeaderEpoch(body) => {
+                KafkaResponseBody::OffsetForLeaderEpoch(version, self.offset_for_leader_epoch(context, body))
+            }
+            KafkaRequestBody::FindCoordinator(body) => {
+                KafkaResponseBody::FindCoordinator(version, self.find_coordinator(context, version, body))
+            }
+            KafkaRequestBody::JoinGroup(body) => {
+                let client_id = header.client_id().unwrap_or_default();
+                KafkaResponseBody::JoinGroup(version, self.join_group(context, handler, client_id, version, body).await)
+            }
+            KafkaRequestBody::SyncGroup(body) => {
+                KafkaResponseBody::SyncGroup(version, self.sync_group(context, handler, body).await)
+            }
+            KafkaRequestBody::Heartbeat(body) => KafkaResponseBody::Heartbeat(version, self.heartbeat(context, body)),
+            KafkaRequestBody::LeaveGroup(body) => {
+                KafkaResponseBody::LeaveGroup(version, self.leave_group(context, version, body))
+            }
+            KafkaRequestBody::OffsetCommit(body) => {
+                KafkaResponseBody::OffsetCommit(version, self.offset_commit(context, version, body))
+            }
+            KafkaRequestBody::OffsetFetch(body) => {
+                KafkaResponseBody::OffsetFetch(version, self.offset_fetch(context, version, body))
+            }
+            KafkaRequestBody::ConsumerGroupHeartbeat(body) => {
+                let client_id = header.client_id().unwrap_or_default();
+                let response = self.consumer_group_heartbeat(context, client_id, body);
+                KafkaResponseBody::ConsumerGroupHeartbeat(version, response)
+            }
+            KafkaRequestBody::ConsumerGroupDescribe(body) => {
+                KafkaResponseBody::ConsumerGroupDescribe(version, self.consumer_group_describe(context, body))
+            }
+            KafkaRequestBody::DescribeGroups(body) => {
+                KafkaResponseBody::DescribeGroups(version, self.describe_groups(context, body))
+            }
+            KafkaRequestBody::DescribeTopicPartitions(body) => {
+                KafkaResponseBody::DescribeTopicPartitions(version, self.describe_topic_partitions(context, body))
+            }
+            KafkaRequestBody::ListGroups(body) => {
+                KafkaResponseBody::ListGroups(version, self.list_groups(context, body))
+            }
+            KafkaRequestBody::DeleteGroups(body) => {
+                KafkaResponseBody::DeleteGroups(version, self.delete_groups(context, body))
+            }
+            KafkaRequestBody::OffsetDelete(body) => {
+                KafkaResponseBody::OffsetDelete(version, self.offset_delete(context, body))
+            }
+            KafkaRequestBody::InitProducerId(body) => {
+                KafkaResponseBody::InitProducerId(version, self.init_producer_id(context, version, body))
+            }
+            KafkaRequestBody::AddPartitionsToTxn(body) => {
+                KafkaResponseBody::AddPartitionsToTxn(version, self.add_partitions_to_txn(context, version, body))
+            }
+            KafkaRequestBody::AddOffsetsToTxn(body) => {
+                KafkaResponseBody::AddOffsetsToTxn(version, self.add_offsets_to_txn(context, version, body))
+            }
+            KafkaRequestBody::EndTxn(body) => KafkaResponseBody::EndTxn(version, self.end_txn(context, version, body)),
+            KafkaRequestBody::TxnOffsetCommit(body) => {
+                KafkaResponseBody::TxnOffsetCommit(version, self.txn_offset_commit(context, version, body))
+            }
+            KafkaRequestBody::DescribeProducers(body) => {
+                KafkaResponseBody::DescribeProducers(version, self.describe_producers(context, body))
+            }
+            KafkaRequestBody::BrokerRegistration(body) => {
+                KafkaResponseBody::BrokerRegistration(version, self.broker_registration(context, body))
+            }
+            KafkaRequestBody::BrokerHeartbeat(body) => {
+                KafkaResponseBody::BrokerHeartbeat(version, self.broker_heartbeat(context, body))
+            }
+            KafkaRequestBody::DescribeTransactions(body) => {
+                KafkaResponseBody::DescribeTransactions(version, self.describe_transactions(context, body))
+            }
+            KafkaRequestBody::ListTransactions(body) => {
+                KafkaResponseBody::ListTransactions(version, self.list_transactions(context, body))
+            }
+            KafkaRequestBody::DescribeAcls(body) => {
+                KafkaResponseBody::DescribeAcls(version, self.describe_acls(context, body))
+            }
+            KafkaRequestBody::CreateAcls(body) => {
+                KafkaResponseBody::CreateAcls(version, self.create_acls(context, body))
+            }
+            KafkaRequestBody::DeleteAcls(body) => {
+                KafkaResponseBody::DeleteAcls(version, self.delete_acls(context, body))
+            }
+            KafkaRequestBody::DescribeConfigs(body) => {
+                KafkaResponseBody::DescribeConfigs(version, self.describe_configs(context, body))
+            }
+            KafkaRequestBody::AlterConfigs(body) => {
+                KafkaResponseBody::AlterConfigs(version, self.alter_configs(context, body))
+            }
+            KafkaRequestBody::IncrementalAlterConfigs(body) => {
+                KafkaResponseBody::IncrementalAlterConfigs(version, self.incremental_alter_configs(context, body))
+            }
+            KafkaRequestBody::DescribeClientQuotas(body) => {
+                KafkaResponseBody::DescribeClientQuotas(version, self.describe_client_quotas(context, body))
+            }
+            KafkaRequestBody::AlterClientQuotas(body) => {
+                KafkaResponseBody::AlterClientQuotas(version, self.alter_client_quotas(context, body))
+            }
+            KafkaRequestBody::Unsupported => {
+                error!(api_key = ?header.api_key(), api_version = header.api_version(), "Unsupported API version");
+                return None;
+            }
+        };
+
+        self.metrics.record_request(header.api_key(), header.api_version(), start.elapsed());
+
+        // Requests parked in the group coordinator would be charged for the
+        // time they wait rather than the time spent handling them.
+        let exempt = matches!(
+            header.api_key(),
+            ApiKey::ApiVersions
+                | ApiKey::SaslHandshake
+                | ApiKey::SaslAuthenticate
+                | ApiKey::JoinGroup
+                | ApiKey::SyncGroup
+        );
+        if let Some(principal) = context.principal.as_ref().filter(|_| !exempt) {
+            let client_id = header.client_id().unwrap_or_default();
+            let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+            let throttle_time_ms =
+                self.quota_manager.record_request_time(&principal.name, client_id, start.elapsed(), now_ms);
+            if throttle_time_ms > 0 {
+                body.set_throttle_time_ms(throttle_time_ms as i32);
+                context.muted_until = Some(Instant::now() + Duration::from_millis(throttle_time_ms as u64));
+            }
+        }
+
+        Some(KafkaResponse::new(KafkaResponseHeader::for_request(&header), body))
+    }
+}
+
+/// Producers predating KIP-588 know a fenced producer only as
+/// INVALID_PRODUCER_EPOCH, which the API added PRODUCER_FENCED in
+/// `fenced_version`.
+fn fenced_error_code(error_code: ErrorCode, version: MessageVersion, fenced_version: i16) -> ErrorCode {
+    if error_code == ErrorCode::ProducerFenced && version.version < fenced_version {
+        ErrorCode::InvalidProducerEpoch
+    } else {
+        error_code
+    }
+}
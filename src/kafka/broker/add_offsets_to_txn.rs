@@ -0,0 +1,30 @@
+use crate::kafka::broker::{fenced_error_code, Broker, RequestContext};
+use crate::kafka::request::KafkaRequestAddOffsetsToTxn;
+use crate::kafka::response::KafkaResponseAddOffsetsToTxn;
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::{ErrorCode, MessageVersion};
+
+impl Broker {
+    pub(crate) fn add_offsets_to_txn(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestAddOffsetsToTxn,
+    ) -> KafkaResponseAddOffsetsToTxn {
+        if !self.authorize(context, AclOperation::Write, ResourceType::TransactionalId, &request.transactional_id) {
+            let error_code = ErrorCode::TransactionalIdAuthorizationFailed;
+            return KafkaResponseAddOffsetsToTxn { error_code, ..Default::default() };
+        }
+        if !self.authorize(context, AclOperation::Read, ResourceType::Group, &request.group_id) {
+            let error_code = ErrorCode::GroupAuthorizationFailed;
+            return KafkaResponseAddOffsetsToTxn { error_code, ..Default::default() };
+        }
+        let error_code = self.transaction_coordinator.add_offsets(
+            &request.transactional_id,
+            request.producer_id,
+            request.producer_epoch,
+            &request.group_id,
+        );
+        KafkaResponseAddOffsetsToTxn { error_code: fenced_error_code(error_code, version, 2), ..Default::default() }
+    }
+}
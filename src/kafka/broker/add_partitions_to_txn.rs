@@ -0,0 +1,72 @@
+use crate::kafka::broker::{fenced_error_code, Broker, RequestContext};
+use crate::kafka::request::KafkaRequestAddPartitionsToTxn;
+use crate::kafka::response::{
+    AddPartitionsToTxnPartitionResult, AddPartitionsToTxnTopicResult, KafkaResponseAddPartitionsToTxn,
+};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::{ErrorCode, MessageVersion};
+
+impl Broker {
+    /// Adds partitions to the transaction. If the connection may not write
+    /// to some of the topics, no partition is added.
+    pub(crate) fn add_partitions_to_txn(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestAddPartitionsToTxn,
+    ) -> KafkaResponseAddPartitionsToTxn {
+        let partitions: Vec<_> = request
+            .topics
+            .iter()
+            .flat_map(|topic| topic.partitions.iter().map(|&partition| TopicPartition::new(topic.name.as_str(), partition)))
+            .collect();
+        let writable = |topic: &str| self.authorize(context, AclOperation::Write, ResourceType::Topic, topic);
+        let results = if !self.authorize(
+            context,
+            AclOperation::Write,
+            ResourceType::TransactionalId,
+            &request.transactional_id,
+        ) {
+            let error_code = ErrorCode::TransactionalIdAuthorizationFailed;
+            partitions.into_iter().map(|topic_partition| (topic_partition, error_code)).collect()
+        } else if !partitions.iter().all(|topic_partition| writable(&topic_partition.topic)) {
+            partitions
+                .into_iter()
+                .map(|topic_partition| {
+                    let error_code = if writable(&topic_partition.topic) {
+                        ErrorCode::OperationNotAttempted
+                    } else {
+                        ErrorCode::TopicAuthorizationFailed
+                    };
+                    (topic_partition, error_code)
+                })
+                .collect()
+        } else {
+            self.transaction_coordinator.add_partitions(
+                &request.transactional_id,
+                request.producer_id,
+                request.producer_epoch,
+                partitions,
+            )
+        };
+
+        let mut topics: Vec<AddPartitionsToTxnTopicResult> = Vec::new();
+        for (topic_partition, error_code) in results {
+            let partition = AddPartitionsToTxnPartitionResult {
+                partition_index: topic_partition.partition,
+                partition_error_code: fenced_error_code(error_code, version, 2),
+                ..Default::default()
+            };
+            match topics.last_mut() {
+                Some(topic) if *topic.name == topic_partition.topic => topic.results.0.push(partition),
+                _ => topics.push(AddPartitionsToTxnTopicResult {
+                    name: topic_partition.topic.into(),
+                    results: vec![partition].into(),
+                    ..Default::default()
+                }),
+            }
+        }
+        KafkaResponseAddPartitionsToTxn { results: topics.into(), ..Default::default() }
+    }
+}
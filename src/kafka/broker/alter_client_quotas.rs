@@ -0,0 +1,105 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::metadata::{append_metadata_records, ClientQuotaRecord};
+use crate::kafka::quota::{QuotaEntity, QUOTA_KEYS, REQUEST_PERCENTAGE};
+use crate::kafka::request::{AlterClientQuotasEntry, KafkaRequestAlterClientQuotas};
+use crate::kafka::response::{AlterClientQuotasEntryResponse, KafkaResponseAlterClientQuotas, QuotaEntityData};
+use crate::kafka::security::{AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::ErrorCode;
+use tracing::{error, info};
+
+impl Broker {
+    /// Writes the quota changes of every valid entry to the metadata log in
+    /// one batch, then applies them.
+    pub(crate) fn alter_client_quotas(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestAlterClientQuotas,
+    ) -> KafkaResponseAlterClientQuotas {
+        let authorized =
+            self.authorize(context, AclOperation::AlterConfigs, ResourceType::Cluster, CLUSTER_RESOURCE_NAME);
+
+        let mut entries = Vec::new();
+        let mut changes = Vec::new();
+        for entry in request.entries.iter() {
+            let result = if authorized {
+                quota_changes(entry).map_err(|message| (ErrorCode::InvalidRequest, message))
+            } else {
+                Err((ErrorCode::ClusterAuthorizationFailed, "Cluster authorization failed".to_owned()))
+            };
+            let (error_code, error_message) = match result {
+                Ok((entity, ops)) => {
+                    changes.extend(ops.into_iter().map(|(key, value)| (entity.clone(), key, value)));
+                    (ErrorCode::None, None)
+                }
+                Err((error_code, message)) => (error_code, Some(message)),
+            };
+            let entity = entry
+                .entity
+                .iter()
+                .map(|data| QuotaEntityData {
+                    entity_type: data.entity_type.clone(),
+                    entity_name: data.entity_name.clone(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+            entries.push(AlterClientQuotasEntryResponse {
+                error_code,
+                error_message: error_message.into(),
+                entity: entity.into(),
+                ..Default::default()
+            });
+        }
+        if request.validate_only {
+            return KafkaResponseAlterClientQuotas { entries: entries.into(), ..Default::default() };
+        }
+
+        let values = changes.iter().map(|(entity, key, value)| ClientQuotaRecord::new(entity, key, *value).to_value());
+        match append_metadata_records(&self.log_manager, values.collect()) {
+            Ok(()) => {
+                for (entity, key, value) in changes {
+                    info!(?entity, key, ?value, "Altered client quota");
+                    self.quota_manager.set_quota(&entity, key, value);
+                }
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to write client quotas");
+                for entry in entries.iter_mut().filter(|entry| entry.error_code == ErrorCode::None) {
+                    entry.error_code = ErrorCode::UnknownServerError;
+                }
+            }
+        }
+        KafkaResponseAlterClientQuotas { entries: entries.into(), ..Default::default() }
+    }
+}
+
+/// The quota keys an entry alters and their new values, `None` removing
+/// the quota.
+type QuotaOps<'a> = Vec<(&'a str, Option<f64>)>;
+
+/// The entity of the entry and the quotas it alters.
+fn quota_changes(entry: &AlterClientQuotasEntry) -> Result<(QuotaEntity, QuotaOps<'_>), String> {
+    let components = entry.entity.iter().map(|data| (data.entity_type.as_str(), data.entity_name.as_deref()));
+    let entity = QuotaEntity::from_components(components)?;
+    let mut ops = QuotaOps::new();
+    for op in entry.ops.iter() {
+        let key = op.key.as_str();
+        if !QUOTA_KEYS.contains(&key) {
+            return Err(format!("Unknown quota key: {key}"));
+        }
+        if ops.iter().any(|(altered, _)| *altered == key) {
+            return Err(format!("Duplicate quota key: {key}"));
+        }
+        if op.remove {
+            ops.push((key, None));
+            continue;
+        }
+        if !(op.value > 0.0 && op.value.is_finite()) {
+            return Err(format!("Invalid value {} for quota {key}: must be positive", op.value));
+        }
+        if key != REQUEST_PERCENTAGE && op.value.fract() != 0.0 {
+            return Err(format!("Invalid value {} for quota {key}: must be a whole number of bytes", op.value));
+        }
+        ops.push((key, Some(op.value)));
+    }
+    Ok((entity, ops))
+}
@@ -0,0 +1,35 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestAlterConfigs;
+use crate::kafka::response::KafkaResponseAlterConfigs;
+
+impl Broker {
+    /// Replaces the dynamic configs of each resource with those of the
+    /// request, removing any it leaves out.
+    pub(crate) fn alter_configs(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestAlterConfigs,
+    ) -> KafkaResponseAlterConfigs {
+        let responses = request
+            .resources
+            .iter()
+            .map(|resource| {
+                self.alter_resource_configs(
+                    context,
+                    resource.resource_type,
+                    &resource.resource_name,
+                    request.validate_only,
+                    |_, configs| {
+                        *configs = resource
+                            .configs
+                            .iter()
+                            .filter_map(|config| Some((config.name.to_string(), config.value.0.clone()?)))
+                            .collect();
+                        Ok(())
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseAlterConfigs { responses: responses.into(), ..Default::default() }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::metadata::BrokerHeartbeatParams;
+use crate::kafka::request::KafkaRequestBrokerHeartbeat;
+use crate::kafka::response::KafkaResponseBrokerHeartbeat;
+use crate::kafka::security::{AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::ErrorCode;
+use std::time::Instant;
+use tracing::warn;
+
+impl Broker {
+    pub(crate) fn broker_heartbeat(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestBrokerHeartbeat,
+    ) -> KafkaResponseBrokerHeartbeat {
+        // Brokers that cannot heartbeat stay fenced.
+        let error = |error_code| KafkaResponseBrokerHeartbeat { error_code, is_fenced: true, ..Default::default() };
+        if !self.authorize(context, AclOperation::ClusterAction, ResourceType::Cluster, CLUSTER_RESOURCE_NAME) {
+            return error(ErrorCode::ClusterAuthorizationFailed);
+        }
+        let params = BrokerHeartbeatParams {
+            broker_id: request.broker_id,
+            broker_epoch: request.broker_epoch,
+            current_metadata_offset: request.current_metadata_offset,
+            want_fence: request.want_fence,
+            want_shut_down: request.want_shut_down,
+        };
+        match self.cluster_control.heartbeat(params, Instant::now()) {
+            Ok(state) => KafkaResponseBrokerHeartbeat {
+                is_caught_up: state.is_caught_up,
+                is_fenced: state.is_fenced,
+                should_shut_down: state.should_shut_down,
+                ..Default::default()
+            },
+            Err(err) => {
+                warn!(broker_id = request.broker_id, error = %err, "Rejected broker heartbeat");
+                error(err.error_code())
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::metadata::{BrokerEndpoint, BrokerFeature, RegisterBrokerParams};
+use crate::kafka::request::KafkaRequestBrokerRegistration;
+use crate::kafka::response::KafkaResponseBrokerRegistration;
+use crate::kafka::security::{AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::ErrorCode;
+use std::time::Instant;
+use tracing::warn;
+
+impl Broker {
+    /// Registers a broker with the controller of this node. The cluster id
+    /// is not checked, the nodes of this cluster have none.
+    pub(crate) fn broker_registration(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestBrokerRegistration,
+    ) -> KafkaResponseBrokerRegistration {
+        let error = |error_code| KafkaResponseBrokerRegistration { error_code, broker_epoch: -1, ..Default::default() };
+        if !self.authorize(context, AclOperation::ClusterAction, ResourceType::Cluster, CLUSTER_RESOURCE_NAME) {
+            return error(ErrorCode::ClusterAuthorizationFailed);
+        }
+        let params = RegisterBrokerParams {
+            broker_id: request.broker_id,
+            incarnation_id: request.incarnation_id,
+            end_points: request
+                .listeners
+                .iter()
+                .map(|listener| {
+                    BrokerEndpoint::new(&listener.name, &listener.host, listener.port, listener.security_protocol)
+                })
+                .collect(),
+            features: request
+                .features
+                .iter()
+                .map(|feature| {
+                    BrokerFeature::new(&feature.name, feature.min_supported_version, feature.max_supported_version)
+                })
+                .collect(),
+            rack: request.rack.as_deref().map(str::to_owned),
+        };
+        match self.cluster_control.register(params, Instant::now()) {
+            Ok(broker_epoch) => KafkaResponseBrokerRegistration { broker_epoch, ..Default::default() },
+            Err(err) => {
+                warn!(broker_id = request.broker_id, error = %err, "Rejected broker registration");
+                error(err.error_code())
+            }
+        }
+    }
+}
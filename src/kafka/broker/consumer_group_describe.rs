@@ -0,0 +1,109 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::coordinator::Assignment;
+use crate::kafka::request::KafkaRequestConsumerGroupDescribe;
+use crate::kafka::response::{
+    ConsumerGroupDescribeResponseAssignment, ConsumerGroupDescribeResponseGroup, ConsumerGroupDescribeResponseMember,
+    ConsumerGroupDescribeResponseTopicPartitions, KafkaResponseConsumerGroupDescribe,
+};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::{ErrorCode, Uuid};
+use std::collections::HashMap;
+
+/// The member type of consumer protocol members in ConsumerGroupDescribe v1.
+const CONSUMER_MEMBER_TYPE: i8 = 1;
+
+impl Broker {
+    pub(crate) fn consumer_group_describe(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestConsumerGroupDescribe,
+    ) -> KafkaResponseConsumerGroupDescribe {
+        let topic_names = self
+            .log_manager
+            .topics()
+            .into_iter()
+            .map(|(name, metadata)| (metadata.topic_id, name))
+            .collect::<HashMap<_, _>>();
+
+        let groups = request
+            .group_ids
+            .0
+            .into_iter()
+            .map(|group_id| {
+                if !self.authorize(context, AclOperation::Describe, ResourceType::Group, &group_id) {
+                    return ConsumerGroupDescribeResponseGroup {
+                        error_code: ErrorCode::GroupAuthorizationFailed,
+                        group_id,
+                        authorized_operations: i32::MIN,
+                        ..Default::default()
+                    };
+                }
+                let authorized_operations = if request.include_authorized_operations {
+                    self.authorized_operations(context, ResourceType::Group, &group_id)
+                } else {
+                    i32::MIN
+                };
+                match self.group_coordinator.describe_consumer_group(&group_id) {
+                    Ok(group) => {
+                        let members = group
+                            .members
+                            .into_iter()
+                            .map(|member| ConsumerGroupDescribeResponseMember {
+                                member_id: member.member_id.into(),
+                                instance_id: member.instance_id.into(),
+                                rack_id: member.rack_id.into(),
+                                member_epoch: member.member_epoch,
+                                client_id: member.client_id.into(),
+                                client_host: member.client_host.into(),
+                                subscribed_topic_names: member
+                                    .subscribed_topic_names
+                                    .into_iter()
+                                    .map(Into::into)
+                                    .collect::<Vec<_>>()
+                                    .into(),
+                                assignment: described_assignment(member.assignment, &topic_names),
+                                target_assignment: described_assignment(member.target_assignment, &topic_names),
+                                member_type: CONSUMER_MEMBER_TYPE,
+                                ..Default::default()
+                            })
+                            .collect::<Vec<_>>();
+                        ConsumerGroupDescribeResponseGroup {
+                            group_id: group.group_id.into(),
+                            group_state: group.state.to_string().into(),
+                            group_epoch: group.group_epoch,
+                            assignment_epoch: group.assignment_epoch,
+                            assignor_name: group.assignor_name.into(),
+                            members: members.into(),
+                            authorized_operations,
+                            ..Default::default()
+                        }
+                    }
+                    Err((error_code, message)) => ConsumerGroupDescribeResponseGroup {
+                        error_code,
+                        error_message: Some(message).into(),
+                        group_id,
+                        authorized_operations,
+                        ..Default::default()
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseConsumerGroupDescribe { groups: groups.into(), ..Default::default() }
+    }
+}
+
+fn described_assignment(
+    assignment: Assignment,
+    topic_names: &HashMap<Uuid, String>,
+) -> ConsumerGroupDescribeResponseAssignment {
+    let topic_partitions = assignment
+        .into_iter()
+        .map(|(topic_id, partitions)| ConsumerGroupDescribeResponseTopicPartitions {
+            topic_id,
+            topic_name: topic_names.get(&topic_id).cloned().unwrap_or_default().into(),
+            partitions: partitions.into_iter().collect::<Vec<_>>().into(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    ConsumerGroupDescribeResponseAssignment { topic_partitions: topic_partitions.into(), ..Default::default() }
+}
@@ -0,0 +1,76 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::coordinator::{Assignment, ConsumerGroupHeartbeatParams};
+use crate::kafka::request::KafkaRequestConsumerGroupHeartbeat;
+use crate::kafka::response::{
+    ConsumerGroupHeartbeatResponseAssignment, ConsumerGroupHeartbeatResponseTopicPartitions,
+    KafkaResponseConsumerGroupHeartbeat,
+};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    pub(crate) fn consumer_group_heartbeat(
+        &self,
+        context: &RequestContext,
+        client_id: &str,
+        request: KafkaRequestConsumerGroupHeartbeat,
+    ) -> KafkaResponseConsumerGroupHeartbeat {
+        let failed = |error_code, message: &str| KafkaResponseConsumerGroupHeartbeat {
+            error_code,
+            error_message: Some(message.to_owned()).into(),
+            ..Default::default()
+        };
+        if !self.authorize(context, AclOperation::Read, ResourceType::Group, &request.group_id) {
+            return failed(ErrorCode::GroupAuthorizationFailed, "Group authorization failed");
+        }
+        let mut subscribed = request.subscribed_topic_names.0.iter().flatten();
+        if !subscribed.all(|name| self.authorize(context, AclOperation::Describe, ResourceType::Topic, name)) {
+            return failed(ErrorCode::TopicAuthorizationFailed, "Topic authorization failed");
+        }
+        let owned_partitions = request.topic_partitions.0.map(|topics| {
+            let mut owned = Assignment::new();
+            for topic in topics {
+                owned.entry(topic.topic_id).or_default().extend(topic.partitions.0);
+            }
+            owned
+        });
+        let params = ConsumerGroupHeartbeatParams {
+            group_id: request.group_id.0,
+            member_id: request.member_id.0,
+            member_epoch: request.member_epoch,
+            instance_id: request.instance_id.0,
+            rack_id: request.rack_id.0,
+            rebalance_timeout_ms: request.rebalance_timeout_ms,
+            subscribed_topic_names: request
+                .subscribed_topic_names
+                .0
+                .map(|names| names.into_iter().map(|name| name.0).collect()),
+            server_assignor: request.server_assignor.0,
+            owned_partitions,
+            client_id: client_id.to_owned(),
+            client_host: format!("/{}", context.client_addr.ip()),
+        };
+        let result = self.group_coordinator.consumer_group_heartbeat(params);
+
+        let assignment = result.assignment.map(|assignment| {
+            let topic_partitions = assignment
+                .into_iter()
+                .map(|(topic_id, partitions)| ConsumerGroupHeartbeatResponseTopicPartitions {
+                    topic_id,
+                    partitions: partitions.into_iter().collect::<Vec<_>>().into(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+            ConsumerGroupHeartbeatResponseAssignment { topic_partitions: topic_partitions.into(), ..Default::default() }
+        });
+        KafkaResponseConsumerGroupHeartbeat {
+            error_code: result.error_code,
+            error_message: result.error_message.into(),
+            member_id: result.member_id.into(),
+            member_epoch: result.member_epoch,
+            heartbeat_interval_ms: result.heartbeat_interval_ms,
+            assignment,
+            ..Default::default()
+        }
+    }
+}
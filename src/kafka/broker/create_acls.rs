@@ -0,0 +1,83 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::metadata::{append_metadata_records, AccessControlEntryRecord};
+use crate::kafka::request::KafkaRequestCreateAcls;
+use crate::kafka::response::{AclCreationResult, KafkaResponseCreateAcls};
+use crate::kafka::security::{AclOperation, ResourceType, StandardAcl, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::{ErrorCode, Uuid};
+use tracing::{error, info};
+
+impl Broker {
+    /// Writes the new ACLs to the metadata log, then applies them. ACLs that
+    /// already exist are accepted without being written again.
+    pub(crate) fn create_acls(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestCreateAcls,
+    ) -> KafkaResponseCreateAcls {
+        let denied = if !self.authorizer.is_enabled() {
+            Some((ErrorCode::SecurityDisabled, "No authorizer is configured"))
+        } else if !self.authorize(context, AclOperation::Alter, ResourceType::Cluster, CLUSTER_RESOURCE_NAME) {
+            Some((ErrorCode::ClusterAuthorizationFailed, "Cluster authorization failed"))
+        } else {
+            None
+        };
+        if let Some((error_code, message)) = denied {
+            let results = request
+                .creations
+                .iter()
+                .map(|_| AclCreationResult {
+                    error_code,
+                    error_message: Some(message.to_owned()).into(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+            return KafkaResponseCreateAcls { results: results.into(), ..Default::default() };
+        }
+
+        let mut results = Vec::new();
+        let mut created: Vec<(Uuid, StandardAcl)> = Vec::new();
+        for creation in request.creations.0 {
+            let acl = StandardAcl::from_codes(
+                creation.resource_type,
+                creation.resource_name.0,
+                creation.resource_pattern_type,
+                creation.principal.0,
+                creation.host.0,
+                creation.operation,
+                creation.permission_type,
+            )
+            .ok_or("Unknown resource, pattern, operation or permission type")
+            .and_then(|acl| acl.validate().map(|()| acl));
+            match acl {
+                Ok(acl) => {
+                    if self.authorizer.find_acl(&acl).is_none() && !created.iter().any(|(_, new)| *new == acl) {
+                        created.push((Uuid::random(), acl));
+                    }
+                    results.push(AclCreationResult::default());
+                }
+                Err(message) => results.push(AclCreationResult {
+                    error_code: ErrorCode::InvalidRequest,
+                    error_message: Some(message.to_owned()).into(),
+                    ..Default::default()
+                }),
+            }
+        }
+
+        let values = created.iter().map(|(id, acl)| AccessControlEntryRecord::new(*id, acl).to_value()).collect();
+        match append_metadata_records(&self.log_manager, values) {
+            Ok(()) => {
+                for (id, acl) in created {
+                    info!(%id, ?acl, "Created ACL");
+                    self.authorizer.add_acl(id, acl);
+                }
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to write ACLs");
+                for result in results.iter_mut().filter(|result| result.error_code == ErrorCode::None) {
+                    result.error_code = ErrorCode::UnknownServerError;
+                }
+            }
+        }
+        KafkaResponseCreateAcls { results: results.into(), ..Default::default() }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::metadata::{append_metadata_records, RemoveAccessControlEntryRecord};
+use crate::kafka::request::KafkaRequestDeleteAcls;
+use crate::kafka::response::{DeleteAclsFilterResult, DeleteAclsMatchingAcl, KafkaResponseDeleteAcls};
+use crate::kafka::security::{AclFilter, AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::ErrorCode;
+use std::collections::HashSet;
+use tracing::{error, info};
+
+impl Broker {
+    /// Deletes the ACLs matching each filter. An ACL matching several
+    /// filters is reported under the first.
+    pub(crate) fn delete_acls(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDeleteAcls,
+    ) -> KafkaResponseDeleteAcls {
+        let denied = if !self.authorizer.is_enabled() {
+            Some((ErrorCode::SecurityDisabled, "No authorizer is configured"))
+        } else if !self.authorize(context, AclOperation::Alter, ResourceType::Cluster, CLUSTER_RESOURCE_NAME) {
+            Some((ErrorCode::ClusterAuthorizationFailed, "Cluster authorization failed"))
+        } else {
+            None
+        };
+        let failed = |error_code, message: &str| DeleteAclsFilterResult {
+            error_code,
+            error_message: Some(message.to_owned()).into(),
+            ..Default::default()
+        };
+        if let Some((error_code, message)) = denied {
+            let results = request.filters.iter().map(|_| failed(error_code, message)).collect::<Vec<_>>();
+            return KafkaResponseDeleteAcls { filter_results: results.into(), ..Default::default() };
+        }
+
+        let mut deleted = HashSet::new();
+        let mut results = Vec::new();
+        for filter in request.filters.0 {
+            let Some(filter) = AclFilter::from_codes(
+                filter.resource_type_filter,
+                filter.resource_name_filter.0,
+                filter.pattern_type_filter,
+                filter.principal_filter.0,
+                filter.host_filter.0,
+                filter.operation,
+                filter.permission_type,
+            ) else {
+                let message = "Unknown resource, pattern, operation or permission type";
+                results.push(failed(ErrorCode::InvalidRequest, message));
+                continue;
+            };
+            let matching_acls = self
+                .authorizer
+                .matching_acls(&filter)
+                .into_iter()
+                .filter(|(id, _)| deleted.insert(*id))
+                .map(|(_, acl)| DeleteAclsMatchingAcl {
+                    resource_type: acl.resource_type.code(),
+                    resource_name: acl.resource_name.into(),
+                    pattern_type: acl.pattern_type.code(),
+                    principal: acl.principal.into(),
+                    host: acl.host.into(),
+                    operation: acl.operation.code(),
+                    permission_type: acl.permission_type.code(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+            results.push(DeleteAclsFilterResult { matching_acls: matching_acls.into(), ..Default::default() });
+        }
+
+        let values = deleted.iter().map(|id| RemoveAccessControlEntryRecord::new(*id).to_value()).collect();
+        match append_metadata_records(&self.log_manager, values) {
+            Ok(()) => {
+                for id in deleted {
+                    info!(%id, "Deleted ACL");
+                    self.authorizer.remove_acl(&id);
+                }
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to write ACL removals");
+                for result in results.iter_mut().filter(|result| result.error_code == ErrorCode::None) {
+                    *result = failed(ErrorCode::UnknownServerError, "Failed to write ACL removals");
+                }
+            }
+        }
+        KafkaResponseDeleteAcls { filter_results: results.into(), ..Default::default() }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestDeleteGroups;
+use crate::kafka::response::{DeleteGroupsResponseResult, KafkaResponseDeleteGroups};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    pub(crate) fn delete_groups(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDeleteGroups,
+    ) -> KafkaResponseDeleteGroups {
+        let (group_ids, unauthorized): (Vec<_>, Vec<_>) = request
+            .groups_names
+            .0
+            .into_iter()
+            .map(|group_id| group_id.0)
+            .partition(|group_id| self.authorize(context, AclOperation::Delete, ResourceType::Group, group_id));
+        let errors = self.group_coordinator.delete_groups(&group_ids);
+        let denied = unauthorized.into_iter().map(|group_id| (group_id, ErrorCode::GroupAuthorizationFailed));
+        let results = group_ids
+            .into_iter()
+            .zip(errors)
+            .chain(denied)
+            .map(|(group_id, error_code)| DeleteGroupsResponseResult {
+                group_id: group_id.into(),
+                error_code,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseDeleteGroups { results: results.into(), ..Default::default() }
+    }
+}
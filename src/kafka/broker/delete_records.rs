@@ -0,0 +1,75 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::{DeleteRecordsPartition, KafkaRequestDeleteRecords, HIGH_WATERMARK};
+use crate::kafka::response::{DeleteRecordsPartitionResult, DeleteRecordsTopicResult, KafkaResponseDeleteRecords};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::ErrorCode;
+use tracing::{error, info};
+
+impl Broker {
+    pub(crate) fn delete_records(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDeleteRecords,
+    ) -> KafkaResponseDeleteRecords {
+        let topics = request
+            .topics
+            .into_iter()
+            .map(|topic| {
+                let authorized = self.authorize(context, AclOperation::Delete, ResourceType::Topic, &topic.name);
+                let partitions = topic
+                    .partitions
+                    .iter()
+                    .map(|partition| {
+                        if !authorized {
+                            let error_code = ErrorCode::TopicAuthorizationFailed;
+                            return DeleteRecordsPartitionResult::new(partition.partition_index, -1, error_code);
+                        }
+                        self.delete_partition_records(&topic.name, partition)
+                    })
+                    .collect();
+                DeleteRecordsTopicResult { name: topic.name, partitions, ..Default::default() }
+            })
+            .collect::<Vec<_>>();
+
+        let deleted = topics.iter().flat_map(|topic| topic.partitions.iter()).any(|p| p.error_code == ErrorCode::None);
+        if deleted {
+            if let Err(err) = self.log_manager.checkpoint_log_start_offsets() {
+                error!(error = %err, "Failed to checkpoint log start offsets after DeleteRecords");
+            }
+        }
+
+        KafkaResponseDeleteRecords { topics: topics.into(), ..Default::default() }
+    }
+
+    fn delete_partition_records(&self, topic: &str, partition: &DeleteRecordsPartition) -> DeleteRecordsPartitionResult {
+        let partition_index = partition.partition_index;
+        let topic_partition = TopicPartition::new(topic, partition_index);
+        let Some(log) = self.log_manager.get_log(&topic_partition) else {
+            return DeleteRecordsPartitionResult::new(partition_index, -1, ErrorCode::UnknownTopicOrPartition);
+        };
+        if !self.log_manager.log_config(topic).cleanup_policy.delete {
+            return DeleteRecordsPartitionResult::new(partition_index, -1, ErrorCode::PolicyViolation);
+        }
+
+        let mut log = log.lock().expect("partition log lock poisoned");
+        let offset = match partition.offset {
+            HIGH_WATERMARK => log.log_end_offset(),
+            offset => offset,
+        };
+        if offset < 0 || offset > log.log_end_offset() {
+            return DeleteRecordsPartitionResult::new(partition_index, -1, ErrorCode::OffsetOutOfRange);
+        }
+
+        match log.increment_log_start_offset(offset) {
+            Ok(deleted) => {
+                info!(partition = %topic_partition, log_start_offset = log.log_start_offset(), deleted, "Deleted records");
+                DeleteRecordsPartitionResult::new(partition_index, log.log_start_offset(), ErrorCode::None)
+            }
+            Err(err) => {
+                error!(partition = %topic_partition, offset, error = %err, "DeleteRecords failed");
+                DeleteRecordsPartitionResult::new(partition_index, -1, ErrorCode::KafkaStorageError)
+            }
+        }
+    }
+}
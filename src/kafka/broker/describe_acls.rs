@@ -0,0 +1,70 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestDescribeAcls;
+use crate::kafka::response::{AclDescription, DescribeAclsResource, KafkaResponseDescribeAcls};
+use crate::kafka::security::{AclFilter, AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    /// Describes the ACLs matching the filter, grouped by resource pattern.
+    pub(crate) fn describe_acls(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDescribeAcls,
+    ) -> KafkaResponseDescribeAcls {
+        let error = |error_code, message: &str| KafkaResponseDescribeAcls {
+            error_code,
+            error_message: Some(message.to_owned()).into(),
+            ..Default::default()
+        };
+        if !self.authorizer.is_enabled() {
+            return error(ErrorCode::SecurityDisabled, "No authorizer is configured");
+        }
+        if !self.authorize(context, AclOperation::Describe, ResourceType::Cluster, CLUSTER_RESOURCE_NAME) {
+            return error(ErrorCode::ClusterAuthorizationFailed, "Cluster authorization failed");
+        }
+        let Some(filter) = AclFilter::from_codes(
+            request.resource_type_filter,
+            request.resource_name_filter.0,
+            request.pattern_type_filter,
+            request.principal_filter.0,
+            request.host_filter.0,
+            request.operation,
+            request.permission_type,
+        ) else {
+            return error(ErrorCode::InvalidRequest, "Unknown resource, pattern, operation or permission type");
+        };
+
+        let mut acls: Vec<_> = self.authorizer.matching_acls(&filter).into_iter().map(|(_, acl)| acl).collect();
+        acls.sort_by(|a, b| {
+            (a.resource_type.code(), &a.resource_name, a.pattern_type.code())
+                .cmp(&(b.resource_type.code(), &b.resource_name, b.pattern_type.code()))
+        });
+        let mut resources: Vec<DescribeAclsResource> = Vec::new();
+        for acl in acls {
+            let description = AclDescription {
+                principal: acl.principal.into(),
+                host: acl.host.into(),
+                operation: acl.operation.code(),
+                permission_type: acl.permission_type.code(),
+                ..Default::default()
+            };
+            match resources.last_mut() {
+                Some(resource)
+                    if resource.resource_type == acl.resource_type.code()
+                        && *resource.resource_name == acl.resource_name
+                        && resource.pattern_type == acl.pattern_type.code() =>
+                {
+                    resource.acls.0.push(description)
+                }
+                _ => resources.push(DescribeAclsResource {
+                    resource_type: acl.resource_type.code(),
+                    resource_name: acl.resource_name.into(),
+                    pattern_type: acl.pattern_type.code(),
+                    acls: vec![description].into(),
+                    ..Default::default()
+                }),
+            }
+        }
+        KafkaResponseDescribeAcls { resources: resources.into(), ..Default::default() }
+    }
+}
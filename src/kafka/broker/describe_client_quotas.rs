@@ -0,0 +1,90 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::quota::{QuotaEntity, QuotaMatch, CLIENT_ID_ENTITY_TYPE, USER_ENTITY_TYPE};
+use crate::kafka::request::{ComponentData, KafkaRequestDescribeClientQuotas};
+use crate::kafka::response::{
+    DescribeClientQuotasEntry, KafkaResponseDescribeClientQuotas, QuotaEntityData, QuotaValueData,
+};
+use crate::kafka::security::{AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    pub(crate) fn describe_client_quotas(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDescribeClientQuotas,
+    ) -> KafkaResponseDescribeClientQuotas {
+        if !self.authorize(context, AclOperation::DescribeConfigs, ResourceType::Cluster, CLUSTER_RESOURCE_NAME) {
+            return KafkaResponseDescribeClientQuotas {
+                error_code: ErrorCode::ClusterAuthorizationFailed,
+                error_message: Some("Cluster authorization failed".to_owned()).into(),
+                ..Default::default()
+            };
+        }
+        let filter = match quota_filter(&request.components) {
+            Ok(filter) => filter,
+            Err(message) => {
+                return KafkaResponseDescribeClientQuotas {
+                    error_code: ErrorCode::InvalidRequest,
+                    error_message: Some(message).into(),
+                    ..Default::default()
+                }
+            }
+        };
+
+        let entries = self
+            .quota_manager
+            .describe(&filter, request.strict)
+            .into_iter()
+            .map(|(entity, values)| {
+                let mut values = values.into_iter().collect::<Vec<_>>();
+                values.sort_by(|(a, _), (b, _)| a.cmp(b));
+                let values = values
+                    .into_iter()
+                    .map(|(key, value)| QuotaValueData { key: key.into(), value, ..Default::default() })
+                    .collect::<Vec<_>>();
+                DescribeClientQuotasEntry {
+                    entity: entity_data(&entity).into(),
+                    values: values.into(),
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseDescribeClientQuotas { entries: Some(entries).into(), ..Default::default() }
+    }
+}
+
+/// The `(entity type, match)` filter of the request components, each entity
+/// type at most once.
+fn quota_filter(components: &[ComponentData]) -> Result<Vec<(String, QuotaMatch)>, String> {
+    let mut filter: Vec<(String, QuotaMatch)> = Vec::new();
+    for component in components {
+        let entity_type = component.entity_type.as_str();
+        if ![USER_ENTITY_TYPE, CLIENT_ID_ENTITY_TYPE].contains(&entity_type) {
+            return Err(format!("Unsupported quota entity type: {entity_type}"));
+        }
+        if filter.iter().any(|(filtered, _)| filtered == entity_type) {
+            return Err(format!("Duplicate filter component entity type: {entity_type}"));
+        }
+        let quota_match = match (component.match_type, component.match_name.as_deref()) {
+            (0, Some(name)) => QuotaMatch::Exact(name.to_owned()),
+            (1, None) => QuotaMatch::Default,
+            (2, None) => QuotaMatch::Any,
+            (0..=2, _) => return Err(format!("Unexpected match for match type {}", component.match_type)),
+            (match_type, _) => return Err(format!("Unknown match type: {match_type}")),
+        };
+        filter.push((entity_type.to_owned(), quota_match));
+    }
+    Ok(filter)
+}
+
+fn entity_data(entity: &QuotaEntity) -> Vec<QuotaEntityData> {
+    entity
+        .components()
+        .into_iter()
+        .map(|(entity_type, name)| QuotaEntityData {
+            entity_type: entity_type.to_owned().into(),
+            entity_name: name.map(str::to_owned).into(),
+            ..Default::default()
+        })
+        .collect()
+}
@@ -0,0 +1,78 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::config::ConfigSource;
+use crate::kafka::request::KafkaRequestDescribeConfigs;
+use crate::kafka::response::{
+    DescribeConfigsResourceResult, DescribeConfigsResult, DescribeConfigsSynonym, KafkaResponseDescribeConfigs,
+};
+use crate::kafka::security::AclOperation;
+
+impl Broker {
+    /// Describes the configs of topics and brokers, with the sources they
+    /// may take their value from if synonyms are requested.
+    pub(crate) fn describe_configs(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDescribeConfigs,
+    ) -> KafkaResponseDescribeConfigs {
+        let results = request
+            .resources
+            .iter()
+            .map(|resource| {
+                let mut result = DescribeConfigsResult {
+                    resource_type: resource.resource_type,
+                    resource_name: resource.resource_name.clone(),
+                    ..Default::default()
+                };
+                let name = &resource.resource_name;
+                let resource_type =
+                    match self.config_resource(context, resource.resource_type, name, AclOperation::DescribeConfigs) {
+                        Ok(resource_type) => resource_type,
+                        Err((error_code, message)) => {
+                            result.error_code = error_code;
+                            result.error_message = Some(message).into();
+                            return result;
+                        }
+                    };
+
+                let keys = resource.configuration_keys.0.as_ref();
+                let configs = self
+                    .dynamic_configs
+                    .describe(resource_type, name)
+                    .into_iter()
+                    .filter(|entry| keys.is_none_or(|keys| keys.iter().any(|key| **key == entry.name)))
+                    .map(|entry| {
+                        let synonyms = if request.include_synonyms {
+                            entry
+                                .synonyms
+                                .into_iter()
+                                .map(|synonym| DescribeConfigsSynonym {
+                                    name: synonym.name.into(),
+                                    value: synonym.value.into(),
+                                    source: synonym.source.code(),
+                                    ..Default::default()
+                                })
+                                .collect()
+                        } else {
+                            Vec::new()
+                        };
+                        DescribeConfigsResourceResult {
+                            name: entry.name.into(),
+                            value: entry.value.into(),
+                            read_only: entry.read_only,
+                            is_default: entry.source == ConfigSource::Default,
+                            config_source: entry.source.code(),
+                            is_sensitive: entry.sensitive,
+                            synonyms: synonyms.into(),
+                            config_type: entry.config_type.code(),
+                            documentation: request.include_documentation.then(|| entry.documentation.to_owned()).into(),
+                            ..Default::default()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                result.configs = configs.into();
+                result
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseDescribeConfigs { results: results.into(), ..Default::default() }
+    }
+}
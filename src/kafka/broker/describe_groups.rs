@@ -0,0 +1,57 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestDescribeGroups;
+use crate::kafka::response::{DescribeGroupsResponseGroup, DescribeGroupsResponseMember, KafkaResponseDescribeGroups};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    pub(crate) fn describe_groups(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDescribeGroups,
+    ) -> KafkaResponseDescribeGroups {
+        let groups = request
+            .groups
+            .iter()
+            .map(|group_id| {
+                if !self.authorize(context, AclOperation::Describe, ResourceType::Group, group_id) {
+                    return DescribeGroupsResponseGroup {
+                        error_code: ErrorCode::GroupAuthorizationFailed,
+                        group_id: group_id.clone(),
+                        authorized_operations: i32::MIN,
+                        ..Default::default()
+                    };
+                }
+                let group = self.group_coordinator.describe_group(group_id);
+                let members = group
+                    .members
+                    .into_iter()
+                    .map(|member| DescribeGroupsResponseMember {
+                        member_id: member.member_id.into(),
+                        group_instance_id: member.group_instance_id.into(),
+                        client_id: member.client_id.into(),
+                        client_host: member.client_host.into(),
+                        member_metadata: member.metadata.into(),
+                        member_assignment: member.assignment.into(),
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>();
+                DescribeGroupsResponseGroup {
+                    error_code: group.error_code,
+                    group_id: group.group_id.into(),
+                    group_state: group.state.into(),
+                    protocol_type: group.protocol_type.into(),
+                    protocol_data: group.protocol_name.into(),
+                    members: members.into(),
+                    authorized_operations: if request.include_authorized_operations {
+                        self.authorized_operations(context, ResourceType::Group, group_id)
+                    } else {
+                        i32::MIN
+                    },
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseDescribeGroups { groups: groups.into(), ..Default::default() }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestDescribeProducers;
+use crate::kafka::response::{
+    DescribeProducersResponseProducer, DescribeProducersResponsePartition, DescribeProducersResponseTopic,
+    KafkaResponseDescribeProducers,
+};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    /// The producers with state on each partition, from the partition's
+    /// producer state.
+    pub(crate) fn describe_producers(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDescribeProducers,
+    ) -> KafkaResponseDescribeProducers {
+        let topics = request
+            .topics
+            .0
+            .into_iter()
+            .map(|topic| {
+                let authorized = self.authorize(context, AclOperation::Read, ResourceType::Topic, &topic.name);
+                let partitions = topic
+                    .partition_indexes
+                    .iter()
+                    .map(|&partition_index| {
+                        if !authorized {
+                            return DescribeProducersResponsePartition {
+                                partition_index,
+                                error_code: ErrorCode::TopicAuthorizationFailed,
+                                ..Default::default()
+                            };
+                        }
+                        let topic_partition = TopicPartition::new(topic.name.as_str(), partition_index);
+                        let Some(log) = self.log_manager.get_log(&topic_partition) else {
+                            return DescribeProducersResponsePartition {
+                                partition_index,
+                                error_code: ErrorCode::UnknownTopicOrPartition,
+                                ..Default::default()
+                            };
+                        };
+                        let producers = log
+                            .lock()
+                            .expect("partition log lock poisoned")
+                            .active_producers()
+                            .into_iter()
+                            .map(|producer| DescribeProducersResponseProducer {
+                                producer_id: producer.producer_id,
+                                producer_epoch: producer.producer_epoch.into(),
+                                last_sequence: producer.last_sequence,
+                                last_timestamp: producer.last_timestamp,
+                                coordinator_epoch: producer.coordinator_epoch,
+                                current_txn_start_offset: producer.current_txn_start_offset,
+                                ..Default::default()
+                            })
+                            .collect::<Vec<_>>();
+                        DescribeProducersResponsePartition {
+                            partition_index,
+                            active_producers: producers.into(),
+                            ..Default::default()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                DescribeProducersResponseTopic { name: topic.name, partitions: partitions.into(), ..Default::default() }
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseDescribeProducers { topics: topics.into(), ..Default::default() }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::coordinator::{CONSUMER_OFFSETS_TOPIC, TRANSACTION_STATE_TOPIC};
+use crate::kafka::metadata::CLUSTER_METADATA_TOPIC;
+use crate::kafka::request::KafkaRequestDescribeTopicPartitions;
+use crate::kafka::response::{
+    DescribeTopicPartitionsResponseCursor, DescribeTopicPartitionsResponsePartition,
+    DescribeTopicPartitionsResponseTopic, KafkaResponseDescribeTopicPartitions,
+};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::ErrorCode;
+
+/// The most partitions described in one response, Kafka's default
+/// `max.request.partition.size.limit`.
+const MAX_RESPONSE_PARTITIONS: i32 = 2000;
+
+impl Broker {
+    /// Describes the partitions of the requested topics, or of every topic
+    /// the connection may describe, in pages of at most
+    /// `response_partition_limit` partitions. This broker leads every
+    /// partition and is its only replica.
+    pub(crate) fn describe_topic_partitions(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDescribeTopicPartitions,
+    ) -> KafkaResponseDescribeTopicPartitions {
+        let all_topics = self.log_manager.topics();
+        let mut names = if request.topics.is_empty() {
+            all_topics
+                .keys()
+                .filter(|name| name.as_str() != CLUSTER_METADATA_TOPIC)
+                .filter(|name| self.authorize(context, AclOperation::Describe, ResourceType::Topic, name))
+                .cloned()
+                .collect::<Vec<_>>()
+        } else {
+            request.topics.0.into_iter().map(|topic| topic.name.0).collect()
+        };
+        names.sort();
+        names.dedup();
+
+        let (cursor_topic, cursor_partition) =
+            request.cursor.map_or((String::new(), 0), |cursor| (cursor.topic_name.0, cursor.partition_index));
+        let mut remaining = request.response_partition_limit.clamp(1, MAX_RESPONSE_PARTITIONS);
+        let mut topics = Vec::new();
+        let mut next_cursor = None;
+        for name in names.into_iter().filter(|name| *name >= cursor_topic) {
+            if !self.authorize(context, AclOperation::Describe, ResourceType::Topic, &name) {
+                topics.push(DescribeTopicPartitionsResponseTopic {
+                    error_code: ErrorCode::TopicAuthorizationFailed,
+                    name: Some(name).into(),
+                    topic_authorized_operations: i32::MIN,
+                    ..Default::default()
+                });
+                continue;
+            }
+            let Some(metadata) = all_topics.get(&name).filter(|_| name != CLUSTER_METADATA_TOPIC) else {
+                topics.push(DescribeTopicPartitionsResponseTopic {
+                    error_code: ErrorCode::UnknownTopicOrPartition,
+                    name: Some(name).into(),
+                    topic_authorized_operations: i32::MIN,
+                    ..Default::default()
+                });
+                continue;
+            };
+
+            let first_partition = if name == cursor_topic { cursor_partition } else { 0 };
+            if remaining == 0 {
+                next_cursor = Some(DescribeTopicPartitionsResponseCursor {
+                    topic_name: name.into(),
+                    partition_index: first_partition,
+                    ..Default::default()
+                });
+                break;
+            }
+            let end_partition = metadata.num_partitions.min(first_partition.saturating_add(remaining));
+            let partitions = (first_partition..end_partition)
+                .map(|partition_index| self.describe_partition(&name, partition_index))
+                .collect::<Vec<_>>();
+            remaining -= partitions.len() as i32;
+            if end_partition < metadata.num_partitions {
+                next_cursor = Some(DescribeTopicPartitionsResponseCursor {
+                    topic_name: name.clone().into(),
+                    partition_index: end_partition,
+                    ..Default::default()
+                });
+            }
+            topics.push(DescribeTopicPartitionsResponseTopic {
+                error_code: ErrorCode::None,
+                is_internal: name == CONSUMER_OFFSETS_TOPIC || name == TRANSACTION_STATE_TOPIC,
+                topic_id: metadata.topic_id,
+                partitions: partitions.into(),
+                topic_authorized_operations: self.authorized_operations(context, ResourceType::Topic, &name),
+                name: Some(name).into(),
+                ..Default::default()
+            });
+            if next_cursor.is_some() {
+                break;
+            }
+        }
+
+        KafkaResponseDescribeTopicPartitions { topics: topics.into(), next_cursor, ..Default::default() }
+    }
+
+    fn describe_partition(&self, topic: &str, partition_index: i32) -> DescribeTopicPartitionsResponsePartition {
+        let Some(log) = self.log_manager.get_log(&TopicPartition::new(topic, partition_index)) else {
+            return DescribeTopicPartitionsResponsePartition {
+                error_code: ErrorCode::UnknownTopicOrPartition,
+                partition_index,
+                ..Default::default()
+            };
+        };
+        let leader_epoch = log.lock().expect("partition log lock poisoned").latest_epoch().unwrap_or(0);
+        let node_id = self.config.node_id;
+        DescribeTopicPartitionsResponsePartition {
+            error_code: ErrorCode::None,
+            partition_index,
+            leader_id: node_id,
+            leader_epoch,
+            replica_nodes: vec![node_id].into(),
+            isr_nodes: vec![node_id].into(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestDescribeTransactions;
+use crate::kafka::response::{
+    DescribeTransactionsResponseState, DescribeTransactionsResponseTopic, KafkaResponseDescribeTransactions,
+};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    pub(crate) fn describe_transactions(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestDescribeTransactions,
+    ) -> KafkaResponseDescribeTransactions {
+        let states = request
+            .transactional_ids
+            .0
+            .into_iter()
+            .map(|transactional_id| {
+                let resource_type = ResourceType::TransactionalId;
+                let described = if self.authorize(context, AclOperation::Describe, resource_type, &transactional_id) {
+                    self.transaction_coordinator.describe_transaction(&transactional_id)
+                } else {
+                    Err(ErrorCode::TransactionalIdAuthorizationFailed)
+                };
+                (transactional_id, described)
+            })
+            .map(|(transactional_id, described)| match described {
+                Ok(metadata) => {
+                    let mut topics: Vec<DescribeTransactionsResponseTopic> = Vec::new();
+                    for topic_partition in &metadata.partitions {
+                        match topics.last_mut() {
+                            Some(topic) if *topic.topic == topic_partition.topic => {
+                                topic.partitions.0.push(topic_partition.partition)
+                            }
+                            _ => topics.push(DescribeTransactionsResponseTopic {
+                                topic: topic_partition.topic.as_str().into(),
+                                partitions: vec![topic_partition.partition].into(),
+                                ..Default::default()
+                            }),
+                        }
+                    }
+                    DescribeTransactionsResponseState {
+                        transactional_id,
+                        transaction_state: metadata.state.to_string().into(),
+                        transaction_timeout_ms: metadata.timeout_ms,
+                        transaction_start_time_ms: metadata.start_timestamp_ms,
+                        producer_id: metadata.producer_id,
+                        producer_epoch: metadata.producer_epoch,
+                        topics: topics.into(),
+                        ..Default::default()
+                    }
+                }
+                Err(error_code) => DescribeTransactionsResponseState {
+                    error_code,
+                    transactional_id,
+                    producer_id: -1,
+                    producer_epoch: -1,
+                    transaction_timeout_ms: -1,
+                    transaction_start_time_ms: -1,
+                    ..Default::default()
+                },
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseDescribeTransactions { transaction_states: states.into(), ..Default::default() }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::kafka::broker::{fenced_error_code, Broker, RequestContext};
+use crate::kafka::request::KafkaRequestEndTxn;
+use crate::kafka::response::KafkaResponseEndTxn;
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::{ErrorCode, MessageVersion};
+
+impl Broker {
+    pub(crate) fn end_txn(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestEndTxn,
+    ) -> KafkaResponseEndTxn {
+        if !self.authorize(context, AclOperation::Write, ResourceType::TransactionalId, &request.transactional_id) {
+            let error_code = ErrorCode::TransactionalIdAuthorizationFailed;
+            return KafkaResponseEndTxn { error_code, ..Default::default() };
+        }
+        let error_code = self.transaction_coordinator.end_txn(
+            &request.transactional_id,
+            request.producer_id,
+            request.producer_epoch,
+            request.committed,
+        );
+        KafkaResponseEndTxn { error_code: fenced_error_code(error_code, version, 2), ..Default::default() }
+    }
+}
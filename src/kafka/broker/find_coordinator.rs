@@ -0,0 +1,73 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::{CoordinatorType, KafkaRequestFindCoordinator};
+use crate::kafka::response::{Coordinator, KafkaResponseFindCoordinator};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::{ErrorCode, KafkaString, MessageVersion};
+
+impl Broker {
+    /// Every group and transactional id is coordinated by this broker,
+    /// reached through the listener the request arrived on, unless it is
+    /// fenced.
+    pub(crate) fn find_coordinator(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestFindCoordinator,
+    ) -> KafkaResponseFindCoordinator {
+        if version.version >= 4 {
+            let coordinators = request
+                .coordinator_keys
+                .0
+                .into_iter()
+                .map(|key| self.coordinator(context, request.key_type, key))
+                .collect::<Vec<_>>();
+            return KafkaResponseFindCoordinator { coordinators: coordinators.into(), ..Default::default() };
+        }
+
+        let coordinator = self.coordinator(context, request.key_type, request.key);
+        KafkaResponseFindCoordinator {
+            error_code: coordinator.error_code,
+            error_message: coordinator.error_message,
+            node_id: coordinator.node_id,
+            host: coordinator.host,
+            port: coordinator.port,
+            ..Default::default()
+        }
+    }
+
+    fn coordinator(&self, context: &RequestContext, key_type: CoordinatorType, key: KafkaString) -> Coordinator {
+        let failed = |error_code, message: &str| Coordinator {
+            node_id: -1,
+            port: -1,
+            error_code,
+            error_message: Some(message.to_owned()).into(),
+            ..Default::default()
+        };
+
+        let (resource_type, denied) = match key_type {
+            CoordinatorType::Group => (ResourceType::Group, ErrorCode::GroupAuthorizationFailed),
+            CoordinatorType::Transaction => {
+                (ResourceType::TransactionalId, ErrorCode::TransactionalIdAuthorizationFailed)
+            }
+        };
+        if !self.authorize(context, AclOperation::Describe, resource_type, &key) {
+            return Coordinator { key, ..failed(denied, "Authorization failed") };
+        }
+        if self.cluster_control.is_fenced(self.config.node_id) {
+            return Coordinator { key, ..failed(ErrorCode::CoordinatorNotAvailable, "The broker is fenced") };
+        }
+        let Some(endpoint) = self.config.advertised_endpoint(&context.listener_name) else {
+            return Coordinator {
+                key,
+                ..failed(ErrorCode::CoordinatorNotAvailable, "The listener has no advertised endpoint")
+            };
+        };
+        Coordinator {
+            key,
+            node_id: self.config.node_id,
+            host: endpoint.host.as_str().into(),
+            port: endpoint.port as i32,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestHeartbeat;
+use crate::kafka::response::KafkaResponseHeartbeat;
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    pub(crate) fn heartbeat(&self, context: &RequestContext, request: KafkaRequestHeartbeat) -> KafkaResponseHeartbeat {
+        if !self.authorize(context, AclOperation::Read, ResourceType::Group, &request.group_id) {
+            return KafkaResponseHeartbeat { error_code: ErrorCode::GroupAuthorizationFailed, ..Default::default() };
+        }
+        let error_code = self.group_coordinator.heartbeat(
+            &request.group_id,
+            request.generation_id,
+            &request.member_id,
+            request.group_instance_id.as_deref(),
+        );
+        KafkaResponseHeartbeat { error_code, ..Default::default() }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::config::{config_def, ConfigError, ConfigType};
+use crate::kafka::metadata::ConfigResourceType;
+use crate::kafka::request::{IncrementalAlterableConfig, KafkaRequestIncrementalAlterConfigs};
+use crate::kafka::response::KafkaResponseIncrementalAlterConfigs;
+use std::collections::{HashMap, HashSet};
+
+const SET: i8 = 0;
+const DELETE: i8 = 1;
+const APPEND: i8 = 2;
+const SUBTRACT: i8 = 3;
+
+impl Broker {
+    /// Applies SET, DELETE, APPEND and SUBTRACT operations to the dynamic
+    /// configs of each resource, leaving other configs as they are.
+    pub(crate) fn incremental_alter_configs(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestIncrementalAlterConfigs,
+    ) -> KafkaResponseIncrementalAlterConfigs {
+        let responses = request
+            .resources
+            .iter()
+            .map(|resource| {
+                let name = &resource.resource_name;
+                self.alter_resource_configs(
+                    context,
+                    resource.resource_type,
+                    name,
+                    request.validate_only,
+                    |resource_type, configs| {
+                        let mut seen = HashSet::new();
+                        for config in resource.configs.iter() {
+                            if !seen.insert(config.name.as_str()) {
+                                let message = format!("Duplicate config key {}", *config.name);
+                                return Err(ConfigError::InvalidRequest(message));
+                            }
+                            self.apply_config_operation(resource_type, name, configs, config)?;
+                        }
+                        Ok(())
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseIncrementalAlterConfigs { responses: responses.into(), ..Default::default() }
+    }
+
+    /// Applies one operation. Lists are appended to and subtracted from
+    /// starting at the value in effect.
+    fn apply_config_operation(
+        &self,
+        resource_type: ConfigResourceType,
+        resource_name: &str,
+        configs: &mut HashMap<String, String>,
+        config: &IncrementalAlterableConfig,
+    ) -> Result<(), ConfigError> {
+        let name = config.name.to_string();
+        let value = || {
+            config.value.0.clone().ok_or_else(|| ConfigError::InvalidRequest(format!("Null value for config {name}")))
+        };
+        match config.config_operation {
+            SET => {
+                configs.insert(name.clone(), value()?);
+            }
+            DELETE => {
+                configs.remove(&name);
+            }
+            APPEND | SUBTRACT => {
+                if config_def(resource_type, &name).is_none_or(|def| def.config_type != ConfigType::List) {
+                    return Err(ConfigError::InvalidConfig(format!("Config {name} is not a list")));
+                }
+                let current = match configs.get(&name) {
+                    Some(current) => Some(current.clone()),
+                    None => self
+                        .dynamic_configs
+                        .describe(resource_type, resource_name)
+                        .into_iter()
+                        .find(|entry| entry.name == name)
+                        .and_then(|entry| entry.value),
+                };
+                let mut items: Vec<String> = current
+                    .iter()
+                    .flat_map(|current| current.split(','))
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_owned)
+                    .collect();
+                let value = value()?;
+                let changes = value.split(',').map(str::trim).filter(|item| !item.is_empty());
+                if config.config_operation == APPEND {
+                    for item in changes {
+                        if !items.iter().any(|existing| existing == item) {
+                            items.push(item.to_owned());
+                        }
+                    }
+                } else {
+                    let removed: Vec<_> = changes.collect();
+                    items.retain(|item| !removed.contains(&item.as_str()));
+                }
+                configs.insert(name.clone(), items.join(","));
+            }
+            operation => {
+                return Err(ConfigError::InvalidRequest(format!("Unknown config operation {operation}")));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::kafka::broker::{fenced_error_code, Broker, RequestContext};
+use crate::kafka::request::KafkaRequestInitProducerId;
+use crate::kafka::response::KafkaResponseInitProducerId;
+use crate::kafka::security::{AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::{ErrorCode, MessageVersion};
+
+impl Broker {
+    /// Gives an idempotent producer a new producer id at epoch 0, also when
+    /// it asks to bump the epoch of its current one. Transactional producers
+    /// get the producer id of their transactional id at a bumped epoch.
+    pub(crate) fn init_producer_id(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestInitProducerId,
+    ) -> KafkaResponseInitProducerId {
+        let transactional_id = request.transactional_id.0.as_deref();
+        let expected = (request.producer_id >= 0).then_some((request.producer_id, request.producer_epoch));
+        let result = self.authorize_producer(context, transactional_id).and_then(|()| {
+            self.transaction_coordinator.init_producer_id(transactional_id, request.transaction_timeout_ms, expected)
+        });
+        match result {
+            Ok((producer_id, producer_epoch)) => {
+                KafkaResponseInitProducerId { producer_id, producer_epoch, ..Default::default() }
+            }
+            Err(error_code) => KafkaResponseInitProducerId {
+                error_code: fenced_error_code(error_code, version, 4),
+                producer_id: -1,
+                producer_epoch: -1,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Idempotent producers need IdempotentWrite on the cluster or Write on
+    /// some topic, transactional ones Write on their transactional id.
+    fn authorize_producer(&self, context: &RequestContext, transactional_id: Option<&str>) -> Result<(), ErrorCode> {
+        match transactional_id {
+            Some(transactional_id) => {
+                if self.authorize(context, AclOperation::Write, ResourceType::TransactionalId, transactional_id) {
+                    Ok(())
+                } else {
+                    Err(ErrorCode::TransactionalIdAuthorizationFailed)
+                }
+            }
+            None => {
+                let idempotent_write = AclOperation::IdempotentWrite;
+                if self.authorize(context, idempotent_write, ResourceType::Cluster, CLUSTER_RESOURCE_NAME)
+                    || self.authorize_any(context, AclOperation::Write, ResourceType::Topic)
+                {
+                    Ok(())
+                } else {
+                    Err(ErrorCode::ClusterAuthorizationFailed)
+                }
+            }
+        }
+    }
+}
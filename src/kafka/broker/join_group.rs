@@ -0,0 +1,68 @@
+use crate::kafka::broker::{Broker, RequestContext, RequestHandler};
+use crate::kafka::coordinator::{JoinGroupParams, JoinGroupResult};
+use crate::kafka::request::KafkaRequestJoinGroup;
+use crate::kafka::response::{JoinGroupResponseMember, KafkaResponseJoinGroup};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::{ErrorCode, MessageVersion};
+use std::time::Instant;
+
+impl Broker {
+    pub(crate) async fn join_group(
+        &self,
+        context: &mut RequestContext,
+        handler: &mut RequestHandler,
+        client_id: &str,
+        version: MessageVersion,
+        request: KafkaRequestJoinGroup,
+    ) -> KafkaResponseJoinGroup {
+        let params = JoinGroupParams {
+            group_id: request.group_id.0,
+            member_id: request.member_id.0,
+            group_instance_id: request.group_instance_id.0,
+            client_id: client_id.to_owned(),
+            client_host: format!("/{}", context.client_addr.ip()),
+            session_timeout_ms: request.session_timeout_ms,
+            rebalance_timeout_ms: request.rebalance_timeout_ms,
+            protocol_type: request.protocol_type.0,
+            protocols: request.protocols.0.into_iter().map(|protocol| (protocol.name.0, protocol.metadata.0)).collect(),
+            require_known_member_id: version.version >= 4,
+            supports_skip_assignment: version.version >= 9,
+        };
+        let result = if self.authorize(context, AclOperation::Read, ResourceType::Group, &params.group_id) {
+            let waiting = Instant::now();
+            let result = handler.wait_remote(self.group_coordinator.join_group(params)).await;
+            context.remote_time += waiting.elapsed();
+            result
+        } else {
+            JoinGroupResult::error(params.member_id, ErrorCode::GroupAuthorizationFailed)
+        };
+
+        let members = result
+            .members
+            .into_iter()
+            .map(|member| JoinGroupResponseMember {
+                member_id: member.member_id.into(),
+                group_instance_id: member.group_instance_id.into(),
+                metadata: member.metadata.into(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        // The protocol name is only nullable from v7.
+        let protocol_name = match result.protocol_name {
+            None if version.version < 7 => Some(String::new()),
+            protocol_name => protocol_name,
+        };
+
+        KafkaResponseJoinGroup {
+            error_code: result.error_code,
+            generation_id: result.generation_id,
+            protocol_type: result.protocol_type.into(),
+            protocol_name: protocol_name.into(),
+            leader: result.leader_id.into(),
+            skip_assignment: result.skip_assignment,
+            member_id: result.member_id.into(),
+            members: members.into(),
+            ..Default::default()
+        }
+    }
+}
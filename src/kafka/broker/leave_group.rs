@@ -0,0 +1,52 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::coordinator::LeavingMember;
+use crate::kafka::request::KafkaRequestLeaveGroup;
+use crate::kafka::response::{KafkaResponseLeaveGroup, LeaveGroupResponseMember};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::{ErrorCode, MessageVersion};
+
+impl Broker {
+    pub(crate) fn leave_group(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestLeaveGroup,
+    ) -> KafkaResponseLeaveGroup {
+        if !self.authorize(context, AclOperation::Read, ResourceType::Group, &request.group_id) {
+            return KafkaResponseLeaveGroup { error_code: ErrorCode::GroupAuthorizationFailed, ..Default::default() };
+        }
+        // Before v3 a single member leaves and its error is the top-level one.
+        if version.version <= 2 {
+            let leaving = LeavingMember { member_id: request.member_id.0, group_instance_id: None };
+            let errors = self.group_coordinator.leave_group(&request.group_id, &[leaving]);
+            return KafkaResponseLeaveGroup {
+                error_code: errors.first().copied().unwrap_or_default(),
+                ..Default::default()
+            };
+        }
+
+        let leaving = request
+            .members
+            .iter()
+            .map(|member| LeavingMember {
+                member_id: member.member_id.0.clone(),
+                group_instance_id: member.group_instance_id.0.clone(),
+            })
+            .collect::<Vec<_>>();
+        let errors = self.group_coordinator.leave_group(&request.group_id, &leaving);
+        let members = request
+            .members
+            .0
+            .into_iter()
+            .zip(errors)
+            .map(|(member, error_code)| LeaveGroupResponseMember {
+                member_id: member.member_id,
+                group_instance_id: member.group_instance_id,
+                error_code,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        KafkaResponseLeaveGroup { error_code: ErrorCode::None, members: members.into(), ..Default::default() }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestListGroups;
+use crate::kafka::response::{KafkaResponseListGroups, ListGroupsResponseGroup};
+use crate::kafka::security::{AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    /// Lists the groups the connection may describe. Describe on the cluster
+    /// allows listing every group.
+    pub(crate) fn list_groups(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestListGroups,
+    ) -> KafkaResponseListGroups {
+        let states = request.states_filter.0.into_iter().map(|state| state.0).collect::<Vec<_>>();
+        let types = request.types_filter.0.into_iter().map(|group_type| group_type.0).collect::<Vec<_>>();
+        let describe_cluster =
+            self.authorize(context, AclOperation::Describe, ResourceType::Cluster, CLUSTER_RESOURCE_NAME);
+        let groups = self
+            .group_coordinator
+            .list_groups(&states, &types)
+            .into_iter()
+            .filter(|listing| {
+                describe_cluster
+                    || self.authorize(context, AclOperation::Describe, ResourceType::Group, &listing.group_id)
+            })
+            .map(|listing| ListGroupsResponseGroup {
+                group_id: listing.group_id.into(),
+                protocol_type: listing.protocol_type.into(),
+                group_state: listing.state.into(),
+                group_type: listing.group_type.into(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseListGroups { error_code: ErrorCode::None, groups: groups.into(), ..Default::default() }
+    }
+}
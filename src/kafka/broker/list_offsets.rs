@@ -0,0 +1,132 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::{
+    IsolationLevel, KafkaRequestListOffsets, ListOffsetsPartition, EARLIEST_LOCAL_TIMESTAMP, EARLIEST_TIMESTAMP,
+    LATEST_TIERED_TIMESTAMP, LATEST_TIMESTAMP, MAX_TIMESTAMP,
+};
+use crate::kafka::response::{KafkaResponseListOffsets, ListOffsetsPartitionResponse, ListOffsetsTopicResponse};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::{TimestampAndOffset, TopicPartition, UNDEFINED_EPOCH};
+use crate::kafka::types::{ErrorCode, MessageVersion};
+use tracing::error;
+
+impl Broker {
+    pub(crate) fn list_offsets(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestListOffsets,
+    ) -> KafkaResponseListOffsets {
+        let topics = request
+            .topics
+            .into_iter()
+            .map(|topic| {
+                let authorized = self.authorize(context, AclOperation::Describe, ResourceType::Topic, &topic.name);
+                let partitions = topic
+                    .partitions
+                    .iter()
+                    .map(|partition| {
+                        if !authorized {
+                            let error_code = ErrorCode::TopicAuthorizationFailed;
+                            return ListOffsetsPartitionResponse::new(partition.partition_index, error_code);
+                        }
+                        if !supports_timestamp(version, partition.timestamp) {
+                            let error_code = ErrorCode::UnsupportedVersion;
+                            return ListOffsetsPartitionResponse::new(partition.partition_index, error_code);
+                        }
+                        self.list_partition_offset(&topic.name, partition, request.isolation_level)
+                    })
+                    .collect();
+                ListOffsetsTopicResponse { name: topic.name, partitions, ..Default::default() }
+            })
+            .collect();
+
+        KafkaResponseListOffsets { topics, ..Default::default() }
+    }
+
+    fn list_partition_offset(
+        &self,
+        topic: &str,
+        partition: &ListOffsetsPartition,
+        isolation_level: IsolationLevel,
+    ) -> ListOffsetsPartitionResponse {
+        let topic_partition = TopicPartition::new(topic, partition.partition_index);
+        let Some(log) = self.log_manager.get_log(&topic_partition) else {
+            return ListOffsetsPartitionResponse::new(partition.partition_index, ErrorCode::UnknownTopicOrPartition);
+        };
+        let log = log.lock().expect("partition log lock poisoned");
+
+        let upper_bound = match isolation_level {
+            IsolationLevel::ReadUncommitted => log.log_end_offset(),
+            IsolationLevel::ReadCommitted => log.last_stable_offset(),
+        };
+        let offset_only = |offset| {
+            let leader_epoch = log.epoch_for_offset(offset).unwrap_or(UNDEFINED_EPOCH);
+            Ok(Some(TimestampAndOffset { timestamp: -1, offset, leader_epoch }))
+        };
+        let found = match partition.timestamp {
+            EARLIEST_TIMESTAMP | EARLIEST_LOCAL_TIMESTAMP => offset_only(log.log_start_offset()),
+            LATEST_TIMESTAMP => offset_only(upper_bound),
+            // Without tiered storage nothing has been uploaded.
+            LATEST_TIERED_TIMESTAMP => offset_only(-1),
+            MAX_TIMESTAMP => log.offset_of_max_timestamp().map(|found| found.filter(|found| found.offset < upper_bound)),
+            timestamp => log.offset_for_timestamp(timestamp).map(|found| found.filter(|found| found.offset < upper_bound)),
+        };
+
+        let mut response = ListOffsetsPartitionResponse::new(partition.partition_index, ErrorCode::None);
+        match found {
+            Ok(Some(found)) => {
+                response.timestamp = found.timestamp;
+                response.offset = found.offset;
+                response.leader_epoch = found.leader_epoch;
+            }
+            Ok(None) => {}
+            Err(err) => {
+                error!(partition = %topic_partition, timestamp = partition.timestamp, error = %err, "ListOffsets failed");
+                response.error_code = ErrorCode::KafkaStorageError;
+            }
+        }
+        response
+    }
+}
+
+/// Whether the special `timestamp` exists in the request `version`: the
+/// max timestamp lookup from v7, the local and tiered ones from v8 and v9.
+fn supports_timestamp(version: MessageVersion, timestamp: i64) -> bool {
+    let min_version = match timestamp {
+        MAX_TIMESTAMP => 7,
+        EARLIEST_LOCAL_TIMESTAMP => 8,
+        LATEST_TIERED_TIMESTAMP => 9,
+        _ => 0,
+    };
+    version.version >= min_version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::types::ApiKey;
+
+    fn version(version: i16) -> MessageVersion {
+        MessageVersion::new(ApiKey::ListOffsets, version)
+    }
+
+    #[test]
+    fn test_max_timestamp_needs_v7() {
+        assert!(!supports_timestamp(version(6), MAX_TIMESTAMP));
+        assert!(supports_timestamp(version(7), MAX_TIMESTAMP));
+        assert!(supports_timestamp(version(1), LATEST_TIMESTAMP));
+    }
+
+    #[test]
+    fn test_earliest_local_timestamp_needs_v8() {
+        assert!(!supports_timestamp(version(7), EARLIEST_LOCAL_TIMESTAMP));
+        assert!(supports_timestamp(version(8), EARLIEST_LOCAL_TIMESTAMP));
+        assert!(supports_timestamp(version(1), EARLIEST_TIMESTAMP));
+    }
+
+    #[test]
+    fn test_latest_tiered_timestamp_needs_v9() {
+        assert!(!supports_timestamp(version(8), LATEST_TIERED_TIMESTAMP));
+        assert!(supports_timestamp(version(9), LATEST_TIERED_TIMESTAMP));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::coordinator::TransactionState;
+use crate::kafka::request::KafkaRequestListTransactions;
+use crate::kafka::response::{KafkaResponseListTransactions, ListTransactionsResponseState};
+use crate::kafka::security::{AclOperation, ResourceType};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl Broker {
+    /// Lists the transactional ids matching every filter. State filters that
+    /// name no state are returned; if no filter names a state, nothing matches.
+    /// Only transactional ids the connection may describe are listed.
+    pub(crate) fn list_transactions(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestListTransactions,
+    ) -> KafkaResponseListTransactions {
+        let (states, unknown): (Vec<_>, Vec<_>) = request
+            .state_filters
+            .0
+            .into_iter()
+            .map(|name| (TransactionState::from_name(&name), name))
+            .partition(|(state, _)| state.is_some());
+        let states = states.into_iter().filter_map(|(state, _)| state).collect::<Vec<_>>();
+        let unknown = unknown.into_iter().map(|(_, name)| name).collect::<Vec<_>>();
+        if states.is_empty() && !unknown.is_empty() {
+            return KafkaResponseListTransactions { unknown_state_filters: unknown.into(), ..Default::default() };
+        }
+
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+        let transactions = self
+            .transaction_coordinator
+            .list_transactions(&states, &request.producer_id_filters, request.duration_filter, now_ms)
+            .into_iter()
+            .filter(|metadata| {
+                let transactional_id = &metadata.transactional_id;
+                self.authorize(context, AclOperation::Describe, ResourceType::TransactionalId, transactional_id)
+            })
+            .map(|metadata| ListTransactionsResponseState {
+                transactional_id: metadata.transactional_id.into(),
+                producer_id: metadata.producer_id,
+                transaction_state: metadata.state.to_string().into(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseListTransactions {
+            unknown_state_filters: unknown.into(),
+            transaction_states: transactions.into(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::coordinator::{OffsetAndMetadata, OffsetCommitParams};
+use crate::kafka::request::KafkaRequestOffsetCommit;
+use crate::kafka::response::{KafkaResponseOffsetCommit, OffsetCommitResponsePartition, OffsetCommitResponseTopic};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::{ErrorCode, MessageVersion};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl Broker {
+    /// Commits the offsets of the topics the connection may read. Partitions
+    /// of other topics fail with TOPIC_AUTHORIZATION_FAILED.
+    pub(crate) fn offset_commit(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestOffsetCommit,
+    ) -> KafkaResponseOffsetCommit {
+        let group_authorized = self.authorize(context, AclOperation::Read, ResourceType::Group, &request.group_id);
+        let topic_errors = request
+            .topics
+            .iter()
+            .map(|topic| {
+                if !group_authorized {
+                    Some(ErrorCode::GroupAuthorizationFailed)
+                } else if !self.authorize(context, AclOperation::Read, ResourceType::Topic, &topic.name) {
+                    Some(ErrorCode::TopicAuthorizationFailed)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+        let offsets = request
+            .topics
+            .iter()
+            .zip(&topic_errors)
+            .filter(|(_, error)| error.is_none())
+            .flat_map(|(topic, _)| {
+                topic.partitions.iter().map(|partition| {
+                    let offset = OffsetAndMetadata {
+                        offset: partition.committed_offset,
+                        leader_epoch: partition.committed_leader_epoch,
+                        metadata: partition.committed_metadata.0.clone().unwrap_or_default(),
+                        commit_timestamp: now_ms,
+                    };
+                    (TopicPartition::new(topic.name.as_str(), partition.partition_index), offset)
+                })
+            })
+            .collect();
+        let params = OffsetCommitParams {
+            group_id: request.group_id.0,
+            generation_id: request.generation_id_or_member_epoch,
+            member_id: request.member_id.0,
+            group_instance_id: request.group_instance_id.0,
+            offsets,
+            api_version: version.version,
+        };
+        let errors = if group_authorized { self.group_coordinator.commit_offsets(params) } else { Vec::new() };
+        let mut errors = errors.into_iter();
+
+        let topics = request
+            .topics
+            .0
+            .into_iter()
+            .zip(topic_errors)
+            .map(|(topic, topic_error)| {
+                let partitions = topic
+                    .partitions
+                    .iter()
+                    .map(|partition| OffsetCommitResponsePartition {
+                        partition_index: partition.partition_index,
+                        error_code: topic_error.unwrap_or_else(|| errors.next().unwrap_or_default()),
+                        ..Default::default()
+                    })
+                    .collect();
+                OffsetCommitResponseTopic { name: topic.name, partitions, ..Default::default() }
+            })
+            .collect();
+        KafkaResponseOffsetCommit { topics, ..Default::default() }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestOffsetDelete;
+use crate::kafka::response::{KafkaResponseOffsetDelete, OffsetDeleteResponsePartition, OffsetDeleteResponseTopic};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::ErrorCode;
+
+impl Broker {
+    /// Deletes the committed offsets of the topics the connection may read.
+    /// Partitions of other topics fail with TOPIC_AUTHORIZATION_FAILED.
+    pub(crate) fn offset_delete(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestOffsetDelete,
+    ) -> KafkaResponseOffsetDelete {
+        if !self.authorize(context, AclOperation::Delete, ResourceType::Group, &request.group_id) {
+            return KafkaResponseOffsetDelete { error_code: ErrorCode::GroupAuthorizationFailed, ..Default::default() };
+        }
+        let (topics, unauthorized): (Vec<_>, Vec<_>) = request
+            .topics
+            .0
+            .into_iter()
+            .partition(|topic| self.authorize(context, AclOperation::Read, ResourceType::Topic, &topic.name));
+        let partitions = topics
+            .iter()
+            .flat_map(|topic| {
+                topic.partitions.iter().map(|&partition| TopicPartition::new(topic.name.as_str(), partition))
+            })
+            .collect();
+        let results = match self.group_coordinator.delete_offsets(&request.group_id, partitions) {
+            Ok(results) => results,
+            Err(error_code) => return KafkaResponseOffsetDelete { error_code, ..Default::default() },
+        };
+
+        let denied = unauthorized.iter().flat_map(|topic| {
+            topic.partitions.iter().map(|&partition| {
+                (TopicPartition::new(topic.name.as_str(), partition), ErrorCode::TopicAuthorizationFailed)
+            })
+        });
+        let mut topics: Vec<OffsetDeleteResponseTopic> = Vec::new();
+        for (topic_partition, error_code) in results.into_iter().chain(denied) {
+            let partition = OffsetDeleteResponsePartition { partition_index: topic_partition.partition, error_code };
+            match topics.last_mut() {
+                Some(topic) if *topic.name == topic_partition.topic => topic.partitions.0.push(partition),
+                _ => topics.push(OffsetDeleteResponseTopic {
+                    name: topic_partition.topic.into(),
+                    partitions: vec![partition].into(),
+                }),
+            }
+        }
+        KafkaResponseOffsetDelete { topics: topics.into(), ..Default::default() }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::coordinator::OffsetAndMetadata;
+use crate::kafka::request::{KafkaRequestOffsetFetch, OffsetFetchRequestTopic};
+use crate::kafka::response::{
+    KafkaResponseOffsetFetch, OffsetFetchResponseGroup, OffsetFetchResponsePartition, OffsetFetchResponseTopic,
+};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::{ErrorCode, MessageVersion};
+
+impl Broker {
+    /// Fetches the committed offsets of one group before v8, of a batch of
+    /// groups from v8.
+    pub(crate) fn offset_fetch(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestOffsetFetch,
+    ) -> KafkaResponseOffsetFetch {
+        if version.version <= 7 {
+            if !self.authorize(context, AclOperation::Describe, ResourceType::Group, &request.group_id) {
+                let error_code = ErrorCode::GroupAuthorizationFailed;
+                return KafkaResponseOffsetFetch { error_code, ..Default::default() };
+            }
+            let topics = self.fetch_group_offsets(context, &request.group_id, request.topics.0);
+            return KafkaResponseOffsetFetch { topics: topics.into(), ..Default::default() };
+        }
+
+        let groups = request
+            .groups
+            .0
+            .into_iter()
+            .map(|group| {
+                if !self.authorize(context, AclOperation::Describe, ResourceType::Group, &group.group_id) {
+                    let error_code = ErrorCode::GroupAuthorizationFailed;
+                    return OffsetFetchResponseGroup { group_id: group.group_id, error_code, ..Default::default() };
+                }
+                let topics = self.fetch_group_offsets(context, &group.group_id, group.topics.0);
+                OffsetFetchResponseGroup { group_id: group.group_id, topics: topics.into(), ..Default::default() }
+            })
+            .collect::<Vec<_>>();
+        KafkaResponseOffsetFetch { groups: groups.into(), ..Default::default() }
+    }
+
+    /// The committed offsets of `topics`, or of every partition if `None`,
+    /// grouped by topic. Requested topics the connection may not describe
+    /// fail with TOPIC_AUTHORIZATION_FAILED, others are left out.
+    fn fetch_group_offsets(
+        &self,
+        context: &RequestContext,
+        group_id: &str,
+        topics: Option<Vec<OffsetFetchRequestTopic>>,
+    ) -> Vec<OffsetFetchResponseTopic> {
+        let authorized = |topic: &str| self.authorize(context, AclOperation::Describe, ResourceType::Topic, topic);
+        let (topics, unauthorized): (Option<Vec<_>>, Vec<_>) = match topics {
+            Some(topics) => {
+                let (topics, unauthorized) = topics.into_iter().partition(|topic| authorized(&topic.name));
+                (Some(topics), unauthorized)
+            }
+            None => (None, Vec::new()),
+        };
+        let partitions = topics.map(|topics| {
+            topics
+                .iter()
+                .flat_map(|topic| {
+                    topic.partition_indexes.iter().map(|&partition| TopicPartition::new(topic.name.as_str(), partition))
+                })
+                .collect()
+        });
+
+        let mut topics: Vec<OffsetFetchResponseTopic> = Vec::new();
+        for (topic_partition, offset) in self.group_coordinator.fetch_offsets(group_id, partitions) {
+            if !authorized(&topic_partition.topic) {
+                continue;
+            }
+            let partition = fetched_partition(topic_partition.partition, offset);
+            match topics.last_mut() {
+                Some(topic) if *topic.name == topic_partition.topic => topic.partitions.0.push(partition),
+                _ => topics.push(OffsetFetchResponseTopic {
+                    name: topic_partition.topic.into(),
+                    partitions: vec![partition].into(),
+                    ..Default::default()
+                }),
+            }
+        }
+        for topic in unauthorized {
+            let partitions = topic
+                .partition_indexes
+                .iter()
+                .map(|&partition_index| OffsetFetchResponsePartition {
+                    error_code: ErrorCode::TopicAuthorizationFailed,
+                    ..fetched_partition(partition_index, None)
+                })
+                .collect::<Vec<_>>();
+            let partitions = partitions.into();
+            topics.push(OffsetFetchResponseTopic { name: topic.name, partitions, ..Default::default() });
+        }
+        topics
+    }
+}
+
+fn fetched_partition(partition_index: i32, offset: Option<OffsetAndMetadata>) -> OffsetFetchResponsePartition {
+    let (committed_offset, committed_leader_epoch, metadata) = match offset {
+        Some(offset) => (offset.offset, offset.leader_epoch, offset.metadata),
+        None => (-1, -1, String::new()),
+    };
+    OffsetFetchResponsePartition {
+        partition_index,
+        committed_offset,
+        committed_leader_epoch,
+        metadata: Some(metadata).into(),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,70 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::{KafkaRequestOffsetForLeaderEpoch, OffsetForLeaderPartition};
+use crate::kafka::response::{EpochEndOffset, KafkaResponseOffsetForLeaderEpoch, OffsetForLeaderTopicResult};
+use crate::kafka::security::{AclOperation, ResourceType, CLUSTER_RESOURCE_NAME};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::ErrorCode;
+
+/// The replica id of consumers, which are authorized per topic rather than
+/// as a follower.
+const CONSUMER_REPLICA_ID: i32 = -1;
+const DEBUGGING_REPLICA_ID: i32 = -2;
+
+impl Broker {
+    pub(crate) fn offset_for_leader_epoch(
+        &self,
+        context: &RequestContext,
+        request: KafkaRequestOffsetForLeaderEpoch,
+    ) -> KafkaResponseOffsetForLeaderEpoch {
+        let follower = !matches!(request.replica_id, CONSUMER_REPLICA_ID | DEBUGGING_REPLICA_ID);
+        let cluster_authorized = !follower
+            || self.authorize(context, AclOperation::ClusterAction, ResourceType::Cluster, CLUSTER_RESOURCE_NAME);
+
+        let topics = request
+            .topics
+            .into_iter()
+            .map(|topic| {
+                let error_code = if !cluster_authorized {
+                    ErrorCode::ClusterAuthorizationFailed
+                } else if !self.authorize(context, AclOperation::Describe, ResourceType::Topic, &topic.topic) {
+                    ErrorCode::TopicAuthorizationFailed
+                } else {
+                    ErrorCode::None
+                };
+                let partitions = topic
+                    .partitions
+                    .iter()
+                    .map(|partition| match error_code {
+                        ErrorCode::None => self.epoch_end_offset(&topic.topic, partition),
+                        error_code => EpochEndOffset::new(partition.partition, error_code),
+                    })
+                    .collect();
+                OffsetForLeaderTopicResult { topic: topic.topic, partitions, ..Default::default() }
+            })
+            .collect();
+
+        KafkaResponseOffsetForLeaderEpoch { topics, ..Default::default() }
+    }
+
+    /// Without a controller, the leader epoch of a partition is the latest
+    /// epoch in its log.
+    fn epoch_end_offset(&self, topic: &str, partition: &OffsetForLeaderPartition) -> EpochEndOffset {
+        let Some(log) = self.log_manager.get_log(&TopicPartition::new(topic, partition.partition)) else {
+            return EpochEndOffset::new(partition.partition, ErrorCode::UnknownTopicOrPartition);
+        };
+        let log = log.lock().expect("partition log lock poisoned");
+
+        let leader_epoch = log.latest_epoch().unwrap_or(0);
+        if partition.current_leader_epoch >= 0 && partition.current_leader_epoch != leader_epoch {
+            let error_code = if partition.current_leader_epoch > leader_epoch {
+                ErrorCode::UnknownLeaderEpoch
+            } else {
+                ErrorCode::FencedLeaderEpoch
+            };
+            return EpochEndOffset::new(partition.partition, error_code);
+        }
+
+        let (leader_epoch, end_offset) = log.end_offset_for_epoch(partition.leader_epoch);
+        EpochEndOffset { leader_epoch, end_offset, ..EpochEndOffset::new(partition.partition, ErrorCode::None) }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::generic_request::KafkaRequest;
+use crate::kafka::response::KafkaResponse;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+use tokio::sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore};
+
+/// A request waiting to be handled, with the state of the connection it
+/// arrived on.
+#[derive(Debug)]
+struct QueuedRequest {
+    context: RequestContext,
+    request: KafkaRequest,
+    handled: oneshot::Sender<HandledRequest>,
+}
+
+/// The outcome of a request, with the connection state as the handler left
+/// it.
+#[derive(Debug)]
+pub(crate) struct HandledRequest {
+    pub(crate) context: RequestContext,
+    pub(crate) response: Option<KafkaResponse>,
+    /// When a request handler picked the request up.
+    pub(crate) started: Instant,
+    pub(crate) handling_time: Duration,
+}
+
+/// One of the `num.io.threads` request handlers, held by a request while it
+/// is handled. A request waiting on a coordinator gives its handler back,
+/// like a request parked in Kafka's purgatory, so that the other members of
+/// a rebalance can still be handled.
+#[derive(Debug)]
+pub(crate) struct RequestHandler {
+    handlers: Arc<Semaphore>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl RequestHandler {
+    /// Awaits `remote` without holding the handler, then waits for a free
+    /// handler to carry on with.
+    pub(crate) async fn wait_remote<F: Future>(&mut self, remote: F) -> F::Output {
+        self.permit = None;
+        let output = remote.await;
+        self.permit = Some(acquire(&self.handlers).await);
+        output
+    }
+}
+
+/// The queue between the network threads, which read and write requests,
+/// and the request handler threads, which handle them. Like Kafka's
+/// `RequestChannel`, it holds at most `queued.max.requests` requests, and
+/// `num.io.threads` of them are handled at a time. The next request is only
+/// handed out once a handler is free, so the queue fills up and connections
+/// wait for room once the handlers fall behind.
+///
+/// Requests are handled on the blocking threads of the handler runtime, so
+/// the disk I/O they do never stalls a network thread or the async tasks of
+/// the handler runtime.
+#[derive(Debug, Clone)]
+pub(crate) struct RequestChannel {
+    requests: mpsc::Sender<QueuedRequest>,
+}
+
+impl RequestChannel {
+    /// Starts dispatching queued requests to `broker` on the handler runtime.
+    pub(crate) fn start(
+        broker: Arc<Broker>,
+        queued_max_requests: usize,
+        num_io_threads: usize,
+        handlers: &Handle,
+    ) -> Self {
+        let (requests, mut queue) = mpsc::channel::<QueuedRequest>(queued_max_requests);
+        let runtime = handlers.clone();
+        handlers.spawn(async move {
+            let handlers = Arc::new(Semaphore::new(num_io_threads));
+            // Waiting for a free handler only once there is a request leaves
+            // the handlers to requests coming back from a coordinator.
+            while let Some(QueuedRequest { mut context, request, handled }) = queue.recv().await {
+                let permit = acquire(&handlers).await;
+                let mut handler = RequestHandler { handlers: handlers.clone(), permit: Some(permit) };
+                let (broker, runtime) = (broker.clone(), runtime.clone());
+                tokio::task::spawn_blocking(move || {
+                    let started = Instant::now();
+                    let response = runtime.block_on(broker.handle_request(&mut context, &mut handler, request));
+                    let handling_time = started.elapsed();
+                    // The connection may have closed in the meantime.
+                    let _ = handled.send(HandledRequest { context, response, started, handling_time });
+                });
+            }
+        });
+        Self { requests }
+    }
+
+    /// Queues the request and waits for it to be handled. `None` once the
+    /// request handlers have stopped.
+    pub(crate) async fn handle(&self, context: RequestContext, request: KafkaRequest) -> Option<HandledRequest> {
+        let (handled, response) = oneshot::channel();
+        self.requests.send(QueuedRequest { context, request, handled }).await.ok()?;
+        response.await.ok()
+    }
+}
+
+async fn acquire(handlers: &Arc<Semaphore>) -> OwnedSemaphorePermit {
+    handlers.clone().acquire_owned().await.expect("request handlers are never closed")
+}
@@ -0,0 +1,155 @@
+use crate::kafka::broker::RequestContext;
+use crate::kafka::request::generic_request::KafkaRequest;
+use crate::kafka::types::ApiKey;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// The tracing target of request log records, so they can be filtered or
+/// routed apart from the rest of the broker log. Records are written at
+/// debug level and so are off unless enabled, for example with
+/// `RUST_LOG=kafka.request.logger=debug`.
+const REQUEST_LOG_TARGET: &str = "kafka.request.logger";
+
+/// One record of the request log, written once the response is sent. The
+/// total time of a request is split into the time it waited to be handled,
+/// was handled, waited on a coordinator, was throttled for and took to send.
+#[derive(Debug)]
+pub(crate) struct RequestLog {
+    api_key: ApiKey,
+    api_version: i16,
+    correlation_id: i32,
+    client_id: Option<String>,
+    request_size: usize,
+    received: Instant,
+    pub(crate) queue_time: Duration,
+    pub(crate) local_time: Duration,
+    pub(crate) remote_time: Duration,
+    pub(crate) throttle_time: Duration,
+    pub(crate) send_time: Duration,
+    /// 0 for requests without a response.
+    pub(crate) response_size: usize,
+}
+
+impl RequestLog {
+    /// Starts the record of a request decoded just now.
+    pub(crate) fn new(request: &KafkaRequest) -> Self {
+        Self {
+            api_key: request.header.api_key(),
+            api_version: request.header.api_version(),
+            correlation_id: request.header.correlation_id(),
+            client_id: request.header.client_id().map(str::to_owned),
+            request_size: request.size,
+            received: Instant::now(),
+            queue_time: Duration::ZERO,
+            local_time: Duration::ZERO,
+            remote_time: Duration::ZERO,
+            throttle_time: Duration::ZERO,
+            send_time: Duration::ZERO,
+            response_size: 0,
+        }
+    }
+
+    /// When the request was decoded.
+    pub(crate) fn received(&self) -> Instant {
+        self.received
+    }
+
+    /// Writes the record, times in milliseconds.
+    pub(crate) fn write(&self, context: &RequestContext) {
+        let principal = context.principal.as_ref().map_or_else(|| "-".to_owned(), ToString::to_string);
+        debug!(
+            target: REQUEST_LOG_TARGET,
+            principal,
+            client_id = self.client_id.as_deref().unwrap_or("-"),
+            client_addr = %context.client_addr,
+            listener = context.listener_name,
+            api_key = ?self.api_key,
+            api_version = self.api_version,
+            correlation_id = self.correlation_id,
+            request_size = self.request_size,
+            response_size = self.response_size,
+            total_ms = millis(self.received.elapsed()),
+            queue_ms = millis(self.queue_time),
+            local_ms = millis(self.local_time),
+            remote_ms = millis(self.remote_time),
+            throttle_ms = millis(self.throttle_time),
+            send_ms = millis(self.send_time),
+            "Completed request"
+        );
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::request::generic_request::KafkaRequest;
+    use binrw::BinRead;
+    use std::io::{Cursor, Write};
+    use std::sync::{Arc, Mutex};
+    use tracing::level_filters::LevelFilter;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Writes a request log record with the broker log at `level`, returning
+    /// the formatted output.
+    fn write_at(level: LevelFilter) -> String {
+        let message = vec![0, 18, 0, 0, 0, 0, 0, 42, 0, 3, b'c', b'l', b'i'];
+        let framed = [(message.len() as i32).to_be_bytes().to_vec(), message].concat();
+        let request = KafkaRequest::read_be(&mut Cursor::new(framed)).unwrap();
+        let mut request_log = RequestLog::new(&request);
+        request_log.queue_time = Duration::from_millis(2);
+        request_log.response_size = 57;
+        let context = RequestContext::new("127.0.0.1:50000".parse().unwrap(), "PLAINTEXT".to_owned(), false);
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(level)
+            .with_ansi(false)
+            .without_time()
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || request_log.write(&context));
+        let output = captured.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_writes_request_fields_at_debug() {
+        let output = write_at(LevelFilter::DEBUG);
+        assert!(output.starts_with("DEBUG kafka.request.logger: Completed request "), "{output}");
+        for field in [
+            "principal=\"User:ANONYMOUS\"",
+            "client_id=\"cli\"",
+            "client_addr=127.0.0.1:50000",
+            "listener=\"PLAINTEXT\"",
+            "api_key=ApiVersions",
+            "api_version=0",
+            "correlation_id=42",
+            "response_size=57",
+            "queue_ms=2.0",
+        ] {
+            assert!(output.contains(field), "{field} missing from {output}");
+        }
+    }
+
+    #[test]
+    fn test_off_at_default_level() {
+        assert_eq!(write_at(LevelFilter::INFO), "");
+    }
+}
@@ -0,0 +1,47 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestSaslAuthenticate;
+use crate::kafka::response::KafkaResponseSaslAuthenticate;
+use crate::kafka::security::{KafkaPrincipal, SaslSession, SaslStep};
+use crate::kafka::types::ErrorCode;
+use tracing::{info, warn};
+
+impl Broker {
+    pub(crate) fn sasl_authenticate(
+        &self,
+        context: &mut RequestContext,
+        request: KafkaRequestSaslAuthenticate,
+    ) -> KafkaResponseSaslAuthenticate {
+        let SaslSession::Authenticate(server) = &mut context.sasl else {
+            if !matches!(context.sasl, SaslSession::Complete) {
+                context.sasl = SaslSession::Failed;
+            }
+            return KafkaResponseSaslAuthenticate {
+                error_code: ErrorCode::IllegalSaslState,
+                error_message: Some("SaslAuthenticate is only expected after SaslHandshake".to_owned()).into(),
+                ..Default::default()
+            };
+        };
+
+        match server.evaluate(&self.credentials, &request.auth_bytes) {
+            Ok(SaslStep::Challenge(challenge)) => {
+                KafkaResponseSaslAuthenticate { auth_bytes: challenge.into(), ..Default::default() }
+            }
+            Ok(SaslStep::Complete { username, response }) => {
+                let principal = KafkaPrincipal::user(username);
+                info!(client = %context.client_addr, %principal, "Authenticated");
+                context.principal = Some(principal);
+                context.sasl = SaslSession::Complete;
+                KafkaResponseSaslAuthenticate { auth_bytes: response.into(), ..Default::default() }
+            }
+            Err(err) => {
+                warn!(client = %context.client_addr, error = %err, "Authentication failed");
+                context.sasl = SaslSession::Failed;
+                KafkaResponseSaslAuthenticate {
+                    error_code: ErrorCode::SaslAuthenticationFailed,
+                    error_message: Some(err.to_string()).into(),
+                    ..Default::default()
+                }
+            }
+        }
+    }
+}
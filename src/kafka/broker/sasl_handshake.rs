@@ -0,0 +1,35 @@
+use crate::kafka::broker::{Broker, RequestContext};
+use crate::kafka::request::KafkaRequestSaslHandshake;
+use crate::kafka::response::KafkaResponseSaslHandshake;
+use crate::kafka::security::{SaslMechanism, SaslServer, SaslSession};
+use crate::kafka::types::{ErrorCode, KafkaArray};
+
+impl Broker {
+    pub(crate) fn sasl_handshake(
+        &self,
+        context: &mut RequestContext,
+        request: KafkaRequestSaslHandshake,
+    ) -> KafkaResponseSaslHandshake {
+        let enabled = &self.config.sasl_enabled_mechanisms;
+        let error_code = match context.sasl {
+            SaslSession::Handshake => match request.mechanism.parse::<SaslMechanism>() {
+                Ok(mechanism) if enabled.contains(&mechanism) => {
+                    context.sasl = SaslSession::Authenticate(SaslServer::new(mechanism));
+                    ErrorCode::None
+                }
+                _ => {
+                    context.sasl = SaslSession::Failed;
+                    ErrorCode::UnsupportedSaslMechanism
+                }
+            },
+            // Authenticated connections and listeners without SASL.
+            SaslSession::Complete => ErrorCode::IllegalSaslState,
+            SaslSession::Authenticate(_) | SaslSession::Failed => {
+                context.sasl = SaslSession::Failed;
+                ErrorCode::IllegalSaslState
+            }
+        };
+        let mechanisms = KafkaArray(enabled.iter().map(|mechanism| mechanism.mechanism_name().into()).collect());
+        KafkaResponseSaslHandshake { error_code, mechanisms }
+    }
+}
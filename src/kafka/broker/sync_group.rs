@@ -0,0 +1,45 @@
+use crate::kafka::broker::{Broker, RequestContext, RequestHandler};
+use crate::kafka::coordinator::SyncGroupParams;
+use crate::kafka::request::KafkaRequestSyncGroup;
+use crate::kafka::response::KafkaResponseSyncGroup;
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::types::ErrorCode;
+use std::time::Instant;
+
+impl Broker {
+    pub(crate) async fn sync_group(
+        &self,
+        context: &mut RequestContext,
+        handler: &mut RequestHandler,
+        request: KafkaRequestSyncGroup,
+    ) -> KafkaResponseSyncGroup {
+        if !self.authorize(context, AclOperation::Read, ResourceType::Group, &request.group_id) {
+            return KafkaResponseSyncGroup { error_code: ErrorCode::GroupAuthorizationFailed, ..Default::default() };
+        }
+        let params = SyncGroupParams {
+            group_id: request.group_id.0,
+            generation_id: request.generation_id,
+            member_id: request.member_id.0,
+            group_instance_id: request.group_instance_id.0,
+            protocol_type: request.protocol_type.0,
+            protocol_name: request.protocol_name.0,
+            assignments: request
+                .assignments
+                .0
+                .into_iter()
+                .map(|assignment| (assignment.member_id.0, assignment.assignment.0))
+                .collect(),
+        };
+        let waiting = Instant::now();
+        let result = handler.wait_remote(self.group_coordinator.sync_group(params)).await;
+        context.remote_time += waiting.elapsed();
+
+        KafkaResponseSyncGroup {
+            error_code: result.error_code,
+            protocol_type: result.protocol_type.into(),
+            protocol_name: result.protocol_name.into(),
+            assignment: result.assignment.into(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::kafka::broker::{fenced_error_code, Broker, RequestContext};
+use crate::kafka::coordinator::{OffsetAndMetadata, TxnOffsetCommitParams};
+use crate::kafka::request::KafkaRequestTxnOffsetCommit;
+use crate::kafka::response::{
+    KafkaResponseTxnOffsetCommit, TxnOffsetCommitResponsePartition, TxnOffsetCommitResponseTopic,
+};
+use crate::kafka::security::{AclOperation, ResourceType};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::{ErrorCode, MessageVersion};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl Broker {
+    /// Commits the offsets of the topics the connection may read as part of
+    /// the transaction. Partitions of other topics fail with
+    /// TOPIC_AUTHORIZATION_FAILED.
+    pub(crate) fn txn_offset_commit(
+        &self,
+        context: &RequestContext,
+        version: MessageVersion,
+        request: KafkaRequestTxnOffsetCommit,
+    ) -> KafkaResponseTxnOffsetCommit {
+        let request_error = if !self.authorize(
+            context,
+            AclOperation::Write,
+            ResourceType::TransactionalId,
+            &request.transactional_id,
+        ) {
+            Some(ErrorCode::TransactionalIdAuthorizationFailed)
+        } else if !self.authorize(context, AclOperation::Read, ResourceType::Group, &request.group_id) {
+            Some(ErrorCode::GroupAuthorizationFailed)
+        } else {
+            None
+        };
+        let topic_errors = request
+            .topics
+            .iter()
+            .map(|topic| {
+                request_error.or_else(|| {
+                    let authorized = self.authorize(context, AclOperation::Read, ResourceType::Topic, &topic.name);
+                    (!authorized).then_some(ErrorCode::TopicAuthorizationFailed)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+        let offsets = request
+            .topics
+            .iter()
+            .zip(&topic_errors)
+            .filter(|(_, error)| error.is_none())
+            .flat_map(|(topic, _)| {
+                topic.partitions.iter().map(|partition| {
+                    let offset = OffsetAndMetadata {
+                        offset: partition.committed_offset,
+                        leader_epoch: partition.committed_leader_epoch,
+                        metadata: partition.committed_metadata.0.clone().unwrap_or_default(),
+                        commit_timestamp: now_ms,
+                    };
+                    (TopicPartition::new(topic.name.as_str(), partition.partition_index), offset)
+                })
+            })
+            .collect();
+        let params = TxnOffsetCommitParams {
+            group_id: request.group_id.0,
+            producer_id: request.producer_id,
+            producer_epoch: request.producer_epoch,
+            generation_id: request.generation_id,
+            member_id: request.member_id.0,
+            group_instance_id: request.group_instance_id.0,
+            offsets,
+        };
+        let errors = match request_error {
+            Some(_) => Vec::new(),
+            None => self.group_coordinator.commit_transactional_offsets(params),
+        };
+        let mut errors = errors.into_iter();
+
+        let topics = request
+            .topics
+            .0
+            .into_iter()
+            .zip(topic_errors)
+            .map(|(topic, topic_error)| {
+                let partitions = topic
+                    .partitions
+                    .iter()
+                    .map(|partition| TxnOffsetCommitResponsePartition {
+                        partition_index: partition.partition_index,
+                        error_code: topic_error
+                            .unwrap_or_else(|| fenced_error_code(errors.next().unwrap_or_default(), version, 3)),
+                        ..Default::default()
+                    })
+                    .collect();
+                TxnOffsetCommitResponseTopic { name: topic.name, partitions, ..Default::default() }
+            })
+            .collect();
+        KafkaResponseTxnOffsetCommit { topics, ..Default::default() }
+    }
+}
@@ -4,35 +4,69 @@ use binrw::{BinRead, BinWrite};
 use binrw::meta::WriteEndian;
 use bytes::{Buf, BytesMut};
 use tokio_util::codec::{Decoder, Encoder};
-use log::warn;
+use tracing::debug;
 use crate::kafka::request::generic_request::KafkaRequest;
 use crate::kafka::response::KafkaGenericResponse;
 
-pub(crate) struct KafkaCodec;
+#[derive(Debug)]
+pub(crate) struct KafkaCodec {
+    /// Requests larger than this fail to decode, before any of the request
+    /// is buffered.
+    max_request_size: usize,
+    /// The size of the last response encoded, after its length prefix.
+    last_response_size: usize,
+}
+
+impl KafkaCodec {
+    pub(crate) fn new(max_request_size: usize) -> Self {
+        Self { max_request_size, last_response_size: 0 }
+    }
+
+    pub(crate) fn set_max_request_size(&mut self, max_request_size: usize) {
+        self.max_request_size = max_request_size;
+    }
+
+    pub(crate) fn last_response_size(&self) -> usize {
+        self.last_response_size
+    }
+}
 
 impl Decoder for KafkaCodec {
     type Item = KafkaRequest;
     type Error = std::io::Error;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        let mut cursor = Cursor::new(&src[..]);
-        
-        match KafkaRequest::read_be(&mut cursor) {
-            Ok(request) => {
-                let pos = cursor.position() as usize;
-                src.advance(pos);
-                Ok(Some(request))
-            },
-            Err(err) => {
-                if let binrw::Error::Io(io_err) = &err {
-                    if io_err.kind() == std::io::ErrorKind::UnexpectedEof {
-                        return Ok(None);
-                    }
-                }
-                warn!("Remaining: {:?}", cursor.into_inner());
-                Err(std::io::Error::new(std::io::ErrorKind::Other, err))
-            }
+        let Some(size_bytes) = src.get(..4) else {
+            return Ok(None);
+        };
+        let message_size = i32::from_be_bytes(size_bytes.try_into().expect("slice of 4 bytes"));
+        let Ok(message_size) = usize::try_from(message_size) else {
+            return Err(std::io::Error::other(format!("Invalid message size: {message_size}")));
+        };
+        if message_size > self.max_request_size {
+            return Err(std::io::Error::other(format!(
+                "Message size {message_size} exceeds the maximum request size {}",
+                self.max_request_size
+            )));
         }
+        if src.len() < 4 + message_size {
+            src.reserve(4 + message_size - src.len());
+            return Ok(None);
+        }
+
+        let mut cursor = Cursor::new(&src[..4 + message_size]);
+        let result = KafkaRequest::read_be(&mut cursor);
+        if let Err(err) = &result {
+            // Only the header is logged: the body may hold credentials, such
+            // as the password of a SASL PLAIN exchange.
+            let api_key = src.get(4..6).map(|bytes| i16::from_be_bytes([bytes[0], bytes[1]]));
+            let api_version = src.get(6..8).map(|bytes| i16::from_be_bytes([bytes[0], bytes[1]]));
+            debug!(message_size, ?api_key, ?api_version, "Failed to decode request");
+            src.advance(4 + message_size);
+            return Err(std::io::Error::other(err.to_string()));
+        }
+        src.advance(4 + message_size);
+        result.map(Some).map_err(std::io::Error::other)
     }
 }
 
@@ -48,9 +82,26 @@ where
     fn encode(&mut self, item: KafkaGenericResponse<H, B>, dst: &mut BytesMut) -> Result<(), Self::Error> {
         let mut writer = Cursor::new(Vec::with_capacity(128));
         item.write_be(&mut writer).map_err(|err| {
-            std::io::Error::new(std::io::ErrorKind::Other, format!("Serialization error: {err:?}"))
+            std::io::Error::other(format!("Serialization error: {err:?}"))
         })?;
-        dst.extend_from_slice(&writer.into_inner());
+        let bytes = writer.into_inner();
+        self.last_response_size = bytes.len() - 4;
+        dst.extend_from_slice(&bytes);
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_oversized_requests() {
+        let mut codec = KafkaCodec::new(16);
+        let mut src = BytesMut::from(&i32::MAX.to_be_bytes()[..]);
+        assert!(codec.decode(&mut src).is_err());
+        assert!(src.capacity() < 1024);
+
+        let mut src = BytesMut::from(&16i32.to_be_bytes()[..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+    }
+}
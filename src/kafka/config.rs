@@ -0,0 +1,610 @@
+mod registry;
+pub(crate) use registry::*;
+mod dynamic;
+pub(crate) use dynamic::*;
+
+use crate::kafka::coordinator::PartitionAssignor;
+use crate::kafka::security::{SaslMechanism, ScramMechanism, SecurityProtocol, SslClientAuth};
+use crate::kafka::storage::{CleanupPolicy, CompressionType, LogConfig};
+use anyhow::Context;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const DEFAULT_LOG_DIR: &str = "/tmp/kafka-logs";
+const DEFAULT_LISTENER: &str = "PLAINTEXT://127.0.0.1:9092";
+
+#[derive(Debug, Clone)]
+pub(crate) struct ServerConfig {
+    /// The `server.properties` entries as written, reported as the static
+    /// configs of DescribeConfigs.
+    pub(crate) properties: HashMap<String, String>,
+    pub(crate) node_id: i32,
+    /// Whether this node is a broker, a controller, or both. Only combined
+    /// nodes are supported.
+    pub(crate) process_roles: Vec<ProcessRole>,
+    /// Brokers not heard from for this long are fenced by the controller.
+    pub(crate) broker_session_timeout_ms: u64,
+    /// How often a broker heartbeats the controller.
+    pub(crate) broker_heartbeat_interval_ms: u64,
+    pub(crate) listeners: Vec<Endpoint>,
+    /// The endpoints handed to clients, per listener name.
+    pub(crate) advertised_listeners: Vec<Endpoint>,
+    /// The security protocol of each listener name. Unmapped listeners use
+    /// the protocol of the same name.
+    pub(crate) listener_security_protocol_map: HashMap<String, SecurityProtocol>,
+    pub(crate) sasl_enabled_mechanisms: Vec<SaslMechanism>,
+    /// A `username=password` file of SASL users.
+    pub(crate) sasl_credentials_file: Option<PathBuf>,
+    /// A PEM file with the private key and certificate chain of TLS listeners.
+    pub(crate) ssl_keystore_location: Option<PathBuf>,
+    /// A PEM file of the CAs trusted to sign client certificates.
+    pub(crate) ssl_truststore_location: Option<PathBuf>,
+    pub(crate) ssl_client_auth: SslClientAuth,
+    /// How often the keystore and truststore are checked for changes.
+    pub(crate) ssl_reload_check_interval_ms: u64,
+    /// Requests are authorized against ACLs when set. Any value enables the
+    /// built-in authorizer.
+    pub(crate) authorizer_class_name: Option<String>,
+    /// Principals, such as `User:admin`, allowed every operation.
+    pub(crate) super_users: Vec<String>,
+    /// Whether resources without ACLs are open to everyone.
+    pub(crate) allow_everyone_if_no_acl_found: bool,
+    pub(crate) log_dirs: Vec<PathBuf>,
+    pub(crate) log_index_interval_bytes: usize,
+    pub(crate) log_segment_bytes: u64,
+    pub(crate) log_retention_ms: i64,
+    pub(crate) log_retention_bytes: i64,
+    pub(crate) log_retention_check_interval_ms: u64,
+    pub(crate) log_cleanup_policy: CleanupPolicy,
+    pub(crate) log_cleaner_enable: bool,
+    pub(crate) log_cleaner_backoff_ms: u64,
+    pub(crate) log_cleaner_io_max_bytes_per_second: f64,
+    pub(crate) log_cleaner_delete_retention_ms: i64,
+    pub(crate) log_cleaner_min_cleanable_ratio: f64,
+    pub(crate) compression_type: CompressionType,
+    pub(crate) group_min_session_timeout_ms: i32,
+    pub(crate) group_max_session_timeout_ms: i32,
+    pub(crate) group_initial_rebalance_delay_ms: u64,
+    pub(crate) group_max_size: usize,
+    pub(crate) group_consumer_session_timeout_ms: i32,
+    pub(crate) group_consumer_heartbeat_interval_ms: i32,
+    pub(crate) group_consumer_max_size: usize,
+    /// The server-side assignors members may pick, the first being the default.
+    pub(crate) group_consumer_assignors: Vec<PartitionAssignor>,
+    pub(crate) offsets_topic_num_partitions: i32,
+    pub(crate) offsets_topic_segment_bytes: u64,
+    pub(crate) offset_metadata_max_bytes: usize,
+    pub(crate) transaction_state_log_num_partitions: i32,
+    pub(crate) transaction_state_log_segment_bytes: u64,
+    pub(crate) transaction_max_timeout_ms: i32,
+    pub(crate) transaction_abort_timed_out_transaction_cleanup_interval_ms: u64,
+    /// The number of samples client quota rates are measured over.
+    pub(crate) quota_window_num: usize,
+    pub(crate) quota_window_size_seconds: u64,
+    /// The worker threads reading and writing requests on connections.
+    pub(crate) num_network_threads: usize,
+    /// The worker threads handling requests, including their disk I/O.
+    pub(crate) num_io_threads: usize,
+    /// Requests read but not yet picked up by a handler, beyond which
+    /// connections stop reading requests.
+    pub(crate) queued_max_requests: usize,
+    /// Connections sending a larger request are closed.
+    pub(crate) socket_request_max_bytes: usize,
+    /// The largest request accepted before a SASL connection has
+    /// authenticated.
+    pub(crate) sasl_server_max_receive_size: usize,
+    /// Connections accepted beyond these limits are closed straight away.
+    pub(crate) max_connections: usize,
+    pub(crate) max_connections_per_ip: usize,
+    /// Client addresses allowed a different number of connections than
+    /// `max_connections_per_ip`.
+    pub(crate) max_connections_per_ip_overrides: HashMap<IpAddr, usize>,
+    /// The new connections accepted per second, measured over the quota
+    /// window.
+    pub(crate) max_connection_creation_rate: f64,
+    /// Connections without a request for this long are closed.
+    pub(crate) connections_max_idle_ms: u64,
+    /// The port metrics are served on in the Prometheus format, none if unset.
+    pub(crate) metrics_port: Option<u16>,
+    /// The host the metrics endpoint binds, the host of the first listener
+    /// if unset.
+    pub(crate) metrics_host: Option<String>,
+    pub(crate) logging_format: LogFormat,
+    /// How long open connections get to finish their in-flight requests on
+    /// shutdown before the logs are flushed regardless.
+    pub(crate) shutdown_timeout_ms: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        let listeners = vec![DEFAULT_LISTENER.parse().expect("default listener is valid")];
+        Self {
+            properties: HashMap::new(),
+            node_id: 1,
+            process_roles: vec![ProcessRole::Broker, ProcessRole::Controller],
+            broker_session_timeout_ms: 9 * 1000,
+            broker_heartbeat_interval_ms: 2 * 1000,
+            advertised_listeners: listeners.clone(),
+            listeners,
+            listener_security_protocol_map: HashMap::new(),
+            sasl_enabled_mechanisms: vec![
+                SaslMechanism::Plain,
+                SaslMechanism::Scram(ScramMechanism::Sha256),
+                SaslMechanism::Scram(ScramMechanism::Sha512),
+            ],
+            sasl_credentials_file: None,
+            ssl_keystore_location: None,
+            ssl_truststore_location: None,
+            ssl_client_auth: SslClientAuth::None,
+            ssl_reload_check_interval_ms: 60 * 1000,
+            authorizer_class_name: None,
+            super_users: Vec::new(),
+            allow_everyone_if_no_acl_found: false,
+            log_dirs: vec![PathBuf::from(DEFAULT_LOG_DIR)],
+            log_index_interval_bytes: 4096,
+            log_segment_bytes: 1024 * 1024 * 1024,
+            log_retention_ms: 168 * 60 * 60 * 1000,
+            log_retention_bytes: -1,
+            log_retention_check_interval_ms: 5 * 60 * 1000,
+            log_cleanup_policy: CleanupPolicy::default(),
+            log_cleaner_enable: true,
+            log_cleaner_backoff_ms: 15 * 1000,
+            log_cleaner_io_max_bytes_per_second: f64::MAX,
+            log_cleaner_delete_retention_ms: 24 * 60 * 60 * 1000,
+            log_cleaner_min_cleanable_ratio: 0.5,
+            compression_type: CompressionType::default(),
+            group_min_session_timeout_ms: 6 * 1000,
+            group_max_session_timeout_ms: 30 * 60 * 1000,
+            group_initial_rebalance_delay_ms: 3 * 1000,
+            group_max_size: i32::MAX as usize,
+            group_consumer_session_timeout_ms: 45 * 1000,
+            group_consumer_heartbeat_interval_ms: 5 * 1000,
+            group_consumer_max_size: i32::MAX as usize,
+            group_consumer_assignors: vec![PartitionAssignor::Uniform, PartitionAssignor::Range],
+            offsets_topic_num_partitions: 50,
+            offsets_topic_segment_bytes: 100 * 1024 * 1024,
+            offset_metadata_max_bytes: 4096,
+            transaction_state_log_num_partitions: 50,
+            transaction_state_log_segment_bytes: 100 * 1024 * 1024,
+            transaction_max_timeout_ms: 15 * 60 * 1000,
+            transaction_abort_timed_out_transaction_cleanup_interval_ms: 10 * 1000,
+            quota_window_num: 11,
+            quota_window_size_seconds: 1,
+            num_network_threads: 3,
+            num_io_threads: 8,
+            queued_max_requests: 500,
+            socket_request_max_bytes: 100 * 1024 * 1024,
+            sasl_server_max_receive_size: 512 * 1024,
+            max_connections: i32::MAX as usize,
+            max_connections_per_ip: i32::MAX as usize,
+            max_connections_per_ip_overrides: HashMap::new(),
+            max_connection_creation_rate: i32::MAX as f64,
+            connections_max_idle_ms: 10 * 60 * 1000,
+            metrics_port: None,
+            metrics_host: None,
+            logging_format: LogFormat::default(),
+            shutdown_timeout_ms: 30 * 1000,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Loads the broker config from a `server.properties` file, falling back
+    /// to defaults when no file is given.
+    pub(crate) fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        Self::from_properties(&parse_properties(&text))
+    }
+
+    pub(crate) fn from_properties(props: &HashMap<String, String>) -> anyhow::Result<Self> {
+        let mut config = Self { properties: props.clone(), ..Self::default() };
+
+        if let Some(value) = props.get("node.id").or_else(|| props.get("broker.id")) {
+            config.node_id = value.parse().context("node.id")?;
+        }
+        if let Some(value) = props.get("process.roles") {
+            config.process_roles = value
+                .split(',')
+                .map(str::trim)
+                .filter(|role| !role.is_empty())
+                .map(|role| role.parse().map_err(anyhow::Error::msg))
+                .collect::<anyhow::Result<_>>()
+                .context("process.roles")?;
+        }
+        let roles = &config.process_roles;
+        anyhow::ensure!(
+            roles.contains(&ProcessRole::Broker) && roles.contains(&ProcessRole::Controller),
+            "process.roles must be broker,controller: separate broker and controller nodes are not supported"
+        );
+        if let Some(value) = props.get("broker.session.timeout.ms") {
+            config.broker_session_timeout_ms = value.parse().context("broker.session.timeout.ms")?;
+        }
+        if let Some(value) = props.get("broker.heartbeat.interval.ms") {
+            config.broker_heartbeat_interval_ms = value.parse().context("broker.heartbeat.interval.ms")?;
+        }
+        if let Some(value) = props.get("listeners") {
+            config.listeners = parse_endpoints(value).context("listeners")?;
+            config.advertised_listeners = config.listeners.clone();
+        }
+        if let Some(value) = props.get("advertised.listeners") {
+            config.advertised_listeners = parse_endpoints(value).context("advertised.listeners")?;
+        }
+        if let Some(value) = props.get("listener.security.protocol.map") {
+            config.listener_security_protocol_map = value
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(|entry| {
+                    let (name, protocol) =
+                        entry.split_once(':').with_context(|| format!("{entry:?} is not NAME:PROTOCOL"))?;
+                    Ok((name.to_owned(), protocol.parse().map_err(anyhow::Error::msg)?))
+                })
+                .collect::<anyhow::Result<_>>()
+                .context("listener.security.protocol.map")?;
+        }
+        for endpoint in &config.listeners {
+            if config.security_protocol(&endpoint.listener_name).is_none() {
+                anyhow::bail!("listener {} has no entry in listener.security.protocol.map", endpoint.listener_name);
+            }
+        }
+        if let Some(value) = props.get("sasl.enabled.mechanisms") {
+            config.sasl_enabled_mechanisms = value
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| name.parse().map_err(anyhow::Error::msg))
+                .collect::<anyhow::Result<_>>()
+                .context("sasl.enabled.mechanisms")?;
+        }
+        if let Some(value) = props.get("sasl.credentials.file") {
+            config.sasl_credentials_file = Some(PathBuf::from(value));
+        }
+        if let Some(value) = props.get("ssl.keystore.location") {
+            config.ssl_keystore_location = Some(PathBuf::from(value));
+        }
+        if let Some(value) = props.get("ssl.truststore.location") {
+            config.ssl_truststore_location = Some(PathBuf::from(value));
+        }
+        if let Some(value) = props.get("ssl.client.auth") {
+            config.ssl_client_auth = value.parse().map_err(anyhow::Error::msg).context("ssl.client.auth")?;
+        }
+        if let Some(value) = props.get("ssl.reload.check.interval.ms") {
+            config.ssl_reload_check_interval_ms = value.parse().context("ssl.reload.check.interval.ms")?;
+        }
+        if let Some(value) = props.get("authorizer.class.name") {
+            config.authorizer_class_name = Some(value.clone()).filter(|name| !name.is_empty());
+        }
+        if let Some(value) = props.get("super.users") {
+            config.super_users =
+                value.split(';').map(str::trim).filter(|user| !user.is_empty()).map(String::from).collect();
+        }
+        if let Some(value) = props.get("allow.everyone.if.no.acl.found") {
+            config.allow_everyone_if_no_acl_found = value.parse().context("allow.everyone.if.no.acl.found")?;
+        }
+        if let Some(dirs) = props.get("log.dirs").or_else(|| props.get("log.dir")) {
+            config.log_dirs = dirs.split(',').map(|dir| PathBuf::from(dir.trim())).collect();
+        }
+        if let Some(value) = props.get("log.index.interval.bytes") {
+            config.log_index_interval_bytes = value.parse().context("log.index.interval.bytes")?;
+        }
+        if let Some(value) = props.get("log.segment.bytes") {
+            config.log_segment_bytes = value.parse().context("log.segment.bytes")?;
+        }
+        if let Some(value) = props.get("log.retention.ms") {
+            config.log_retention_ms = value.parse().context("log.retention.ms")?;
+        } else if let Some(value) = props.get("log.retention.minutes") {
+            config.log_retention_ms = value.parse::<i64>().context("log.retention.minutes")? * 60 * 1000;
+        } else if let Some(value) = props.get("log.retention.hours") {
+            config.log_retention_ms = value.parse::<i64>().context("log.retention.hours")? * 60 * 60 * 1000;
+        }
+        if let Some(value) = props.get("log.retention.bytes") {
+            config.log_retention_bytes = value.parse().context("log.retention.bytes")?;
+        }
+        if let Some(value) = props.get("log.retention.check.interval.ms") {
+            config.log_retention_check_interval_ms = value.parse().context("log.retention.check.interval.ms")?;
+        }
+        if let Some(value) = props.get("log.cleanup.policy") {
+            config.log_cleanup_policy = value.parse().map_err(anyhow::Error::msg).context("log.cleanup.policy")?;
+        }
+        if let Some(value) = props.get("log.cleaner.enable") {
+            config.log_cleaner_enable = value.parse().context("log.cleaner.enable")?;
+        }
+        if let Some(value) = props.get("log.cleaner.backoff.ms") {
+            config.log_cleaner_backoff_ms = value.parse().context("log.cleaner.backoff.ms")?;
+        }
+        if let Some(value) = props.get("log.cleaner.io.max.bytes.per.second") {
+            config.log_cleaner_io_max_bytes_per_second = value.parse().context("log.cleaner.io.max.bytes.per.second")?;
+        }
+        if let Some(value) = props.get("log.cleaner.delete.retention.ms") {
+            config.log_cleaner_delete_retention_ms = value.parse().context("log.cleaner.delete.retention.ms")?;
+        }
+        if let Some(value) = props.get("log.cleaner.min.cleanable.ratio") {
+            config.log_cleaner_min_cleanable_ratio = value.parse().context("log.cleaner.min.cleanable.ratio")?;
+        }
+        if let Some(value) = props.get("compression.type") {
+            config.compression_type = value.parse().map_err(anyhow::Error::msg).context("compression.type")?;
+        }
+        if let Some(value) = props.get("group.min.session.timeout.ms") {
+            config.group_min_session_timeout_ms = value.parse().context("group.min.session.timeout.ms")?;
+        }
+        if let Some(value) = props.get("group.max.session.timeout.ms") {
+            config.group_max_session_timeout_ms = value.parse().context("group.max.session.timeout.ms")?;
+        }
+        if let Some(value) = props.get("group.initial.rebalance.delay.ms") {
+            config.group_initial_rebalance_delay_ms = value.parse().context("group.initial.rebalance.delay.ms")?;
+        }
+        if let Some(value) = props.get("group.max.size") {
+            config.group_max_size = value.parse().context("group.max.size")?;
+        }
+        if let Some(value) = props.get("group.consumer.session.timeout.ms") {
+            config.group_consumer_session_timeout_ms = value.parse().context("group.consumer.session.timeout.ms")?;
+        }
+        if let Some(value) = props.get("group.consumer.heartbeat.interval.ms") {
+            config.group_consumer_heartbeat_interval_ms = value.parse().context("group.consumer.heartbeat.interval.ms")?;
+        }
+        if let Some(value) = props.get("group.consumer.max.size") {
+            config.group_consumer_max_size = value.parse().context("group.consumer.max.size")?;
+        }
+        if let Some(value) = props.get("group.consumer.assignors") {
+            config.group_consumer_assignors = value
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| name.parse().map_err(anyhow::Error::msg))
+                .collect::<anyhow::Result<_>>()
+                .context("group.consumer.assignors")?;
+            anyhow::ensure!(!config.group_consumer_assignors.is_empty(), "group.consumer.assignors is empty");
+        }
+        if let Some(value) = props.get("offsets.topic.num.partitions") {
+            config.offsets_topic_num_partitions = value.parse().context("offsets.topic.num.partitions")?;
+        }
+        if let Some(value) = props.get("offsets.topic.segment.bytes") {
+            config.offsets_topic_segment_bytes = value.parse().context("offsets.topic.segment.bytes")?;
+        }
+        if let Some(value) = props.get("offset.metadata.max.bytes") {
+            config.offset_metadata_max_bytes = value.parse().context("offset.metadata.max.bytes")?;
+        }
+        if let Some(value) = props.get("transaction.state.log.num.partitions") {
+            config.transaction_state_log_num_partitions = value.parse().context("transaction.state.log.num.partitions")?;
+        }
+        if let Some(value) = props.get("transaction.state.log.segment.bytes") {
+            config.transaction_state_log_segment_bytes = value.parse().context("transaction.state.log.segment.bytes")?;
+        }
+        if let Some(value) = props.get("transaction.max.timeout.ms") {
+            config.transaction_max_timeout_ms = value.parse().context("transaction.max.timeout.ms")?;
+        }
+        if let Some(value) = props.get("transaction.abort.timed.out.transaction.cleanup.interval.ms") {
+            config.transaction_abort_timed_out_transaction_cleanup_interval_ms =
+                value.parse().context("transaction.abort.timed.out.transaction.cleanup.interval.ms")?;
+        }
+        if let Some(value) = props.get("quota.window.num") {
+            config.quota_window_num = value.parse().context("quota.window.num")?;
+            anyhow::ensure!(config.quota_window_num >= 1, "quota.window.num must be at least 1");
+        }
+        if let Some(value) = props.get("quota.window.size.seconds") {
+            config.quota_window_size_seconds = value.parse().context("quota.window.size.seconds")?;
+            anyhow::ensure!(config.quota_window_size_seconds >= 1, "quota.window.size.seconds must be at least 1");
+        }
+        if let Some(value) = props.get("num.network.threads") {
+            config.num_network_threads = value.parse().context("num.network.threads")?;
+            anyhow::ensure!(config.num_network_threads >= 1, "num.network.threads must be at least 1");
+        }
+        if let Some(value) = props.get("num.io.threads") {
+            config.num_io_threads = value.parse().context("num.io.threads")?;
+            anyhow::ensure!(config.num_io_threads >= 1, "num.io.threads must be at least 1");
+        }
+        if let Some(value) = props.get("queued.max.requests") {
+            config.queued_max_requests = value.parse().context("queued.max.requests")?;
+            anyhow::ensure!(config.queued_max_requests >= 1, "queued.max.requests must be at least 1");
+        }
+        if let Some(value) = props.get("socket.request.max.bytes") {
+            config.socket_request_max_bytes = value.parse().context("socket.request.max.bytes")?;
+        }
+        if let Some(value) = props.get("sasl.server.max.receive.size") {
+            config.sasl_server_max_receive_size = value.parse().context("sasl.server.max.receive.size")?;
+        }
+        if let Some(value) = props.get("max.connections") {
+            config.max_connections = value.parse().context("max.connections")?;
+        }
+        if let Some(value) = props.get("max.connections.per.ip") {
+            config.max_connections_per_ip = value.parse().context("max.connections.per.ip")?;
+        }
+        if let Some(value) = props.get("max.connections.per.ip.overrides") {
+            config.max_connections_per_ip_overrides = value
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(|entry| {
+                    let (addr, count) =
+                        entry.rsplit_once(':').with_context(|| format!("{entry:?} is not ADDRESS:COUNT"))?;
+                    Ok((addr.parse().context(addr.to_owned())?, count.parse().context(count.to_owned())?))
+                })
+                .collect::<anyhow::Result<_>>()
+                .context("max.connections.per.ip.overrides")?;
+        }
+        if let Some(value) = props.get("max.connection.creation.rate") {
+            config.max_connection_creation_rate = value.parse().context("max.connection.creation.rate")?;
+            anyhow::ensure!(config.max_connection_creation_rate > 0.0, "max.connection.creation.rate must be positive");
+        }
+        if let Some(value) = props.get("connections.max.idle.ms") {
+            config.connections_max_idle_ms = value.parse().context("connections.max.idle.ms")?;
+        }
+        if let Some(value) = props.get("metrics.host") {
+            config.metrics_host = Some(value.clone());
+        }
+        if let Some(value) = props.get("metrics.port") {
+            config.metrics_port = Some(value.parse().context("metrics.port")?);
+        }
+        if let Some(value) = props.get("logging.format") {
+            config.logging_format = value.parse().map_err(anyhow::Error::msg).context("logging.format")?;
+        }
+        if let Some(value) = props.get("shutdown.timeout.ms") {
+            config.shutdown_timeout_ms = value.parse().context("shutdown.timeout.ms")?;
+        }
+
+        Ok(config)
+    }
+
+    /// The address the metrics endpoint binds, none if metrics are not
+    /// served.
+    pub(crate) fn metrics_bind_address(&self) -> Option<String> {
+        let port = self.metrics_port?;
+        let host = match &self.metrics_host {
+            Some(host) => host.clone(),
+            None => self.listeners.first().map(|listener| listener.host.clone()).unwrap_or_default(),
+        };
+        Some(Endpoint { listener_name: "metrics".to_owned(), host, port }.bind_address())
+    }
+
+    /// The advertised endpoint of `listener_name`, falling back to the
+    /// listener's bind address.
+    pub(crate) fn advertised_endpoint(&self, listener_name: &str) -> Option<&Endpoint> {
+        self.advertised_listeners
+            .iter()
+            .chain(&self.listeners)
+            .find(|endpoint| endpoint.listener_name == listener_name)
+    }
+
+    /// The security protocol of `listener_name`, `None` if it is neither
+    /// mapped nor named after a protocol.
+    pub(crate) fn security_protocol(&self, listener_name: &str) -> Option<SecurityProtocol> {
+        match self.listener_security_protocol_map.get(listener_name) {
+            Some(protocol) => Some(*protocol),
+            None => listener_name.parse().ok(),
+        }
+    }
+
+    /// The log config every topic starts from before its own overrides.
+    pub(crate) fn default_log_config(&self) -> LogConfig {
+        LogConfig {
+            segment_bytes: self.log_segment_bytes,
+            retention_ms: self.log_retention_ms,
+            retention_bytes: self.log_retention_bytes,
+            cleanup_policy: self.log_cleanup_policy,
+            delete_retention_ms: self.log_cleaner_delete_retention_ms,
+            min_cleanable_dirty_ratio: self.log_cleaner_min_cleanable_ratio,
+            compression_type: self.compression_type,
+        }
+    }
+}
+
+/// A `NAME://host:port` entry of `listeners` or `advertised.listeners`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Endpoint {
+    pub(crate) listener_name: String,
+    pub(crate) host: String,
+    pub(crate) port: u16,
+}
+
+impl Endpoint {
+    /// The address to bind, an empty host binding all interfaces.
+    pub(crate) fn bind_address(&self) -> String {
+        let host = if self.host.is_empty() { "0.0.0.0" } else { &self.host };
+        format!("{host}:{}", self.port)
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (listener_name, address) = value
+            .split_once("://")
+            .with_context(|| format!("endpoint {value:?} is not of the form NAME://host:port"))?;
+        let (host, port) = address.rsplit_once(':').with_context(|| format!("endpoint {value:?} has no port"))?;
+        Ok(Self {
+            listener_name: listener_name.to_owned(),
+            host: host.trim_start_matches('[').trim_end_matches(']').to_owned(),
+            port: port.parse().with_context(|| format!("endpoint {value:?} has an invalid port"))?,
+        })
+    }
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}://{}:{}", self.listener_name, self.host, self.port)
+    }
+}
+
+/// A role of `process.roles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProcessRole {
+    Broker,
+    /// The writer of `__cluster_metadata`.
+    Controller,
+}
+
+impl FromStr for ProcessRole {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "broker" => Ok(ProcessRole::Broker),
+            "controller" => Ok(ProcessRole::Controller),
+            other => Err(format!("unknown process role {other:?}")),
+        }
+    }
+}
+
+impl Display for ProcessRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessRole::Broker => write!(f, "broker"),
+            ProcessRole::Controller => write!(f, "controller"),
+        }
+    }
+}
+
+/// How the broker log, including the request log, is written to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line, for log shippers.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("unknown log format {other:?}")),
+        }
+    }
+}
+
+impl Display for LogFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Text => write!(f, "text"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+fn parse_endpoints(value: &str) -> anyhow::Result<Vec<Endpoint>> {
+    value.split(',').map(str::trim).filter(|endpoint| !endpoint.is_empty()).map(str::parse).collect()
+}
+
+/// Parses the Java `.properties` subset Kafka configs use: `key=value` lines
+/// with `#` or `!` comments.
+pub(crate) fn parse_properties(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=').or_else(|| line.split_once(':'))?;
+            Some((key.trim().to_owned(), value.trim().to_owned()))
+        })
+        .collect()
+}
@@ -0,0 +1,286 @@
+use crate::kafka::config::{
+    config_def, BrokerConfigDef, ConfigSource, ConfigType, ServerConfig, TopicConfigDef, BROKER_CONFIGS, TOPIC_CONFIGS,
+};
+use crate::kafka::metadata::{append_metadata_records, ConfigRecord, ConfigResourceType};
+use crate::kafka::storage::LogManager;
+use crate::kafka::types::ErrorCode;
+use std::collections::HashMap;
+use std::io;
+use std::sync::RwLock;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ConfigError {
+    #[error("{0}")]
+    InvalidConfig(String),
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("Failed to write config records: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl ConfigError {
+    pub(crate) fn error_code(&self) -> ErrorCode {
+        match self {
+            ConfigError::InvalidConfig(_) => ErrorCode::InvalidConfig,
+            ConfigError::InvalidRequest(_) => ErrorCode::InvalidRequest,
+            ConfigError::Io(_) => ErrorCode::UnknownServerError,
+        }
+    }
+}
+
+/// A config as reported by DescribeConfigs.
+#[derive(Debug, Clone)]
+pub(crate) struct ConfigEntry {
+    pub(crate) name: String,
+    pub(crate) value: Option<String>,
+    pub(crate) source: ConfigSource,
+    pub(crate) read_only: bool,
+    pub(crate) sensitive: bool,
+    pub(crate) config_type: ConfigType,
+    pub(crate) documentation: &'static str,
+    /// Every value the config may come from, most specific first. The first
+    /// one is in effect.
+    pub(crate) synonyms: Vec<ConfigSynonym>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ConfigSynonym {
+    pub(crate) name: String,
+    pub(crate) value: Option<String>,
+    pub(crate) source: ConfigSource,
+}
+
+/// The dynamic topic and broker configs, replayed from the ConfigRecords
+/// of the metadata log and changed with AlterConfigs.
+#[derive(Debug)]
+pub(crate) struct DynamicConfigs {
+    config: ServerConfig,
+    defaults: ServerConfig,
+    topics: RwLock<HashMap<String, HashMap<String, String>>>,
+    /// Keyed by broker id, the empty id holding the defaults of all brokers.
+    brokers: RwLock<HashMap<String, HashMap<String, String>>>,
+}
+
+impl DynamicConfigs {
+    pub(crate) fn new(
+        config: ServerConfig,
+        topics: HashMap<String, HashMap<String, String>>,
+        brokers: HashMap<String, HashMap<String, String>>,
+    ) -> Self {
+        Self {
+            config,
+            defaults: ServerConfig::default(),
+            topics: RwLock::new(topics),
+            brokers: RwLock::new(brokers),
+        }
+    }
+
+    /// The dynamic configs set on the resource.
+    pub(crate) fn overrides(&self, resource_type: ConfigResourceType, name: &str) -> HashMap<String, String> {
+        let resources = self.resources(resource_type).read().expect("dynamic config lock poisoned");
+        resources.get(name).cloned().unwrap_or_default()
+    }
+
+    /// The dynamic broker configs in effect on this broker, keyed by the
+    /// topic configs they are the default of.
+    pub(crate) fn log_config_overrides(&self) -> HashMap<String, String> {
+        TOPIC_CONFIGS
+            .iter()
+            .filter_map(|def| Some((def.name.to_owned(), self.dynamic_broker_value(def.broker_config)?)))
+            .collect()
+    }
+
+    /// The configs of the resource. The empty broker name describes only the
+    /// dynamic defaults of all brokers.
+    pub(crate) fn describe(&self, resource_type: ConfigResourceType, name: &str) -> Vec<ConfigEntry> {
+        match resource_type {
+            ConfigResourceType::Topic => {
+                let overrides = self.overrides(resource_type, name);
+                TOPIC_CONFIGS.iter().map(|def| self.describe_topic_config(def, overrides.get(def.name))).collect()
+            }
+            ConfigResourceType::Broker if name.is_empty() => {
+                let defaults = self.overrides(resource_type, name);
+                BROKER_CONFIGS
+                    .iter()
+                    .filter(|def| defaults.contains_key(def.name))
+                    .map(|def| self.describe_broker_config(def, false))
+                    .collect()
+            }
+            ConfigResourceType::Broker => {
+                BROKER_CONFIGS.iter().map(|def| self.describe_broker_config(def, true)).collect()
+            }
+        }
+    }
+
+    /// Checks that every config may be set dynamically on the resource type
+    /// and that its value is valid.
+    pub(crate) fn validate(
+        &self,
+        resource_type: ConfigResourceType,
+        configs: &HashMap<String, String>,
+    ) -> Result<(), ConfigError> {
+        for (name, value) in configs {
+            let Some(def) = config_def(resource_type, name) else {
+                let kind = match resource_type {
+                    ConfigResourceType::Topic => "topic",
+                    ConfigResourceType::Broker => "broker",
+                };
+                return Err(ConfigError::InvalidConfig(format!("Unknown {kind} config name: {name}")));
+            };
+            if resource_type == ConfigResourceType::Broker && !def.dynamic {
+                return Err(ConfigError::InvalidConfig(format!("Cannot update these configs dynamically: {name}")));
+            }
+            def.validate(value).map_err(|reason| {
+                ConfigError::InvalidConfig(format!("Invalid value {value} for configuration {name}: {reason}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the dynamic configs of the resource: the changes are written
+    /// to the metadata log as ConfigRecords, then applied to the logs.
+    pub(crate) fn replace(
+        &self,
+        log_manager: &LogManager,
+        resource_type: ConfigResourceType,
+        name: &str,
+        configs: HashMap<String, String>,
+    ) -> Result<(), ConfigError> {
+        let current = self.overrides(resource_type, name);
+        let removed = current.keys().filter(|key| !configs.contains_key(*key)).map(|key| (key, None));
+        let changed = configs.iter().filter(|(key, value)| current.get(*key) != Some(value));
+        let records = changed
+            .map(|(key, value)| (key, Some(value.clone())))
+            .chain(removed)
+            .map(|(key, value)| ConfigRecord::new(resource_type, name, key, value).to_value())
+            .collect();
+        append_metadata_records(log_manager, records)?;
+
+        let resources = self.resources(resource_type);
+        resources.write().expect("dynamic config lock poisoned").insert(name.to_owned(), configs.clone());
+        match resource_type {
+            ConfigResourceType::Topic => log_manager.update_topic_config(name, &configs),
+            ConfigResourceType::Broker => log_manager.update_default_log_config(&self.log_config_overrides()),
+        }
+        Ok(())
+    }
+
+    fn resources(&self, resource_type: ConfigResourceType) -> &RwLock<HashMap<String, HashMap<String, String>>> {
+        match resource_type {
+            ConfigResourceType::Topic => &self.topics,
+            ConfigResourceType::Broker => &self.brokers,
+        }
+    }
+
+    /// The dynamic value of a broker config on this broker, falling back to
+    /// the default of all brokers.
+    fn dynamic_broker_value(&self, name: &str) -> Option<String> {
+        let brokers = self.brokers.read().expect("dynamic config lock poisoned");
+        let value = |broker: &str| brokers.get(broker).and_then(|configs| configs.get(name)).cloned();
+        value(&self.config.node_id.to_string()).or_else(|| value(""))
+    }
+
+    fn describe_broker_config(&self, def: &BrokerConfigDef, per_broker: bool) -> ConfigEntry {
+        let mut synonyms = Vec::new();
+        let brokers = self.brokers.read().expect("dynamic config lock poisoned");
+        let node_id = self.config.node_id.to_string();
+        let scopes = [(node_id.as_str(), ConfigSource::DynamicBroker), ("", ConfigSource::DynamicDefaultBroker)];
+        for (broker, source) in scopes.into_iter().skip(usize::from(!per_broker)) {
+            if let Some(value) = brokers.get(broker).and_then(|configs| configs.get(def.name)) {
+                synonyms.push(ConfigSynonym { name: def.name.to_owned(), value: Some(value.clone()), source });
+            }
+        }
+        for name in std::iter::once(&def.name).chain(def.synonyms) {
+            if let Some(value) = self.config.properties.get(*name) {
+                let source = ConfigSource::StaticBroker;
+                synonyms.push(ConfigSynonym { name: (*name).to_owned(), value: Some(value.clone()), source });
+            }
+        }
+        let default = def.value(&self.defaults);
+        synonyms.push(ConfigSynonym { name: def.name.to_owned(), value: default, source: ConfigSource::Default });
+
+        // Static values are reported as parsed, so `log.retention.hours`
+        // shows up in milliseconds.
+        let source = synonyms[0].source;
+        let value = match source {
+            ConfigSource::DynamicBroker | ConfigSource::DynamicDefaultBroker => synonyms[0].value.clone(),
+            _ => def.value(&self.config),
+        };
+        let mut entry = ConfigEntry {
+            name: def.name.to_owned(),
+            value,
+            source,
+            read_only: !def.dynamic,
+            sensitive: def.sensitive,
+            config_type: def.config_type,
+            documentation: def.documentation,
+            synonyms,
+        };
+        if def.sensitive {
+            entry.value = None;
+            entry.synonyms.iter_mut().for_each(|synonym| synonym.value = None);
+        }
+        entry
+    }
+
+    fn describe_topic_config(&self, def: &TopicConfigDef, value: Option<&String>) -> ConfigEntry {
+        let mut entry = self.describe_broker_config(def.broker_def(), true);
+        entry.name = def.name.to_owned();
+        entry.read_only = false;
+        entry.documentation = def.documentation;
+        if let Some(value) = value {
+            let source = ConfigSource::DynamicTopic;
+            entry.synonyms.insert(0, ConfigSynonym { name: def.name.to_owned(), value: Some(value.clone()), source });
+            entry.value = Some(value.clone());
+            entry.source = source;
+        }
+        entry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describes_sources_in_order_of_precedence() {
+        let props = HashMap::from([("log.retention.hours".to_owned(), "1".to_owned())]);
+        let config = ServerConfig::from_properties(&props).unwrap();
+        let topics = HashMap::from([("orders".to_owned(), HashMap::from([("segment.bytes".to_owned(), "1024".to_owned())]))]);
+        let brokers = HashMap::from([(String::new(), HashMap::from([("log.segment.bytes".to_owned(), "2048".to_owned())]))]);
+        let configs = DynamicConfigs::new(config, topics, brokers);
+
+        let entries = configs.describe(ConfigResourceType::Topic, "orders");
+        let entry = |name: &str| entries.iter().find(|entry| entry.name == name).unwrap();
+        let segment_bytes = entry("segment.bytes");
+        assert_eq!(segment_bytes.value.as_deref(), Some("1024"));
+        let sources: Vec<_> = segment_bytes.synonyms.iter().map(|synonym| synonym.source).collect();
+        assert_eq!(
+            sources,
+            [ConfigSource::DynamicTopic, ConfigSource::DynamicDefaultBroker, ConfigSource::Default]
+        );
+        let retention_ms = entry("retention.ms");
+        assert_eq!(retention_ms.value.as_deref(), Some("3600000"));
+        assert_eq!(retention_ms.source, ConfigSource::StaticBroker);
+        assert_eq!(retention_ms.synonyms[0].name, "log.retention.hours");
+
+        let defaults = configs.describe(ConfigResourceType::Broker, "");
+        assert_eq!(defaults.len(), 1);
+        assert_eq!(defaults[0].source, ConfigSource::DynamicDefaultBroker);
+        assert_eq!(configs.log_config_overrides(), HashMap::from([("segment.bytes".to_owned(), "2048".to_owned())]));
+    }
+
+    #[test]
+    fn test_rejects_static_and_invalid_configs() {
+        let configs = DynamicConfigs::new(ServerConfig::default(), HashMap::new(), HashMap::new());
+        let validate = |resource_type, name: &str, value: &str| {
+            configs.validate(resource_type, &HashMap::from([(name.to_owned(), value.to_owned())]))
+        };
+
+        assert!(validate(ConfigResourceType::Topic, "retention.ms", "1000").is_ok());
+        assert!(validate(ConfigResourceType::Topic, "retention.ms", "soon").is_err());
+        assert!(validate(ConfigResourceType::Topic, "log.retention.ms", "1000").is_err());
+        assert!(validate(ConfigResourceType::Broker, "log.retention.ms", "1000").is_ok());
+        assert!(validate(ConfigResourceType::Broker, "node.id", "2").is_err());
+    }
+}
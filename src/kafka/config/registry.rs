@@ -0,0 +1,671 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::metadata::ConfigResourceType;
+use crate::kafka::storage::{
+    CompressionType, CLEANUP_POLICY_CONFIG, COMPRESSION_TYPE_CONFIG, DELETE_RETENTION_MS_CONFIG,
+    MIN_CLEANABLE_DIRTY_RATIO_CONFIG, RETENTION_BYTES_CONFIG, RETENTION_MS_CONFIG, SEGMENT_BYTES_CONFIG,
+};
+
+/// The type of a config value, with its DescribeConfigs code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConfigType {
+    Boolean = 1,
+    String = 2,
+    Int = 3,
+    Long = 5,
+    Double = 6,
+    /// Comma-separated values, which IncrementalAlterConfigs can append to
+    /// and subtract from.
+    List = 7,
+    Class = 8,
+}
+
+impl ConfigType {
+    pub(crate) fn code(self) -> i8 {
+        self as i8
+    }
+}
+
+/// Where a config value comes from, in order of precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConfigSource {
+    DynamicTopic = 1,
+    /// A dynamic config of this broker only.
+    DynamicBroker = 3,
+    /// A dynamic config shared by all brokers.
+    DynamicDefaultBroker = 4,
+    /// Set in `server.properties`.
+    StaticBroker = 5,
+    Default = 6,
+}
+
+impl ConfigSource {
+    pub(crate) fn code(self) -> i8 {
+        self as i8
+    }
+}
+
+/// Constraints on a config value beyond its type.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Validator {
+    Any,
+    AtLeast(i64),
+    Between(f64, f64),
+    OneOf(&'static [&'static str]),
+    /// A non-empty list of the given values.
+    ListOf(&'static [&'static str]),
+}
+
+/// A broker config known to DescribeConfigs and AlterConfigs.
+#[derive(Debug)]
+pub(crate) struct BrokerConfigDef {
+    pub(crate) name: &'static str,
+    pub(crate) config_type: ConfigType,
+    /// Alternative `server.properties` names read when `name` is not set,
+    /// such as `log.retention.hours`.
+    pub(crate) synonyms: &'static [&'static str],
+    /// Whether AlterConfigs may change the config without a restart.
+    pub(crate) dynamic: bool,
+    /// Sensitive values are never returned by DescribeConfigs.
+    pub(crate) sensitive: bool,
+    pub(crate) validator: Validator,
+    pub(crate) documentation: &'static str,
+    value: fn(&ServerConfig) -> Option<String>,
+}
+
+impl BrokerConfigDef {
+    /// The value in effect in `config`, `None` if unset.
+    pub(crate) fn value(&self, config: &ServerConfig) -> Option<String> {
+        (self.value)(config)
+    }
+
+    /// Checks that `value` parses as the config type and passes the
+    /// validator, returning why it does not.
+    pub(crate) fn validate(&self, value: &str) -> Result<(), String> {
+        let parses = match self.config_type {
+            ConfigType::Boolean => value.parse::<bool>().is_ok(),
+            ConfigType::Int => value.parse::<i32>().is_ok(),
+            ConfigType::Long => value.parse::<i64>().is_ok(),
+            ConfigType::Double => value.parse::<f64>().is_ok(),
+            ConfigType::String | ConfigType::List | ConfigType::Class => true,
+        };
+        if !parses {
+            return Err(format!("expected a value of type {:?}", self.config_type));
+        }
+
+        match self.validator {
+            Validator::Any => Ok(()),
+            Validator::AtLeast(min) => match value.parse::<i64>() {
+                Ok(number) if number >= min => Ok(()),
+                _ => Err(format!("value must be at least {min}")),
+            },
+            Validator::Between(min, max) => match value.parse::<f64>() {
+                Ok(number) if (min..=max).contains(&number) => Ok(()),
+                _ => Err(format!("value must be between {min} and {max}")),
+            },
+            Validator::OneOf(values) if values.contains(&value) => Ok(()),
+            Validator::OneOf(values) => Err(format!("string must be one of: {}", values.join(", "))),
+            Validator::ListOf(values) => {
+                let items: Vec<_> = value.split(',').map(str::trim).collect();
+                if items.iter().all(|item| values.contains(item)) {
+                    Ok(())
+                } else {
+                    Err(format!("list items must be among: {}", values.join(", ")))
+                }
+            }
+        }
+    }
+}
+
+/// A topic config, defaulting to the value of a broker config.
+#[derive(Debug)]
+pub(crate) struct TopicConfigDef {
+    pub(crate) name: &'static str,
+    pub(crate) broker_config: &'static str,
+    pub(crate) documentation: &'static str,
+}
+
+impl TopicConfigDef {
+    /// The broker config providing the type, validator and default.
+    pub(crate) fn broker_def(&self) -> &'static BrokerConfigDef {
+        broker_config_def(self.broker_config).expect("topic configs name registered broker configs")
+    }
+}
+
+pub(crate) static BROKER_CONFIGS: &[BrokerConfigDef] = &[
+    BrokerConfigDef {
+        name: "advertised.listeners",
+        config_type: ConfigType::String,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The listeners handed to clients, if different from listeners.",
+        value: |config| Some(join(&config.advertised_listeners, ",")),
+    },
+    BrokerConfigDef {
+        name: "allow.everyone.if.no.acl.found",
+        config_type: ConfigType::Boolean,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "Whether resources without ACLs are open to everyone.",
+        value: |config| Some(config.allow_everyone_if_no_acl_found.to_string()),
+    },
+    BrokerConfigDef {
+        name: "authorizer.class.name",
+        config_type: ConfigType::Class,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "Enables authorization of requests against ACLs when set.",
+        value: |config| config.authorizer_class_name.clone(),
+    },
+    BrokerConfigDef {
+        name: "broker.heartbeat.interval.ms",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "How often the broker heartbeats the controller.",
+        value: |config| Some(config.broker_heartbeat_interval_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "broker.session.timeout.ms",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "How long the controller waits for a heartbeat before fencing a broker.",
+        value: |config| Some(config.broker_session_timeout_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "compression.type",
+        config_type: ConfigType::String,
+        synonyms: &[],
+        dynamic: true,
+        sensitive: false,
+        validator: Validator::OneOf(CompressionType::NAMES),
+        documentation: "The default compression codec of topics, producer keeping the codec of the producer.",
+        value: |config| Some(config.compression_type.to_string()),
+    },
+    BrokerConfigDef {
+        name: "connections.max.idle.ms",
+        config_type: ConfigType::Long,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "How long a connection may go without a request before it is closed.",
+        value: |config| Some(config.connections_max_idle_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "group.initial.rebalance.delay.ms",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(0),
+        documentation: "How long the first rebalance of an empty classic group waits for more members.",
+        value: |config| Some(config.group_initial_rebalance_delay_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "group.max.session.timeout.ms",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The maximum session timeout of classic group members.",
+        value: |config| Some(config.group_max_session_timeout_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "group.max.size",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The maximum number of members of a classic group.",
+        value: |config| Some(config.group_max_size.to_string()),
+    },
+    BrokerConfigDef {
+        name: "group.min.session.timeout.ms",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The minimum session timeout of classic group members.",
+        value: |config| Some(config.group_min_session_timeout_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "listeners",
+        config_type: ConfigType::String,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The NAME://host:port endpoints the broker listens on.",
+        value: |config| Some(join(&config.listeners, ",")),
+    },
+    BrokerConfigDef {
+        name: "log.cleaner.delete.retention.ms",
+        config_type: ConfigType::Long,
+        synonyms: &[],
+        dynamic: true,
+        sensitive: false,
+        validator: Validator::AtLeast(0),
+        documentation: "How long tombstones are retained in compacted topics.",
+        value: |config| Some(config.log_cleaner_delete_retention_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "log.cleaner.enable",
+        config_type: ConfigType::Boolean,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "Whether the log cleaner compacts topics with cleanup.policy=compact.",
+        value: |config| Some(config.log_cleaner_enable.to_string()),
+    },
+    BrokerConfigDef {
+        name: "log.cleaner.min.cleanable.ratio",
+        config_type: ConfigType::Double,
+        synonyms: &[],
+        dynamic: true,
+        sensitive: false,
+        validator: Validator::Between(0.0, 1.0),
+        documentation: "The minimum ratio of dirty bytes for a log to be compacted.",
+        value: |config| Some(config.log_cleaner_min_cleanable_ratio.to_string()),
+    },
+    BrokerConfigDef {
+        name: "log.cleanup.policy",
+        config_type: ConfigType::List,
+        synonyms: &[],
+        dynamic: true,
+        sensitive: false,
+        validator: Validator::ListOf(&["delete", "compact"]),
+        documentation: "The default cleanup policy of topics: delete, compact or both.",
+        value: |config| Some(config.log_cleanup_policy.to_string()),
+    },
+    BrokerConfigDef {
+        name: "log.dirs",
+        config_type: ConfigType::String,
+        synonyms: &["log.dir"],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The directories holding partition logs.",
+        value: |config| {
+            Some(config.log_dirs.iter().map(|dir| dir.display().to_string()).collect::<Vec<_>>().join(","))
+        },
+    },
+    BrokerConfigDef {
+        name: "log.retention.bytes",
+        config_type: ConfigType::Long,
+        synonyms: &[],
+        dynamic: true,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The maximum size of a partition log before old segments are deleted, -1 for no limit.",
+        value: |config| Some(config.log_retention_bytes.to_string()),
+    },
+    BrokerConfigDef {
+        name: "log.retention.check.interval.ms",
+        config_type: ConfigType::Long,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "How often logs are checked for segments to delete.",
+        value: |config| Some(config.log_retention_check_interval_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "log.retention.ms",
+        config_type: ConfigType::Long,
+        synonyms: &["log.retention.minutes", "log.retention.hours"],
+        dynamic: true,
+        sensitive: false,
+        validator: Validator::AtLeast(-1),
+        documentation: "How long segments are kept before they are deleted, -1 for no limit.",
+        value: |config| Some(config.log_retention_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "log.segment.bytes",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: true,
+        sensitive: false,
+        validator: Validator::AtLeast(14),
+        documentation: "The maximum size of a log segment before a new one is rolled.",
+        value: |config| Some(config.log_segment_bytes.to_string()),
+    },
+    BrokerConfigDef {
+        name: "logging.format",
+        config_type: ConfigType::String,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::OneOf(&["text", "json"]),
+        documentation: "How log lines, including the request log, are written: text or json.",
+        value: |config| Some(config.logging_format.to_string()),
+    },
+    BrokerConfigDef {
+        name: "max.connection.creation.rate",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The new connections accepted per second, beyond which connections are closed.",
+        value: |config| Some(config.max_connection_creation_rate.to_string()),
+    },
+    BrokerConfigDef {
+        name: "max.connections",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(0),
+        documentation: "The open connections allowed, beyond which new connections are closed.",
+        value: |config| Some(config.max_connections.to_string()),
+    },
+    BrokerConfigDef {
+        name: "max.connections.per.ip",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(0),
+        documentation: "The open connections allowed from one client address.",
+        value: |config| Some(config.max_connections_per_ip.to_string()),
+    },
+    BrokerConfigDef {
+        name: "max.connections.per.ip.overrides",
+        config_type: ConfigType::String,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "Comma-separated address:count overrides of max.connections.per.ip.",
+        value: |config| {
+            let mut overrides = config
+                .max_connections_per_ip_overrides
+                .iter()
+                .map(|(addr, count)| format!("{addr}:{count}"))
+                .collect::<Vec<_>>();
+            overrides.sort();
+            Some(overrides.join(","))
+        },
+    },
+    BrokerConfigDef {
+        name: "metrics.host",
+        config_type: ConfigType::String,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The host the /metrics endpoint binds, the host of the first listener if unset.",
+        value: |config| config.metrics_host.clone(),
+    },
+    BrokerConfigDef {
+        name: "metrics.port",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Between(0.0, 65535.0),
+        documentation: "The port the /metrics endpoint is served on, disabled if unset.",
+        value: |config| config.metrics_port.map(|port| port.to_string()),
+    },
+    BrokerConfigDef {
+        name: "node.id",
+        config_type: ConfigType::Int,
+        synonyms: &["broker.id"],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(0),
+        documentation: "The id of this broker.",
+        value: |config| Some(config.node_id.to_string()),
+    },
+    BrokerConfigDef {
+        name: "num.io.threads",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The threads handling requests, including their disk I/O.",
+        value: |config| Some(config.num_io_threads.to_string()),
+    },
+    BrokerConfigDef {
+        name: "num.network.threads",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The threads reading requests from and writing responses to connections.",
+        value: |config| Some(config.num_network_threads.to_string()),
+    },
+    BrokerConfigDef {
+        name: "offsets.topic.num.partitions",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The number of partitions of the __consumer_offsets topic.",
+        value: |config| Some(config.offsets_topic_num_partitions.to_string()),
+    },
+    BrokerConfigDef {
+        name: "process.roles",
+        config_type: ConfigType::List,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The roles of this node, broker and controller.",
+        value: |config| Some(join(&config.process_roles, ",")),
+    },
+    BrokerConfigDef {
+        name: "queued.max.requests",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The requests waiting for a handler before connections stop reading requests.",
+        value: |config| Some(config.queued_max_requests.to_string()),
+    },
+    BrokerConfigDef {
+        name: "quota.window.num",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The number of samples client quota rates are measured over.",
+        value: |config| Some(config.quota_window_num.to_string()),
+    },
+    BrokerConfigDef {
+        name: "quota.window.size.seconds",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The time span of each client quota sample.",
+        value: |config| Some(config.quota_window_size_seconds.to_string()),
+    },
+    BrokerConfigDef {
+        name: "sasl.enabled.mechanisms",
+        config_type: ConfigType::List,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "The SASL mechanisms clients may authenticate with.",
+        value: |config| Some(join(&config.sasl_enabled_mechanisms, ",")),
+    },
+    BrokerConfigDef {
+        name: "sasl.server.max.receive.size",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The largest request accepted before a SASL connection has authenticated.",
+        value: |config| Some(config.sasl_server_max_receive_size.to_string()),
+    },
+    BrokerConfigDef {
+        name: "shutdown.timeout.ms",
+        config_type: ConfigType::Long,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(0),
+        documentation: "How long connections may finish in-flight requests on shutdown before logs are flushed.",
+        value: |config| Some(config.shutdown_timeout_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "socket.request.max.bytes",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The largest request accepted; connections sending a larger one are closed.",
+        value: |config| Some(config.socket_request_max_bytes.to_string()),
+    },
+    BrokerConfigDef {
+        name: "super.users",
+        config_type: ConfigType::String,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::Any,
+        documentation: "Semicolon-separated principals allowed every operation.",
+        value: |config| Some(config.super_users.join(";")),
+    },
+    BrokerConfigDef {
+        name: "transaction.max.timeout.ms",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The maximum transaction timeout producers may request.",
+        value: |config| Some(config.transaction_max_timeout_ms.to_string()),
+    },
+    BrokerConfigDef {
+        name: "transaction.state.log.num.partitions",
+        config_type: ConfigType::Int,
+        synonyms: &[],
+        dynamic: false,
+        sensitive: false,
+        validator: Validator::AtLeast(1),
+        documentation: "The number of partitions of the __transaction_state topic.",
+        value: |config| Some(config.transaction_state_log_num_partitions.to_string()),
+    },
+];
+
+pub(crate) static TOPIC_CONFIGS: &[TopicConfigDef] = &[
+    TopicConfigDef {
+        name: CLEANUP_POLICY_CONFIG,
+        broker_config: "log.cleanup.policy",
+        documentation: "Whether old segments are deleted, compacted or both.",
+    },
+    TopicConfigDef {
+        name: COMPRESSION_TYPE_CONFIG,
+        broker_config: "compression.type",
+        documentation: "The compression codec of the topic, producer keeping the codec of the producer.",
+    },
+    TopicConfigDef {
+        name: DELETE_RETENTION_MS_CONFIG,
+        broker_config: "log.cleaner.delete.retention.ms",
+        documentation: "How long tombstones are retained when the topic is compacted.",
+    },
+    TopicConfigDef {
+        name: MIN_CLEANABLE_DIRTY_RATIO_CONFIG,
+        broker_config: "log.cleaner.min.cleanable.ratio",
+        documentation: "The minimum ratio of dirty bytes for the log to be compacted.",
+    },
+    TopicConfigDef {
+        name: RETENTION_BYTES_CONFIG,
+        broker_config: "log.retention.bytes",
+        documentation: "The maximum size of a partition before old segments are deleted, -1 for no limit.",
+    },
+    TopicConfigDef {
+        name: RETENTION_MS_CONFIG,
+        broker_config: "log.retention.ms",
+        documentation: "How long segments are kept before they are deleted, -1 for no limit.",
+    },
+    TopicConfigDef {
+        name: SEGMENT_BYTES_CONFIG,
+        broker_config: "log.segment.bytes",
+        documentation: "The maximum size of a log segment before a new one is rolled.",
+    },
+];
+
+/// The definition of a config of the resource type, for topics that of the
+/// broker config providing its default.
+pub(crate) fn config_def(resource_type: ConfigResourceType, name: &str) -> Option<&'static BrokerConfigDef> {
+    match resource_type {
+        ConfigResourceType::Topic => topic_config_def(name).map(TopicConfigDef::broker_def),
+        ConfigResourceType::Broker => broker_config_def(name),
+    }
+}
+
+pub(crate) fn broker_config_def(name: &str) -> Option<&'static BrokerConfigDef> {
+    BROKER_CONFIGS.iter().find(|def| def.name == name)
+}
+
+pub(crate) fn topic_config_def(name: &str) -> Option<&'static TopicConfigDef> {
+    TOPIC_CONFIGS.iter().find(|def| def.name == name)
+}
+
+fn join(values: &[impl ToString], separator: &str) -> String {
+    values.iter().map(ToString::to_string).collect::<Vec<_>>().join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_defaults_round_trip_through_properties() {
+        let defaults = ServerConfig::default();
+        let props: HashMap<_, _> = BROKER_CONFIGS
+            .iter()
+            .filter_map(|def| Some((def.name.to_owned(), def.value(&defaults)?)))
+            .collect();
+        for (name, value) in &props {
+            let def = broker_config_def(name).unwrap();
+            assert_eq!(def.validate(value), Ok(()), "{name}");
+        }
+
+        let config = ServerConfig::from_properties(&props).unwrap();
+        for def in BROKER_CONFIGS {
+            assert_eq!(def.value(&config), def.value(&defaults), "{}", def.name);
+        }
+    }
+
+    #[test]
+    fn test_validators_reject_bad_values() {
+        let def = |name| broker_config_def(name).unwrap();
+        assert!(def("log.segment.bytes").validate("1048576").is_ok());
+        assert!(def("log.segment.bytes").validate("10").is_err());
+        assert!(def("log.segment.bytes").validate("lots").is_err());
+        assert!(def("log.cleanup.policy").validate("compact,delete").is_ok());
+        assert!(def("log.cleanup.policy").validate("compact,archive").is_err());
+        assert!(def("log.cleaner.min.cleanable.ratio").validate("1.5").is_err());
+        assert!(def("compression.type").validate("zstd").is_ok());
+        assert!(def("compression.type").validate("brotli").is_err());
+    }
+}
@@ -0,0 +1,16 @@
+mod assignor;
+pub(crate) use assignor::*;
+mod consumer_group;
+pub(crate) use consumer_group::*;
+mod group;
+pub(crate) use group::*;
+mod group_coordinator;
+pub(crate) use group_coordinator::*;
+mod producer_id_manager;
+pub(crate) use producer_id_manager::*;
+mod records;
+pub(crate) use records::*;
+mod transaction;
+pub(crate) use transaction::*;
+mod transaction_coordinator;
+pub(crate) use transaction_coordinator::*;
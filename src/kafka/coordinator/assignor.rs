@@ -0,0 +1,216 @@
+use crate::kafka::coordinator::Assignment;
+use crate::kafka::types::Uuid;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The server-side assignors of the consumer group protocol (KIP-848),
+/// selected by name in `group.consumer.assignors` and ConsumerGroupHeartbeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PartitionAssignor {
+    /// Spreads the partitions of all subscribed topics evenly, keeping
+    /// partitions with their current owner where balance allows.
+    Uniform,
+    /// Gives each subscriber a contiguous range of every topic's partitions.
+    Range,
+}
+
+impl PartitionAssignor {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            PartitionAssignor::Uniform => "uniform",
+            PartitionAssignor::Range => "range",
+        }
+    }
+
+    /// Assigns the partitions of `topics`, given as partition counts by
+    /// topic id, to the members subscribed to them. `current` is the previous
+    /// target assignment. Every member gets an entry, possibly empty.
+    pub(crate) fn assign(
+        self,
+        subscriptions: &BTreeMap<String, BTreeSet<Uuid>>,
+        topics: &BTreeMap<Uuid, i32>,
+        current: &HashMap<String, Assignment>,
+    ) -> HashMap<String, Assignment> {
+        let mut assignment = match self {
+            PartitionAssignor::Uniform => assign_uniform(subscriptions, topics, current),
+            PartitionAssignor::Range => assign_range(subscriptions, topics),
+        };
+        for member_id in subscriptions.keys() {
+            assignment.entry(member_id.clone()).or_default();
+        }
+        assignment
+    }
+}
+
+impl FromStr for PartitionAssignor {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "uniform" => Ok(PartitionAssignor::Uniform),
+            "range" => Ok(PartitionAssignor::Range),
+            _ => Err(format!("unknown assignor {name:?}")),
+        }
+    }
+}
+
+impl Display for PartitionAssignor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+fn assign_uniform(
+    subscriptions: &BTreeMap<String, BTreeSet<Uuid>>,
+    topics: &BTreeMap<Uuid, i32>,
+    current: &HashMap<String, Assignment>,
+) -> HashMap<String, Assignment> {
+    let subscribers = |topic_id: Uuid| {
+        subscriptions.iter().filter(move |(_, topic_ids)| topic_ids.contains(&topic_id)).map(|(member_id, _)| member_id)
+    };
+    let partitions = topics
+        .iter()
+        .filter(|(topic_id, _)| subscribers(**topic_id).next().is_some())
+        .flat_map(|(topic_id, &num_partitions)| (0..num_partitions).map(move |partition| (*topic_id, partition)))
+        .collect::<Vec<_>>();
+    if partitions.is_empty() {
+        return HashMap::new();
+    }
+    let quota = partitions.len().div_ceil(subscriptions.len());
+
+    let mut owners = HashMap::new();
+    let mut counts = subscriptions.keys().map(|member_id| (member_id, 0usize)).collect::<HashMap<_, _>>();
+    // Keep current owners that are still subscribed, up to the quota.
+    for (member_id, topic_ids) in subscriptions {
+        let owned = current.get(member_id).into_iter().flatten();
+        for (topic_id, owned_partitions) in owned.filter(|(topic_id, _)| topic_ids.contains(topic_id)) {
+            for &partition in owned_partitions {
+                let count = counts.get_mut(member_id).expect("member is counted");
+                if *count < quota && partition < topics.get(topic_id).copied().unwrap_or(0) {
+                    owners.entry((*topic_id, partition)).or_insert_with(|| {
+                        *count += 1;
+                        member_id
+                    });
+                }
+            }
+        }
+    }
+    // Give the rest to the least loaded subscriber.
+    for &(topic_id, partition) in &partitions {
+        if owners.contains_key(&(topic_id, partition)) {
+            continue;
+        }
+        let member_id = subscribers(topic_id).min_by_key(|member_id| counts[member_id]).expect("topic has a subscriber");
+        *counts.get_mut(member_id).expect("member is counted") += 1;
+        owners.insert((topic_id, partition), member_id);
+    }
+    // With different subscriptions, move partitions from members holding at
+    // least two more than another subscriber until that no longer happens.
+    loop {
+        let moved = partitions.iter().find_map(|&(topic_id, partition)| {
+            let owner = owners[&(topic_id, partition)];
+            let target = subscribers(topic_id).min_by_key(|member_id| counts[member_id])?;
+            (counts[target] + 1 < counts[owner]).then_some(((topic_id, partition), owner, target))
+        });
+        let Some((topic_partition, owner, target)) = moved else {
+            break;
+        };
+        *counts.get_mut(owner).expect("member is counted") -= 1;
+        *counts.get_mut(target).expect("member is counted") += 1;
+        owners.insert(topic_partition, target);
+    }
+
+    let mut assignment = HashMap::<String, Assignment>::new();
+    for ((topic_id, partition), member_id) in owners {
+        assignment.entry(member_id.clone()).or_default().entry(topic_id).or_default().insert(partition);
+    }
+    assignment
+}
+
+fn assign_range(
+    subscriptions: &BTreeMap<String, BTreeSet<Uuid>>,
+    topics: &BTreeMap<Uuid, i32>,
+) -> HashMap<String, Assignment> {
+    let mut assignment = HashMap::<String, Assignment>::new();
+    for (topic_id, &num_partitions) in topics {
+        let subscribers = subscriptions
+            .iter()
+            .filter(|(_, topic_ids)| topic_ids.contains(topic_id))
+            .map(|(member_id, _)| member_id)
+            .collect::<Vec<_>>();
+        if subscribers.is_empty() {
+            continue;
+        }
+        let per_member = num_partitions as usize / subscribers.len();
+        let with_extra = num_partitions as usize % subscribers.len();
+
+        let mut start = 0;
+        for (i, member_id) in subscribers.into_iter().enumerate() {
+            let len = per_member + usize::from(i < with_extra);
+            if len > 0 {
+                let partitions = (start..start + len).map(|partition| partition as i32).collect();
+                assignment.entry(member_id.clone()).or_default().insert(*topic_id, partitions);
+            }
+            start += len;
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscriptions(members: &[(&str, &[Uuid])]) -> BTreeMap<String, BTreeSet<Uuid>> {
+        members.iter().map(|(member_id, topic_ids)| (member_id.to_string(), topic_ids.iter().copied().collect())).collect()
+    }
+
+    fn count(assignment: &Assignment) -> usize {
+        assignment.values().map(BTreeSet::len).sum()
+    }
+
+    #[test]
+    fn test_uniform_is_balanced_and_sticky() {
+        let (foo, bar) = (Uuid([1; 16]), Uuid([2; 16]));
+        let topics = BTreeMap::from([(foo, 4), (bar, 3)]);
+        let two = subscriptions(&[("a", &[foo, bar]), ("b", &[foo, bar])]);
+        let first = PartitionAssignor::Uniform.assign(&two, &topics, &HashMap::new());
+        assert_eq!(count(&first["a"]) + count(&first["b"]), 7);
+        assert!(count(&first["a"]).abs_diff(count(&first["b"])) <= 1);
+
+        let three = subscriptions(&[("a", &[foo, bar]), ("b", &[foo, bar]), ("c", &[foo, bar])]);
+        let second = PartitionAssignor::Uniform.assign(&three, &topics, &first);
+        let counts = ["a", "b", "c"].map(|member_id| count(&second[member_id]));
+        assert_eq!(counts.iter().sum::<usize>(), 7);
+        assert!(counts.iter().all(|&count| count == 2 || count == 3));
+        // Existing members only give up partitions, they never swap them.
+        for member_id in ["a", "b"] {
+            for (topic_id, partitions) in &second[member_id] {
+                assert!(partitions.is_subset(&first[member_id][topic_id]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_uniform_with_different_subscriptions() {
+        let (foo, bar) = (Uuid([1; 16]), Uuid([2; 16]));
+        let topics = BTreeMap::from([(foo, 4), (bar, 2)]);
+        let members = subscriptions(&[("a", &[foo]), ("b", &[foo, bar])]);
+        let assignment = PartitionAssignor::Uniform.assign(&members, &topics, &HashMap::new());
+        assert_eq!(assignment["a"].keys().collect::<Vec<_>>(), vec![&foo]);
+        assert_eq!(count(&assignment["a"]), 3);
+        assert_eq!(count(&assignment["b"]), 3);
+    }
+
+    #[test]
+    fn test_range_gives_contiguous_ranges() {
+        let foo = Uuid([1; 16]);
+        let topics = BTreeMap::from([(foo, 5)]);
+        let members = subscriptions(&[("a", &[foo]), ("b", &[foo]), ("c", &[])]);
+        let assignment = PartitionAssignor::Range.assign(&members, &topics, &HashMap::new());
+        assert_eq!(assignment["a"][&foo], BTreeSet::from([0, 1, 2]));
+        assert_eq!(assignment["b"][&foo], BTreeSet::from([3, 4]));
+        assert!(assignment["c"].is_empty());
+    }
+}
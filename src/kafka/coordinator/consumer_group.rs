@@ -0,0 +1,267 @@
+use crate::kafka::coordinator::PartitionAssignor;
+use crate::kafka::storage::TopicMetadata;
+use crate::kafka::types::Uuid;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// Partitions by topic id.
+pub(crate) type Assignment = BTreeMap<Uuid, BTreeSet<i32>>;
+
+/// The member epoch a member leaves with.
+pub(crate) const LEAVE_GROUP_MEMBER_EPOCH: i32 = -1;
+/// The member epoch a static member leaves with when it intends to rejoin.
+pub(crate) const LEAVE_GROUP_STATIC_MEMBER_EPOCH: i32 = -2;
+
+/// The state of a consumer protocol group, derived from its epochs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConsumerGroupState {
+    Empty,
+    /// The group epoch moved past the target assignment.
+    Assigning,
+    /// Members are converging towards the target assignment.
+    Reconciling,
+    Stable,
+}
+
+impl Display for ConsumerGroupState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConsumerGroupState::Empty => "Empty",
+            ConsumerGroupState::Assigning => "Assigning",
+            ConsumerGroupState::Reconciling => "Reconciling",
+            ConsumerGroupState::Stable => "Stable",
+        })
+    }
+}
+
+/// Where a member is in reconciling its assignment with the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MemberState {
+    /// The member owns its target assignment at the assignment epoch.
+    Stable,
+    /// The member must revoke partitions before it gets a new epoch.
+    UnrevokedPartitions,
+    /// Some target partitions are still owned by other members.
+    UnreleasedPartitions,
+}
+
+/// A member of a consumer protocol group.
+#[derive(Debug)]
+pub(crate) struct ConsumerGroupMember {
+    pub(crate) member_id: String,
+    pub(crate) instance_id: Option<String>,
+    pub(crate) rack_id: Option<String>,
+    pub(crate) client_id: String,
+    pub(crate) client_host: String,
+    pub(crate) member_epoch: i32,
+    pub(crate) previous_member_epoch: i32,
+    pub(crate) state: MemberState,
+    pub(crate) rebalance_timeout: Duration,
+    pub(crate) subscribed_topic_names: BTreeSet<String>,
+    pub(crate) server_assignor: Option<PartitionAssignor>,
+    pub(crate) assigned_partitions: Assignment,
+    pub(crate) partitions_pending_revocation: Assignment,
+    pub(crate) last_heartbeat: Instant,
+    /// While revoking, the member is fenced if it does not finish by then.
+    pub(crate) revocation_deadline: Option<Instant>,
+}
+
+impl ConsumerGroupMember {
+    pub(crate) fn new(member_id: String, now: Instant) -> Self {
+        Self {
+            member_id,
+            instance_id: None,
+            rack_id: None,
+            client_id: String::new(),
+            client_host: String::new(),
+            member_epoch: 0,
+            previous_member_epoch: 0,
+            state: MemberState::Stable,
+            rebalance_timeout: Duration::ZERO,
+            subscribed_topic_names: BTreeSet::new(),
+            server_assignor: None,
+            assigned_partitions: Assignment::new(),
+            partitions_pending_revocation: Assignment::new(),
+            last_heartbeat: now,
+            revocation_deadline: None,
+        }
+    }
+}
+
+/// A group using the consumer protocol of KIP-848: members heartbeat their
+/// subscriptions and the coordinator computes their assignment.
+#[derive(Debug)]
+pub(crate) struct ConsumerGroup {
+    pub(crate) group_id: String,
+    /// Bumped whenever the membership, subscriptions or subscribed topics change.
+    pub(crate) group_epoch: i32,
+    /// The group epoch the target assignment was computed at.
+    pub(crate) assignment_epoch: i32,
+    pub(crate) members: BTreeMap<String, ConsumerGroupMember>,
+    /// Member ids of static members, by instance id.
+    pub(crate) static_members: HashMap<String, String>,
+    pub(crate) target_assignment: HashMap<String, Assignment>,
+    /// The subscribed topics as of the last group epoch, by name.
+    pub(crate) subscription_metadata: BTreeMap<String, TopicMetadata>,
+    /// The assignor that computed the target assignment.
+    pub(crate) assignor: Option<PartitionAssignor>,
+}
+
+impl ConsumerGroup {
+    pub(crate) fn new(group_id: String) -> Self {
+        Self {
+            group_id,
+            group_epoch: 0,
+            assignment_epoch: 0,
+            members: BTreeMap::new(),
+            static_members: HashMap::new(),
+            target_assignment: HashMap::new(),
+            subscription_metadata: BTreeMap::new(),
+            assignor: None,
+        }
+    }
+
+    pub(crate) fn state(&self) -> ConsumerGroupState {
+        if self.members.is_empty() {
+            ConsumerGroupState::Empty
+        } else if self.group_epoch > self.assignment_epoch {
+            ConsumerGroupState::Assigning
+        } else if self.members.values().any(|member| {
+            member.state != MemberState::Stable || member.member_epoch != self.assignment_epoch
+        }) {
+            ConsumerGroupState::Reconciling
+        } else {
+            ConsumerGroupState::Stable
+        }
+    }
+
+    pub(crate) fn subscribed_topic_names(&self) -> BTreeSet<String> {
+        self.members.values().flat_map(|member| member.subscribed_topic_names.iter().cloned()).collect()
+    }
+
+    /// Removes the member, releasing its partitions, and bumps the epoch.
+    pub(crate) fn remove_member(&mut self, member_id: &str) -> Option<ConsumerGroupMember> {
+        let member = self.members.remove(member_id)?;
+        if let Some(instance_id) = &member.instance_id {
+            if self.static_members.get(instance_id).map(String::as_str) == Some(member_id) {
+                self.static_members.remove(instance_id);
+            }
+        }
+        self.target_assignment.remove(member_id);
+        self.group_epoch += 1;
+        Some(member)
+    }
+
+    /// Recomputes the target assignment at the current group epoch with the
+    /// assignor preferred by most members, or `default_assignor`.
+    pub(crate) fn compute_target_assignment(&mut self, default_assignor: PartitionAssignor) {
+        let mut votes = BTreeMap::new();
+        for assignor in self.members.values().filter_map(|member| member.server_assignor) {
+            votes.entry(assignor.name()).or_insert((assignor, 0)).1 += 1;
+        }
+        let assignor = votes
+            .into_values()
+            .max_by_key(|(_, count)| *count)
+            .map_or(default_assignor, |(assignor, _)| assignor);
+
+        let topics = self
+            .subscription_metadata
+            .values()
+            .map(|metadata| (metadata.topic_id, metadata.num_partitions))
+            .collect();
+        let subscriptions = self
+            .members
+            .values()
+            .map(|member| {
+                let topic_ids = member
+                    .subscribed_topic_names
+                    .iter()
+                    .filter_map(|name| self.subscription_metadata.get(name).map(|metadata| metadata.topic_id))
+                    .collect();
+                (member.member_id.clone(), topic_ids)
+            })
+            .collect();
+
+        self.target_assignment = assignor.assign(&subscriptions, &topics, &self.target_assignment);
+        self.assignor = Some(assignor);
+        self.assignment_epoch = self.group_epoch;
+    }
+
+    /// Moves the member towards its target assignment. `owned` are the
+    /// partitions the member reported owning, `None` if it did not report
+    /// them. Partitions other members still own are withheld until released.
+    pub(crate) fn reconcile(&mut self, member_id: &str, owned: Option<&Assignment>) {
+        let owned_by_others = self
+            .members
+            .values()
+            .filter(|member| member.member_id != member_id)
+            .flat_map(|member| {
+                [&member.assigned_partitions, &member.partitions_pending_revocation]
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|(topic_id, partitions)| partitions.iter().map(|partition| (*topic_id, *partition)))
+            })
+            .collect::<HashSet<_>>();
+        let target = self.target_assignment.get(member_id).cloned().unwrap_or_default();
+        let target_epoch = self.assignment_epoch;
+        let Some(member) = self.members.get_mut(member_id) else {
+            return;
+        };
+
+        let owns_any = |revoking: &Assignment| {
+            owned.is_none_or(|owned| {
+                revoking.iter().any(|(topic_id, partitions)| {
+                    owned.get(topic_id).is_some_and(|owned| !owned.is_disjoint(partitions))
+                })
+            })
+        };
+        let (epoch, assigned) = match member.state {
+            MemberState::Stable if member.member_epoch == target_epoch => return,
+            MemberState::Stable => (member.member_epoch, member.assigned_partitions.clone()),
+            MemberState::UnrevokedPartitions if owns_any(&member.partitions_pending_revocation) => return,
+            // Revocation is complete, move on to the next epoch.
+            MemberState::UnrevokedPartitions => {
+                ((member.member_epoch + 1).min(target_epoch), member.assigned_partitions.clone())
+            }
+            MemberState::UnreleasedPartitions => (member.member_epoch, member.assigned_partitions.clone()),
+        };
+
+        let mut kept = Assignment::new();
+        let mut revoking = Assignment::new();
+        let mut assignable = Assignment::new();
+        let mut unreleased = false;
+        for topic_id in assigned.keys().chain(target.keys()).collect::<BTreeSet<_>>() {
+            let current = assigned.get(topic_id).cloned().unwrap_or_default();
+            let target = target.get(topic_id).cloned().unwrap_or_default();
+            let retained = current.intersection(&target).copied().collect::<BTreeSet<_>>();
+            let revoked = current.difference(&target).copied().collect::<BTreeSet<_>>();
+            let mut added = target.difference(&current).copied().collect::<BTreeSet<_>>();
+            let before = added.len();
+            added.retain(|partition| !owned_by_others.contains(&(*topic_id, *partition)));
+            unreleased |= added.len() < before;
+
+            for (assignment, partitions) in [(&mut kept, retained), (&mut revoking, revoked), (&mut assignable, added)] {
+                if !partitions.is_empty() {
+                    assignment.insert(*topic_id, partitions);
+                }
+            }
+        }
+
+        member.previous_member_epoch = member.member_epoch;
+        if !revoking.is_empty() && owns_any(&revoking) {
+            member.state = MemberState::UnrevokedPartitions;
+            member.member_epoch = epoch;
+            member.assigned_partitions = kept;
+            member.partitions_pending_revocation = revoking;
+            return;
+        }
+        for (topic_id, partitions) in assignable {
+            kept.entry(topic_id).or_default().extend(partitions);
+        }
+        member.state = if unreleased { MemberState::UnreleasedPartitions } else { MemberState::Stable };
+        member.member_epoch = target_epoch;
+        member.assigned_partitions = kept;
+        member.partitions_pending_revocation = Assignment::new();
+    }
+}
@@ -0,0 +1,305 @@
+use crate::kafka::coordinator::OffsetAndMetadata;
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString};
+use binrw::{binrw, BinRead};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// States of the classic rebalance protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GroupState {
+    /// No members, but the group may still hold committed offsets.
+    Empty,
+    /// Waiting for every member to rejoin.
+    PreparingRebalance,
+    /// Waiting for the leader's assignment.
+    CompletingRebalance,
+    Stable,
+    /// Removed, requests are redirected to find the coordinator again.
+    Dead,
+}
+
+impl GroupState {
+    fn can_transition_from(self, previous: GroupState) -> bool {
+        use GroupState::*;
+        match self {
+            Empty => previous == PreparingRebalance,
+            PreparingRebalance => matches!(previous, Empty | CompletingRebalance | Stable),
+            CompletingRebalance => previous == PreparingRebalance,
+            Stable => previous == CompletingRebalance,
+            Dead => true,
+        }
+    }
+}
+
+impl Display for GroupState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GroupState::Empty => "Empty",
+            GroupState::PreparingRebalance => "PreparingRebalance",
+            GroupState::CompletingRebalance => "CompletingRebalance",
+            GroupState::Stable => "Stable",
+            GroupState::Dead => "Dead",
+        })
+    }
+}
+
+/// The protocol type of groups formed by Kafka consumers, whose member
+/// metadata is a [`ConsumerProtocolSubscription`].
+pub(crate) const CONSUMER_PROTOCOL_TYPE: &str = "consumer";
+
+/// The leading fields of the member metadata of consumer protocol groups,
+/// common to all its versions.
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct ConsumerProtocolSubscription {
+    _version: i16,
+    #[brw(args(false, (false,)))]
+    topics: KafkaArray<KafkaString>,
+}
+
+#[derive(Debug)]
+pub(crate) struct JoinGroupMember {
+    pub(crate) member_id: String,
+    pub(crate) group_instance_id: Option<String>,
+    pub(crate) metadata: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub(crate) struct JoinGroupResult {
+    pub(crate) error_code: ErrorCode,
+    pub(crate) generation_id: i32,
+    pub(crate) protocol_type: Option<String>,
+    pub(crate) protocol_name: Option<String>,
+    pub(crate) leader_id: String,
+    pub(crate) member_id: String,
+    /// The members and their metadata, only sent to the leader.
+    pub(crate) members: Vec<JoinGroupMember>,
+    /// Tells a rejoining static leader to keep the current assignment.
+    pub(crate) skip_assignment: bool,
+}
+
+impl JoinGroupResult {
+    pub(crate) fn error(member_id: impl Into<String>, error_code: ErrorCode) -> Self {
+        Self {
+            error_code,
+            generation_id: -1,
+            protocol_type: None,
+            protocol_name: None,
+            leader_id: String::new(),
+            member_id: member_id.into(),
+            members: Vec::new(),
+            skip_assignment: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SyncGroupResult {
+    pub(crate) error_code: ErrorCode,
+    pub(crate) protocol_type: Option<String>,
+    pub(crate) protocol_name: Option<String>,
+    pub(crate) assignment: Vec<u8>,
+}
+
+impl SyncGroupResult {
+    pub(crate) fn error(error_code: ErrorCode) -> Self {
+        Self { error_code, protocol_type: None, protocol_name: None, assignment: Vec::new() }
+    }
+}
+
+/// A member of a classic group.
+#[derive(Debug)]
+pub(crate) struct Member {
+    pub(crate) member_id: String,
+    pub(crate) group_instance_id: Option<String>,
+    pub(crate) client_id: String,
+    pub(crate) client_host: String,
+    pub(crate) session_timeout: Duration,
+    pub(crate) rebalance_timeout: Duration,
+    /// Supported protocols in order of preference, with their metadata.
+    pub(crate) protocols: Vec<(String, Vec<u8>)>,
+    pub(crate) assignment: Vec<u8>,
+    pub(crate) awaiting_join: Option<oneshot::Sender<JoinGroupResult>>,
+    pub(crate) awaiting_sync: Option<oneshot::Sender<SyncGroupResult>>,
+    pub(crate) last_heartbeat: Instant,
+}
+
+impl Member {
+    pub(crate) fn metadata(&self, protocol: &str) -> Option<&[u8]> {
+        self.protocols.iter().find(|(name, _)| name == protocol).map(|(_, metadata)| metadata.as_slice())
+    }
+
+    /// Whether the session is alive at `now`. Members waiting on a JoinGroup
+    /// or SyncGroup response are kept alive.
+    pub(crate) fn is_alive(&self, now: Instant) -> bool {
+        self.awaiting_join.is_some() || self.awaiting_sync.is_some() || now < self.last_heartbeat + self.session_timeout
+    }
+
+    /// Fails any JoinGroup or SyncGroup this member is waiting on.
+    pub(crate) fn fail_pending(&mut self, error_code: ErrorCode) {
+        if let Some(sender) = self.awaiting_join.take() {
+            let _ = sender.send(JoinGroupResult::error(self.member_id.clone(), error_code));
+        }
+        if let Some(sender) = self.awaiting_sync.take() {
+            let _ = sender.send(SyncGroupResult::error(error_code));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Group {
+    pub(crate) group_id: String,
+    state: GroupState,
+    pub(crate) generation_id: i32,
+    pub(crate) protocol_type: Option<String>,
+    pub(crate) protocol_name: Option<String>,
+    pub(crate) leader_id: Option<String>,
+    pub(crate) members: BTreeMap<String, Member>,
+    /// Member ids of static members, by group instance id.
+    pub(crate) static_members: HashMap<String, String>,
+    /// Member ids handed out with MEMBER_ID_REQUIRED that have not joined
+    /// yet, with the time they expire.
+    pub(crate) pending_members: HashMap<String, Instant>,
+    /// When the current rebalance stops waiting for members to rejoin.
+    pub(crate) rebalance_deadline: Option<Instant>,
+    /// While the first rebalance of an empty group waits for more members
+    /// (`group.initial.rebalance.delay.ms`), joins complete no earlier than this.
+    pub(crate) join_delay_until: Option<Instant>,
+    pub(crate) offsets: HashMap<TopicPartition, OffsetAndMetadata>,
+    /// Offsets committed in transactions that have not completed yet, by
+    /// producer id. They become visible when the transaction commits.
+    pub(crate) pending_txn_offsets: HashMap<i64, HashMap<TopicPartition, OffsetAndMetadata>>,
+}
+
+impl Group {
+    pub(crate) fn new(group_id: String) -> Self {
+        Self {
+            group_id,
+            state: GroupState::Empty,
+            generation_id: 0,
+            protocol_type: None,
+            protocol_name: None,
+            leader_id: None,
+            members: BTreeMap::new(),
+            static_members: HashMap::new(),
+            pending_members: HashMap::new(),
+            rebalance_deadline: None,
+            join_delay_until: None,
+            offsets: HashMap::new(),
+            pending_txn_offsets: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn state(&self) -> GroupState {
+        self.state
+    }
+
+    pub(crate) fn transition_to(&mut self, state: GroupState) {
+        debug_assert!(state.can_transition_from(self.state), "{:?} -> {state:?}", self.state);
+        self.state = state;
+    }
+
+    pub(crate) fn is_leader(&self, member_id: &str) -> bool {
+        self.leader_id.as_deref() == Some(member_id)
+    }
+
+    /// Whether a member with these protocols may join: the protocol type
+    /// must match the group's and at least one protocol must be supported
+    /// by every member.
+    pub(crate) fn supports_protocols(&self, protocol_type: &str, protocols: &[(String, Vec<u8>)]) -> bool {
+        if protocol_type.is_empty() || protocols.is_empty() {
+            return false;
+        }
+        if self.members.is_empty() {
+            return true;
+        }
+        self.protocol_type.as_deref() == Some(protocol_type)
+            && protocols.iter().any(|(name, _)| self.members.values().all(|member| member.metadata(name).is_some()))
+    }
+
+    /// Picks the protocol supported by all members that most members list
+    /// first among the supported ones.
+    pub(crate) fn select_protocol(&self) -> Option<String> {
+        let first = self.members.values().next()?;
+        let candidates = first
+            .protocols
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| self.members.values().all(|member| member.metadata(name).is_some()))
+            .collect::<Vec<_>>();
+
+        let mut votes = HashMap::new();
+        for member in self.members.values() {
+            if let Some((name, _)) = member.protocols.iter().find(|(name, _)| candidates.contains(&name.as_str())) {
+                *votes.entry(name.as_str()).or_insert(0) += 1;
+            }
+        }
+        let mut selected: Option<(&str, usize)> = None;
+        for candidate in candidates {
+            let count = votes.get(candidate).copied().unwrap_or(0);
+            if selected.is_none_or(|(_, max)| count > max) {
+                selected = Some((candidate, count));
+            }
+        }
+        selected.map(|(name, _)| name.to_owned())
+    }
+
+    /// The topics the members of a `consumer` protocol group subscribe to,
+    /// decoded from their metadata. `None` for other protocol types or if a
+    /// member's metadata cannot be decoded.
+    pub(crate) fn subscribed_topics(&self) -> Option<BTreeSet<String>> {
+        if self.protocol_type.as_deref() != Some(CONSUMER_PROTOCOL_TYPE) {
+            return None;
+        }
+        let protocol = self.protocol_name.as_deref()?;
+        let mut topics = BTreeSet::new();
+        for member in self.members.values() {
+            let mut reader = Cursor::new(member.metadata(protocol)?);
+            let subscription = ConsumerProtocolSubscription::read(&mut reader).ok()?;
+            topics.extend(subscription.topics.0.into_iter().map(|topic| topic.0));
+        }
+        Some(topics)
+    }
+
+    /// The JoinGroup response for `member_id` in the current generation.
+    pub(crate) fn join_result(&self, member_id: &str) -> JoinGroupResult {
+        let members = if self.is_leader(member_id) {
+            let protocol = self.protocol_name.as_deref().unwrap_or_default();
+            self.members
+                .values()
+                .map(|member| JoinGroupMember {
+                    member_id: member.member_id.clone(),
+                    group_instance_id: member.group_instance_id.clone(),
+                    metadata: member.metadata(protocol).unwrap_or_default().to_vec(),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        JoinGroupResult {
+            error_code: ErrorCode::None,
+            generation_id: self.generation_id,
+            protocol_type: self.protocol_type.clone(),
+            protocol_name: self.protocol_name.clone(),
+            leader_id: self.leader_id.clone().unwrap_or_default(),
+            member_id: member_id.to_owned(),
+            members,
+            skip_assignment: false,
+        }
+    }
+
+    pub(crate) fn sync_result(&self, member_id: &str) -> SyncGroupResult {
+        SyncGroupResult {
+            error_code: ErrorCode::None,
+            protocol_type: self.protocol_type.clone(),
+            protocol_name: self.protocol_name.clone(),
+            assignment: self.members.get(member_id).map(|member| member.assignment.clone()).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,759 @@
+mod admin;
+mod consumer;
+pub(crate) use consumer::*;
+mod offsets;
+pub(crate) use offsets::*;
+
+use crate::kafka::config::ServerConfig;
+use crate::kafka::coordinator::{ConsumerGroup, Group, GroupState, JoinGroupResult, Member, SyncGroupResult};
+use crate::kafka::storage::LogManager;
+use crate::kafka::types::{ErrorCode, Uuid};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use tracing::info;
+
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A JoinGroup request as seen by the coordinator.
+#[derive(Debug, Clone)]
+pub(crate) struct JoinGroupParams {
+    pub(crate) group_id: String,
+    pub(crate) member_id: String,
+    pub(crate) group_instance_id: Option<String>,
+    pub(crate) client_id: String,
+    pub(crate) client_host: String,
+    pub(crate) session_timeout_ms: i32,
+    /// `-1` when the client predates rebalance timeouts.
+    pub(crate) rebalance_timeout_ms: i32,
+    pub(crate) protocol_type: String,
+    pub(crate) protocols: Vec<(String, Vec<u8>)>,
+    /// From v4 unknown members first get MEMBER_ID_REQUIRED with their id.
+    pub(crate) require_known_member_id: bool,
+    /// From v9 a rejoining static leader can be told to skip the assignment.
+    pub(crate) supports_skip_assignment: bool,
+}
+
+/// A SyncGroup request as seen by the coordinator.
+#[derive(Debug, Clone)]
+pub(crate) struct SyncGroupParams {
+    pub(crate) group_id: String,
+    pub(crate) generation_id: i32,
+    pub(crate) member_id: String,
+    pub(crate) group_instance_id: Option<String>,
+    pub(crate) protocol_type: Option<String>,
+    pub(crate) protocol_name: Option<String>,
+    /// The assignment per member id, only sent by the leader.
+    pub(crate) assignments: Vec<(String, Vec<u8>)>,
+}
+
+/// A member leaving, identified by member id or, for static members, by
+/// group instance id alone.
+#[derive(Debug, Clone)]
+pub(crate) struct LeavingMember {
+    pub(crate) member_id: String,
+    pub(crate) group_instance_id: Option<String>,
+}
+
+/// A response that is either known now or completes when the rebalance does.
+enum Pending<T> {
+    Ready(T),
+    Waiting(oneshot::Receiver<T>),
+}
+
+/// Coordinates the classic and consumer protocol groups: membership,
+/// rebalances, session expiry and committed offsets. Every group is
+/// coordinated by this broker.
+#[derive(Debug)]
+pub(crate) struct GroupCoordinator {
+    config: ServerConfig,
+    log_manager: Arc<LogManager>,
+    /// Classic groups, and the committed offsets of every group. A consumer
+    /// protocol group's offsets live in an empty classic group of the same id.
+    groups: Mutex<HashMap<String, Group>>,
+    /// Consumer protocol groups. Locked after `groups` when both are needed.
+    consumer_groups: Mutex<HashMap<String, ConsumerGroup>>,
+}
+
+impl GroupCoordinator {
+    pub(crate) fn new(config: ServerConfig, log_manager: Arc<LogManager>) -> Self {
+        Self { config, log_manager, groups: Mutex::new(HashMap::new()), consumer_groups: Mutex::new(HashMap::new()) }
+    }
+
+    /// Expires sessions and completes rebalances every [`TICK_INTERVAL`].
+    pub(crate) async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(TICK_INTERVAL);
+        loop {
+            interval.tick().await;
+            self.tick(Instant::now());
+        }
+    }
+
+    fn groups(&self) -> MutexGuard<'_, HashMap<String, Group>> {
+        self.groups.lock().unwrap()
+    }
+
+    fn consumer_groups(&self) -> MutexGuard<'_, HashMap<String, ConsumerGroup>> {
+        self.consumer_groups.lock().unwrap()
+    }
+
+    /// Joins `params.member_id` to the group, waiting for the rebalance the
+    /// join triggers to complete.
+    pub(crate) async fn join_group(&self, params: JoinGroupParams) -> JoinGroupResult {
+        let member_id = params.member_id.clone();
+        match self.try_join_group(params, Instant::now()) {
+            Pending::Ready(result) => result,
+            Pending::Waiting(receiver) => receiver
+                .await
+                .unwrap_or_else(|_| JoinGroupResult::error(member_id, ErrorCode::CoordinatorNotAvailable)),
+        }
+    }
+
+    fn try_join_group(&self, params: JoinGroupParams, now: Instant) -> Pending<JoinGroupResult> {
+        let error = |code| Pending::Ready(JoinGroupResult::error(params.member_id.clone(), code));
+
+        if params.group_id.is_empty() {
+            return error(ErrorCode::InvalidGroupId);
+        }
+        if params.session_timeout_ms < self.config.group_min_session_timeout_ms
+            || params.session_timeout_ms > self.config.group_max_session_timeout_ms
+        {
+            return error(ErrorCode::InvalidSessionTimeout);
+        }
+
+        let mut groups = self.groups();
+        let mut consumer_groups = self.consumer_groups();
+        match consumer_groups.get(&params.group_id) {
+            Some(group) if !group.members.is_empty() => return error(ErrorCode::InconsistentGroupProtocol),
+            // An empty consumer protocol group converts to a classic one.
+            Some(_) => {
+                consumer_groups.remove(&params.group_id);
+            }
+            None => {}
+        }
+        drop(consumer_groups);
+        if !groups.contains_key(&params.group_id) {
+            if !params.member_id.is_empty() {
+                return error(ErrorCode::UnknownMemberId);
+            }
+            // A new group takes any protocols. A join without any is refused
+            // before the group is created, so it leaves no empty group behind.
+            if params.protocol_type.is_empty() || params.protocols.is_empty() {
+                return error(ErrorCode::InconsistentGroupProtocol);
+            }
+        }
+        let group = groups.entry(params.group_id.clone()).or_insert_with(|| Group::new(params.group_id.clone()));
+        if group.state() == GroupState::Dead {
+            return error(ErrorCode::CoordinatorNotAvailable);
+        }
+        if !group.supports_protocols(&params.protocol_type, &params.protocols) {
+            return error(ErrorCode::InconsistentGroupProtocol);
+        }
+
+        if params.member_id.is_empty() {
+            self.join_unknown_member(group, params, now)
+        } else {
+            self.join_known_member(group, params, now)
+        }
+    }
+
+    fn join_unknown_member(&self, group: &mut Group, params: JoinGroupParams, now: Instant) -> Pending<JoinGroupResult> {
+        if let Some(instance_id) = &params.group_instance_id {
+            let member_id = format!("{instance_id}-{}", Uuid::random());
+            if let Some(old_member_id) = group.static_members.get(instance_id).cloned() {
+                return self.replace_static_member(group, old_member_id, member_id, params, now);
+            }
+            if group.members.len() >= self.config.group_max_size {
+                return Pending::Ready(JoinGroupResult::error("", ErrorCode::GroupMaxSizeReached));
+            }
+            group.static_members.insert(instance_id.clone(), member_id.clone());
+            return self.add_member_and_rebalance(group, member_id, params, now);
+        }
+
+        if group.members.len() + group.pending_members.len() >= self.config.group_max_size {
+            return Pending::Ready(JoinGroupResult::error("", ErrorCode::GroupMaxSizeReached));
+        }
+        let member_id = format!("{}-{}", params.client_id, Uuid::random());
+        if params.require_known_member_id {
+            let expiry = now + session_timeout(&params);
+            group.pending_members.insert(member_id.clone(), expiry);
+            return Pending::Ready(JoinGroupResult::error(member_id, ErrorCode::MemberIdRequired));
+        }
+        self.add_member_and_rebalance(group, member_id, params, now)
+    }
+
+    fn join_known_member(&self, group: &mut Group, params: JoinGroupParams, now: Instant) -> Pending<JoinGroupResult> {
+        let member_id = params.member_id.clone();
+        if group.pending_members.remove(&member_id).is_some() {
+            return self.add_member_and_rebalance(group, member_id, params, now);
+        }
+        if let Err(error_code) = validate_instance(group, &member_id, params.group_instance_id.as_deref()) {
+            return Pending::Ready(JoinGroupResult::error(member_id, error_code));
+        }
+        let Some(member) = group.members.get_mut(&member_id) else {
+            return Pending::Ready(JoinGroupResult::error(member_id, ErrorCode::UnknownMemberId));
+        };
+        let protocols_changed = member.protocols != params.protocols;
+        update_member(member, &params, now);
+
+        match group.state() {
+            GroupState::PreparingRebalance => self.await_join(group, member_id, now),
+            GroupState::CompletingRebalance | GroupState::Stable if !protocols_changed && !group.is_leader(&member_id) => {
+                // The member missed the response, or rejoined without cause.
+                Pending::Ready(group.join_result(&member_id))
+            }
+            GroupState::CompletingRebalance | GroupState::Stable => {
+                self.prepare_rebalance(group, now);
+                self.await_join(group, member_id, now)
+            }
+            GroupState::Empty | GroupState::Dead => {
+                Pending::Ready(JoinGroupResult::error(member_id, ErrorCode::UnknownMemberId))
+            }
+        }
+    }
+
+    /// A static member rejoined with a new member id, fencing the old one.
+    fn replace_static_member(
+        &self,
+        group: &mut Group,
+        old_member_id: String,
+        member_id: String,
+        params: JoinGroupParams,
+        now: Instant,
+    ) -> Pending<JoinGroupResult> {
+        let Some(mut member) = group.members.remove(&old_member_id) else {
+            return Pending::Ready(JoinGroupResult::error("", ErrorCode::UnknownMemberId));
+        };
+        member.fail_pending(ErrorCode::FencedInstanceId);
+        let protocols_changed = member.protocols != params.protocols;
+        member.member_id = member_id.clone();
+        update_member(&mut member, &params, now);
+        group.members.insert(member_id.clone(), member);
+        if let Some(instance_id) = &params.group_instance_id {
+            group.static_members.insert(instance_id.clone(), member_id.clone());
+        }
+        let was_leader = group.is_leader(&old_member_id);
+        if was_leader {
+            group.leader_id = Some(member_id.clone());
+        }
+        info!(group = group.group_id, old_member_id, member_id, "Replaced static member");
+
+        match group.state() {
+            GroupState::Stable if !protocols_changed && (!was_leader || params.supports_skip_assignment) => {
+                let mut result = group.join_result(&member_id);
+                result.skip_assignment = was_leader;
+                Pending::Ready(result)
+            }
+            GroupState::PreparingRebalance => self.await_join(group, member_id, now),
+            _ => {
+                self.prepare_rebalance(group, now);
+                self.await_join(group, member_id, now)
+            }
+        }
+    }
+
+    fn add_member_and_rebalance(
+        &self,
+        group: &mut Group,
+        member_id: String,
+        params: JoinGroupParams,
+        now: Instant,
+    ) -> Pending<JoinGroupResult> {
+        if group.members.is_empty() {
+            group.protocol_type = Some(params.protocol_type.clone());
+        }
+        if group.leader_id.is_none() {
+            group.leader_id = Some(member_id.clone());
+        }
+        let mut member = Member {
+            member_id: member_id.clone(),
+            group_instance_id: params.group_instance_id.clone(),
+            client_id: params.client_id.clone(),
+            client_host: params.client_host.clone(),
+            session_timeout: Duration::ZERO,
+            rebalance_timeout: Duration::ZERO,
+            protocols: Vec::new(),
+            assignment: Vec::new(),
+            awaiting_join: None,
+            awaiting_sync: None,
+            last_heartbeat: now,
+        };
+        update_member(&mut member, &params, now);
+        group.members.insert(member_id.clone(), member);
+        info!(group = group.group_id, member_id, "Member joined group");
+
+        if group.state() == GroupState::PreparingRebalance {
+            // Each new member of a freshly created group extends the
+            // initial delay, up to the rebalance deadline.
+            if let Some(delay_until) = group.join_delay_until {
+                let extended = delay_until.max(now) + self.initial_rebalance_delay();
+                group.join_delay_until = Some(group.rebalance_deadline.map_or(extended, |deadline| extended.min(deadline)));
+            }
+        } else {
+            self.prepare_rebalance(group, now);
+        }
+        self.await_join(group, member_id, now)
+    }
+
+    /// Parks the member's JoinGroup until the rebalance completes.
+    fn await_join(&self, group: &mut Group, member_id: String, now: Instant) -> Pending<JoinGroupResult> {
+        let (sender, receiver) = oneshot::channel();
+        if let Some(member) = group.members.get_mut(&member_id) {
+            if let Some(previous) = member.awaiting_join.replace(sender) {
+                let _ = previous.send(JoinGroupResult::error(member_id, ErrorCode::RebalanceInProgress));
+            }
+        }
+        self.maybe_complete_join(group, now);
+        Pending::Waiting(receiver)
+    }
+
+    fn initial_rebalance_delay(&self) -> Duration {
+        Duration::from_millis(self.config.group_initial_rebalance_delay_ms)
+    }
+
+    fn prepare_rebalance(&self, group: &mut Group, now: Instant) {
+        if group.state() == GroupState::CompletingRebalance {
+            for member in group.members.values_mut() {
+                if let Some(sender) = member.awaiting_sync.take() {
+                    let _ = sender.send(SyncGroupResult::error(ErrorCode::RebalanceInProgress));
+                }
+            }
+        }
+        if group.state() == GroupState::Empty && !self.initial_rebalance_delay().is_zero() {
+            group.join_delay_until = Some(now + self.initial_rebalance_delay());
+        }
+        let rebalance_timeout = group.members.values().map(|member| member.rebalance_timeout).max().unwrap_or_default();
+        group.rebalance_deadline = Some(now + rebalance_timeout);
+        info!(group = group.group_id, generation = group.generation_id, "Preparing to rebalance group");
+        group.transition_to(GroupState::PreparingRebalance);
+    }
+
+    /// Completes the join phase once every member rejoined, or once the
+    /// rebalance deadline passed, dropping the members that did not.
+    fn maybe_complete_join(&self, group: &mut Group, now: Instant) {
+        if group.state() != GroupState::PreparingRebalance {
+            return;
+        }
+        if group.join_delay_until.is_some_and(|delay_until| now < delay_until) {
+            return;
+        }
+        let all_joined = group.pending_members.is_empty()
+            && group.members.values().all(|member| member.awaiting_join.is_some());
+        let deadline_passed = group.rebalance_deadline.is_some_and(|deadline| now >= deadline);
+        if !all_joined && !deadline_passed {
+            return;
+        }
+
+        let missing = group
+            .members
+            .values()
+            .filter(|member| member.awaiting_join.is_none())
+            .map(|member| member.member_id.clone())
+            .collect::<Vec<_>>();
+        for member_id in missing {
+            info!(group = group.group_id, member_id, "Removing member that did not rejoin");
+            remove_member(group, &member_id);
+        }
+        group.pending_members.clear();
+        group.join_delay_until = None;
+        group.rebalance_deadline = None;
+        group.generation_id += 1;
+
+        if group.members.is_empty() {
+            group.protocol_name = None;
+            group.transition_to(GroupState::Empty);
+            info!(group = group.group_id, generation = group.generation_id, "Group is empty");
+            return;
+        }
+
+        group.protocol_name = group.select_protocol();
+        group.transition_to(GroupState::CompletingRebalance);
+        info!(
+            group = group.group_id,
+            generation = group.generation_id,
+            protocol = group.protocol_name,
+            "Completed join phase of rebalance"
+        );
+
+        let results = group.members.keys().map(|member_id| group.join_result(member_id)).collect::<Vec<_>>();
+        for (member, result) in group.members.values_mut().zip(results) {
+            member.last_heartbeat = now;
+            if let Some(sender) = member.awaiting_join.take() {
+                let _ = sender.send(result);
+            }
+        }
+    }
+
+    /// Waits for the leader's assignment and returns this member's share.
+    pub(crate) async fn sync_group(&self, params: SyncGroupParams) -> SyncGroupResult {
+        match self.try_sync_group(params, Instant::now()) {
+            Pending::Ready(result) => result,
+            Pending::Waiting(receiver) => {
+                receiver.await.unwrap_or_else(|_| SyncGroupResult::error(ErrorCode::CoordinatorNotAvailable))
+            }
+        }
+    }
+
+    fn try_sync_group(&self, params: SyncGroupParams, now: Instant) -> Pending<SyncGroupResult> {
+        let error = |code| Pending::Ready(SyncGroupResult::error(code));
+
+        let mut groups = self.groups();
+        let Some(group) = groups.get_mut(&params.group_id) else {
+            return error(ErrorCode::UnknownMemberId);
+        };
+        if let Err(error_code) = validate_member(group, &params.member_id, params.group_instance_id.as_deref()) {
+            return error(error_code);
+        }
+        if params.generation_id != group.generation_id {
+            return error(ErrorCode::IllegalGeneration);
+        }
+        let protocol_mismatch = |requested: &Option<String>, current: &Option<String>| {
+            requested.as_ref().is_some_and(|requested| Some(requested) != current.as_ref())
+        };
+        if protocol_mismatch(&params.protocol_type, &group.protocol_type)
+            || protocol_mismatch(&params.protocol_name, &group.protocol_name)
+        {
+            return error(ErrorCode::InconsistentGroupProtocol);
+        }
+
+        match group.state() {
+            GroupState::Empty | GroupState::Dead => error(ErrorCode::UnknownMemberId),
+            GroupState::PreparingRebalance => error(ErrorCode::RebalanceInProgress),
+            GroupState::Stable => {
+                if let Some(member) = group.members.get_mut(&params.member_id) {
+                    member.last_heartbeat = now;
+                }
+                Pending::Ready(group.sync_result(&params.member_id))
+            }
+            GroupState::CompletingRebalance => {
+                let (sender, receiver) = oneshot::channel();
+                if let Some(member) = group.members.get_mut(&params.member_id) {
+                    member.last_heartbeat = now;
+                    member.awaiting_sync = Some(sender);
+                }
+                if group.is_leader(&params.member_id) {
+                    let mut assignments = params.assignments.into_iter().collect::<HashMap<_, _>>();
+                    for member in group.members.values_mut() {
+                        member.assignment = assignments.remove(&member.member_id).unwrap_or_default();
+                    }
+                    group.transition_to(GroupState::Stable);
+                    info!(group = group.group_id, generation = group.generation_id, "Group is stable");
+
+                    let results =
+                        group.members.keys().map(|member_id| group.sync_result(member_id)).collect::<Vec<_>>();
+                    for (member, result) in group.members.values_mut().zip(results) {
+                        if let Some(sender) = member.awaiting_sync.take() {
+                            let _ = sender.send(result);
+                        }
+                    }
+                }
+                Pending::Waiting(receiver)
+            }
+        }
+    }
+
+    /// Keeps the member's session alive. Tells it to rejoin while the group
+    /// is rebalancing.
+    pub(crate) fn heartbeat(
+        &self,
+        group_id: &str,
+        generation_id: i32,
+        member_id: &str,
+        group_instance_id: Option<&str>,
+    ) -> ErrorCode {
+        let mut groups = self.groups();
+        let Some(group) = groups.get_mut(group_id) else {
+            return ErrorCode::UnknownMemberId;
+        };
+        if group.state() == GroupState::Dead {
+            return ErrorCode::CoordinatorNotAvailable;
+        }
+        if let Err(error_code) = validate_member(group, member_id, group_instance_id) {
+            return error_code;
+        }
+        if generation_id != group.generation_id {
+            return ErrorCode::IllegalGeneration;
+        }
+
+        let state = group.state();
+        if let Some(member) = group.members.get_mut(member_id) {
+            member.last_heartbeat = Instant::now();
+        }
+        match state {
+            GroupState::PreparingRebalance => ErrorCode::RebalanceInProgress,
+            GroupState::CompletingRebalance | GroupState::Stable => ErrorCode::None,
+            GroupState::Empty | GroupState::Dead => ErrorCode::UnknownMemberId,
+        }
+    }
+
+    /// Removes the members from the group, rebalancing the rest. Returns the
+    /// error for each member, in order.
+    pub(crate) fn leave_group(&self, group_id: &str, members: &[LeavingMember]) -> Vec<ErrorCode> {
+        let now = Instant::now();
+        let mut groups = self.groups();
+        let Some(group) = groups.get_mut(group_id) else {
+            return vec![ErrorCode::UnknownMemberId; members.len()];
+        };
+        if group.state() == GroupState::Dead {
+            return vec![ErrorCode::CoordinatorNotAvailable; members.len()];
+        }
+
+        let mut removed = false;
+        let errors = members
+            .iter()
+            .map(|leaving| {
+                let member_id = match (leaving.member_id.is_empty(), &leaving.group_instance_id) {
+                    (true, Some(instance_id)) => match group.static_members.get(instance_id) {
+                        Some(member_id) => member_id.clone(),
+                        None => return ErrorCode::UnknownMemberId,
+                    },
+                    _ => leaving.member_id.clone(),
+                };
+                if group.pending_members.remove(&member_id).is_some() {
+                    return ErrorCode::None;
+                }
+                if let Err(error_code) = validate_member(group, &member_id, leaving.group_instance_id.as_deref()) {
+                    return error_code;
+                }
+                info!(group = group.group_id, member_id, "Member left group");
+                if let Some(mut member) = remove_member(group, &member_id) {
+                    member.fail_pending(ErrorCode::UnknownMemberId);
+                }
+                removed = true;
+                ErrorCode::None
+            })
+            .collect();
+
+        if removed {
+            self.member_removed(group, now);
+        }
+        errors
+    }
+
+    fn member_removed(&self, group: &mut Group, now: Instant) {
+        if matches!(group.state(), GroupState::CompletingRebalance | GroupState::Stable) {
+            self.prepare_rebalance(group, now);
+        }
+        self.maybe_complete_join(group, now);
+    }
+
+    /// Expires dead sessions and pending member ids, completes rebalances
+    /// past their deadline and drops groups left without members or offsets.
+    pub(crate) fn tick(&self, now: Instant) {
+        self.expire_consumer_group_members(now);
+        let mut groups = self.groups();
+        for group in groups.values_mut() {
+            group.pending_members.retain(|_, expiry| now < *expiry);
+
+            let expired = group
+                .members
+                .values()
+                .filter(|member| !member.is_alive(now))
+                .map(|member| member.member_id.clone())
+                .collect::<Vec<_>>();
+            for member_id in &expired {
+                info!(group = group.group_id, member_id, "Member session expired");
+                remove_member(group, member_id);
+            }
+            if expired.is_empty() {
+                self.maybe_complete_join(group, now);
+            } else {
+                self.member_removed(group, now);
+            }
+
+            if group.state() == GroupState::Empty
+                && group.members.is_empty()
+                && group.pending_members.is_empty()
+                && group.offsets.is_empty()
+                && group.pending_txn_offsets.is_empty()
+            {
+                group.transition_to(GroupState::Dead);
+            }
+        }
+        groups.retain(|_, group| group.state() != GroupState::Dead);
+    }
+
+    #[cfg(test)]
+    fn group_state(&self, group_id: &str) -> Option<GroupState> {
+        self.groups().get(group_id).map(Group::state)
+    }
+}
+
+fn session_timeout(params: &JoinGroupParams) -> Duration {
+    Duration::from_millis(params.session_timeout_ms as u64)
+}
+
+fn update_member(member: &mut Member, params: &JoinGroupParams, now: Instant) {
+    let session_timeout = session_timeout(params);
+    member.session_timeout = session_timeout;
+    member.rebalance_timeout = if params.rebalance_timeout_ms < 0 {
+        session_timeout
+    } else {
+        Duration::from_millis(params.rebalance_timeout_ms as u64)
+    };
+    member.protocols = params.protocols.clone();
+    member.client_id = params.client_id.clone();
+    member.client_host = params.client_host.clone();
+    member.last_heartbeat = now;
+}
+
+/// Removes the member, handing leadership to another member if needed.
+fn remove_member(group: &mut Group, member_id: &str) -> Option<Member> {
+    let member = group.members.remove(member_id)?;
+    if let Some(instance_id) = &member.group_instance_id {
+        group.static_members.remove(instance_id);
+    }
+    if group.is_leader(member_id) {
+        group.leader_id = group.members.keys().next().cloned();
+    }
+    Some(member)
+}
+
+/// A static member must use the member id its instance id was last given.
+fn validate_instance(group: &Group, member_id: &str, group_instance_id: Option<&str>) -> Result<(), ErrorCode> {
+    match group_instance_id.and_then(|instance_id| group.static_members.get(instance_id)) {
+        Some(current) if current != member_id => Err(ErrorCode::FencedInstanceId),
+        _ => Ok(()),
+    }
+}
+
+fn validate_member(group: &Group, member_id: &str, group_instance_id: Option<&str>) -> Result<(), ErrorCode> {
+    validate_instance(group, member_id, group_instance_id)?;
+    if !group.members.contains_key(member_id) {
+        return Err(ErrorCode::UnknownMemberId);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn coordinator() -> (TempDir, GroupCoordinator) {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig {
+            log_dirs: vec![dir.path().to_path_buf()],
+            group_initial_rebalance_delay_ms: 0,
+            ..ServerConfig::default()
+        };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        (dir, GroupCoordinator::new(config, log_manager))
+    }
+
+    fn join_params(member_id: &str, group_instance_id: Option<&str>) -> JoinGroupParams {
+        JoinGroupParams {
+            group_id: "group".to_owned(),
+            member_id: member_id.to_owned(),
+            group_instance_id: group_instance_id.map(str::to_owned),
+            client_id: "client".to_owned(),
+            client_host: "/127.0.0.1".to_owned(),
+            session_timeout_ms: 10_000,
+            rebalance_timeout_ms: 10_000,
+            protocol_type: "consumer".to_owned(),
+            protocols: vec![("range".to_owned(), vec![1]), ("roundrobin".to_owned(), vec![2])],
+            require_known_member_id: true,
+            supports_skip_assignment: true,
+        }
+    }
+
+    fn sync_params(member_id: &str, generation_id: i32, assignments: Vec<(String, Vec<u8>)>) -> SyncGroupParams {
+        SyncGroupParams {
+            group_id: "group".to_owned(),
+            generation_id,
+            member_id: member_id.to_owned(),
+            group_instance_id: None,
+            protocol_type: Some("consumer".to_owned()),
+            protocol_name: Some("range".to_owned()),
+            assignments,
+        }
+    }
+
+    async fn join_new_member(coordinator: &GroupCoordinator) -> String {
+        let result = coordinator.join_group(join_params("", None)).await;
+        assert_eq!(result.error_code, ErrorCode::MemberIdRequired);
+        result.member_id
+    }
+
+    #[tokio::test]
+    async fn test_two_members_join_and_sync() {
+        let (_dir, coordinator) = coordinator();
+        let first = join_new_member(&coordinator).await;
+        let result = coordinator.join_group(join_params(&first, None)).await;
+        assert_eq!(result.error_code, ErrorCode::None);
+        assert_eq!(result.generation_id, 1);
+        assert_eq!(result.leader_id, first);
+
+        let second = join_new_member(&coordinator).await;
+        let (first_join, second_join) = tokio::join!(
+            coordinator.join_group(join_params(&first, None)),
+            coordinator.join_group(join_params(&second, None)),
+        );
+        assert_eq!(first_join.generation_id, 2);
+        assert_eq!(first_join.protocol_name.as_deref(), Some("range"));
+        assert_eq!(first_join.members.len(), 2);
+        assert!(second_join.members.is_empty());
+        assert_eq!(coordinator.group_state("group"), Some(GroupState::CompletingRebalance));
+
+        let assignments = vec![(first.clone(), vec![1]), (second.clone(), vec![2])];
+        let (second_sync, first_sync) = tokio::join!(
+            coordinator.sync_group(sync_params(&second, 2, Vec::new())),
+            coordinator.sync_group(sync_params(&first, 2, assignments)),
+        );
+        assert_eq!(first_sync.assignment, vec![1]);
+        assert_eq!(second_sync.assignment, vec![2]);
+        assert_eq!(coordinator.group_state("group"), Some(GroupState::Stable));
+        assert_eq!(coordinator.heartbeat("group", 2, &second, None), ErrorCode::None);
+        assert_eq!(coordinator.heartbeat("group", 1, &second, None), ErrorCode::IllegalGeneration);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_join_creates_no_group() {
+        let (_dir, coordinator) = coordinator();
+        let params = JoinGroupParams { protocols: Vec::new(), ..join_params("", None) };
+        let result = coordinator.join_group(params).await;
+        assert_eq!(result.error_code, ErrorCode::InconsistentGroupProtocol);
+        assert_eq!(coordinator.group_state("group"), None);
+    }
+
+    #[tokio::test]
+    async fn test_expired_session_removes_member() {
+        let (_dir, coordinator) = coordinator();
+        let member_id = join_new_member(&coordinator).await;
+        coordinator.join_group(join_params(&member_id, None)).await;
+        coordinator.sync_group(sync_params(&member_id, 1, vec![(member_id.clone(), vec![1])])).await;
+
+        coordinator.tick(Instant::now() + Duration::from_secs(11));
+        assert_eq!(coordinator.group_state("group"), None);
+        assert_eq!(coordinator.heartbeat("group", 1, &member_id, None), ErrorCode::UnknownMemberId);
+    }
+
+    #[tokio::test]
+    async fn test_static_member_rejoin_fences_old_member_id() {
+        let (_dir, coordinator) = coordinator();
+        let first = coordinator.join_group(join_params("", Some("instance"))).await;
+        assert_eq!(first.error_code, ErrorCode::None);
+        coordinator.sync_group(sync_params(&first.member_id, 1, Vec::new())).await;
+
+        let rejoined = coordinator.join_group(join_params("", Some("instance"))).await;
+        assert_eq!(rejoined.generation_id, 1);
+        assert!(rejoined.skip_assignment);
+        assert_ne!(rejoined.member_id, first.member_id);
+        assert_eq!(
+            coordinator.heartbeat("group", 1, &first.member_id, Some("instance")),
+            ErrorCode::FencedInstanceId
+        );
+        assert_eq!(coordinator.heartbeat("group", 1, &rejoined.member_id, Some("instance")), ErrorCode::None);
+    }
+
+    #[tokio::test]
+    async fn test_leave_group_by_instance_id() {
+        let (_dir, coordinator) = coordinator();
+        let joined = coordinator.join_group(join_params("", Some("instance"))).await;
+        let leaving = LeavingMember { member_id: String::new(), group_instance_id: Some("instance".to_owned()) };
+        assert_eq!(coordinator.leave_group("group", &[leaving]), vec![ErrorCode::None]);
+        assert_eq!(coordinator.group_state("group"), Some(GroupState::Empty));
+        assert_eq!(coordinator.heartbeat("group", 1, &joined.member_id, None), ErrorCode::UnknownMemberId);
+    }
+}
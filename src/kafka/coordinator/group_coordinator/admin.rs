@@ -0,0 +1,329 @@
+use crate::kafka::coordinator::{
+    group_metadata_tombstone, offset_commit_record, GroupCoordinator, GroupState, CONSUMER_PROTOCOL_TYPE,
+};
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::ErrorCode;
+use std::collections::BTreeSet;
+use tracing::{error, info};
+
+/// The group type of classic protocol groups in ListGroups.
+const CLASSIC_GROUP_TYPE: &str = "classic";
+/// The group type of consumer protocol groups in ListGroups.
+const CONSUMER_GROUP_TYPE: &str = "consumer";
+
+/// A group as listed by ListGroups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GroupListing {
+    pub(crate) group_id: String,
+    pub(crate) protocol_type: String,
+    pub(crate) state: String,
+    pub(crate) group_type: &'static str,
+}
+
+/// A classic group as described by DescribeGroups.
+#[derive(Debug)]
+pub(crate) struct GroupDescription {
+    pub(crate) error_code: ErrorCode,
+    pub(crate) group_id: String,
+    pub(crate) state: String,
+    pub(crate) protocol_type: String,
+    /// The selected protocol, only while the group is stable.
+    pub(crate) protocol_name: String,
+    pub(crate) members: Vec<GroupMemberDescription>,
+}
+
+impl GroupDescription {
+    fn error(group_id: &str, error_code: ErrorCode) -> Self {
+        Self {
+            error_code,
+            group_id: group_id.to_owned(),
+            state: String::new(),
+            protocol_type: String::new(),
+            protocol_name: String::new(),
+            members: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct GroupMemberDescription {
+    pub(crate) member_id: String,
+    pub(crate) group_instance_id: Option<String>,
+    pub(crate) client_id: String,
+    pub(crate) client_host: String,
+    /// The metadata and assignment are only known while the group is stable.
+    pub(crate) metadata: Vec<u8>,
+    pub(crate) assignment: Vec<u8>,
+}
+
+impl GroupCoordinator {
+    /// The committed offset of every group and partition.
+    pub(crate) fn committed_offsets(&self) -> Vec<(String, TopicPartition, i64)> {
+        let groups = self.groups();
+        groups
+            .iter()
+            .flat_map(|(group_id, group)| {
+                group.offsets.iter().map(|(topic_partition, offset)| {
+                    (group_id.clone(), topic_partition.clone(), offset.offset)
+                })
+            })
+            .collect()
+    }
+
+    /// Every live group, classic and consumer protocol, sorted by id. Empty
+    /// filters match all groups; states and types match case-insensitively.
+    pub(crate) fn list_groups(&self, states: &[String], types: &[String]) -> Vec<GroupListing> {
+        let groups = self.groups();
+        let consumer_groups = self.consumer_groups();
+        let classic = groups
+            .values()
+            .filter(|group| group.state() != GroupState::Dead && !consumer_groups.contains_key(&group.group_id))
+            .map(|group| GroupListing {
+                group_id: group.group_id.clone(),
+                protocol_type: group.protocol_type.clone().unwrap_or_default(),
+                state: group.state().to_string(),
+                group_type: CLASSIC_GROUP_TYPE,
+            });
+        let consumer = consumer_groups.values().map(|group| GroupListing {
+            group_id: group.group_id.clone(),
+            protocol_type: CONSUMER_PROTOCOL_TYPE.to_owned(),
+            state: group.state().to_string(),
+            group_type: CONSUMER_GROUP_TYPE,
+        });
+
+        let matches =
+            |filter: &[String], value: &str| filter.is_empty() || filter.iter().any(|f| f.eq_ignore_ascii_case(value));
+        let mut listings = classic
+            .chain(consumer)
+            .filter(|listing| matches(states, &listing.state) && matches(types, listing.group_type))
+            .collect::<Vec<_>>();
+        listings.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+        listings
+    }
+
+    /// Describes a classic group. Unknown groups are described as dead, like
+    /// Kafka does, while consumer protocol groups are not found.
+    pub(crate) fn describe_group(&self, group_id: &str) -> GroupDescription {
+        if group_id.is_empty() {
+            return GroupDescription::error(group_id, ErrorCode::InvalidGroupId);
+        }
+        let groups = self.groups();
+        if self.consumer_groups().contains_key(group_id) {
+            return GroupDescription::error(group_id, ErrorCode::GroupIdNotFound);
+        }
+        let Some(group) = groups.get(group_id) else {
+            let dead = GroupDescription::error(group_id, ErrorCode::None);
+            return GroupDescription { state: GroupState::Dead.to_string(), ..dead };
+        };
+
+        let stable = group.state() == GroupState::Stable;
+        let protocol_name = if stable { group.protocol_name.clone().unwrap_or_default() } else { String::new() };
+        let members = group
+            .members
+            .values()
+            .map(|member| GroupMemberDescription {
+                member_id: member.member_id.clone(),
+                group_instance_id: member.group_instance_id.clone(),
+                client_id: member.client_id.clone(),
+                client_host: member.client_host.clone(),
+                metadata: if stable { member.metadata(&protocol_name).unwrap_or_default().to_vec() } else { Vec::new() },
+                assignment: if stable { member.assignment.clone() } else { Vec::new() },
+            })
+            .collect();
+        GroupDescription {
+            error_code: ErrorCode::None,
+            group_id: group.group_id.clone(),
+            state: group.state().to_string(),
+            protocol_type: group.protocol_type.clone().unwrap_or_default(),
+            protocol_name,
+            members,
+        }
+    }
+
+    /// Deletes groups without members along with their committed offsets.
+    /// Returns the error for each group, in order.
+    pub(crate) fn delete_groups(&self, group_ids: &[String]) -> Vec<ErrorCode> {
+        group_ids.iter().map(|group_id| self.delete_group(group_id)).collect()
+    }
+
+    fn delete_group(&self, group_id: &str) -> ErrorCode {
+        if group_id.is_empty() {
+            return ErrorCode::InvalidGroupId;
+        }
+        let mut groups = self.groups();
+        let mut consumer_groups = self.consumer_groups();
+        let group = groups.get(group_id).filter(|group| group.state() != GroupState::Dead);
+        let consumer_group = consumer_groups.get(group_id);
+        if group.is_none() && consumer_group.is_none() {
+            return ErrorCode::GroupIdNotFound;
+        }
+        if group.is_some_and(|group| !group.members.is_empty() || !group.pending_members.is_empty())
+            || consumer_group.is_some_and(|group| !group.members.is_empty())
+        {
+            return ErrorCode::NonEmptyGroup;
+        }
+
+        let mut records = group
+            .into_iter()
+            .flat_map(|group| group.offsets.keys())
+            .map(|topic_partition| offset_commit_record(group_id, topic_partition, None))
+            .collect::<Vec<_>>();
+        records.push(group_metadata_tombstone(group_id));
+        if let Err(err) = self.append_group_records(group_id, records) {
+            error!(group = group_id, error = %err, "Failed to write group deletion");
+            return ErrorCode::NotCoordinator;
+        }
+
+        if let Some(mut group) = groups.remove(group_id) {
+            group.transition_to(GroupState::Dead);
+        }
+        consumer_groups.remove(group_id);
+        info!(group = group_id, "Deleted group");
+        ErrorCode::None
+    }
+
+    /// Deletes the committed offsets of `partitions`, which the group's
+    /// members must not be subscribed to. Returns the error for each
+    /// partition, in order, or the error for the whole group.
+    pub(crate) fn delete_offsets(
+        &self,
+        group_id: &str,
+        partitions: Vec<TopicPartition>,
+    ) -> Result<Vec<(TopicPartition, ErrorCode)>, ErrorCode> {
+        if group_id.is_empty() {
+            return Err(ErrorCode::InvalidGroupId);
+        }
+        let mut groups = self.groups();
+        let consumer_groups = self.consumer_groups();
+        let subscribed = match (groups.get(group_id), consumer_groups.get(group_id)) {
+            (_, Some(consumer_group)) => consumer_group.subscribed_topic_names(),
+            (Some(group), None) if group.state() == GroupState::Dead => return Err(ErrorCode::GroupIdNotFound),
+            (Some(group), None) if group.state() == GroupState::Empty => BTreeSet::new(),
+            // Without knowing what the members consume, no offset is safe to delete.
+            (Some(group), None) => group.subscribed_topics().ok_or(ErrorCode::NonEmptyGroup)?,
+            (None, None) => return Err(ErrorCode::GroupIdNotFound),
+        };
+        drop(consumer_groups);
+
+        let offsets = groups.get(group_id).map(|group| &group.offsets);
+        let mut results = Vec::with_capacity(partitions.len());
+        let mut records = Vec::new();
+        let mut deleted = Vec::new();
+        for (i, topic_partition) in partitions.into_iter().enumerate() {
+            let error_code = if subscribed.contains(&topic_partition.topic) {
+                ErrorCode::GroupSubscribedToTopic
+            } else if self.log_manager.get_log(&topic_partition).is_none() {
+                ErrorCode::UnknownTopicOrPartition
+            } else {
+                if offsets.is_some_and(|offsets| offsets.contains_key(&topic_partition)) {
+                    records.push(offset_commit_record(group_id, &topic_partition, None));
+                    deleted.push(i);
+                }
+                ErrorCode::None
+            };
+            results.push((topic_partition, error_code));
+        }
+        if records.is_empty() {
+            return Ok(results);
+        }
+
+        match self.append_group_records(group_id, records) {
+            Ok(()) => {
+                let group = groups.get_mut(group_id).expect("group has the deleted offsets");
+                for &i in &deleted {
+                    group.offsets.remove(&results[i].0);
+                }
+                info!(group = group_id, partitions = deleted.len(), "Deleted committed offsets");
+            }
+            Err(err) => {
+                error!(group = group_id, error = %err, "Failed to write offset deletion");
+                for &i in &deleted {
+                    results[i].1 = ErrorCode::NotCoordinator;
+                }
+            }
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::config::ServerConfig;
+    use crate::kafka::coordinator::{ConsumerGroupHeartbeatParams, OffsetAndMetadata, OffsetCommitParams};
+    use crate::kafka::storage::LogManager;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn coordinator(dir: &TempDir) -> GroupCoordinator {
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        for topic in ["foo", "bar"] {
+            log_manager.get_or_create_log(&TopicPartition::new(topic, 0)).unwrap();
+        }
+        let coordinator = GroupCoordinator::new(config, log_manager);
+        coordinator.load().unwrap();
+        coordinator
+    }
+
+    fn commit(coordinator: &GroupCoordinator, group_id: &str, topics: &[&str]) {
+        let offset = OffsetAndMetadata { offset: 1, leader_epoch: -1, metadata: String::new(), commit_timestamp: 0 };
+        let errors = coordinator.commit_offsets(OffsetCommitParams {
+            group_id: group_id.to_owned(),
+            generation_id: -1,
+            member_id: String::new(),
+            group_instance_id: None,
+            offsets: topics.iter().map(|topic| (TopicPartition::new(*topic, 0), offset.clone())).collect(),
+            api_version: 9,
+        });
+        assert!(errors.iter().all(|error_code| *error_code == ErrorCode::None));
+    }
+
+    #[test]
+    fn test_deleted_group_stays_deleted_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let coordinator = coordinator(&dir);
+            commit(&coordinator, "group", &["foo"]);
+            let listed = coordinator.list_groups(&["empty".to_owned()], &[]);
+            assert_eq!(listed.iter().map(|listing| listing.group_id.as_str()).collect::<Vec<_>>(), vec!["group"]);
+            assert_eq!(coordinator.delete_groups(&["group".to_owned(), "missing".to_owned()]), vec![
+                ErrorCode::None,
+                ErrorCode::GroupIdNotFound
+            ]);
+        }
+
+        let coordinator = coordinator(&dir);
+        assert!(coordinator.list_groups(&[], &[]).is_empty());
+        assert!(coordinator.fetch_offsets("group", None).is_empty());
+    }
+
+    #[test]
+    fn test_offsets_of_subscribed_topics_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let coordinator = coordinator(&dir);
+        commit(&coordinator, "group", &["foo", "bar"]);
+        let joined = coordinator.consumer_group_heartbeat(ConsumerGroupHeartbeatParams {
+            group_id: "group".to_owned(),
+            member_id: String::new(),
+            member_epoch: 0,
+            instance_id: None,
+            rack_id: None,
+            rebalance_timeout_ms: 60_000,
+            subscribed_topic_names: Some(vec!["foo".to_owned()]),
+            server_assignor: None,
+            owned_partitions: None,
+            client_id: "client".to_owned(),
+            client_host: "/127.0.0.1".to_owned(),
+        });
+        assert_eq!(joined.error_code, ErrorCode::None);
+        assert_eq!(coordinator.list_groups(&[], &["Consumer".to_owned()]).len(), 1);
+        assert_eq!(coordinator.delete_groups(&["group".to_owned()]), vec![ErrorCode::NonEmptyGroup]);
+
+        let (foo, bar) = (TopicPartition::new("foo", 0), TopicPartition::new("bar", 0));
+        let results = coordinator.delete_offsets("group", vec![foo.clone(), bar.clone()]).unwrap();
+        assert_eq!(results, vec![(foo.clone(), ErrorCode::GroupSubscribedToTopic), (bar, ErrorCode::None)]);
+        let remaining = coordinator.fetch_offsets("group", None);
+        assert_eq!(remaining.into_iter().map(|(topic_partition, _)| topic_partition).collect::<Vec<_>>(), vec![foo]);
+    }
+}
@@ -0,0 +1,536 @@
+use crate::kafka::coordinator::{
+    Assignment, ConsumerGroup, ConsumerGroupMember, ConsumerGroupState, GroupCoordinator, MemberState,
+    LEAVE_GROUP_MEMBER_EPOCH, LEAVE_GROUP_STATIC_MEMBER_EPOCH,
+};
+use crate::kafka::types::{ErrorCode, Uuid};
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// A ConsumerGroupHeartbeat request as seen by the coordinator. Optional
+/// fields are `None` when unchanged since the member's last heartbeat.
+#[derive(Debug, Clone)]
+pub(crate) struct ConsumerGroupHeartbeatParams {
+    pub(crate) group_id: String,
+    pub(crate) member_id: String,
+    pub(crate) member_epoch: i32,
+    pub(crate) instance_id: Option<String>,
+    pub(crate) rack_id: Option<String>,
+    /// -1 if unchanged.
+    pub(crate) rebalance_timeout_ms: i32,
+    pub(crate) subscribed_topic_names: Option<Vec<String>>,
+    pub(crate) server_assignor: Option<String>,
+    pub(crate) owned_partitions: Option<Assignment>,
+    pub(crate) client_id: String,
+    pub(crate) client_host: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct ConsumerGroupHeartbeatResult {
+    pub(crate) error_code: ErrorCode,
+    pub(crate) error_message: Option<String>,
+    pub(crate) member_id: Option<String>,
+    pub(crate) member_epoch: i32,
+    pub(crate) heartbeat_interval_ms: i32,
+    /// The member's assignment, only sent when it changed.
+    pub(crate) assignment: Option<Assignment>,
+}
+
+impl ConsumerGroupHeartbeatResult {
+    fn error(error_code: ErrorCode, error_message: impl Into<String>) -> Self {
+        Self {
+            error_code,
+            error_message: Some(error_message.into()),
+            member_id: None,
+            member_epoch: -1,
+            heartbeat_interval_ms: 0,
+            assignment: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ConsumerGroupDescription {
+    pub(crate) group_id: String,
+    pub(crate) state: ConsumerGroupState,
+    pub(crate) group_epoch: i32,
+    pub(crate) assignment_epoch: i32,
+    pub(crate) assignor_name: String,
+    pub(crate) members: Vec<ConsumerGroupMemberDescription>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ConsumerGroupMemberDescription {
+    pub(crate) member_id: String,
+    pub(crate) instance_id: Option<String>,
+    pub(crate) rack_id: Option<String>,
+    pub(crate) member_epoch: i32,
+    pub(crate) client_id: String,
+    pub(crate) client_host: String,
+    pub(crate) subscribed_topic_names: Vec<String>,
+    pub(crate) assignment: Assignment,
+    pub(crate) target_assignment: Assignment,
+}
+
+impl GroupCoordinator {
+    /// Joins, leaves or keeps a member of a consumer protocol group alive,
+    /// moving it one step towards its target assignment.
+    pub(crate) fn consumer_group_heartbeat(&self, params: ConsumerGroupHeartbeatParams) -> ConsumerGroupHeartbeatResult {
+        let now = Instant::now();
+        if let Err((error_code, message)) = self.validate_consumer_group_heartbeat(&params) {
+            return ConsumerGroupHeartbeatResult::error(error_code, message);
+        }
+
+        let groups = self.groups();
+        if groups.get(&params.group_id).is_some_and(|group| !group.members.is_empty() || !group.pending_members.is_empty()) {
+            let message = format!("Group {} is not a consumer group.", params.group_id);
+            return ConsumerGroupHeartbeatResult::error(ErrorCode::GroupIdNotFound, message);
+        }
+        let mut consumer_groups = self.consumer_groups();
+        drop(groups);
+
+        if !consumer_groups.contains_key(&params.group_id) {
+            if params.member_epoch != 0 {
+                return unknown_member(&params);
+            }
+            consumer_groups.insert(params.group_id.clone(), ConsumerGroup::new(params.group_id.clone()));
+        }
+        let group = consumer_groups.get_mut(&params.group_id).expect("group exists");
+        if matches!(params.member_epoch, LEAVE_GROUP_MEMBER_EPOCH | LEAVE_GROUP_STATIC_MEMBER_EPOCH) {
+            return self.leave_consumer_group(group, &params);
+        }
+
+        let (member_id, mut bump_epoch) = match self.join_or_validate_member(group, &params, now) {
+            Ok(joined) => joined,
+            Err((error_code, message)) => return ConsumerGroupHeartbeatResult::error(error_code, message),
+        };
+        let member = group.members.get_mut(&member_id).expect("member joined");
+        let previous_assignment = member.assigned_partitions.clone();
+        bump_epoch |= update_consumer_member(member, &params, now);
+
+        let topics = self.log_manager.topics();
+        let subscription_metadata = group
+            .subscribed_topic_names()
+            .into_iter()
+            .filter_map(|name| topics.get(&name).map(|metadata| (name, *metadata)))
+            .collect::<BTreeMap<_, _>>();
+        if subscription_metadata != group.subscription_metadata {
+            group.subscription_metadata = subscription_metadata;
+            bump_epoch = true;
+        }
+        if bump_epoch {
+            group.group_epoch += 1;
+            info!(group = group.group_id, epoch = group.group_epoch, "Bumped consumer group epoch");
+        }
+        if group.group_epoch > group.assignment_epoch {
+            group.compute_target_assignment(self.config.group_consumer_assignors[0]);
+            info!(group = group.group_id, epoch = group.assignment_epoch, "Computed target assignment");
+        }
+
+        group.reconcile(&member_id, params.owned_partitions.as_ref());
+        let member = group.members.get_mut(&member_id).expect("member joined");
+        member.revocation_deadline = match member.state {
+            MemberState::UnrevokedPartitions => Some(member.revocation_deadline.unwrap_or(now + member.rebalance_timeout)),
+            _ => None,
+        };
+
+        let send_assignment = params.member_epoch == 0
+            || member.assigned_partitions != previous_assignment
+            || params.owned_partitions.as_ref().is_some_and(|owned| *owned != member.assigned_partitions);
+        ConsumerGroupHeartbeatResult {
+            error_code: ErrorCode::None,
+            error_message: None,
+            member_id: Some(member_id),
+            member_epoch: member.member_epoch,
+            heartbeat_interval_ms: self.config.group_consumer_heartbeat_interval_ms,
+            assignment: send_assignment.then(|| member.assigned_partitions.clone()),
+        }
+    }
+
+    /// Describes a consumer protocol group, or fails with GROUP_ID_NOT_FOUND.
+    pub(crate) fn describe_consumer_group(&self, group_id: &str) -> Result<ConsumerGroupDescription, (ErrorCode, String)> {
+        if let Some(group) = self.consumer_groups().get(group_id) {
+            let members = group
+                .members
+                .values()
+                .map(|member| ConsumerGroupMemberDescription {
+                    member_id: member.member_id.clone(),
+                    instance_id: member.instance_id.clone(),
+                    rack_id: member.rack_id.clone(),
+                    member_epoch: member.member_epoch,
+                    client_id: member.client_id.clone(),
+                    client_host: member.client_host.clone(),
+                    subscribed_topic_names: member.subscribed_topic_names.iter().cloned().collect(),
+                    assignment: member.assigned_partitions.clone(),
+                    target_assignment: group.target_assignment.get(&member.member_id).cloned().unwrap_or_default(),
+                })
+                .collect();
+            return Ok(ConsumerGroupDescription {
+                group_id: group.group_id.clone(),
+                state: group.state(),
+                group_epoch: group.group_epoch,
+                assignment_epoch: group.assignment_epoch,
+                assignor_name: group.assignor.map(|assignor| assignor.name().to_owned()).unwrap_or_default(),
+                members,
+            });
+        }
+        let message = match self.groups().contains_key(group_id) {
+            true => format!("Group {group_id} is not a consumer group."),
+            false => format!("Group {group_id} not found."),
+        };
+        Err((ErrorCode::GroupIdNotFound, message))
+    }
+
+    /// Removes members whose session expired or that did not revoke their
+    /// partitions within the rebalance timeout.
+    pub(crate) fn expire_consumer_group_members(&self, now: Instant) {
+        let session_timeout = Duration::from_millis(self.config.group_consumer_session_timeout_ms as u64);
+        for group in self.consumer_groups().values_mut() {
+            let expired = group
+                .members
+                .values()
+                .filter(|member| {
+                    now >= member.last_heartbeat + session_timeout
+                        || member.revocation_deadline.is_some_and(|deadline| now >= deadline)
+                })
+                .map(|member| member.member_id.clone())
+                .collect::<Vec<_>>();
+            for member_id in expired {
+                info!(group = group.group_id, member_id, "Fencing expired consumer group member");
+                group.remove_member(&member_id);
+            }
+        }
+    }
+
+    fn validate_consumer_group_heartbeat(&self, params: &ConsumerGroupHeartbeatParams) -> Result<(), (ErrorCode, String)> {
+        let invalid = |message: &str| Err((ErrorCode::InvalidRequest, message.to_owned()));
+        if params.group_id.is_empty() {
+            return invalid("GroupId can't be empty.");
+        }
+        if params.instance_id.as_deref() == Some("") {
+            return invalid("InstanceId can't be empty.");
+        }
+        if params.rack_id.as_deref() == Some("") {
+            return invalid("RackId can't be empty.");
+        }
+        match params.member_epoch {
+            0 => {
+                if params.rebalance_timeout_ms == -1 {
+                    return invalid("RebalanceTimeoutMs must be provided in first request.");
+                }
+                if params.owned_partitions.as_ref().is_some_and(|owned| !owned.is_empty()) {
+                    return invalid("TopicPartitions must be empty when (re-)joining.");
+                }
+                if params.subscribed_topic_names.is_none() {
+                    return invalid("SubscribedTopicNames must be set in first request.");
+                }
+            }
+            LEAVE_GROUP_STATIC_MEMBER_EPOCH if params.instance_id.is_none() => {
+                return invalid("InstanceId can't be null.");
+            }
+            epoch if epoch < LEAVE_GROUP_STATIC_MEMBER_EPOCH => return invalid("MemberEpoch is invalid."),
+            _ if params.member_id.is_empty() => return invalid("MemberId can't be empty."),
+            _ => {}
+        }
+        if let Some(assignor) = &params.server_assignor {
+            let assignors = &self.config.group_consumer_assignors;
+            if !assignors.iter().any(|supported| supported.name() == assignor) {
+                let supported = assignors.iter().map(|assignor| assignor.name()).collect::<Vec<_>>().join(", ");
+                let message = format!("ServerAssignor {assignor} is not supported. Supported assignors: {supported}.");
+                return Err((ErrorCode::UnsupportedAssignor, message));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a joining member, or checks that a known member heartbeats with
+    /// its current epoch. Returns the member id and whether the member is new.
+    fn join_or_validate_member(
+        &self,
+        group: &mut ConsumerGroup,
+        params: &ConsumerGroupHeartbeatParams,
+        now: Instant,
+    ) -> Result<(String, bool), (ErrorCode, String)> {
+        if params.member_epoch > 0 {
+            let Some(member) = group.members.get(&params.member_id) else {
+                return Err((ErrorCode::UnknownMemberId, format!("Member {} is not a member of the group.", params.member_id)));
+            };
+            if let Some(instance_id) = &params.instance_id {
+                if group.static_members.get(instance_id) != Some(&params.member_id) {
+                    return Err((ErrorCode::FencedInstanceId, format!("Static member {instance_id} was fenced.")));
+                }
+            }
+            validate_member_epoch(member, params.member_epoch, params.owned_partitions.as_ref())?;
+            return Ok((params.member_id.clone(), false));
+        }
+
+        let member_id = match params.member_id.is_empty() {
+            true => Uuid::random().to_base64(),
+            false => params.member_id.clone(),
+        };
+        if let Some(old_member_id) = params.instance_id.as_ref().and_then(|id| group.static_members.get(id)).cloned() {
+            return replace_static_member(group, old_member_id, member_id);
+        }
+        if group.remove_member(&member_id).is_some() {
+            info!(group = group.group_id, member_id, "Consumer group member rejoined");
+        }
+        if group.members.len() >= self.config.group_consumer_max_size {
+            let message = format!("The consumer group has reached its maximum capacity of {} members.", self.config.group_consumer_max_size);
+            return Err((ErrorCode::GroupMaxSizeReached, message));
+        }
+        if let Some(instance_id) = &params.instance_id {
+            group.static_members.insert(instance_id.clone(), member_id.clone());
+        }
+        group.members.insert(member_id.clone(), ConsumerGroupMember::new(member_id.clone(), now));
+        info!(group = group.group_id, member_id, "Member joined consumer group");
+        Ok((member_id, true))
+    }
+
+    fn leave_consumer_group(&self, group: &mut ConsumerGroup, params: &ConsumerGroupHeartbeatParams) -> ConsumerGroupHeartbeatResult {
+        let Some(member) = group.members.get_mut(&params.member_id) else {
+            return unknown_member(params);
+        };
+        if params.member_epoch == LEAVE_GROUP_STATIC_MEMBER_EPOCH {
+            // The static member keeps its partitions until it rejoins or its
+            // session expires.
+            member.previous_member_epoch = member.member_epoch;
+            member.member_epoch = LEAVE_GROUP_STATIC_MEMBER_EPOCH;
+            info!(group = group.group_id, member_id = params.member_id, "Static member left consumer group temporarily");
+        } else {
+            group.remove_member(&params.member_id);
+            info!(group = group.group_id, member_id = params.member_id, "Member left consumer group");
+        }
+        ConsumerGroupHeartbeatResult {
+            error_code: ErrorCode::None,
+            error_message: None,
+            member_id: Some(params.member_id.clone()),
+            member_epoch: params.member_epoch,
+            heartbeat_interval_ms: 0,
+            assignment: None,
+        }
+    }
+}
+
+fn unknown_member(params: &ConsumerGroupHeartbeatParams) -> ConsumerGroupHeartbeatResult {
+    let message = format!("Member {} is not a member of group {}.", params.member_id, params.group_id);
+    ConsumerGroupHeartbeatResult::error(ErrorCode::UnknownMemberId, message)
+}
+
+/// A member may heartbeat with its previous epoch if it missed the response
+/// bumping it, as long as it owns nothing it was not assigned.
+fn validate_member_epoch(
+    member: &ConsumerGroupMember,
+    received: i32,
+    owned: Option<&Assignment>,
+) -> Result<(), (ErrorCode, String)> {
+    let fenced = |relation: &str| {
+        let message = format!(
+            "The consumer group member has a {relation} member epoch ({received}) than the one known by the group \
+             coordinator ({}). The member must abandon all its partitions and rejoin.",
+            member.member_epoch
+        );
+        Err((ErrorCode::FencedMemberEpoch, message))
+    };
+    if received > member.member_epoch {
+        return fenced("greater");
+    }
+    if received < member.member_epoch {
+        let owns_only_assigned = owned.is_some_and(|owned| {
+            owned.iter().all(|(topic_id, partitions)| {
+                member.assigned_partitions.get(topic_id).is_some_and(|assigned| partitions.is_subset(assigned))
+            })
+        });
+        if received != member.previous_member_epoch || !owns_only_assigned {
+            return fenced("smaller");
+        }
+    }
+    Ok(())
+}
+
+/// A static member rejoined under a new member id. It takes over the
+/// partitions and epoch of the member it replaces, if that one left with the
+/// static leave epoch.
+fn replace_static_member(
+    group: &mut ConsumerGroup,
+    old_member_id: String,
+    member_id: String,
+) -> Result<(String, bool), (ErrorCode, String)> {
+    let Some(old_member) = group.members.get(&old_member_id) else {
+        return Err((ErrorCode::UnknownMemberId, format!("Member {old_member_id} is not a member of the group.")));
+    };
+    if old_member.member_epoch != LEAVE_GROUP_STATIC_MEMBER_EPOCH {
+        let instance_id = old_member.instance_id.clone().unwrap_or_default();
+        let message = format!("Static member {old_member_id} with instance id {instance_id} is not released yet.");
+        return Err((ErrorCode::UnreleasedInstanceId, message));
+    }
+
+    let mut member = group.members.remove(&old_member_id).expect("old member exists");
+    member.member_epoch = member.previous_member_epoch;
+    member.member_id = member_id.clone();
+    if let Some(target) = group.target_assignment.remove(&old_member_id) {
+        group.target_assignment.insert(member_id.clone(), target);
+    }
+    if let Some(instance_id) = &member.instance_id {
+        group.static_members.insert(instance_id.clone(), member_id.clone());
+    }
+    group.members.insert(member_id.clone(), member);
+    info!(group = group.group_id, old_member_id, member_id, "Replaced static consumer group member");
+    Ok((member_id, false))
+}
+
+/// Applies the heartbeat's changes to the member. Returns whether its
+/// subscription changed.
+fn update_consumer_member(member: &mut ConsumerGroupMember, params: &ConsumerGroupHeartbeatParams, now: Instant) -> bool {
+    member.last_heartbeat = now;
+    member.client_id = params.client_id.clone();
+    member.client_host = params.client_host.clone();
+    if params.instance_id.is_some() {
+        member.instance_id = params.instance_id.clone();
+    }
+    if params.rack_id.is_some() {
+        member.rack_id = params.rack_id.clone();
+    }
+    if params.rebalance_timeout_ms >= 0 {
+        member.rebalance_timeout = Duration::from_millis(params.rebalance_timeout_ms as u64);
+    }
+
+    let mut changed = false;
+    if let Some(names) = &params.subscribed_topic_names {
+        let names = names.iter().cloned().collect::<BTreeSet<_>>();
+        changed |= names != member.subscribed_topic_names;
+        member.subscribed_topic_names = names;
+    }
+    if let Some(assignor) = &params.server_assignor {
+        let assignor = assignor.parse().ok();
+        changed |= assignor != member.server_assignor;
+        member.server_assignor = assignor;
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::config::ServerConfig;
+    use crate::kafka::coordinator::Group;
+    use crate::kafka::storage::{LogManager, TopicPartition};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn coordinator(partitions: i32) -> (TempDir, GroupCoordinator, Uuid) {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        for partition in 0..partitions {
+            log_manager.get_or_create_log(&TopicPartition::new("foo", partition)).unwrap();
+        }
+        let topic_id = log_manager.topics()["foo"].topic_id;
+        (dir, GroupCoordinator::new(config, log_manager), topic_id)
+    }
+
+    fn heartbeat(member_id: &str, member_epoch: i32, owned: Option<Assignment>) -> ConsumerGroupHeartbeatParams {
+        ConsumerGroupHeartbeatParams {
+            group_id: "group".to_owned(),
+            member_id: member_id.to_owned(),
+            member_epoch,
+            instance_id: None,
+            rack_id: None,
+            rebalance_timeout_ms: if member_epoch == 0 { 60_000 } else { -1 },
+            subscribed_topic_names: (member_epoch == 0).then(|| vec!["foo".to_owned()]),
+            server_assignor: None,
+            owned_partitions: owned,
+            client_id: "client".to_owned(),
+            client_host: "/127.0.0.1".to_owned(),
+        }
+    }
+
+    fn partitions(topic_id: Uuid, partitions: &[i32]) -> Assignment {
+        Assignment::from([(topic_id, partitions.iter().copied().collect())])
+    }
+
+    #[test]
+    fn test_second_member_waits_for_revocation() {
+        let (_dir, coordinator, foo) = coordinator(4);
+
+        let first = coordinator.consumer_group_heartbeat(heartbeat("", 0, None));
+        assert_eq!(first.error_code, ErrorCode::None);
+        let a = first.member_id.unwrap();
+        assert_eq!(first.member_epoch, 1);
+        assert_eq!(first.assignment, Some(partitions(foo, &[0, 1, 2, 3])));
+
+        let second = coordinator.consumer_group_heartbeat(heartbeat("b", 0, None));
+        assert_eq!(second.member_epoch, 2);
+        // Every partition is still owned by the first member.
+        assert_eq!(second.assignment, Some(Assignment::new()));
+
+        // The first member is asked to revoke half of its partitions and
+        // stays at its epoch until it does.
+        let revoke = coordinator.consumer_group_heartbeat(heartbeat(&a, 1, Some(partitions(foo, &[0, 1, 2, 3]))));
+        assert_eq!(revoke.member_epoch, 1);
+        let kept = revoke.assignment.unwrap();
+        assert_eq!(kept[&foo].len(), 2);
+        assert_eq!(coordinator.describe_consumer_group("group").unwrap().state, ConsumerGroupState::Reconciling);
+
+        let revoked = coordinator.consumer_group_heartbeat(heartbeat(&a, 1, Some(kept.clone())));
+        assert_eq!(revoked.member_epoch, 2);
+        let released = coordinator.consumer_group_heartbeat(heartbeat("b", 2, Some(Assignment::new())));
+        let assigned = released.assignment.unwrap();
+        assert!(assigned[&foo].is_disjoint(&kept[&foo]));
+        assert_eq!(assigned[&foo].len(), 2);
+        assert_eq!(coordinator.describe_consumer_group("group").unwrap().state, ConsumerGroupState::Stable);
+
+        // A stale epoch with partitions the member was never given is fenced.
+        let fenced = coordinator.consumer_group_heartbeat(heartbeat(&a, 1, Some(partitions(foo, &[0, 1, 2, 3]))));
+        assert_eq!(fenced.error_code, ErrorCode::FencedMemberEpoch);
+    }
+
+    #[test]
+    fn test_expired_member_releases_partitions() {
+        let (_dir, coordinator, foo) = coordinator(2);
+        let a = coordinator.consumer_group_heartbeat(heartbeat("a", 0, None));
+        assert_eq!(a.assignment, Some(partitions(foo, &[0, 1])));
+        coordinator.consumer_group_heartbeat(heartbeat("b", 0, None));
+
+        coordinator.expire_consumer_group_members(Instant::now() + Duration::from_secs(60));
+        let group = coordinator.describe_consumer_group("group").unwrap();
+        assert!(group.members.is_empty());
+        assert_eq!(group.state, ConsumerGroupState::Empty);
+
+        let rejoined = coordinator.consumer_group_heartbeat(heartbeat("b", 3, Some(Assignment::new())));
+        assert_eq!(rejoined.error_code, ErrorCode::UnknownMemberId);
+        let rejoined = coordinator.consumer_group_heartbeat(heartbeat("b", 0, None));
+        assert_eq!(rejoined.assignment, Some(partitions(foo, &[0, 1])));
+    }
+
+    #[test]
+    fn test_static_member_keeps_assignment_across_restart() {
+        let (_dir, coordinator, foo) = coordinator(2);
+        let join = |member_id: &str| ConsumerGroupHeartbeatParams {
+            instance_id: Some("instance".to_owned()),
+            ..heartbeat(member_id, 0, None)
+        };
+        let first = coordinator.consumer_group_heartbeat(join("a"));
+        assert_eq!(first.assignment, Some(partitions(foo, &[0, 1])));
+
+        let unreleased = coordinator.consumer_group_heartbeat(join("b"));
+        assert_eq!(unreleased.error_code, ErrorCode::UnreleasedInstanceId);
+
+        let leave = ConsumerGroupHeartbeatParams { instance_id: Some("instance".to_owned()), ..heartbeat("a", -2, None) };
+        assert_eq!(coordinator.consumer_group_heartbeat(leave).member_epoch, -2);
+        let replaced = coordinator.consumer_group_heartbeat(join("b"));
+        assert_eq!(replaced.error_code, ErrorCode::None);
+        assert_eq!(replaced.member_epoch, first.member_epoch);
+        assert_eq!(replaced.assignment, Some(partitions(foo, &[0, 1])));
+    }
+
+    #[test]
+    fn test_classic_group_with_members_is_not_a_consumer_group() {
+        let (_dir, coordinator, _) = coordinator(1);
+        let mut group = Group::new("group".to_owned());
+        group.pending_members.insert("classic".to_owned(), Instant::now() + Duration::from_secs(60));
+        coordinator.groups().insert("group".to_owned(), group);
+
+        let result = coordinator.consumer_group_heartbeat(heartbeat("", 0, None));
+        assert_eq!(result.error_code, ErrorCode::GroupIdNotFound);
+    }
+}
@@ -0,0 +1,427 @@
+use super::validate_member;
+use crate::kafka::coordinator::{
+    offset_commit_record, partition_for_key, ConsumerGroup, Group, GroupCoordinator, GroupRecord, GroupState,
+    OffsetAndMetadata, CONSUMER_OFFSETS_TOPIC,
+};
+use crate::kafka::record::{EndTransactionMarker, Record, RecordBatch, TRANSACTIONAL_FLAG};
+use crate::kafka::storage::{AppendError, TopicPartition, CLEANUP_POLICY_CONFIG, SEGMENT_BYTES_CONFIG};
+use crate::kafka::types::ErrorCode;
+use std::collections::HashMap;
+use std::io;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+/// An OffsetCommit request for one group, as seen by the coordinator.
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetCommitParams {
+    pub(crate) group_id: String,
+    /// -1 for commits from outside the group, such as admin tools.
+    pub(crate) generation_id: i32,
+    pub(crate) member_id: String,
+    pub(crate) group_instance_id: Option<String>,
+    pub(crate) offsets: Vec<(TopicPartition, OffsetAndMetadata)>,
+    /// Members of consumer protocol groups must commit with v9 or later,
+    /// which carry their member epoch.
+    pub(crate) api_version: i16,
+}
+
+/// Offsets that passed validation, with their position in the request.
+type AcceptedOffsets = Vec<(usize, TopicPartition, OffsetAndMetadata)>;
+
+/// A TxnOffsetCommit request, as seen by the coordinator.
+#[derive(Debug, Clone)]
+pub(crate) struct TxnOffsetCommitParams {
+    pub(crate) group_id: String,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    /// -1 before v3, which did not carry the member's generation.
+    pub(crate) generation_id: i32,
+    pub(crate) member_id: String,
+    pub(crate) group_instance_id: Option<String>,
+    pub(crate) offsets: Vec<(TopicPartition, OffsetAndMetadata)>,
+}
+
+impl GroupCoordinator {
+    /// Makes `__consumer_offsets` a compacted topic and rebuilds the
+    /// committed offsets of every group from its partitions. Offsets of
+    /// transactions without a marker yet are kept pending.
+    pub(crate) fn load(&self) -> io::Result<()> {
+        let overrides = HashMap::from([
+            (CLEANUP_POLICY_CONFIG.to_owned(), "compact".to_owned()),
+            (SEGMENT_BYTES_CONFIG.to_owned(), self.config.offsets_topic_segment_bytes.to_string()),
+        ]);
+        self.log_manager.update_topic_config(CONSUMER_OFFSETS_TOPIC, &overrides);
+
+        let mut groups = self.groups();
+        let logs = self.log_manager.logs().into_iter().filter(|(tp, _)| tp.topic == CONSUMER_OFFSETS_TOPIC);
+        for (topic_partition, log) in logs {
+            let log = log.lock().expect("partition log lock poisoned");
+            // Records of open transactions, by producer id.
+            let mut txn_records: HashMap<i64, Vec<GroupRecord>> = HashMap::new();
+            for batch in log.batches(log.log_start_offset()) {
+                let batch = batch.map_err(io::Error::other)?;
+                let batch = RecordBatch::parse(batch.header, &batch.bytes).map_err(io::Error::other)?;
+                let producer_id = batch.header.producer_id;
+                if batch.header.is_control() {
+                    let marker = EndTransactionMarker::parse(&batch).map_err(io::Error::other)?;
+                    let records = txn_records.remove(&producer_id).unwrap_or_default();
+                    if marker.is_some_and(|marker| marker.is_commit()) {
+                        records.into_iter().for_each(|record| replay(&mut groups, record));
+                    }
+                    continue;
+                }
+                for record in batch.records {
+                    let Some(key) = record.key else {
+                        continue;
+                    };
+                    match GroupRecord::parse(&key, record.value.as_deref()) {
+                        Ok(Some(record)) if batch.header.is_transactional() => {
+                            txn_records.entry(producer_id).or_default().push(record)
+                        }
+                        Ok(Some(record)) => replay(&mut groups, record),
+                        Ok(None) => {}
+                        Err(err) => warn!(partition = %topic_partition, error = %err, "Skipping undecodable group record"),
+                    }
+                }
+            }
+            for (producer_id, records) in txn_records {
+                for record in records {
+                    if let GroupRecord::OffsetCommit { group_id, topic_partition, offset: Some(offset) } = record {
+                        let group = groups.entry(group_id.clone()).or_insert_with(|| Group::new(group_id));
+                        group.pending_txn_offsets.entry(producer_id).or_default().insert(topic_partition, offset);
+                    }
+                }
+            }
+        }
+        info!(groups = groups.len(), "Loaded consumer group offsets");
+        Ok(())
+    }
+
+    /// The `__consumer_offsets` partition holding the records of `group_id`.
+    pub(crate) fn offsets_partition_for(&self, group_id: &str) -> TopicPartition {
+        TopicPartition::new(CONSUMER_OFFSETS_TOPIC, partition_for_key(group_id, self.config.offsets_topic_num_partitions))
+    }
+
+    /// Validates the commit against the group's generation, writes the
+    /// offsets to `__consumer_offsets` and caches them. Returns the error for
+    /// each offset, in order.
+    pub(crate) fn commit_offsets(&self, params: OffsetCommitParams) -> Vec<ErrorCode> {
+        let mut groups = self.groups();
+        let validation = match self.consumer_groups().get(&params.group_id) {
+            Some(consumer_group) => validate_consumer_group_offset_commit(consumer_group, &params),
+            None => validate_offset_commit(groups.get(&params.group_id), &params),
+        };
+        if let Err(error_code) = validation {
+            return vec![error_code; params.offsets.len()];
+        }
+        let group =
+            groups.entry(params.group_id.clone()).or_insert_with(|| Group::new(params.group_id.clone()));
+        if let Some(member) = group.members.get_mut(&params.member_id) {
+            member.last_heartbeat = Instant::now();
+        }
+
+        let (mut errors, records, accepted) = self.offset_commit_records(&params.group_id, params.offsets);
+        if records.is_empty() {
+            return errors;
+        }
+
+        match self.append_group_records(&params.group_id, records) {
+            Ok(()) => {
+                for (_, topic_partition, offset) in accepted {
+                    group.offsets.insert(topic_partition, offset);
+                }
+            }
+            Err(err) => {
+                error!(group = params.group_id, error = %err, "Failed to write offset commit");
+                for (i, _, _) in accepted {
+                    errors[i] = ErrorCode::NotCoordinator;
+                }
+            }
+        }
+        errors
+    }
+
+    /// Validates a transactional commit and writes the offsets to
+    /// `__consumer_offsets` as part of the producer's transaction. They stay
+    /// pending until [`Self::complete_transaction`]. Returns the error for
+    /// each offset, in order.
+    pub(crate) fn commit_transactional_offsets(&self, params: TxnOffsetCommitParams) -> Vec<ErrorCode> {
+        let mut groups = self.groups();
+        let validation =
+            validate_txn_offset_commit(groups.get(&params.group_id), self.consumer_groups().get(&params.group_id), &params);
+        if let Err(error_code) = validation {
+            return vec![error_code; params.offsets.len()];
+        }
+        let (mut errors, records, accepted) = self.offset_commit_records(&params.group_id, params.offsets);
+        if records.is_empty() {
+            return errors;
+        }
+
+        let mut batch = RecordBatch::new(records, now_ms());
+        batch.header.attributes = TRANSACTIONAL_FLAG;
+        batch.header.producer_id = params.producer_id;
+        batch.header.producer_epoch = params.producer_epoch;
+        let error_code = match self.append_group_batch(&params.group_id, batch) {
+            Ok(()) => {
+                let group =
+                    groups.entry(params.group_id.clone()).or_insert_with(|| Group::new(params.group_id.clone()));
+                let pending = group.pending_txn_offsets.entry(params.producer_id).or_default();
+                for (_, topic_partition, offset) in accepted {
+                    pending.insert(topic_partition, offset);
+                }
+                return errors;
+            }
+            Err(AppendError::ProducerState(err)) => {
+                warn!(group = params.group_id, error = %err, "Rejected transactional offset commit");
+                ErrorCode::ProducerFenced
+            }
+            Err(err) => {
+                error!(group = params.group_id, error = %err, "Failed to write transactional offset commit");
+                ErrorCode::NotCoordinator
+            }
+        };
+        for (i, _, _) in accepted {
+            errors[i] = error_code;
+        }
+        errors
+    }
+
+    /// Makes the offsets `producer_id` committed in its transaction visible,
+    /// or discards them, once the transaction's markers are written.
+    pub(crate) fn complete_transaction(&self, producer_id: i64, commit: bool) {
+        for group in self.groups().values_mut() {
+            let Some(offsets) = group.pending_txn_offsets.remove(&producer_id) else {
+                continue;
+            };
+            if commit {
+                group.offsets.extend(offsets);
+            }
+        }
+    }
+
+    /// The records for the offsets that pass validation, with the error for
+    /// each offset and the accepted offsets by their position.
+    fn offset_commit_records(
+        &self,
+        group_id: &str,
+        offsets: Vec<(TopicPartition, OffsetAndMetadata)>,
+    ) -> (Vec<ErrorCode>, Vec<Record>, AcceptedOffsets) {
+        let mut errors = Vec::with_capacity(offsets.len());
+        let mut records = Vec::new();
+        let mut accepted = Vec::new();
+        for (i, (topic_partition, offset)) in offsets.into_iter().enumerate() {
+            if offset.metadata.len() > self.config.offset_metadata_max_bytes {
+                errors.push(ErrorCode::OffsetMetadataTooLarge);
+            } else if self.log_manager.get_log(&topic_partition).is_none() {
+                errors.push(ErrorCode::UnknownTopicOrPartition);
+            } else {
+                errors.push(ErrorCode::None);
+                records.push(offset_commit_record(group_id, &topic_partition, Some(&offset)));
+                accepted.push((i, topic_partition, offset));
+            }
+        }
+        (errors, records, accepted)
+    }
+
+    /// The committed offsets of `partitions`, or of every partition the
+    /// group committed for if `partitions` is `None`.
+    pub(crate) fn fetch_offsets(
+        &self,
+        group_id: &str,
+        partitions: Option<Vec<TopicPartition>>,
+    ) -> Vec<(TopicPartition, Option<OffsetAndMetadata>)> {
+        let groups = self.groups();
+        let offsets = groups.get(group_id).map(|group| &group.offsets);
+        match partitions {
+            Some(partitions) => partitions
+                .into_iter()
+                .map(|topic_partition| {
+                    let offset = offsets.and_then(|offsets| offsets.get(&topic_partition)).cloned();
+                    (topic_partition, offset)
+                })
+                .collect(),
+            None => {
+                let mut all = offsets
+                    .into_iter()
+                    .flatten()
+                    .map(|(topic_partition, offset)| (topic_partition.clone(), Some(offset.clone())))
+                    .collect::<Vec<_>>();
+                all.sort_by(|(a, _), (b, _)| a.cmp(b));
+                all
+            }
+        }
+    }
+
+    /// Appends `records` as one batch to the group's `__consumer_offsets`
+    /// partition, creating the partition on first use.
+    pub(crate) fn append_group_records(&self, group_id: &str, records: Vec<Record>) -> io::Result<()> {
+        self.append_group_batch(group_id, RecordBatch::new(records, now_ms())).map_err(io::Error::other)
+    }
+
+    fn append_group_batch(&self, group_id: &str, batch: RecordBatch) -> Result<(), AppendError> {
+        let topic_partition = self.offsets_partition_for(group_id);
+        let log = self.log_manager.get_or_create_log(&topic_partition)?;
+        let config = self.log_manager.log_config(CONSUMER_OFFSETS_TOPIC);
+        log.lock().expect("partition log lock poisoned").append(batch.to_bytes(), &config)?;
+        Ok(())
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64)
+}
+
+/// Commits from outside the group (a negative generation) are only accepted
+/// while the group has no members. Members must commit for the current
+/// generation, and not while the group waits for the leader's assignment.
+fn validate_offset_commit(group: Option<&Group>, params: &OffsetCommitParams) -> Result<(), ErrorCode> {
+    if params.group_id.is_empty() {
+        return Err(ErrorCode::InvalidGroupId);
+    }
+    let Some(group) = group else {
+        return if params.generation_id < 0 { Ok(()) } else { Err(ErrorCode::IllegalGeneration) };
+    };
+    if group.state() == GroupState::Dead {
+        return Err(ErrorCode::CoordinatorNotAvailable);
+    }
+    if params.generation_id < 0 && group.state() == GroupState::Empty {
+        return Ok(());
+    }
+    validate_member(group, &params.member_id, params.group_instance_id.as_deref())?;
+    if params.generation_id != group.generation_id {
+        return Err(ErrorCode::IllegalGeneration);
+    }
+    if group.state() == GroupState::CompletingRebalance {
+        return Err(ErrorCode::RebalanceInProgress);
+    }
+    Ok(())
+}
+
+/// Members must commit with their current member epoch. Commits from outside
+/// the group are only accepted while it has no members.
+fn validate_consumer_group_offset_commit(group: &ConsumerGroup, params: &OffsetCommitParams) -> Result<(), ErrorCode> {
+    if params.generation_id < 0 && group.members.is_empty() {
+        return Ok(());
+    }
+    let Some(member) = group.members.get(&params.member_id) else {
+        return Err(ErrorCode::UnknownMemberId);
+    };
+    if params.api_version < 9 {
+        return Err(ErrorCode::UnsupportedVersion);
+    }
+    if params.generation_id != member.member_epoch {
+        return Err(ErrorCode::StaleMemberEpoch);
+    }
+    Ok(())
+}
+
+/// Producers that predate KIP-447 send no generation and are not validated.
+/// Others must commit as a current member of the group.
+fn validate_txn_offset_commit(
+    group: Option<&Group>,
+    consumer_group: Option<&ConsumerGroup>,
+    params: &TxnOffsetCommitParams,
+) -> Result<(), ErrorCode> {
+    if params.group_id.is_empty() {
+        return Err(ErrorCode::InvalidGroupId);
+    }
+    if params.generation_id < 0 {
+        return Ok(());
+    }
+    if let Some(consumer_group) = consumer_group {
+        let Some(member) = consumer_group.members.get(&params.member_id) else {
+            return Err(ErrorCode::UnknownMemberId);
+        };
+        return if member.member_epoch == params.generation_id { Ok(()) } else { Err(ErrorCode::IllegalGeneration) };
+    }
+    let Some(group) = group else {
+        return Err(ErrorCode::IllegalGeneration);
+    };
+    if group.state() == GroupState::Dead {
+        return Err(ErrorCode::CoordinatorNotAvailable);
+    }
+    validate_member(group, &params.member_id, params.group_instance_id.as_deref())?;
+    if params.generation_id != group.generation_id {
+        return Err(ErrorCode::IllegalGeneration);
+    }
+    Ok(())
+}
+
+fn replay(groups: &mut HashMap<String, Group>, record: GroupRecord) {
+    match record {
+        GroupRecord::OffsetCommit { group_id, topic_partition, offset: Some(offset) } => {
+            let group = groups.entry(group_id.clone()).or_insert_with(|| Group::new(group_id));
+            group.offsets.insert(topic_partition, offset);
+        }
+        GroupRecord::OffsetCommit { group_id, topic_partition, offset: None } => {
+            if let Some(group) = groups.get_mut(&group_id) {
+                group.offsets.remove(&topic_partition);
+            }
+        }
+        GroupRecord::GroupMetadata { group_id, deleted: true } => {
+            groups.remove(&group_id);
+        }
+        GroupRecord::GroupMetadata { deleted: false, .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::config::ServerConfig;
+    use crate::kafka::storage::LogManager;
+    use std::sync::Arc;
+
+    fn offset(offset: i64) -> OffsetAndMetadata {
+        OffsetAndMetadata { offset, leader_epoch: -1, metadata: String::new(), commit_timestamp: 1_000 }
+    }
+
+    fn commit(group_id: &str, offsets: Vec<(TopicPartition, OffsetAndMetadata)>) -> OffsetCommitParams {
+        OffsetCommitParams {
+            group_id: group_id.to_owned(),
+            generation_id: -1,
+            member_id: String::new(),
+            group_instance_id: None,
+            offsets,
+            api_version: 9,
+        }
+    }
+
+    #[test]
+    fn test_committed_offsets_survive_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let foo = TopicPartition::new("foo", 0);
+        {
+            let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+            log_manager.get_or_create_log(&foo).unwrap();
+            let coordinator = GroupCoordinator::new(config.clone(), log_manager);
+            coordinator.load().unwrap();
+
+            let errors = coordinator.commit_offsets(commit("group", vec![(foo.clone(), offset(5))]));
+            assert_eq!(errors, vec![ErrorCode::None]);
+            let errors = coordinator.commit_offsets(commit("group", vec![(foo.clone(), offset(8))]));
+            assert_eq!(errors, vec![ErrorCode::None]);
+            let unknown = TopicPartition::new("bar", 0);
+            let errors = coordinator.commit_offsets(commit("group", vec![(unknown, offset(1))]));
+            assert_eq!(errors, vec![ErrorCode::UnknownTopicOrPartition]);
+        }
+
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        let coordinator = GroupCoordinator::new(config, log_manager);
+        coordinator.load().unwrap();
+        assert_eq!(coordinator.fetch_offsets("group", None), vec![(foo.clone(), Some(offset(8)))]);
+        let missing = TopicPartition::new("foo", 1);
+        assert_eq!(coordinator.fetch_offsets("group", Some(vec![missing.clone()])), vec![(missing, None)]);
+    }
+
+    #[test]
+    fn test_offsets_partition_matches_java_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        let coordinator = GroupCoordinator::new(config, log_manager);
+        // "my-group".hashCode() is -1906497762 in Java.
+        assert_eq!(coordinator.offsets_partition_for("my-group").partition, 36);
+    }
+}
@@ -0,0 +1,87 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::info;
+
+pub(crate) const PRODUCER_ID_BLOCK_FILE: &str = "producer-id-block";
+
+const PRODUCER_ID_BLOCK_VERSION: i32 = 0;
+/// Producer ids reserved at a time, as Kafka's controller hands them out.
+const PRODUCER_ID_BLOCK_SIZE: i64 = 1000;
+
+/// Hands out producer ids for InitProducerId. Ids are reserved in blocks
+/// whose end is persisted before any id of the block is used, so ids are
+/// never handed out twice, even across restarts.
+#[derive(Debug)]
+pub(crate) struct ProducerIdManager {
+    path: PathBuf,
+    block: Mutex<Range<i64>>,
+}
+
+impl ProducerIdManager {
+    /// Reads the first unreserved id from `producer-id-block` in `log_dir`.
+    pub(crate) fn load(log_dir: &Path) -> io::Result<Self> {
+        let path = log_dir.join(PRODUCER_ID_BLOCK_FILE);
+        let next_block_start = match fs::read_to_string(&path) {
+            Ok(text) => parse_next_block_start(&text)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => 0,
+            Err(err) => return Err(err),
+        };
+        Ok(Self { path, block: Mutex::new(next_block_start..next_block_start) })
+    }
+
+    /// The next unused producer id, reserving a new block when the current
+    /// one is used up.
+    pub(crate) fn generate(&self) -> io::Result<i64> {
+        let mut block = self.block.lock().expect("producer id block lock poisoned");
+        if block.is_empty() {
+            let end = block.end + PRODUCER_ID_BLOCK_SIZE;
+            self.write_next_block_start(end)?;
+            info!(start = block.end, end, "Reserved producer id block");
+            *block = block.end..end;
+        }
+        let producer_id = block.start;
+        block.start += 1;
+        Ok(producer_id)
+    }
+
+    fn write_next_block_start(&self, next_block_start: i64) -> io::Result<()> {
+        let text = format!("version: {PRODUCER_ID_BLOCK_VERSION}\nnext_block_start: {next_block_start}\n");
+        let tmp_path = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(text.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+fn parse_next_block_start(text: &str) -> io::Result<i64> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("{PRODUCER_ID_BLOCK_FILE}: {message}"));
+    let mut fields = text.lines().filter_map(|line| line.split_once(':')).map(|(key, value)| (key.trim(), value.trim()));
+    match fields.next() {
+        Some(("version", version)) if version.parse() == Ok(PRODUCER_ID_BLOCK_VERSION) => {}
+        _ => return Err(invalid("missing or unsupported version")),
+    }
+    match fields.next() {
+        Some(("next_block_start", start)) => start.parse().map_err(|_| invalid("malformed next_block_start")),
+        _ => Err(invalid("missing next_block_start")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_not_reused_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProducerIdManager::load(dir.path()).unwrap();
+        assert_eq!(manager.generate().unwrap(), 0);
+        assert_eq!(manager.generate().unwrap(), 1);
+
+        let manager = ProducerIdManager::load(dir.path()).unwrap();
+        assert_eq!(manager.generate().unwrap(), PRODUCER_ID_BLOCK_SIZE);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::kafka::coordinator::{TransactionMetadata, TransactionState};
+use crate::kafka::record::Record;
+use crate::kafka::storage::TopicPartition;
+use crate::kafka::types::{KafkaArray, KafkaNullableArray, KafkaString, TagBuffer};
+use binrw::{binrw, BinRead, BinResult, BinWrite};
+use std::io::Cursor;
+use tracing::warn;
+
+pub(crate) const CONSUMER_OFFSETS_TOPIC: &str = "__consumer_offsets";
+pub(crate) const TRANSACTION_STATE_TOPIC: &str = "__transaction_state";
+
+const OFFSET_COMMIT_KEY_VERSION: i16 = 1;
+const GROUP_METADATA_KEY_VERSION: i16 = 2;
+const OFFSET_COMMIT_VALUE_VERSION: i16 = 3;
+const TRANSACTION_LOG_KEY_VERSION: i16 = 0;
+const TRANSACTION_LOG_VALUE_VERSION: i16 = 0;
+
+/// The partition of an internal topic with `num_partitions` partitions that
+/// holds the records of `key`, chosen like Kafka does so existing tooling
+/// finds them.
+pub(crate) fn partition_for_key(key: &str, num_partitions: i32) -> i32 {
+    let hash = key.encode_utf16().fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as i32));
+    (hash & 0x7fff_ffff) % num_partitions
+}
+
+/// An offset committed by a group for one partition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OffsetAndMetadata {
+    pub(crate) offset: i64,
+    pub(crate) leader_epoch: i32,
+    pub(crate) metadata: String,
+    pub(crate) commit_timestamp: i64,
+}
+
+/// The `__consumer_offsets` records the coordinator replays, in Kafka's
+/// key and value schemas. Records with unknown key versions are skipped.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum GroupRecord {
+    /// `None` is a tombstone deleting the offset.
+    OffsetCommit { group_id: String, topic_partition: TopicPartition, offset: Option<OffsetAndMetadata> },
+    /// The membership of a group. Only tombstones, which delete the group,
+    /// are of interest; the value is not decoded.
+    GroupMetadata { group_id: String, deleted: bool },
+}
+
+impl GroupRecord {
+    /// Decodes a record key, prefixed by its version, and its value.
+    pub(crate) fn parse(key: &[u8], value: Option<&[u8]>) -> BinResult<Option<Self>> {
+        let mut reader = Cursor::new(key);
+        let version = i16::read_be(&mut reader)?;
+
+        match version {
+            0 | 1 => {
+                let key = OffsetCommitKey::read(&mut reader)?;
+                let offset = match value {
+                    Some(value) => {
+                        let mut reader = Cursor::new(value);
+                        let version = i16::read_be(&mut reader)?;
+                        let value = OffsetCommitValue::read_args(&mut reader, (version,))?;
+                        Some(OffsetAndMetadata {
+                            offset: value.offset,
+                            leader_epoch: value.leader_epoch,
+                            metadata: value.metadata.0,
+                            commit_timestamp: value.commit_timestamp,
+                        })
+                    }
+                    None => None,
+                };
+                Ok(Some(Self::OffsetCommit {
+                    group_id: key.group.0,
+                    topic_partition: TopicPartition::new(key.topic.0, key.partition),
+                    offset,
+                }))
+            }
+            GROUP_METADATA_KEY_VERSION => {
+                let key = GroupMetadataKey::read(&mut reader)?;
+                Ok(Some(Self::GroupMetadata { group_id: key.group.0, deleted: value.is_none() }))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The record committing `offset`, or a tombstone deleting the committed
+/// offset if `offset` is `None`.
+pub(crate) fn offset_commit_record(
+    group_id: &str,
+    topic_partition: &TopicPartition,
+    offset: Option<&OffsetAndMetadata>,
+) -> Record {
+    let key = OffsetCommitKey {
+        group: group_id.into(),
+        topic: topic_partition.topic.as_str().into(),
+        partition: topic_partition.partition,
+    };
+    let value = offset.map(|offset| {
+        let value = OffsetCommitValue {
+            offset: offset.offset,
+            leader_epoch: offset.leader_epoch,
+            metadata: offset.metadata.as_str().into(),
+            commit_timestamp: offset.commit_timestamp,
+            expire_timestamp: -1,
+            _tagged_fields: TagBuffer,
+        };
+        encode(OFFSET_COMMIT_VALUE_VERSION, |writer| value.write_args(writer, (OFFSET_COMMIT_VALUE_VERSION,)))
+    });
+    Record::new(Some(encode(OFFSET_COMMIT_KEY_VERSION, |writer| key.write(writer))), value)
+}
+
+/// The tombstone deleting the group's metadata, written when the group is
+/// deleted so it is not recreated on reload.
+pub(crate) fn group_metadata_tombstone(group_id: &str) -> Record {
+    let key = GroupMetadataKey { group: group_id.into() };
+    Record::new(Some(encode(GROUP_METADATA_KEY_VERSION, |writer| key.write(writer))), None)
+}
+
+/// A `__transaction_state` record: the metadata of a transactional id, or
+/// `None` for a tombstone.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TransactionRecord {
+    pub(crate) transactional_id: String,
+    pub(crate) metadata: Option<TransactionMetadata>,
+}
+
+impl TransactionRecord {
+    /// Decodes a record key and value, each prefixed by its version. Records
+    /// with unknown versions or states are skipped.
+    pub(crate) fn parse(key: &[u8], value: Option<&[u8]>) -> BinResult<Option<Self>> {
+        let mut reader = Cursor::new(key);
+        if i16::read_be(&mut reader)? != TRANSACTION_LOG_KEY_VERSION {
+            return Ok(None);
+        }
+        let transactional_id = TransactionLogKey::read(&mut reader)?.transactional_id.0;
+        let Some(value) = value else {
+            return Ok(Some(Self { transactional_id, metadata: None }));
+        };
+
+        let mut reader = Cursor::new(value);
+        let version = i16::read_be(&mut reader)?;
+        if version != TRANSACTION_LOG_VALUE_VERSION {
+            return Ok(None);
+        }
+        let value = TransactionLogValue::read(&mut reader)?;
+        let Some(state) = TransactionState::from_id(value.transaction_status) else {
+            warn!(transactional_id, status = value.transaction_status, "Skipping transaction in unknown state");
+            return Ok(None);
+        };
+        let partitions = value
+            .transaction_partitions
+            .0
+            .into_iter()
+            .flatten()
+            .flat_map(|topic| {
+                let name = topic.topic.0;
+                topic.partition_ids.0.into_iter().map(move |partition| TopicPartition::new(name.as_str(), partition))
+            })
+            .collect();
+        let metadata = TransactionMetadata {
+            transactional_id: transactional_id.clone(),
+            producer_id: value.producer_id,
+            producer_epoch: value.producer_epoch,
+            timeout_ms: value.transaction_timeout_ms,
+            state,
+            partitions,
+            start_timestamp_ms: value.transaction_start_timestamp_ms,
+            last_update_timestamp_ms: value.transaction_last_update_timestamp_ms,
+        };
+        Ok(Some(Self { transactional_id, metadata: Some(metadata) }))
+    }
+}
+
+/// The record storing the current metadata of a transactional id.
+pub(crate) fn transaction_record(metadata: &TransactionMetadata) -> Record {
+    let key = TransactionLogKey { transactional_id: metadata.transactional_id.as_str().into() };
+    let mut topics: Vec<TransactionLogPartitions> = Vec::new();
+    for topic_partition in &metadata.partitions {
+        match topics.last_mut() {
+            Some(topic) if *topic.topic == topic_partition.topic => topic.partition_ids.0.push(topic_partition.partition),
+            _ => topics.push(TransactionLogPartitions {
+                topic: topic_partition.topic.as_str().into(),
+                partition_ids: vec![topic_partition.partition].into(),
+            }),
+        }
+    }
+    // Kafka writes no partitions for transactions that are not in flight.
+    let in_flight = matches!(
+        metadata.state,
+        TransactionState::Ongoing | TransactionState::PrepareCommit | TransactionState::PrepareAbort
+    );
+    let value = TransactionLogValue {
+        producer_id: metadata.producer_id,
+        producer_epoch: metadata.producer_epoch,
+        transaction_timeout_ms: metadata.timeout_ms,
+        transaction_status: metadata.state.id(),
+        transaction_partitions: KafkaNullableArray(in_flight.then_some(topics)),
+        transaction_last_update_timestamp_ms: metadata.last_update_timestamp_ms,
+        transaction_start_timestamp_ms: metadata.start_timestamp_ms,
+    };
+    Record::new(
+        Some(encode(TRANSACTION_LOG_KEY_VERSION, |writer| key.write(writer))),
+        Some(encode(TRANSACTION_LOG_VALUE_VERSION, |writer| value.write(writer))),
+    )
+}
+
+fn encode(version: i16, write: impl FnOnce(&mut Cursor<Vec<u8>>) -> BinResult<()>) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    version.write_be(&mut writer).expect("writing to a Vec cannot fail");
+    write(&mut writer).expect("writing to a Vec cannot fail");
+    writer.into_inner()
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct OffsetCommitKey {
+    #[brw(args(false))]
+    group: KafkaString,
+    #[brw(args(false))]
+    topic: KafkaString,
+    partition: i32,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct GroupMetadataKey {
+    #[brw(args(false))]
+    group: KafkaString,
+}
+
+#[binrw]
+#[brw(big, import(version: i16))]
+#[derive(Debug)]
+struct OffsetCommitValue {
+    offset: i64,
+    #[brw(if(version >= 3, -1))]
+    leader_epoch: i32,
+    #[brw(args(version >= 4))]
+    metadata: KafkaString,
+    commit_timestamp: i64,
+    /// Only stored by version 1.
+    #[brw(if(version == 1, -1))]
+    expire_timestamp: i64,
+    #[brw(if(version >= 4))]
+    _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct TransactionLogKey {
+    #[brw(args(false))]
+    transactional_id: KafkaString,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct TransactionLogValue {
+    producer_id: i64,
+    producer_epoch: i16,
+    transaction_timeout_ms: i32,
+    transaction_status: i8,
+    #[brw(args(false, ()))]
+    transaction_partitions: KafkaNullableArray<TransactionLogPartitions>,
+    transaction_last_update_timestamp_ms: i64,
+    transaction_start_timestamp_ms: i64,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct TransactionLogPartitions {
+    #[brw(args(false))]
+    topic: KafkaString,
+    #[brw(args(false, ()))]
+    partition_ids: KafkaArray<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_commit_round_trip() {
+        let topic_partition = TopicPartition::new("foo", 3);
+        let offset = OffsetAndMetadata { offset: 42, leader_epoch: 7, metadata: "meta".to_owned(), commit_timestamp: 1_000 };
+        let record = offset_commit_record("group", &topic_partition, Some(&offset));
+
+        let key = record.key.as_deref().unwrap();
+        assert_eq!(&key[..2], &[0, 1]);
+        let parsed = GroupRecord::parse(key, record.value.as_deref()).unwrap();
+        assert_eq!(
+            parsed,
+            Some(GroupRecord::OffsetCommit { group_id: "group".to_owned(), topic_partition, offset: Some(offset) })
+        );
+    }
+
+    #[test]
+    fn test_group_metadata_tombstone() {
+        let record = group_metadata_tombstone("group");
+        let parsed = GroupRecord::parse(record.key.as_deref().unwrap(), None).unwrap();
+        assert_eq!(parsed, Some(GroupRecord::GroupMetadata { group_id: "group".to_owned(), deleted: true }));
+    }
+
+    #[test]
+    fn test_version_one_value_with_expire_timestamp() {
+        let key = [vec![0, 0, 0, 1, b'g', 0, 1, b't'], 0i32.to_be_bytes().to_vec()].concat();
+        let value = [
+            1i16.to_be_bytes().to_vec(),
+            5i64.to_be_bytes().to_vec(),
+            vec![0, 0],
+            10i64.to_be_bytes().to_vec(),
+            20i64.to_be_bytes().to_vec(),
+        ]
+        .concat();
+
+        let Some(GroupRecord::OffsetCommit { offset: Some(offset), .. }) = GroupRecord::parse(&key, Some(&value)).unwrap()
+        else {
+            panic!("expected an offset commit");
+        };
+        assert_eq!(offset, OffsetAndMetadata { offset: 5, leader_epoch: -1, metadata: String::new(), commit_timestamp: 10 });
+    }
+
+    #[test]
+    fn test_transaction_round_trip() {
+        let mut metadata = TransactionMetadata::new("txn".to_owned(), 42, 60_000, 1_000);
+        metadata.producer_epoch = 3;
+        metadata.state = TransactionState::Ongoing;
+        metadata.partitions =
+            [TopicPartition::new("foo", 0), TopicPartition::new("foo", 2), TopicPartition::new("bar", 1)].into();
+        metadata.start_timestamp_ms = 1_500;
+        let record = transaction_record(&metadata);
+
+        let parsed = TransactionRecord::parse(record.key.as_deref().unwrap(), record.value.as_deref()).unwrap();
+        assert_eq!(parsed, Some(TransactionRecord { transactional_id: "txn".to_owned(), metadata: Some(metadata) }));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::kafka::storage::TopicPartition;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// The producer epoch after which a transactional id gets a new producer id
+/// instead of a bumped epoch.
+const MAX_PRODUCER_EPOCH: i16 = i16::MAX - 1;
+
+/// States of a transactional id, with the ids Kafka stores them under in
+/// `__transaction_state`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TransactionState {
+    /// No transaction has been started since the producer was initialized.
+    Empty,
+    Ongoing,
+    /// The outcome is decided, markers are being written to the partitions.
+    PrepareCommit,
+    PrepareAbort,
+    CompleteCommit,
+    CompleteAbort,
+}
+
+impl TransactionState {
+    pub(crate) fn id(self) -> i8 {
+        match self {
+            TransactionState::Empty => 0,
+            TransactionState::Ongoing => 1,
+            TransactionState::PrepareCommit => 2,
+            TransactionState::PrepareAbort => 3,
+            TransactionState::CompleteCommit => 4,
+            TransactionState::CompleteAbort => 5,
+        }
+    }
+
+    pub(crate) fn from_id(id: i8) -> Option<Self> {
+        match id {
+            0 => Some(TransactionState::Empty),
+            1 => Some(TransactionState::Ongoing),
+            2 => Some(TransactionState::PrepareCommit),
+            // An epoch fence is an abort at the bumped epoch.
+            3 | 7 => Some(TransactionState::PrepareAbort),
+            4 => Some(TransactionState::CompleteCommit),
+            5 => Some(TransactionState::CompleteAbort),
+            _ => None,
+        }
+    }
+
+    /// The state named like [`Display`] shows it, as admin clients filter by.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        [
+            TransactionState::Empty,
+            TransactionState::Ongoing,
+            TransactionState::PrepareCommit,
+            TransactionState::PrepareAbort,
+            TransactionState::CompleteCommit,
+            TransactionState::CompleteAbort,
+        ]
+        .into_iter()
+        .find(|state| state.to_string() == name)
+    }
+
+    /// Whether markers are still to be written for the transaction.
+    pub(crate) fn is_prepared(self) -> bool {
+        matches!(self, TransactionState::PrepareCommit | TransactionState::PrepareAbort)
+    }
+}
+
+impl Display for TransactionState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TransactionState::Empty => "Empty",
+            TransactionState::Ongoing => "Ongoing",
+            TransactionState::PrepareCommit => "PrepareCommit",
+            TransactionState::PrepareAbort => "PrepareAbort",
+            TransactionState::CompleteCommit => "CompleteCommit",
+            TransactionState::CompleteAbort => "CompleteAbort",
+        })
+    }
+}
+
+/// The producer and current transaction of a transactional id, as written
+/// to `__transaction_state` on every change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TransactionMetadata {
+    pub(crate) transactional_id: String,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    pub(crate) timeout_ms: i32,
+    pub(crate) state: TransactionState,
+    /// The partitions the current transaction wrote to.
+    pub(crate) partitions: BTreeSet<TopicPartition>,
+    /// When the current transaction started, -1 outside transactions.
+    pub(crate) start_timestamp_ms: i64,
+    pub(crate) last_update_timestamp_ms: i64,
+}
+
+impl TransactionMetadata {
+    pub(crate) fn new(transactional_id: String, producer_id: i64, timeout_ms: i32, now_ms: i64) -> Self {
+        Self {
+            transactional_id,
+            producer_id,
+            producer_epoch: 0,
+            timeout_ms,
+            state: TransactionState::Empty,
+            partitions: BTreeSet::new(),
+            start_timestamp_ms: -1,
+            last_update_timestamp_ms: now_ms,
+        }
+    }
+
+    /// Whether the epoch can no longer be bumped, so that the producer needs
+    /// a new producer id.
+    pub(crate) fn is_epoch_exhausted(&self) -> bool {
+        self.producer_epoch >= MAX_PRODUCER_EPOCH
+    }
+
+    /// Whether the ongoing transaction ran past its timeout at `now_ms`.
+    pub(crate) fn is_expired(&self, now_ms: i64) -> bool {
+        self.state == TransactionState::Ongoing && now_ms - self.start_timestamp_ms > self.timeout_ms as i64
+    }
+}
@@ -0,0 +1,544 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::coordinator::{
+    partition_for_key, transaction_record, GroupCoordinator, ProducerIdManager, TransactionMetadata, TransactionRecord,
+    TransactionState, TRANSACTION_STATE_TOPIC,
+};
+use crate::kafka::record::{EndTransactionMarker, RecordBatch};
+use crate::kafka::storage::{LogManager, TopicPartition, CLEANUP_POLICY_CONFIG, SEGMENT_BYTES_CONFIG};
+use crate::kafka::types::ErrorCode;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+/// The epoch of this broker as coordinator of the transactions it writes
+/// markers for. It never changes, as every transaction is coordinated here.
+const COORDINATOR_EPOCH: i32 = 0;
+
+/// Coordinates transactional producers: hands out their producer ids and
+/// epochs, records the partitions of their transactions in
+/// `__transaction_state`, and completes transactions by writing commit or
+/// abort markers to each of them. Every transactional id is coordinated by
+/// this broker.
+#[derive(Debug)]
+pub(crate) struct TransactionCoordinator {
+    config: ServerConfig,
+    log_manager: Arc<LogManager>,
+    group_coordinator: Arc<GroupCoordinator>,
+    producer_id_manager: ProducerIdManager,
+    /// Every transactional id, with its metadata as last written.
+    transactions: Mutex<HashMap<String, TransactionMetadata>>,
+}
+
+impl TransactionCoordinator {
+    pub(crate) fn new(
+        config: ServerConfig,
+        log_manager: Arc<LogManager>,
+        group_coordinator: Arc<GroupCoordinator>,
+        producer_id_manager: ProducerIdManager,
+    ) -> Self {
+        Self { config, log_manager, group_coordinator, producer_id_manager, transactions: Mutex::new(HashMap::new()) }
+    }
+
+    /// Makes `__transaction_state` a compacted topic, rebuilds the metadata of
+    /// every transactional id from its partitions and completes the
+    /// transactions whose markers may not all have been written.
+    pub(crate) fn load(&self) -> io::Result<()> {
+        let overrides = HashMap::from([
+            (CLEANUP_POLICY_CONFIG.to_owned(), "compact".to_owned()),
+            (SEGMENT_BYTES_CONFIG.to_owned(), self.config.transaction_state_log_segment_bytes.to_string()),
+        ]);
+        self.log_manager.update_topic_config(TRANSACTION_STATE_TOPIC, &overrides);
+
+        let mut transactions = self.transactions();
+        let logs = self.log_manager.logs().into_iter().filter(|(tp, _)| tp.topic == TRANSACTION_STATE_TOPIC);
+        for (topic_partition, log) in logs {
+            let log = log.lock().expect("partition log lock poisoned");
+            for batch in log.batches(log.log_start_offset()) {
+                let batch = batch.map_err(io::Error::other)?;
+                if batch.header.is_control() {
+                    continue;
+                }
+                let batch = RecordBatch::parse(batch.header, &batch.bytes).map_err(io::Error::other)?;
+                for record in batch.records {
+                    let Some(key) = record.key else {
+                        continue;
+                    };
+                    match TransactionRecord::parse(&key, record.value.as_deref()) {
+                        Ok(Some(TransactionRecord { transactional_id, metadata: Some(metadata) })) => {
+                            transactions.insert(transactional_id, metadata);
+                        }
+                        Ok(Some(TransactionRecord { transactional_id, metadata: None })) => {
+                            transactions.remove(&transactional_id);
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            warn!(partition = %topic_partition, error = %err, "Skipping undecodable transaction record")
+                        }
+                    }
+                }
+            }
+        }
+        info!(transactions = transactions.len(), "Loaded transaction state");
+
+        let now_ms = now_ms();
+        for metadata in transactions.values_mut().filter(|metadata| metadata.state.is_prepared()) {
+            if let Err(err) = self.complete(metadata, now_ms) {
+                warn!(transactional_id = metadata.transactional_id, error = %err, "Failed to complete transaction");
+            }
+        }
+        Ok(())
+    }
+
+    /// Aborts timed out transactions every
+    /// `transaction.abort.timed.out.transaction.cleanup.interval.ms`.
+    pub(crate) async fn run(self: Arc<Self>) {
+        let period = Duration::from_millis(self.config.transaction_abort_timed_out_transaction_cleanup_interval_ms);
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            self.tick(now_ms());
+        }
+    }
+
+    fn transactions(&self) -> MutexGuard<'_, HashMap<String, TransactionMetadata>> {
+        self.transactions.lock().unwrap()
+    }
+
+    /// The `__transaction_state` partition holding the records of
+    /// `transactional_id`.
+    pub(crate) fn partition_for(&self, transactional_id: &str) -> TopicPartition {
+        let partition = partition_for_key(transactional_id, self.config.transaction_state_log_num_partitions);
+        TopicPartition::new(TRANSACTION_STATE_TOPIC, partition)
+    }
+
+    /// Gives a producer its producer id and epoch. Idempotent producers get a
+    /// new producer id at epoch 0. A transactional producer gets the producer
+    /// id of its transactional id at a bumped epoch, which fences older
+    /// instances of it; a transaction they left open is aborted. `expected`
+    /// is the producer id and epoch a running producer bumps from (KIP-360).
+    pub(crate) fn init_producer_id(
+        &self,
+        transactional_id: Option<&str>,
+        timeout_ms: i32,
+        expected: Option<(i64, i16)>,
+    ) -> Result<(i64, i16), ErrorCode> {
+        let Some(transactional_id) = transactional_id else {
+            return Ok((self.generate_producer_id()?, 0));
+        };
+        if transactional_id.is_empty() {
+            return Err(ErrorCode::InvalidRequest);
+        }
+        if timeout_ms <= 0 || timeout_ms > self.config.transaction_max_timeout_ms {
+            return Err(ErrorCode::InvalidTransactionTimeout);
+        }
+
+        let now_ms = now_ms();
+        let mut transactions = self.transactions();
+        let metadata = match transactions.entry(transactional_id.to_owned()) {
+            Entry::Vacant(entry) => {
+                let metadata =
+                    TransactionMetadata::new(transactional_id.to_owned(), self.generate_producer_id()?, timeout_ms, now_ms);
+                self.write(&metadata).map_err(|err| write_failed(transactional_id, err))?;
+                info!(transactional_id, producer_id = metadata.producer_id, "Initialized transactional id");
+                return Ok((entry.insert(metadata).producer_id, 0));
+            }
+            Entry::Occupied(entry) => entry.into_mut(),
+        };
+        if metadata.state.is_prepared() {
+            return Err(ErrorCode::ConcurrentTransactions);
+        }
+        if expected.is_some_and(|expected| expected != (metadata.producer_id, metadata.producer_epoch)) {
+            return Err(ErrorCode::ProducerFenced);
+        }
+        if metadata.state == TransactionState::Ongoing {
+            info!(transactional_id, "Aborting the transaction of a fenced producer");
+            self.end_transaction(metadata, false, true, now_ms).map_err(|err| write_failed(transactional_id, err))?;
+        }
+
+        let mut initialized = metadata.clone();
+        if initialized.is_epoch_exhausted() {
+            initialized.producer_id = self.generate_producer_id()?;
+            initialized.producer_epoch = 0;
+        } else {
+            initialized.producer_epoch += 1;
+        }
+        initialized.state = TransactionState::Empty;
+        initialized.partitions.clear();
+        initialized.timeout_ms = timeout_ms;
+        initialized.start_timestamp_ms = -1;
+        initialized.last_update_timestamp_ms = now_ms;
+        self.update(metadata, initialized).map_err(|err| write_failed(transactional_id, err))?;
+        let (producer_id, producer_epoch) = (metadata.producer_id, metadata.producer_epoch);
+        info!(transactional_id, producer_id, producer_epoch, "Bumped producer epoch");
+        Ok((producer_id, producer_epoch))
+    }
+
+    /// Adds `partitions` to the producer's transaction, starting one if none
+    /// is ongoing. Nothing is added if any of them does not exist.
+    pub(crate) fn add_partitions(
+        &self,
+        transactional_id: &str,
+        producer_id: i64,
+        producer_epoch: i16,
+        partitions: Vec<TopicPartition>,
+    ) -> Vec<(TopicPartition, ErrorCode)> {
+        let mut transactions = self.transactions();
+        let metadata = match validate(&mut transactions, transactional_id, producer_id, producer_epoch) {
+            Ok(metadata) => metadata,
+            Err(error_code) => return partitions.into_iter().map(|tp| (tp, error_code)).collect(),
+        };
+        if partitions.iter().any(|tp| self.log_manager.get_log(tp).is_none()) {
+            return partitions
+                .into_iter()
+                .map(|tp| match self.log_manager.get_log(&tp) {
+                    Some(_) => (tp, ErrorCode::OperationNotAttempted),
+                    None => (tp, ErrorCode::UnknownTopicOrPartition),
+                })
+                .collect();
+        }
+
+        let error_code = match self.add_to_transaction(metadata, &partitions) {
+            Ok(()) => ErrorCode::None,
+            Err(err) => write_failed(transactional_id, err),
+        };
+        partitions.into_iter().map(|tp| (tp, error_code)).collect()
+    }
+
+    /// Adds the `__consumer_offsets` partition of `group_id` to the
+    /// producer's transaction, so offsets can be committed in it.
+    pub(crate) fn add_offsets(
+        &self,
+        transactional_id: &str,
+        producer_id: i64,
+        producer_epoch: i16,
+        group_id: &str,
+    ) -> ErrorCode {
+        let mut transactions = self.transactions();
+        let metadata = match validate(&mut transactions, transactional_id, producer_id, producer_epoch) {
+            Ok(metadata) => metadata,
+            Err(error_code) => return error_code,
+        };
+        let topic_partition = self.group_coordinator.offsets_partition_for(group_id);
+        let result = self
+            .log_manager
+            .get_or_create_log(&topic_partition)
+            .and_then(|_| self.add_to_transaction(metadata, &[topic_partition]));
+        match result {
+            Ok(()) => ErrorCode::None,
+            Err(err) => write_failed(transactional_id, err),
+        }
+    }
+
+    /// Commits or aborts the producer's ongoing transaction. Retrying an
+    /// outcome that has been reached succeeds.
+    pub(crate) fn end_txn(&self, transactional_id: &str, producer_id: i64, producer_epoch: i16, commit: bool) -> ErrorCode {
+        let mut transactions = self.transactions();
+        let metadata = match validate(&mut transactions, transactional_id, producer_id, producer_epoch) {
+            Ok(metadata) => metadata,
+            Err(error_code) => return error_code,
+        };
+        match metadata.state {
+            TransactionState::Ongoing => {}
+            TransactionState::CompleteCommit if commit => return ErrorCode::None,
+            TransactionState::CompleteAbort if !commit => return ErrorCode::None,
+            _ => return ErrorCode::InvalidTxnState,
+        }
+        match self.end_transaction(metadata, commit, false, now_ms()) {
+            Ok(()) => ErrorCode::None,
+            Err(err) => write_failed(transactional_id, err),
+        }
+    }
+
+    /// Aborts the transactions that ran past their timeout, bumping the epoch
+    /// to fence their producer, and retries completing the prepared ones.
+    pub(crate) fn tick(&self, now_ms: i64) {
+        let mut transactions = self.transactions();
+        for metadata in transactions.values_mut() {
+            let result = if metadata.state.is_prepared() {
+                self.complete(metadata, now_ms)
+            } else if metadata.is_expired(now_ms) {
+                info!(transactional_id = metadata.transactional_id, "Aborting timed out transaction");
+                self.end_transaction(metadata, false, true, now_ms)
+            } else {
+                continue;
+            };
+            if let Err(err) = result {
+                warn!(transactional_id = metadata.transactional_id, error = %err, "Failed to complete transaction");
+            }
+        }
+    }
+
+    /// The metadata of `transactional_id`, as written last.
+    pub(crate) fn describe_transaction(&self, transactional_id: &str) -> Result<TransactionMetadata, ErrorCode> {
+        self.transactions().get(transactional_id).cloned().ok_or(ErrorCode::TransactionalIdNotFound)
+    }
+
+    /// The transactional ids in one of `states` with one of `producer_ids`,
+    /// whose transaction started at least `min_duration_ms` before `now_ms`,
+    /// sorted by id. Empty filters and a negative duration match all.
+    pub(crate) fn list_transactions(
+        &self,
+        states: &[TransactionState],
+        producer_ids: &[i64],
+        min_duration_ms: i64,
+        now_ms: i64,
+    ) -> Vec<TransactionMetadata> {
+        let mut listings = self
+            .transactions()
+            .values()
+            .filter(|metadata| states.is_empty() || states.contains(&metadata.state))
+            .filter(|metadata| producer_ids.is_empty() || producer_ids.contains(&metadata.producer_id))
+            .filter(|metadata| min_duration_ms < 0 || now_ms - metadata.start_timestamp_ms >= min_duration_ms)
+            .cloned()
+            .collect::<Vec<_>>();
+        listings.sort_by(|a, b| a.transactional_id.cmp(&b.transactional_id));
+        listings
+    }
+
+    fn add_to_transaction(&self, metadata: &mut TransactionMetadata, partitions: &[TopicPartition]) -> io::Result<()> {
+        if metadata.state == TransactionState::Ongoing && partitions.iter().all(|tp| metadata.partitions.contains(tp)) {
+            return Ok(());
+        }
+        let now_ms = now_ms();
+        let mut updated = metadata.clone();
+        if updated.state != TransactionState::Ongoing {
+            updated.state = TransactionState::Ongoing;
+            updated.start_timestamp_ms = now_ms;
+        }
+        updated.partitions.extend(partitions.iter().cloned());
+        updated.last_update_timestamp_ms = now_ms;
+        self.update(metadata, updated)
+    }
+
+    /// Decides the outcome of the ongoing transaction, optionally at a bumped
+    /// epoch, and completes it.
+    fn end_transaction(
+        &self,
+        metadata: &mut TransactionMetadata,
+        commit: bool,
+        bump_epoch: bool,
+        now_ms: i64,
+    ) -> io::Result<()> {
+        let mut prepared = metadata.clone();
+        if bump_epoch && !prepared.is_epoch_exhausted() {
+            prepared.producer_epoch += 1;
+        }
+        prepared.state = if commit { TransactionState::PrepareCommit } else { TransactionState::PrepareAbort };
+        prepared.last_update_timestamp_ms = now_ms;
+        self.update(metadata, prepared)?;
+        self.complete(metadata, now_ms)
+    }
+
+    /// Writes the markers of a prepared transaction to each of its partitions
+    /// and records it as complete. Partitions that no longer exist are
+    /// skipped, ones that already got their marker ignore it.
+    fn complete(&self, metadata: &mut TransactionMetadata, now_ms: i64) -> io::Result<()> {
+        let commit = metadata.state == TransactionState::PrepareCommit;
+        let marker = EndTransactionMarker::new(commit, COORDINATOR_EPOCH);
+        for topic_partition in &metadata.partitions {
+            let Some(log) = self.log_manager.get_log(topic_partition) else {
+                warn!(partition = %topic_partition, "Skipping transaction marker for a deleted partition");
+                continue;
+            };
+            let bytes = marker.to_batch(metadata.producer_id, metadata.producer_epoch, now_ms).to_bytes();
+            let config = self.log_manager.log_config(&topic_partition.topic);
+            log.lock().expect("partition log lock poisoned").append(bytes, &config).map_err(io::Error::other)?;
+        }
+        self.group_coordinator.complete_transaction(metadata.producer_id, commit);
+
+        let mut completed = metadata.clone();
+        completed.state = if commit { TransactionState::CompleteCommit } else { TransactionState::CompleteAbort };
+        completed.partitions.clear();
+        completed.last_update_timestamp_ms = now_ms;
+        self.update(metadata, completed)?;
+        info!(transactional_id = metadata.transactional_id, state = %metadata.state, "Completed transaction");
+        Ok(())
+    }
+
+    /// Writes `updated` to `__transaction_state` and then applies it.
+    fn update(&self, metadata: &mut TransactionMetadata, updated: TransactionMetadata) -> io::Result<()> {
+        self.write(&updated)?;
+        *metadata = updated;
+        Ok(())
+    }
+
+    fn write(&self, metadata: &TransactionMetadata) -> io::Result<()> {
+        let topic_partition = self.partition_for(&metadata.transactional_id);
+        let log = self.log_manager.get_or_create_log(&topic_partition)?;
+        let config = self.log_manager.log_config(TRANSACTION_STATE_TOPIC);
+        let batch = RecordBatch::new(vec![transaction_record(metadata)], metadata.last_update_timestamp_ms);
+        log.lock().expect("partition log lock poisoned").append(batch.to_bytes(), &config).map_err(io::Error::other)?;
+        Ok(())
+    }
+
+    fn generate_producer_id(&self) -> Result<i64, ErrorCode> {
+        self.producer_id_manager.generate().map_err(|err| {
+            error!(error = %err, "Failed to allocate a producer id");
+            ErrorCode::UnknownServerError
+        })
+    }
+}
+
+/// Requests about a transaction must come from its current producer, and
+/// not while its markers are being written.
+fn validate<'a>(
+    transactions: &'a mut HashMap<String, TransactionMetadata>,
+    transactional_id: &str,
+    producer_id: i64,
+    producer_epoch: i16,
+) -> Result<&'a mut TransactionMetadata, ErrorCode> {
+    let Some(metadata) = transactions.get_mut(transactional_id) else {
+        return Err(ErrorCode::InvalidProducerIdMapping);
+    };
+    if metadata.producer_id != producer_id {
+        return Err(ErrorCode::InvalidProducerIdMapping);
+    }
+    if metadata.producer_epoch != producer_epoch {
+        return Err(ErrorCode::ProducerFenced);
+    }
+    if metadata.state.is_prepared() {
+        return Err(ErrorCode::ConcurrentTransactions);
+    }
+    Ok(metadata)
+}
+
+fn write_failed(transactional_id: &str, err: io::Error) -> ErrorCode {
+    error!(transactional_id, error = %err, "Failed to write transaction state");
+    ErrorCode::NotCoordinator
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::coordinator::{OffsetAndMetadata, TxnOffsetCommitParams};
+    use crate::kafka::record::{Record, TRANSACTIONAL_FLAG};
+    use crate::kafka::storage::PartitionLog;
+    use std::path::Path;
+
+    const TIMEOUT_MS: i32 = 60_000;
+
+    fn start(dir: &Path) -> (Arc<LogManager>, Arc<GroupCoordinator>, TransactionCoordinator) {
+        let config = ServerConfig { log_dirs: vec![dir.to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        log_manager.get_or_create_log(&TopicPartition::new("foo", 0)).unwrap();
+        let group_coordinator = Arc::new(GroupCoordinator::new(config.clone(), log_manager.clone()));
+        group_coordinator.load().unwrap();
+        let producer_id_manager = ProducerIdManager::load(dir).unwrap();
+        let coordinator =
+            TransactionCoordinator::new(config, log_manager.clone(), group_coordinator.clone(), producer_id_manager);
+        coordinator.load().unwrap();
+        (log_manager, group_coordinator, coordinator)
+    }
+
+    fn produce(log_manager: &LogManager, producer_id: i64, producer_epoch: i16, sequence: i32) {
+        let mut batch = RecordBatch::new(vec![Record::new(None, Some(b"value".to_vec()))], 1_000);
+        batch.header.attributes = TRANSACTIONAL_FLAG;
+        batch.header.producer_id = producer_id;
+        batch.header.producer_epoch = producer_epoch;
+        batch.header.base_sequence = sequence;
+        let log = log_manager.get_log(&TopicPartition::new("foo", 0)).unwrap();
+        log.lock().unwrap().append(batch.to_bytes(), &log_manager.log_config("foo")).unwrap();
+    }
+
+    fn with_foo<T>(log_manager: &LogManager, f: impl FnOnce(&PartitionLog) -> T) -> T {
+        let log = log_manager.get_log(&TopicPartition::new("foo", 0)).unwrap();
+        let log = log.lock().unwrap();
+        f(&log)
+    }
+
+    #[test]
+    fn test_commit_exposes_data_and_offsets() {
+        let dir = tempfile::tempdir().unwrap();
+        let (log_manager, group_coordinator, coordinator) = start(dir.path());
+        let foo = TopicPartition::new("foo", 0);
+
+        let (producer_id, epoch) = coordinator.init_producer_id(Some("txn"), TIMEOUT_MS, None).unwrap();
+        let added = coordinator.add_partitions("txn", producer_id, epoch, vec![foo.clone(), TopicPartition::new("bar", 0)]);
+        assert_eq!(
+            added,
+            vec![
+                (foo.clone(), ErrorCode::OperationNotAttempted),
+                (TopicPartition::new("bar", 0), ErrorCode::UnknownTopicOrPartition)
+            ]
+        );
+        let added = coordinator.add_partitions("txn", producer_id, epoch, vec![foo.clone()]);
+        assert_eq!(added, vec![(foo.clone(), ErrorCode::None)]);
+        produce(&log_manager, producer_id, epoch, 0);
+        assert_eq!(with_foo(&log_manager, PartitionLog::last_stable_offset), 0);
+
+        assert_eq!(coordinator.add_offsets("txn", producer_id, epoch, "group"), ErrorCode::None);
+        let offset = OffsetAndMetadata { offset: 1, leader_epoch: -1, metadata: String::new(), commit_timestamp: 1_000 };
+        let errors = group_coordinator.commit_transactional_offsets(TxnOffsetCommitParams {
+            group_id: "group".to_owned(),
+            producer_id,
+            producer_epoch: epoch,
+            generation_id: -1,
+            member_id: String::new(),
+            group_instance_id: None,
+            offsets: vec![(foo.clone(), offset.clone())],
+        });
+        assert_eq!(errors, vec![ErrorCode::None]);
+        assert_eq!(group_coordinator.fetch_offsets("group", None), vec![]);
+
+        assert_eq!(coordinator.end_txn("txn", producer_id, epoch, true), ErrorCode::None);
+        assert_eq!(coordinator.end_txn("txn", producer_id, epoch, true), ErrorCode::None);
+        assert_eq!(coordinator.end_txn("txn", producer_id, epoch, false), ErrorCode::InvalidTxnState);
+        assert_eq!(with_foo(&log_manager, PartitionLog::last_stable_offset), 2);
+        assert_eq!(group_coordinator.fetch_offsets("group", None), vec![(foo, Some(offset))]);
+    }
+
+    #[test]
+    fn test_timed_out_transaction_is_aborted_and_fenced() {
+        let dir = tempfile::tempdir().unwrap();
+        let foo = TopicPartition::new("foo", 0);
+        let (producer_id, epoch) = {
+            let (log_manager, _, coordinator) = start(dir.path());
+            let (producer_id, epoch) = coordinator.init_producer_id(Some("txn"), 1_000, None).unwrap();
+            coordinator.add_partitions("txn", producer_id, epoch, vec![foo.clone()]);
+            produce(&log_manager, producer_id, epoch, 0);
+
+            coordinator.tick(now_ms() + 2_000);
+            assert_eq!(coordinator.end_txn("txn", producer_id, epoch, true), ErrorCode::ProducerFenced);
+            let aborted = with_foo(&log_manager, |log| log.aborted_transactions(0, log.log_end_offset()));
+            assert_eq!(aborted.len(), 1);
+            assert_eq!((aborted[0].producer_id, aborted[0].first_offset), (producer_id, 0));
+            (producer_id, epoch)
+        };
+
+        let (_, _, coordinator) = start(dir.path());
+        let added = coordinator.add_partitions("txn", producer_id, epoch, vec![foo.clone()]);
+        assert_eq!(added, vec![(foo, ErrorCode::ProducerFenced)]);
+        assert_eq!(coordinator.init_producer_id(Some("txn"), TIMEOUT_MS, None), Ok((producer_id, epoch + 2)));
+    }
+
+    #[test]
+    fn test_list_transactions_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, _, coordinator) = start(dir.path());
+        let foo = TopicPartition::new("foo", 0);
+        let (ongoing_id, epoch) = coordinator.init_producer_id(Some("ongoing"), TIMEOUT_MS, None).unwrap();
+        coordinator.add_partitions("ongoing", ongoing_id, epoch, vec![foo.clone()]);
+        let (empty_id, _) = coordinator.init_producer_id(Some("empty"), TIMEOUT_MS, None).unwrap();
+
+        let ids = |listings: Vec<TransactionMetadata>| {
+            listings.into_iter().map(|metadata| metadata.transactional_id).collect::<Vec<_>>()
+        };
+        let now_ms = now_ms();
+        assert_eq!(ids(coordinator.list_transactions(&[], &[], -1, now_ms)), ["empty", "ongoing"]);
+        assert_eq!(ids(coordinator.list_transactions(&[TransactionState::Ongoing], &[], -1, now_ms)), ["ongoing"]);
+        assert_eq!(ids(coordinator.list_transactions(&[], &[empty_id], -1, now_ms)), ["empty"]);
+        assert!(coordinator.list_transactions(&[TransactionState::Ongoing], &[], 60_000, now_ms).is_empty());
+
+        let described = coordinator.describe_transaction("ongoing").unwrap();
+        assert_eq!(described.state, TransactionState::Ongoing);
+        assert_eq!(described.partitions.into_iter().collect::<Vec<_>>(), vec![foo]);
+        assert_eq!(coordinator.describe_transaction("missing"), Err(ErrorCode::TransactionalIdNotFound));
+    }
+}
@@ -0,0 +1,12 @@
+mod records;
+pub(crate) use records::*;
+mod image;
+pub(crate) use image::*;
+mod writer;
+pub(crate) use writer::*;
+mod epoch;
+pub(crate) use epoch::*;
+mod cluster_control;
+pub(crate) use cluster_control::*;
+
+pub(crate) const CLUSTER_METADATA_TOPIC: &str = "__cluster_metadata";
@@ -0,0 +1,434 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::metadata::{
+    append_to_metadata_log, metadata_log, BrokerEndpoint, BrokerFeature, FenceBrokerRecord, RegisterBrokerRecord,
+    UnfenceBrokerRecord, CLUSTER_METADATA_TOPIC,
+};
+use crate::kafka::storage::{LogManager, PartitionLog};
+use crate::kafka::types::{ErrorCode, Uuid};
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+const METADATA_VERSION_FEATURE: &str = "metadata.version";
+/// The `metadata.version` levels whose records the broker reads, 3.0-IV1
+/// through 3.5-IV2.
+const MIN_METADATA_VERSION: i16 = 1;
+const MAX_METADATA_VERSION: i16 = 11;
+/// How often sessions are checked for expiry.
+const SESSION_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum BrokerLifecycleError {
+    #[error("broker {0} is registered by another incarnation that is still heartbeating")]
+    DuplicateRegistration(i32),
+    #[error("broker {0} is not registered")]
+    NotRegistered(i32),
+    #[error("broker {broker_id} is not registered in epoch {epoch}")]
+    StaleEpoch { broker_id: i32, epoch: i64 },
+    #[error("Failed to write broker records: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl BrokerLifecycleError {
+    pub(crate) fn error_code(&self) -> ErrorCode {
+        match self {
+            BrokerLifecycleError::DuplicateRegistration(_) => ErrorCode::DuplicateBrokerRegistration,
+            BrokerLifecycleError::NotRegistered(_) => ErrorCode::BrokerIdNotRegistered,
+            BrokerLifecycleError::StaleEpoch { .. } => ErrorCode::StaleBrokerEpoch,
+            BrokerLifecycleError::Io(_) => ErrorCode::UnknownServerError,
+        }
+    }
+}
+
+/// The registration of a broker with the controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BrokerRegistration {
+    /// Identifies the run of the broker that registered.
+    pub(crate) incarnation_id: Uuid,
+    /// The offset of the registration record in the metadata log.
+    pub(crate) epoch: i64,
+    /// Fenced brokers are left out of the metadata handed to clients.
+    pub(crate) fenced: bool,
+}
+
+/// A BrokerRegistration request as seen by the controller.
+#[derive(Debug)]
+pub(crate) struct RegisterBrokerParams {
+    pub(crate) broker_id: i32,
+    pub(crate) incarnation_id: Uuid,
+    pub(crate) end_points: Vec<BrokerEndpoint>,
+    pub(crate) features: Vec<BrokerFeature>,
+    pub(crate) rack: Option<String>,
+}
+
+impl RegisterBrokerParams {
+    /// A new incarnation of the broker of this node, with its advertised
+    /// listeners and the features it supports.
+    pub(crate) fn local(config: &ServerConfig) -> Self {
+        let end_points = config
+            .listeners
+            .iter()
+            .filter_map(|listener| config.advertised_endpoint(&listener.listener_name))
+            .map(|endpoint| {
+                let protocol = config.security_protocol(&endpoint.listener_name);
+                let protocol = protocol.expect("listener protocols are validated");
+                BrokerEndpoint::new(&endpoint.listener_name, &endpoint.host, endpoint.port, protocol.id())
+            })
+            .collect();
+        Self {
+            broker_id: config.node_id,
+            incarnation_id: Uuid::random(),
+            end_points,
+            features: vec![BrokerFeature::new(METADATA_VERSION_FEATURE, MIN_METADATA_VERSION, MAX_METADATA_VERSION)],
+            rack: None,
+        }
+    }
+}
+
+/// A BrokerHeartbeat request as seen by the controller.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BrokerHeartbeatParams {
+    pub(crate) broker_id: i32,
+    pub(crate) broker_epoch: i64,
+    /// The highest metadata offset the broker has applied.
+    pub(crate) current_metadata_offset: i64,
+    pub(crate) want_fence: bool,
+    pub(crate) want_shut_down: bool,
+}
+
+/// The state of a broker after a heartbeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BrokerHeartbeatState {
+    pub(crate) is_caught_up: bool,
+    pub(crate) is_fenced: bool,
+    /// The broker has been shut down in a controlled way and may exit.
+    pub(crate) should_shut_down: bool,
+}
+
+/// The heartbeat session of a registered broker.
+#[derive(Debug, Clone, Copy)]
+struct BrokerSession {
+    last_contact: Instant,
+    /// Set once the broker asked to shut down, it stays fenced from then on.
+    shutting_down: bool,
+}
+
+/// The brokers registered with the controller, like Kafka's
+/// `ClusterControlManager` and `BrokerHeartbeatManager`. Brokers register
+/// fenced, are unfenced by a heartbeat once they have caught up with the
+/// metadata log, and are fenced again when they ask to shut down or stop
+/// heartbeating for `broker.session.timeout.ms`.
+///
+/// Writers lock the metadata log first, then the registrations, then the
+/// sessions, so that a broker epoch is allocated and written atomically.
+#[derive(Debug)]
+pub(crate) struct ClusterControl {
+    log_manager: Arc<LogManager>,
+    session_timeout: Duration,
+    brokers: RwLock<HashMap<i32, BrokerRegistration>>,
+    sessions: Mutex<HashMap<i32, BrokerSession>>,
+}
+
+impl ClusterControl {
+    /// Starts a session for every unfenced broker, so brokers that no
+    /// longer heartbeat are fenced once it expires. The earlier run of this
+    /// node's broker is gone, so its registration gets none and the new
+    /// incarnation can replace it straight away.
+    pub(crate) fn new(
+        log_manager: Arc<LogManager>,
+        brokers: HashMap<i32, BrokerRegistration>,
+        config: &ServerConfig,
+    ) -> Self {
+        let session = BrokerSession { last_contact: Instant::now(), shutting_down: false };
+        let sessions = brokers
+            .iter()
+            .filter(|&(&broker_id, registration)| broker_id != config.node_id && !registration.fenced)
+            .map(|(&broker_id, _)| (broker_id, session))
+            .collect();
+        Self {
+            log_manager,
+            session_timeout: Duration::from_millis(config.broker_session_timeout_ms),
+            brokers: RwLock::new(brokers),
+            sessions: Mutex::new(sessions),
+        }
+    }
+
+    /// Fences the brokers whose session expired every
+    /// [`SESSION_CHECK_INTERVAL`].
+    pub(crate) async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(SESSION_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.fence_expired_sessions(Instant::now()) {
+                error!(error = %err, "Fencing brokers with expired sessions failed");
+            }
+        }
+    }
+
+    /// Registers a new incarnation of a broker, fenced until it has caught
+    /// up with the metadata log. Returns its broker epoch, the offset of the
+    /// registration record. Another incarnation of the broker may only
+    /// replace a registration that is fenced or whose session has expired.
+    pub(crate) fn register(&self, params: RegisterBrokerParams, now: Instant) -> Result<i64, BrokerLifecycleError> {
+        let log = metadata_log(&self.log_manager)?;
+        let mut log = log.lock().expect("partition log lock poisoned");
+        let mut brokers = self.brokers();
+        let mut sessions = self.sessions();
+        if let Some(registration) = brokers.get(&params.broker_id) {
+            let live = !registration.fenced
+                && sessions.get(&params.broker_id).is_some_and(|session| !self.expired(session, now));
+            if live && registration.incarnation_id != params.incarnation_id {
+                return Err(BrokerLifecycleError::DuplicateRegistration(params.broker_id));
+            }
+        }
+
+        let epoch = log.log_end_offset();
+        let record = RegisterBrokerRecord::new(
+            params.broker_id,
+            params.incarnation_id,
+            epoch,
+            params.end_points,
+            params.features,
+            params.rack,
+            true,
+        );
+        self.append(&mut log, record.to_value())?;
+        let registration = BrokerRegistration { incarnation_id: params.incarnation_id, epoch, fenced: true };
+        brokers.insert(params.broker_id, registration);
+        sessions.insert(params.broker_id, BrokerSession { last_contact: now, shutting_down: false });
+        info!(broker_id = params.broker_id, broker_epoch = epoch, "Registered broker");
+        Ok(epoch)
+    }
+
+    /// Renews the session of a broker and moves it towards the state it
+    /// asks for: unfenced once caught up, or fenced for good once it asks to
+    /// shut down. With no partitions to move off it, a broker that asked to
+    /// shut down may do so straight away.
+    pub(crate) fn heartbeat(
+        &self,
+        params: BrokerHeartbeatParams,
+        now: Instant,
+    ) -> Result<BrokerHeartbeatState, BrokerLifecycleError> {
+        let broker_id = params.broker_id;
+        let log = metadata_log(&self.log_manager)?;
+        let mut log = log.lock().expect("partition log lock poisoned");
+        let mut brokers = self.brokers();
+        let mut sessions = self.sessions();
+        let registration = brokers.get_mut(&broker_id).ok_or(BrokerLifecycleError::NotRegistered(broker_id))?;
+        if registration.epoch != params.broker_epoch {
+            return Err(BrokerLifecycleError::StaleEpoch { broker_id, epoch: params.broker_epoch });
+        }
+        let session = sessions.entry(broker_id).or_insert(BrokerSession { last_contact: now, shutting_down: false });
+        session.last_contact = now;
+        session.shutting_down |= params.want_shut_down;
+
+        let is_caught_up = params.current_metadata_offset >= registration.epoch;
+        let fenced = params.want_fence || session.shutting_down || (registration.fenced && !is_caught_up);
+        if fenced != registration.fenced {
+            let record = if fenced {
+                FenceBrokerRecord::new(broker_id, registration.epoch).to_value()
+            } else {
+                UnfenceBrokerRecord::new(broker_id, registration.epoch).to_value()
+            };
+            self.append(&mut log, record)?;
+            registration.fenced = fenced;
+            info!(broker_id, broker_epoch = registration.epoch, fenced, "Changed broker fencing");
+        }
+        Ok(BrokerHeartbeatState { is_caught_up, is_fenced: fenced, should_shut_down: session.shutting_down })
+    }
+
+    /// Fences the unfenced brokers not heard from for the session timeout.
+    /// Returns their ids.
+    pub(crate) fn fence_expired_sessions(&self, now: Instant) -> io::Result<Vec<i32>> {
+        let log = metadata_log(&self.log_manager)?;
+        let mut log = log.lock().expect("partition log lock poisoned");
+        let mut brokers = self.brokers();
+        let sessions = self.sessions();
+        let mut fenced = Vec::new();
+        for (&broker_id, registration) in brokers.iter_mut() {
+            if registration.fenced || !sessions.get(&broker_id).is_some_and(|session| self.expired(session, now)) {
+                continue;
+            }
+            self.append(&mut log, FenceBrokerRecord::new(broker_id, registration.epoch).to_value())?;
+            registration.fenced = true;
+            info!(broker_id, broker_epoch = registration.epoch, "Fenced broker after its session expired");
+            fenced.push(broker_id);
+        }
+        Ok(fenced)
+    }
+
+    /// Whether the broker is fenced. Unregistered brokers are.
+    pub(crate) fn is_fenced(&self, broker_id: i32) -> bool {
+        let brokers = self.brokers.read().expect("broker registrations lock poisoned");
+        brokers.get(&broker_id).is_none_or(|registration| registration.fenced)
+    }
+
+    /// The end of the metadata log. The broker of this node applies records
+    /// as they are written, so it has always caught up to here.
+    pub(crate) fn metadata_end_offset(&self) -> io::Result<i64> {
+        let log = metadata_log(&self.log_manager)?;
+        let log_end_offset = log.lock().expect("partition log lock poisoned").log_end_offset();
+        Ok(log_end_offset)
+    }
+
+    fn expired(&self, session: &BrokerSession, now: Instant) -> bool {
+        now.saturating_duration_since(session.last_contact) >= self.session_timeout
+    }
+
+    fn append(&self, log: &mut PartitionLog, record: Vec<u8>) -> io::Result<()> {
+        append_to_metadata_log(log, &self.log_manager.log_config(CLUSTER_METADATA_TOPIC), vec![record])
+    }
+
+    fn brokers(&self) -> RwLockWriteGuard<'_, HashMap<i32, BrokerRegistration>> {
+        self.brokers.write().expect("broker registrations lock poisoned")
+    }
+
+    fn sessions(&self) -> MutexGuard<'_, HashMap<i32, BrokerSession>> {
+        self.sessions.lock().expect("broker sessions lock poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::metadata::MetadataImage;
+
+    /// The default `broker.session.timeout.ms`.
+    const SESSION_TIMEOUT: Duration = Duration::from_secs(9);
+
+    fn cluster_control(config: &ServerConfig) -> (Arc<LogManager>, ClusterControl) {
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        let cluster_control = ClusterControl::new(log_manager.clone(), HashMap::new(), config);
+        (log_manager, cluster_control)
+    }
+
+    fn heartbeat(broker_id: i32, broker_epoch: i64, current_metadata_offset: i64) -> BrokerHeartbeatParams {
+        BrokerHeartbeatParams {
+            broker_id,
+            broker_epoch,
+            current_metadata_offset,
+            want_fence: false,
+            want_shut_down: false,
+        }
+    }
+
+    fn registration(broker_id: i32) -> RegisterBrokerParams {
+        RegisterBrokerParams { broker_id, ..RegisterBrokerParams::local(&ServerConfig::default()) }
+    }
+
+    #[test]
+    fn test_registers_fenced_until_caught_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let (log_manager, cluster_control) = cluster_control(&config);
+        let now = Instant::now();
+        assert!(cluster_control.is_fenced(1));
+
+        let params = RegisterBrokerParams::local(&config);
+        let incarnation_id = params.incarnation_id;
+        let epoch = cluster_control.register(params, now).unwrap();
+        assert!(cluster_control.is_fenced(1));
+        let state = cluster_control.heartbeat(heartbeat(1, epoch, epoch - 1), now).unwrap();
+        assert_eq!(state, BrokerHeartbeatState { is_caught_up: false, is_fenced: true, should_shut_down: false });
+        let state = cluster_control.heartbeat(heartbeat(1, epoch, epoch), now).unwrap();
+        assert_eq!(state, BrokerHeartbeatState { is_caught_up: true, is_fenced: false, should_shut_down: false });
+        assert!(!cluster_control.is_fenced(1));
+        let image = MetadataImage::load(&log_manager).unwrap();
+        assert_eq!(image.brokers()[&1], BrokerRegistration { incarnation_id, epoch, fenced: false });
+
+        // A stale epoch leaves the new incarnation alone.
+        let params = RegisterBrokerParams { incarnation_id, ..RegisterBrokerParams::local(&config) };
+        let new_epoch = cluster_control.register(params, now).unwrap();
+        let end_offset = cluster_control.metadata_end_offset().unwrap();
+        cluster_control.heartbeat(heartbeat(1, new_epoch, end_offset), now).unwrap();
+        let stale = cluster_control.heartbeat(heartbeat(1, epoch, end_offset), now).unwrap_err();
+        assert_eq!(stale.error_code(), ErrorCode::StaleBrokerEpoch);
+        assert!(!cluster_control.is_fenced(1));
+        let unknown = cluster_control.heartbeat(heartbeat(2, new_epoch, end_offset), now).unwrap_err();
+        assert_eq!(unknown.error_code(), ErrorCode::BrokerIdNotRegistered);
+    }
+
+    #[test]
+    fn test_controlled_shutdown_keeps_broker_fenced() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let (log_manager, cluster_control) = cluster_control(&config);
+        let now = Instant::now();
+        let epoch = cluster_control.register(registration(1), now).unwrap();
+        cluster_control.heartbeat(heartbeat(1, epoch, epoch), now).unwrap();
+
+        let shut_down = BrokerHeartbeatParams { want_shut_down: true, ..heartbeat(1, epoch, epoch) };
+        let state = cluster_control.heartbeat(shut_down, now).unwrap();
+        assert_eq!(state, BrokerHeartbeatState { is_caught_up: true, is_fenced: true, should_shut_down: true });
+        let state = cluster_control.heartbeat(heartbeat(1, epoch, epoch), now).unwrap();
+        assert!(state.is_fenced && state.should_shut_down);
+        let image = MetadataImage::load(&log_manager).unwrap();
+        assert!(image.brokers()[&1].fenced);
+    }
+
+    #[test]
+    fn test_fences_brokers_after_session_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let (log_manager, cluster_control) = cluster_control(&config);
+        let now = Instant::now();
+        let first = cluster_control.register(registration(1), now).unwrap();
+        let second = cluster_control.register(registration(2), now).unwrap();
+        cluster_control.heartbeat(heartbeat(1, first, second), now).unwrap();
+        cluster_control.heartbeat(heartbeat(2, second, second), now).unwrap();
+
+        let later = now + SESSION_TIMEOUT / 2;
+        cluster_control.heartbeat(heartbeat(2, second, second), later).unwrap();
+        assert!(cluster_control.fence_expired_sessions(later).unwrap().is_empty());
+        assert_eq!(cluster_control.fence_expired_sessions(now + SESSION_TIMEOUT).unwrap(), vec![1]);
+        assert!(cluster_control.is_fenced(1));
+        assert!(!cluster_control.is_fenced(2));
+        let image = MetadataImage::load(&log_manager).unwrap();
+        assert!(image.brokers()[&1].fenced);
+
+        // Another incarnation may only take over an expired registration.
+        let duplicate = cluster_control.register(registration(2), later).unwrap_err();
+        assert_eq!(duplicate.error_code(), ErrorCode::DuplicateBrokerRegistration);
+        cluster_control.register(registration(1), now + SESSION_TIMEOUT).unwrap();
+    }
+
+    #[test]
+    fn test_restarted_node_replaces_its_registration() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let (log_manager, cluster_control) = cluster_control(&config);
+        let now = Instant::now();
+        for broker_id in [1, 2] {
+            let epoch = cluster_control.register(registration(broker_id), now).unwrap();
+            cluster_control.heartbeat(heartbeat(broker_id, epoch, i64::MAX), now).unwrap();
+        }
+
+        // Both stay unfenced across the crash, but only the other broker is
+        // still heartbeating.
+        let image = MetadataImage::load(&log_manager).unwrap();
+        let cluster_control = ClusterControl::new(log_manager.clone(), image.brokers().clone(), &config);
+        cluster_control.register(RegisterBrokerParams::local(&config), Instant::now()).unwrap();
+        let duplicate = cluster_control.register(registration(2), Instant::now()).unwrap_err();
+        assert_eq!(duplicate.error_code(), ErrorCode::DuplicateBrokerRegistration);
+    }
+
+    #[test]
+    fn test_concurrent_registrations_get_distinct_epochs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let (_log_manager, cluster_control) = cluster_control(&config);
+        let cluster_control = Arc::new(cluster_control);
+        let registrations: Vec<_> = (0..8)
+            .map(|broker_id| {
+                let cluster_control = cluster_control.clone();
+                std::thread::spawn(move || cluster_control.register(registration(broker_id), Instant::now()).unwrap())
+            })
+            .collect();
+        let mut epochs: Vec<_> = registrations.into_iter().map(|thread| thread.join().unwrap()).collect();
+        epochs.sort();
+        epochs.dedup();
+        assert_eq!(epochs.len(), 8);
+    }
+}
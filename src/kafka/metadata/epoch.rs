@@ -0,0 +1,74 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::metadata::CLUSTER_METADATA_TOPIC;
+use crate::kafka::record::LeaderChangeMessage;
+use crate::kafka::storage::{ElectionState, LogManager, QuorumStateFile, TopicPartition};
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The leader epoch of `__cluster_metadata-0` on a combined node. This is
+/// not a KRaft quorum: there are no other voters, elections or Raft
+/// requests. The node is the only writer of its metadata log and starts a
+/// new epoch whenever it starts, so that the records of each run are told
+/// apart by their epoch.
+#[derive(Debug)]
+pub(crate) struct MetadataEpoch {
+    leader_id: i32,
+    epoch: i32,
+}
+
+impl MetadataEpoch {
+    /// Starts an epoch led by this node, past both the epoch in the
+    /// `quorum-state` file and the latest epoch in the log, with a
+    /// LeaderChange control record. Metadata records appended afterwards are
+    /// written in that epoch.
+    pub(crate) fn start(config: &ServerConfig, log_manager: &LogManager) -> io::Result<Self> {
+        let log = log_manager.get_or_create_log(&TopicPartition::new(CLUSTER_METADATA_TOPIC, 0))?;
+        let mut log = log.lock().expect("partition log lock poisoned");
+        let file = QuorumStateFile::new(log.dir());
+        let stored_epoch = file.read()?.map_or(0, |state| state.epoch);
+        let epoch = stored_epoch.max(log.latest_epoch().unwrap_or(0)) + 1;
+
+        let leader_id = config.node_id;
+        file.write(&ElectionState { epoch, leader_id, voted_id: leader_id })?;
+        let message = LeaderChangeMessage { leader_id, voters: vec![leader_id], granting_voters: vec![leader_id] };
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+        let config = log_manager.log_config(CLUSTER_METADATA_TOPIC);
+        log.append(message.to_batch(epoch, now_ms).to_bytes(), &config).map_err(io::Error::other)?;
+        log.flush()?;
+        Ok(Self { leader_id, epoch })
+    }
+
+    pub(crate) fn leader_id(&self) -> i32 {
+        self.leader_id
+    }
+
+    pub(crate) fn epoch(&self) -> i32 {
+        self.epoch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::metadata::{append_metadata_records, ConfigRecord, ConfigResourceType};
+
+    #[test]
+    fn test_every_start_is_a_new_epoch() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = LogManager::startup(config.clone()).unwrap();
+        assert_eq!(MetadataEpoch::start(&config, &log_manager).unwrap().epoch(), 1);
+        let metadata_epoch = MetadataEpoch::start(&config, &log_manager).unwrap();
+        assert_eq!((metadata_epoch.leader_id(), metadata_epoch.epoch()), (1, 2));
+
+        let record = ConfigRecord::new(ConfigResourceType::Topic, "orders", "retention.ms", Some("1000".to_owned()));
+        append_metadata_records(&log_manager, vec![record.to_value()]).unwrap();
+        let log = log_manager.get_log(&TopicPartition::new(CLUSTER_METADATA_TOPIC, 0)).unwrap();
+        let log = log.lock().unwrap();
+        assert_eq!(log.end_offset_for_epoch(2), (2, 3));
+        assert_eq!(log.recovery_point(), 3);
+
+        let state = QuorumStateFile::new(log.dir()).read().unwrap();
+        assert_eq!(state, Some(ElectionState { epoch: 2, leader_id: 1, voted_id: 1 }));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::kafka::metadata::{BrokerRegistration, ConfigResourceType, MetadataRecord, CLUSTER_METADATA_TOPIC};
+use crate::kafka::quota::QuotaEntity;
+use crate::kafka::record::RecordBatch;
+use crate::kafka::security::{ScramCredential, ScramMechanism, StandardAcl, MIN_SCRAM_ITERATIONS};
+use crate::kafka::storage::{LogManager, TopicPartition};
+use crate::kafka::types::Uuid;
+use std::collections::HashMap;
+use std::io;
+use tracing::warn;
+
+/// The broker's view of cluster metadata, built by replaying the records of
+/// the `__cluster_metadata` log.
+#[derive(Debug, Default)]
+pub(crate) struct MetadataImage {
+    brokers: HashMap<i32, BrokerRegistration>,
+    topic_configs: HashMap<String, HashMap<String, String>>,
+    broker_configs: HashMap<String, HashMap<String, String>>,
+    client_quotas: HashMap<QuotaEntity, HashMap<String, f64>>,
+    scram_credentials: HashMap<(ScramMechanism, String), ScramCredential>,
+    acls: HashMap<Uuid, StandardAcl>,
+}
+
+impl MetadataImage {
+    /// Replays the local `__cluster_metadata-0` log, if there is one.
+    pub(crate) fn load(log_manager: &LogManager) -> io::Result<Self> {
+        let mut image = Self::default();
+        let Some(log) = log_manager.get_log(&TopicPartition::new(CLUSTER_METADATA_TOPIC, 0)) else {
+            return Ok(image);
+        };
+        let log = log.lock().expect("partition log lock poisoned");
+
+        for batch in log.batches(0) {
+            let batch = batch.map_err(io::Error::other)?;
+            if batch.header.is_control() {
+                continue;
+            }
+            let batch = RecordBatch::parse(batch.header, &batch.bytes).map_err(io::Error::other)?;
+            for value in batch.records.into_iter().filter_map(|record| record.value) {
+                match MetadataRecord::parse(&value) {
+                    Ok(Some(record)) => image.replay(record),
+                    Ok(None) => {}
+                    Err(err) => warn!(error = %err, "Skipping undecodable metadata record"),
+                }
+            }
+        }
+        Ok(image)
+    }
+
+    pub(crate) fn replay(&mut self, record: MetadataRecord) {
+        match record {
+            MetadataRecord::RegisterBroker(record) => {
+                let registration = BrokerRegistration {
+                    incarnation_id: record.incarnation_id,
+                    epoch: record.broker_epoch,
+                    fenced: record.fenced,
+                };
+                self.brokers.insert(record.broker_id, registration);
+            }
+            MetadataRecord::FenceBroker(record) => self.set_fenced(record.id, record.epoch, true),
+            MetadataRecord::UnfenceBroker(record) => self.set_fenced(record.id, record.epoch, false),
+            MetadataRecord::Config(record) => {
+                let resources = match record.resource_type {
+                    ConfigResourceType::Topic => &mut self.topic_configs,
+                    ConfigResourceType::Broker => &mut self.broker_configs,
+                };
+                let configs = resources.entry(record.resource_name.0).or_default();
+                match record.value.0 {
+                    Some(value) => configs.insert(record.name.0, value),
+                    None => configs.remove(&record.name.0),
+                };
+            }
+            MetadataRecord::ClientQuota(record) => {
+                let Some(entity) = record.to_entity() else {
+                    warn!(entity = ?record.entity, "Skipping client quota of unknown entity");
+                    return;
+                };
+                if record.remove {
+                    if let Some(values) = self.client_quotas.get_mut(&entity) {
+                        values.remove(&record.key.0);
+                        if values.is_empty() {
+                            self.client_quotas.remove(&entity);
+                        }
+                    }
+                } else {
+                    self.client_quotas.entry(entity).or_default().insert(record.key.0, record.value);
+                }
+            }
+            MetadataRecord::UserScramCredential(record) => {
+                let Some(mechanism) = ScramMechanism::from_type(record.mechanism) else {
+                    warn!(user = record.name.0, mechanism = record.mechanism, "Skipping unknown SCRAM mechanism");
+                    return;
+                };
+                if record.iterations < MIN_SCRAM_ITERATIONS {
+                    warn!(
+                        user = record.name.0,
+                        iterations = record.iterations,
+                        "Skipping SCRAM credential with too few iterations"
+                    );
+                    return;
+                }
+                let credential = ScramCredential {
+                    salt: record.salt.0,
+                    stored_key: record.stored_key.0,
+                    server_key: record.server_key.0,
+                    iterations: record.iterations,
+                };
+                self.scram_credentials.insert((mechanism, record.name.0), credential);
+            }
+            MetadataRecord::RemoveUserScramCredential(record) => {
+                if let Some(mechanism) = ScramMechanism::from_type(record.mechanism) {
+                    self.scram_credentials.remove(&(mechanism, record.name.0));
+                }
+            }
+            MetadataRecord::AccessControlEntry(record) => match record.to_acl() {
+                Some(acl) => {
+                    self.acls.insert(record.id, acl);
+                }
+                None => warn!(id = %record.id, "Skipping invalid ACL"),
+            },
+            MetadataRecord::RemoveAccessControlEntry(record) => {
+                self.acls.remove(&record.id);
+            }
+        }
+    }
+
+    /// Fences or unfences a registration, unless the broker has registered
+    /// again since.
+    fn set_fenced(&mut self, broker_id: i32, epoch: i64, fenced: bool) {
+        match self.brokers.get_mut(&broker_id) {
+            Some(registration) if registration.epoch == epoch => registration.fenced = fenced,
+            _ => warn!(broker_id, epoch, "Skipping fencing change of an unknown broker epoch"),
+        }
+    }
+
+    /// The registered brokers, keyed by id.
+    pub(crate) fn brokers(&self) -> &HashMap<i32, BrokerRegistration> {
+        &self.brokers
+    }
+
+    /// Topic-level config overrides, keyed by topic name.
+    pub(crate) fn topic_configs(&self) -> &HashMap<String, HashMap<String, String>> {
+        &self.topic_configs
+    }
+
+    /// Dynamic broker configs, keyed by broker id, the empty id holding the
+    /// defaults of all brokers.
+    pub(crate) fn broker_configs(&self) -> &HashMap<String, HashMap<String, String>> {
+        &self.broker_configs
+    }
+
+    /// Client quotas set with AlterClientQuotas, keyed by entity and quota.
+    pub(crate) fn client_quotas(&self) -> &HashMap<QuotaEntity, HashMap<String, f64>> {
+        &self.client_quotas
+    }
+
+    /// SCRAM credentials created with AlterUserScramCredentials, keyed by
+    /// mechanism and user.
+    pub(crate) fn scram_credentials(&self) -> &HashMap<(ScramMechanism, String), ScramCredential> {
+        &self.scram_credentials
+    }
+
+    /// ACLs created with CreateAcls, keyed by id.
+    pub(crate) fn acls(&self) -> &HashMap<Uuid, StandardAcl> {
+        &self.acls
+    }
+}
@@ -0,0 +1,442 @@
+use crate::kafka::quota::QuotaEntity;
+use crate::kafka::security::StandardAcl;
+use crate::kafka::types::{
+    CompactArray, CompactNullableString, CompactString, KafkaBytes, TagBuffer, UnsignedVarInt, Uuid,
+};
+use binrw::{binread, binrw, BinRead, BinResult, BinWrite};
+use std::io::Cursor;
+
+/// The frame version preceding the record type in every record value.
+const FRAME_VERSION: u32 = 1;
+const REGISTER_BROKER_RECORD_TYPE: u32 = 0;
+const CONFIG_RECORD_TYPE: u32 = 4;
+const FENCE_BROKER_RECORD_TYPE: u32 = 7;
+const UNFENCE_BROKER_RECORD_TYPE: u32 = 8;
+const USER_SCRAM_CREDENTIAL_RECORD_TYPE: u32 = 11;
+const CLIENT_QUOTA_RECORD_TYPE: u32 = 14;
+const ACCESS_CONTROL_ENTRY_RECORD_TYPE: u32 = 17;
+const REMOVE_ACCESS_CONTROL_ENTRY_RECORD_TYPE: u32 = 18;
+const REMOVE_USER_SCRAM_CREDENTIAL_RECORD_TYPE: u32 = 22;
+
+/// The metadata log records the broker replays. Other record types are
+/// skipped.
+#[derive(Debug)]
+pub(crate) enum MetadataRecord {
+    RegisterBroker(RegisterBrokerRecord),
+    FenceBroker(FenceBrokerRecord),
+    UnfenceBroker(UnfenceBrokerRecord),
+    Config(ConfigRecord),
+    ClientQuota(ClientQuotaRecord),
+    UserScramCredential(UserScramCredentialRecord),
+    RemoveUserScramCredential(RemoveUserScramCredentialRecord),
+    AccessControlEntry(AccessControlEntryRecord),
+    RemoveAccessControlEntry(RemoveAccessControlEntryRecord),
+}
+
+impl MetadataRecord {
+    /// Decodes a record value: a frame version, the record type and version,
+    /// followed by the record body.
+    pub(crate) fn parse(value: &[u8]) -> BinResult<Option<Self>> {
+        let mut reader = Cursor::new(value);
+        let _frame_version = UnsignedVarInt::read(&mut reader)?;
+        let record_type = *UnsignedVarInt::read(&mut reader)?;
+        let _version = UnsignedVarInt::read(&mut reader)?;
+
+        match record_type {
+            REGISTER_BROKER_RECORD_TYPE => Ok(Some(Self::RegisterBroker(RegisterBrokerRecord::read(&mut reader)?))),
+            FENCE_BROKER_RECORD_TYPE => Ok(Some(Self::FenceBroker(FenceBrokerRecord::read(&mut reader)?))),
+            UNFENCE_BROKER_RECORD_TYPE => Ok(Some(Self::UnfenceBroker(UnfenceBrokerRecord::read(&mut reader)?))),
+            CONFIG_RECORD_TYPE => Ok(Some(Self::Config(ConfigRecord::read(&mut reader)?))),
+            CLIENT_QUOTA_RECORD_TYPE => Ok(Some(Self::ClientQuota(ClientQuotaRecord::read(&mut reader)?))),
+            USER_SCRAM_CREDENTIAL_RECORD_TYPE => {
+                Ok(Some(Self::UserScramCredential(UserScramCredentialRecord::read(&mut reader)?)))
+            }
+            REMOVE_USER_SCRAM_CREDENTIAL_RECORD_TYPE => {
+                Ok(Some(Self::RemoveUserScramCredential(RemoveUserScramCredentialRecord::read(&mut reader)?)))
+            }
+            ACCESS_CONTROL_ENTRY_RECORD_TYPE => {
+                Ok(Some(Self::AccessControlEntry(AccessControlEntryRecord::read(&mut reader)?)))
+            }
+            REMOVE_ACCESS_CONTROL_ENTRY_RECORD_TYPE => {
+                Ok(Some(Self::RemoveAccessControlEntry(RemoveAccessControlEntryRecord::read(&mut reader)?)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Encodes a version 0 record value, the inverse of [`MetadataRecord::parse`].
+fn encode(record_type: u32, body: &impl for<'a> BinWrite<Args<'a> = ()>) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    for varint in [FRAME_VERSION, record_type, 0] {
+        UnsignedVarInt(varint).write(&mut writer).expect("writing to a Vec cannot fail");
+    }
+    body.write_be(&mut writer).expect("writing to a Vec cannot fail");
+    writer.into_inner()
+}
+
+/// A broker registering with the controller, fenced or not. It replaces the
+/// registration of an earlier incarnation of the broker.
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+pub(crate) struct RegisterBrokerRecord {
+    pub(crate) broker_id: i32,
+    pub(crate) incarnation_id: Uuid,
+    pub(crate) broker_epoch: i64,
+    end_points: CompactArray<BrokerEndpoint>,
+    features: CompactArray<BrokerFeature>,
+    rack: CompactNullableString,
+    #[br(map = |fenced: u8| fenced != 0)]
+    #[bw(map = |fenced: &bool| u8::from(*fenced))]
+    pub(crate) fenced: bool,
+    _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug, Clone)]
+pub(crate) struct BrokerEndpoint {
+    name: CompactString,
+    host: CompactString,
+    port: u16,
+    security_protocol: i16,
+    _tagged_fields: TagBuffer,
+}
+
+/// A feature and the range of its levels a broker supports.
+#[binrw]
+#[brw(big)]
+#[derive(Debug, Clone)]
+pub(crate) struct BrokerFeature {
+    name: CompactString,
+    min_supported_version: i16,
+    max_supported_version: i16,
+    _tagged_fields: TagBuffer,
+}
+
+impl RegisterBrokerRecord {
+    pub(crate) fn new(
+        broker_id: i32,
+        incarnation_id: Uuid,
+        broker_epoch: i64,
+        end_points: Vec<BrokerEndpoint>,
+        features: Vec<BrokerFeature>,
+        rack: Option<String>,
+        fenced: bool,
+    ) -> Self {
+        Self {
+            broker_id,
+            incarnation_id,
+            broker_epoch,
+            end_points: end_points.into(),
+            features: features.into(),
+            rack: CompactNullableString(rack),
+            fenced,
+            _tagged_fields: TagBuffer,
+        }
+    }
+
+    pub(crate) fn to_value(&self) -> Vec<u8> {
+        encode(REGISTER_BROKER_RECORD_TYPE, self)
+    }
+}
+
+impl BrokerEndpoint {
+    pub(crate) fn new(name: &str, host: &str, port: u16, security_protocol: i16) -> Self {
+        Self {
+            name: CompactString(name.to_owned()),
+            host: CompactString(host.to_owned()),
+            port,
+            security_protocol,
+            _tagged_fields: TagBuffer,
+        }
+    }
+}
+
+impl BrokerFeature {
+    pub(crate) fn new(name: &str, min_supported_version: i16, max_supported_version: i16) -> Self {
+        Self {
+            name: CompactString(name.to_owned()),
+            min_supported_version,
+            max_supported_version,
+            _tagged_fields: TagBuffer,
+        }
+    }
+}
+
+/// Fences the registration of broker `id` in broker epoch `epoch`.
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+pub(crate) struct FenceBrokerRecord {
+    pub(crate) id: i32,
+    pub(crate) epoch: i64,
+    _tagged_fields: TagBuffer,
+}
+
+impl FenceBrokerRecord {
+    pub(crate) fn new(id: i32, epoch: i64) -> Self {
+        Self { id, epoch, _tagged_fields: TagBuffer }
+    }
+
+    pub(crate) fn to_value(&self) -> Vec<u8> {
+        encode(FENCE_BROKER_RECORD_TYPE, self)
+    }
+}
+
+/// Unfences the registration of broker `id` in broker epoch `epoch`.
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+pub(crate) struct UnfenceBrokerRecord {
+    pub(crate) id: i32,
+    pub(crate) epoch: i64,
+    _tagged_fields: TagBuffer,
+}
+
+impl UnfenceBrokerRecord {
+    pub(crate) fn new(id: i32, epoch: i64) -> Self {
+        Self { id, epoch, _tagged_fields: TagBuffer }
+    }
+
+    pub(crate) fn to_value(&self) -> Vec<u8> {
+        encode(UNFENCE_BROKER_RECORD_TYPE, self)
+    }
+}
+
+#[binrw]
+#[brw(big, repr = i8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ConfigResourceType {
+    Topic = 2,
+    Broker = 4,
+}
+
+impl ConfigResourceType {
+    /// The resource type of a DescribeConfigs or AlterConfigs resource,
+    /// `None` for types without configs here, such as broker loggers.
+    pub(crate) fn from_code(code: i8) -> Option<Self> {
+        match code {
+            2 => Some(ConfigResourceType::Topic),
+            4 => Some(ConfigResourceType::Broker),
+            _ => None,
+        }
+    }
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+pub(crate) struct ConfigRecord {
+    pub(crate) resource_type: ConfigResourceType,
+    /// The topic name, or for brokers the broker id, empty for the defaults
+    /// of all brokers.
+    pub(crate) resource_name: CompactString,
+    pub(crate) name: CompactString,
+    /// `None` removes the config.
+    pub(crate) value: CompactNullableString,
+    _tagged_fields: TagBuffer,
+}
+
+impl ConfigRecord {
+    pub(crate) fn new(resource_type: ConfigResourceType, resource_name: &str, name: &str, value: Option<String>) -> Self {
+        Self {
+            resource_type,
+            resource_name: CompactString(resource_name.to_owned()),
+            name: CompactString(name.to_owned()),
+            value: CompactNullableString(value),
+            _tagged_fields: TagBuffer,
+        }
+    }
+
+    pub(crate) fn to_value(&self) -> Vec<u8> {
+        encode(CONFIG_RECORD_TYPE, self)
+    }
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+pub(crate) struct ClientQuotaRecord {
+    pub(crate) entity: CompactArray<EntityData>,
+    pub(crate) key: CompactString,
+    pub(crate) value: f64,
+    /// Whether the quota is removed rather than set to `value`.
+    #[br(map = |remove: u8| remove != 0)]
+    #[bw(map = |remove: &bool| u8::from(*remove))]
+    pub(crate) remove: bool,
+    _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug, Clone)]
+pub(crate) struct EntityData {
+    pub(crate) entity_type: CompactString,
+    /// `None` for the default entity of the type.
+    pub(crate) entity_name: CompactNullableString,
+    _tagged_fields: TagBuffer,
+}
+
+impl ClientQuotaRecord {
+    /// A record setting the quota `key` of the entity, or removing it for
+    /// `None`.
+    pub(crate) fn new(entity: &QuotaEntity, key: &str, value: Option<f64>) -> Self {
+        let entity = entity
+            .components()
+            .into_iter()
+            .map(|(entity_type, name)| EntityData {
+                entity_type: CompactString(entity_type.to_owned()),
+                entity_name: CompactNullableString(name.map(str::to_owned)),
+                _tagged_fields: TagBuffer,
+            })
+            .collect::<Vec<_>>();
+        Self {
+            entity: entity.into(),
+            key: CompactString(key.to_owned()),
+            value: value.unwrap_or_default(),
+            remove: value.is_none(),
+            _tagged_fields: TagBuffer,
+        }
+    }
+
+    /// The entity of the record, `None` if it has entity types this broker
+    /// does not know.
+    pub(crate) fn to_entity(&self) -> Option<QuotaEntity> {
+        let entity = self.entity.entries.iter().flatten();
+        QuotaEntity::from_components(entity.map(|data| (data.entity_type.0.as_str(), data.entity_name.0.as_deref())))
+            .ok()
+    }
+
+    pub(crate) fn to_value(&self) -> Vec<u8> {
+        encode(CLIENT_QUOTA_RECORD_TYPE, self)
+    }
+}
+
+#[binread]
+#[br(big)]
+#[derive(Debug)]
+pub(crate) struct UserScramCredentialRecord {
+    pub(crate) name: CompactString,
+    /// 1 for SCRAM-SHA-256, 2 for SCRAM-SHA-512.
+    pub(crate) mechanism: i8,
+    #[br(args(true))]
+    pub(crate) salt: KafkaBytes,
+    #[br(args(true))]
+    pub(crate) stored_key: KafkaBytes,
+    #[br(args(true))]
+    pub(crate) server_key: KafkaBytes,
+    pub(crate) iterations: i32,
+    _tagged_fields: TagBuffer,
+}
+
+#[binread]
+#[br(big)]
+#[derive(Debug)]
+pub(crate) struct RemoveUserScramCredentialRecord {
+    pub(crate) name: CompactString,
+    pub(crate) mechanism: i8,
+    _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+pub(crate) struct AccessControlEntryRecord {
+    pub(crate) id: Uuid,
+    pub(crate) resource_type: i8,
+    pub(crate) resource_name: CompactString,
+    pub(crate) pattern_type: i8,
+    pub(crate) principal: CompactString,
+    pub(crate) host: CompactString,
+    pub(crate) operation: i8,
+    pub(crate) permission_type: i8,
+    _tagged_fields: TagBuffer,
+}
+
+impl AccessControlEntryRecord {
+    pub(crate) fn new(id: Uuid, acl: &StandardAcl) -> Self {
+        Self {
+            id,
+            resource_type: acl.resource_type.code(),
+            resource_name: CompactString(acl.resource_name.clone()),
+            pattern_type: acl.pattern_type.code(),
+            principal: CompactString(acl.principal.clone()),
+            host: CompactString(acl.host.clone()),
+            operation: acl.operation.code(),
+            permission_type: acl.permission_type.code(),
+            _tagged_fields: TagBuffer,
+        }
+    }
+
+    /// The ACL of the record, `None` if it holds codes this broker does not
+    /// know or a binding that is not valid.
+    pub(crate) fn to_acl(&self) -> Option<StandardAcl> {
+        let acl = StandardAcl::from_codes(
+            self.resource_type,
+            self.resource_name.0.clone(),
+            self.pattern_type,
+            self.principal.0.clone(),
+            self.host.0.clone(),
+            self.operation,
+            self.permission_type,
+        )?;
+        acl.validate().ok().map(|()| acl)
+    }
+
+    pub(crate) fn to_value(&self) -> Vec<u8> {
+        encode(ACCESS_CONTROL_ENTRY_RECORD_TYPE, self)
+    }
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+pub(crate) struct RemoveAccessControlEntryRecord {
+    pub(crate) id: Uuid,
+    _tagged_fields: TagBuffer,
+}
+
+impl RemoveAccessControlEntryRecord {
+    pub(crate) fn new(id: Uuid) -> Self {
+        Self { id, _tagged_fields: TagBuffer }
+    }
+
+    pub(crate) fn to_value(&self) -> Vec<u8> {
+        encode(REMOVE_ACCESS_CONTROL_ENTRY_RECORD_TYPE, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::security::{AclOperation, AclPermissionType, PatternType, ResourceType};
+
+    #[test]
+    fn test_access_control_entry_round_trip() {
+        let acl = StandardAcl {
+            resource_type: ResourceType::Topic,
+            resource_name: "orders".to_owned(),
+            pattern_type: PatternType::Prefixed,
+            principal: "User:alice".to_owned(),
+            host: "*".to_owned(),
+            operation: AclOperation::Write,
+            permission_type: AclPermissionType::Allow,
+        };
+        let id = Uuid::random();
+
+        let value = AccessControlEntryRecord::new(id, &acl).to_value();
+        let Some(MetadataRecord::AccessControlEntry(record)) = MetadataRecord::parse(&value).unwrap() else {
+            panic!("expected an access control entry record");
+        };
+        assert_eq!(record.id, id);
+        assert_eq!(record.to_acl(), Some(acl));
+
+        let value = RemoveAccessControlEntryRecord::new(id).to_value();
+        let Some(MetadataRecord::RemoveAccessControlEntry(record)) = MetadataRecord::parse(&value).unwrap() else {
+            panic!("expected a remove access control entry record");
+        };
+        assert_eq!(record.id, id);
+    }
+}
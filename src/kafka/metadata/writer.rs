@@ -0,0 +1,40 @@
+use crate::kafka::metadata::CLUSTER_METADATA_TOPIC;
+use crate::kafka::record::{Record, RecordBatch};
+use crate::kafka::storage::{LogConfig, LogManager, PartitionLog, TopicPartition};
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Appends record values, as encoded by the records' `to_value`, to the
+/// `__cluster_metadata-0` log in one batch, so they are replayed together.
+/// The batch is written in the current epoch of the metadata log and is
+/// committed once flushed.
+pub(crate) fn append_metadata_records(log_manager: &LogManager, values: Vec<Vec<u8>>) -> io::Result<()> {
+    if values.is_empty() {
+        return Ok(());
+    }
+    let log = metadata_log(log_manager)?;
+    let config = log_manager.log_config(CLUSTER_METADATA_TOPIC);
+    let mut log = log.lock().expect("partition log lock poisoned");
+    append_to_metadata_log(&mut log, &config, values)
+}
+
+/// The `__cluster_metadata-0` log, for writers that hold its lock while they
+/// derive records from it.
+pub(crate) fn metadata_log(log_manager: &LogManager) -> io::Result<Arc<Mutex<PartitionLog>>> {
+    log_manager.get_or_create_log(&TopicPartition::new(CLUSTER_METADATA_TOPIC, 0))
+}
+
+/// Like [`append_metadata_records`], to the already locked metadata log.
+pub(crate) fn append_to_metadata_log(
+    log: &mut PartitionLog,
+    config: &LogConfig,
+    values: Vec<Vec<u8>>,
+) -> io::Result<()> {
+    let records = values.into_iter().map(|value| Record::new(None, Some(value))).collect();
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+    let mut batch = RecordBatch::new(records, now_ms);
+    batch.header.partition_leader_epoch = log.latest_epoch().unwrap_or(0);
+    log.append(batch.to_bytes(), config).map_err(io::Error::other)?;
+    log.flush()
+}
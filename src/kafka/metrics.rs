@@ -0,0 +1,206 @@
+mod exporter;
+pub(crate) use exporter::*;
+mod histogram;
+pub(crate) use histogram::*;
+
+use crate::kafka::coordinator::GroupCoordinator;
+use crate::kafka::quota::ConnectionRejection;
+use crate::kafka::storage::{LogManager, TopicPartition};
+use crate::kafka::types::ApiKey;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The broker metrics exported in the Prometheus text format. Counters are
+/// recorded as requests and connections come and go, gauges of the logs and
+/// groups are read when scraped.
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+    /// The handling time of the requests of each API key and version.
+    requests: Mutex<HashMap<(ApiKey, i16), Histogram>>,
+    /// Open connections per listener.
+    connections: Mutex<BTreeMap<String, i64>>,
+    rejected_connections: Mutex<BTreeMap<(String, ConnectionRejection), u64>>,
+    idle_connections_closed: AtomicU64,
+    decode_errors: AtomicU64,
+}
+
+impl Metrics {
+    pub(crate) fn record_request(&self, api_key: ApiKey, api_version: i16, elapsed: Duration) {
+        let mut requests = self.requests.lock().expect("request metrics lock poisoned");
+        requests.entry((api_key, api_version)).or_default().observe(elapsed);
+    }
+
+    pub(crate) fn connection_opened(&self, listener_name: &str) {
+        *self.connections().entry(listener_name.to_owned()).or_default() += 1;
+    }
+
+    pub(crate) fn connection_closed(&self, listener_name: &str) {
+        *self.connections().entry(listener_name.to_owned()).or_default() -= 1;
+    }
+
+    pub(crate) fn connection_rejected(&self, listener_name: &str, reason: ConnectionRejection) {
+        let mut rejected = self.rejected_connections.lock().expect("connection metrics lock poisoned");
+        *rejected.entry((listener_name.to_owned(), reason)).or_default() += 1;
+    }
+
+    pub(crate) fn idle_connection_closed(&self) {
+        self.idle_connections_closed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_decode_error(&self) {
+        self.decode_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The metrics in the Prometheus text exposition format.
+    pub(crate) fn render(&self, log_manager: &LogManager, group_coordinator: &GroupCoordinator) -> String {
+        let mut out = String::new();
+
+        let mut requests = self
+            .requests
+            .lock()
+            .expect("request metrics lock poisoned")
+            .iter()
+            .map(|((api_key, api_version), histogram)| (format!("{api_key:?}"), *api_version, histogram.clone()))
+            .collect::<Vec<_>>();
+        requests.sort_by(|(a, a_version, _), (b, b_version, _)| (a, a_version).cmp(&(b, b_version)));
+        header(&mut out, "kafka_network_requests_total", "counter", "Requests handled, by API and version.");
+        for (request, version, histogram) in &requests {
+            let labels = labels(&[("request", request), ("version", &version.to_string())]);
+            out.push_str(&format!("kafka_network_requests_total{{{labels}}} {}\n", histogram.count()));
+        }
+        let name = "kafka_network_request_duration_seconds";
+        header(&mut out, name, "histogram", "Time spent handling requests, by API and version.");
+        for (request, version, histogram) in &requests {
+            histogram.write_samples(&mut out, name, &labels(&[("request", request), ("version", &version.to_string())]));
+        }
+
+        header(&mut out, "kafka_network_request_decode_errors_total", "counter", "Requests that failed to decode.");
+        let decode_errors = self.decode_errors.load(Ordering::Relaxed);
+        out.push_str(&format!("kafka_network_request_decode_errors_total {decode_errors}\n"));
+
+        header(&mut out, "kafka_server_active_connections", "gauge", "Open client connections, by listener.");
+        for (listener, count) in self.connections().iter() {
+            out.push_str(&format!("kafka_server_active_connections{{{}}} {count}\n", labels(&[("listener", listener)])));
+        }
+        let name = "kafka_server_connections_rejected_total";
+        header(&mut out, name, "counter", "Connections closed on accept for exceeding a limit, by listener and limit.");
+        let rejected = self.rejected_connections.lock().expect("connection metrics lock poisoned").clone();
+        for ((listener, reason), count) in &rejected {
+            let labels = labels(&[("listener", listener), ("reason", &reason.to_string())]);
+            out.push_str(&format!("{name}{{{labels}}} {count}\n"));
+        }
+        let name = "kafka_server_idle_connections_closed_total";
+        header(&mut out, name, "counter", "Connections closed after connections.max.idle.ms without a request.");
+        out.push_str(&format!("{name} {}\n", self.idle_connections_closed.load(Ordering::Relaxed)));
+
+        let mut logs = log_manager
+            .logs()
+            .into_iter()
+            .map(|(topic_partition, log)| {
+                let log = log.lock().expect("partition log lock poisoned");
+                (topic_partition, log.log_end_offset(), log.bytes_in())
+            })
+            .collect::<Vec<_>>();
+        logs.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+        let mut bytes_in = BTreeMap::<&str, u64>::new();
+        for (topic_partition, _, bytes) in &logs {
+            *bytes_in.entry(&topic_partition.topic).or_default() += bytes;
+        }
+        header(&mut out, "kafka_server_bytes_in_total", "counter", "Bytes appended to the logs of each topic.");
+        for (topic, bytes) in bytes_in {
+            out.push_str(&format!("kafka_server_bytes_in_total{{{}}} {bytes}\n", labels(&[("topic", topic)])));
+        }
+
+        header(&mut out, "kafka_log_log_end_offset", "gauge", "The offset of the next record appended to a partition.");
+        for (topic_partition, log_end_offset, _) in &logs {
+            let labels = partition_labels(topic_partition);
+            out.push_str(&format!("kafka_log_log_end_offset{{{labels}}} {log_end_offset}\n"));
+        }
+
+        // Every partition has this broker as its only replica, which is
+        // always in sync.
+        let name = "kafka_server_under_replicated_partitions";
+        header(&mut out, name, "gauge", "Partitions with replicas out of sync with the leader.");
+        out.push_str(&format!("{name} 0\n"));
+
+        let log_end_offsets = logs
+            .iter()
+            .map(|(topic_partition, log_end_offset, _)| (topic_partition, *log_end_offset))
+            .collect::<HashMap<_, _>>();
+        let mut committed = group_coordinator.committed_offsets();
+        committed.sort();
+        header(&mut out, "kafka_consumergroup_lag", "gauge", "Records between a group's committed offset and the log end.");
+        for (group_id, topic_partition, offset) in &committed {
+            let Some(log_end_offset) = log_end_offsets.get(topic_partition) else {
+                continue;
+            };
+            let labels = format!("{},{}", labels(&[("group", group_id)]), partition_labels(topic_partition));
+            out.push_str(&format!("kafka_consumergroup_lag{{{labels}}} {}\n", (log_end_offset - offset).max(0)));
+        }
+        out
+    }
+
+    fn connections(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, i64>> {
+        self.connections.lock().expect("connection metrics lock poisoned")
+    }
+}
+
+fn header(out: &mut String, name: &str, metric_type: &str, help: &str) {
+    out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {metric_type}\n"));
+}
+
+/// Formats `name="value"` label pairs, escaping the values.
+fn labels(pairs: &[(&str, &str)]) -> String {
+    pairs
+        .iter()
+        .map(|(name, value)| {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            format!("{name}=\"{value}\"")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn partition_labels(topic_partition: &TopicPartition) -> String {
+    labels(&[("topic", &topic_partition.topic), ("partition", &topic_partition.partition.to_string())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::config::ServerConfig;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_renders_prometheus_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        let group_coordinator = GroupCoordinator::new(config, log_manager.clone());
+        let metrics = Metrics::default();
+        metrics.record_request(ApiKey::ListOffsets, 7, Duration::from_millis(3));
+        metrics.record_request(ApiKey::ListOffsets, 7, Duration::from_secs(20));
+        metrics.connection_opened("PLAINTEXT");
+        metrics.record_decode_error();
+        metrics.connection_rejected("PLAINTEXT", ConnectionRejection::MaxConnectionsPerIp);
+
+        let text = metrics.render(&log_manager, &group_coordinator);
+        assert!(text.contains("kafka_network_requests_total{request=\"ListOffsets\",version=\"7\"} 2\n"));
+        assert!(text.contains(
+            "kafka_network_request_duration_seconds_bucket{request=\"ListOffsets\",version=\"7\",le=\"0.005\"} 1\n"
+        ));
+        assert!(text.contains(
+            "kafka_network_request_duration_seconds_bucket{request=\"ListOffsets\",version=\"7\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(text.contains("kafka_server_active_connections{listener=\"PLAINTEXT\"} 1\n"));
+        assert!(text.contains("kafka_network_request_decode_errors_total 1\n"));
+        assert!(text.contains("kafka_server_under_replicated_partitions 0\n"));
+        assert!(text.contains(
+            "kafka_server_connections_rejected_total{listener=\"PLAINTEXT\",reason=\"max_connections_per_ip\"} 1\n"
+        ));
+        assert_eq!(labels(&[("group", "a\"b")]), "group=\"a\\\"b\"");
+    }
+}
@@ -0,0 +1,93 @@
+use crate::kafka::coordinator::GroupCoordinator;
+use crate::kafka::metrics::Metrics;
+use crate::kafka::storage::LogManager;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::Handle;
+use tracing::warn;
+
+/// Scrapes sending more than this before the end of their headers are
+/// dropped.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves the broker metrics to Prometheus at `GET /metrics`, one request
+/// per HTTP/1.1 connection. Metrics are rendered on the blocking threads of
+/// the request handler runtime, as they lock every partition log and the
+/// group coordinator.
+#[derive(Debug)]
+pub(crate) struct MetricsExporter {
+    metrics: Arc<Metrics>,
+    log_manager: Arc<LogManager>,
+    group_coordinator: Arc<GroupCoordinator>,
+    request_handlers: Handle,
+}
+
+impl MetricsExporter {
+    pub(crate) fn new(
+        metrics: Arc<Metrics>,
+        log_manager: Arc<LogManager>,
+        group_coordinator: Arc<GroupCoordinator>,
+        request_handlers: Handle,
+    ) -> Self {
+        Self { metrics, log_manager, group_coordinator, request_handlers }
+    }
+
+    pub(crate) async fn run(self: Arc<Self>, listener: TcpListener) -> io::Result<()> {
+        loop {
+            let (socket, addr) = listener.accept().await?;
+            let exporter = self.clone();
+            tokio::spawn(async move {
+                match tokio::time::timeout(SCRAPE_TIMEOUT, exporter.handle_scrape(socket)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(err)) => warn!(client = %addr, error = %err, "Failed to serve metrics"),
+                    Err(_) => warn!(client = %addr, "Metrics scrape timed out"),
+                }
+            });
+        }
+    }
+
+    async fn handle_scrape(self: Arc<Self>, mut socket: TcpStream) -> io::Result<()> {
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            if request.len() > MAX_REQUEST_BYTES {
+                return Err(io::Error::other("request headers too large"));
+            }
+            let n = socket.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(());
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+
+        let request_line = request.split(|&byte| byte == b'\r').next().unwrap_or_default();
+        let request_line = String::from_utf8_lossy(request_line);
+        let mut parts = request_line.split(' ');
+        let method = parts.next().unwrap_or_default();
+        let path = parts.next().unwrap_or_default().split('?').next().unwrap_or_default();
+        let (status, body) = match (method, path) {
+            ("GET", "/metrics") => ("200 OK", self.clone().render().await?),
+            ("GET", _) => ("404 Not Found", "Not Found\n".to_owned()),
+            _ => ("405 Method Not Allowed", "Method Not Allowed\n".to_owned()),
+        };
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        socket.write_all(response.as_bytes()).await?;
+        socket.shutdown().await
+    }
+
+    async fn render(self: Arc<Self>) -> io::Result<String> {
+        let handlers = self.request_handlers.clone();
+        handlers
+            .spawn_blocking(move || self.metrics.render(&self.log_manager, &self.group_coordinator))
+            .await
+            .map_err(io::Error::other)
+    }
+}
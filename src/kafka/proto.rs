@@ -11,7 +11,43 @@ use std::sync::LazyLock;
 pub(crate) static API_REGISTRY: LazyLock<HashMap<ApiKey, RangeInclusive<i16>>> = LazyLock::new(|| {
     use crate::kafka::types::ApiKey::*;
     let mut registry = HashMap::new();
+    registry.insert(ListOffsets, 1..=9);
+    registry.insert(OffsetCommit, 2..=9);
+    registry.insert(OffsetFetch, 1..=9);
+    registry.insert(FindCoordinator, 0..=4);
+    registry.insert(JoinGroup, 0..=9);
+    registry.insert(Heartbeat, 0..=4);
+    registry.insert(LeaveGroup, 0..=5);
+    registry.insert(SyncGroup, 0..=5);
+    registry.insert(DescribeGroups, 0..=5);
+    registry.insert(ListGroups, 0..=5);
+    registry.insert(SaslHandshake, 1..=1);
     registry.insert(ApiVersions, 0..=4);
+    registry.insert(DeleteRecords, 0..=2);
+    registry.insert(OffsetForLeaderEpoch, 0..=4);
+    registry.insert(InitProducerId, 0..=5);
+    registry.insert(AddPartitionsToTxn, 0..=3);
+    registry.insert(AddOffsetsToTxn, 0..=4);
+    registry.insert(EndTxn, 0..=4);
+    registry.insert(TxnOffsetCommit, 0..=4);
+    registry.insert(DescribeAcls, 0..=3);
+    registry.insert(CreateAcls, 0..=3);
+    registry.insert(DeleteAcls, 0..=3);
+    registry.insert(DescribeConfigs, 0..=4);
+    registry.insert(AlterConfigs, 0..=2);
+    registry.insert(SaslAuthenticate, 0..=2);
+    registry.insert(DeleteGroups, 0..=2);
+    registry.insert(IncrementalAlterConfigs, 0..=1);
+    registry.insert(OffsetDelete, 0..=0);
+    registry.insert(DescribeClientQuotas, 0..=1);
+    registry.insert(AlterClientQuotas, 0..=1);
+    registry.insert(DescribeProducers, 0..=0);
+    registry.insert(BrokerRegistration, 0..=3);
+    registry.insert(BrokerHeartbeat, 0..=0);
+    registry.insert(DescribeTransactions, 0..=0);
+    registry.insert(ListTransactions, 0..=1);
+    registry.insert(ConsumerGroupHeartbeat, 0..=0);
+    registry.insert(ConsumerGroupDescribe, 0..=1);
     registry.insert(DescribeTopicPartitions, 0..=0);
     registry
 });
@@ -38,7 +74,7 @@ impl BinWrite for ApiVersionsResponse {
     fn write_options<W: Write + Seek>(
         &self,
         writer: &mut W,
-        options: Endian,
+        _: Endian,
         _: Self::Args<'_>,
     ) -> binrw::BinResult<()> {
         match self {
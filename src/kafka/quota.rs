@@ -0,0 +1,74 @@
+mod connection;
+pub(crate) use connection::*;
+mod manager;
+pub(crate) use manager::*;
+mod rate;
+pub(crate) use rate::*;
+
+pub(crate) const USER_ENTITY_TYPE: &str = "user";
+pub(crate) const CLIENT_ID_ENTITY_TYPE: &str = "client-id";
+
+pub(crate) const PRODUCER_BYTE_RATE: &str = "producer_byte_rate";
+pub(crate) const CONSUMER_BYTE_RATE: &str = "consumer_byte_rate";
+pub(crate) const REQUEST_PERCENTAGE: &str = "request_percentage";
+
+/// The quota keys clients may set.
+pub(crate) const QUOTA_KEYS: [&str; 3] = [PRODUCER_BYTE_RATE, CONSUMER_BYTE_RATE, REQUEST_PERCENTAGE];
+
+/// The users and client ids a quota applies to. For each type, `None` leaves
+/// it out of the entity, `Some(None)` is the default entity of the type, and
+/// `Some(Some(name))` the named one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct QuotaEntity {
+    pub(crate) user: Option<Option<String>>,
+    pub(crate) client_id: Option<Option<String>>,
+}
+
+impl QuotaEntity {
+    /// Builds an entity from `(entity type, name)` components, a `None` name
+    /// standing for the default entity of the type.
+    pub(crate) fn from_components<'a>(
+        components: impl IntoIterator<Item = (&'a str, Option<&'a str>)>,
+    ) -> Result<Self, String> {
+        let mut entity = Self::default();
+        for (entity_type, name) in components {
+            let slot = match entity_type {
+                USER_ENTITY_TYPE => &mut entity.user,
+                CLIENT_ID_ENTITY_TYPE => &mut entity.client_id,
+                _ => return Err(format!("Unsupported quota entity type: {entity_type}")),
+            };
+            if slot.is_some() {
+                return Err(format!("Duplicate quota entity type: {entity_type}"));
+            }
+            *slot = Some(name.map(str::to_owned));
+        }
+        if entity == Self::default() {
+            return Err("The quota entity has no components".to_owned());
+        }
+        Ok(entity)
+    }
+
+    /// The `(entity type, name)` components of the entity.
+    pub(crate) fn components(&self) -> Vec<(&'static str, Option<&str>)> {
+        let user = self.user.as_ref().map(|name| (USER_ENTITY_TYPE, name.as_deref()));
+        let client_id = self.client_id.as_ref().map(|name| (CLIENT_ID_ENTITY_TYPE, name.as_deref()));
+        user.into_iter().chain(client_id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_components_round_trip() {
+        let entity = QuotaEntity::from_components([(CLIENT_ID_ENTITY_TYPE, Some("app")), (USER_ENTITY_TYPE, None)])
+            .unwrap();
+        assert_eq!(entity, QuotaEntity { user: Some(None), client_id: Some(Some("app".to_owned())) });
+        assert_eq!(entity.components(), [(USER_ENTITY_TYPE, None), (CLIENT_ID_ENTITY_TYPE, Some("app"))]);
+
+        assert!(QuotaEntity::from_components([]).is_err());
+        assert!(QuotaEntity::from_components([("ip", Some("127.0.0.1"))]).is_err());
+        assert!(QuotaEntity::from_components([(USER_ENTITY_TYPE, None), (USER_ENTITY_TYPE, Some("alice"))]).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::quota::Rate;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+/// Why a new connection was closed without being handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum ConnectionRejection {
+    MaxConnections,
+    MaxConnectionsPerIp,
+    CreationRate,
+}
+
+impl Display for ConnectionRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::MaxConnections => "max_connections",
+            Self::MaxConnectionsPerIp => "max_connections_per_ip",
+            Self::CreationRate => "creation_rate",
+        })
+    }
+}
+
+/// Limits the connections the broker keeps open, in total and per client
+/// address, and how fast new ones are accepted, like Kafka's
+/// `ConnectionQuotas`. Unlike Kafka, the acceptor never waits for a slot:
+/// connections over a limit are closed.
+#[derive(Debug)]
+pub(crate) struct ConnectionQuotas {
+    max_connections: usize,
+    max_connections_per_ip: usize,
+    per_ip_overrides: HashMap<IpAddr, usize>,
+    max_creation_rate: f64,
+    open: Mutex<OpenConnections>,
+}
+
+#[derive(Debug)]
+struct OpenConnections {
+    total: usize,
+    per_ip: HashMap<IpAddr, usize>,
+    creation_rate: Rate,
+}
+
+impl ConnectionQuotas {
+    pub(crate) fn new(config: &ServerConfig) -> Self {
+        let window_ms = config.quota_window_size_seconds as i64 * 1000;
+        Self {
+            max_connections: config.max_connections,
+            max_connections_per_ip: config.max_connections_per_ip,
+            per_ip_overrides: config.max_connections_per_ip_overrides.clone(),
+            max_creation_rate: config.max_connection_creation_rate,
+            open: Mutex::new(OpenConnections {
+                total: 0,
+                per_ip: HashMap::new(),
+                creation_rate: Rate::new(window_ms, config.quota_window_num),
+            }),
+        }
+    }
+
+    /// Counts a new connection from `ip`, unless it would exceed a limit.
+    /// The connection is counted until the returned slot is dropped.
+    pub(crate) fn acquire(self: &Arc<Self>, ip: IpAddr, now_ms: i64) -> Result<ConnectionSlot, ConnectionRejection> {
+        let mut open = self.open();
+        if open.total >= self.max_connections {
+            return Err(ConnectionRejection::MaxConnections);
+        }
+        let max_per_ip = self.per_ip_overrides.get(&ip).copied().unwrap_or(self.max_connections_per_ip);
+        if open.per_ip.get(&ip).copied().unwrap_or_default() >= max_per_ip {
+            return Err(ConnectionRejection::MaxConnectionsPerIp);
+        }
+        // Rejected attempts count towards the rate too, so a client retrying
+        // in a loop stays rejected until it backs off.
+        open.creation_rate.record(1.0, now_ms);
+        if open.creation_rate.measure(now_ms) > self.max_creation_rate {
+            return Err(ConnectionRejection::CreationRate);
+        }
+
+        open.total += 1;
+        *open.per_ip.entry(ip).or_default() += 1;
+        Ok(ConnectionSlot { quotas: self.clone(), ip })
+    }
+
+    fn open(&self) -> std::sync::MutexGuard<'_, OpenConnections> {
+        self.open.lock().expect("connection quotas lock poisoned")
+    }
+}
+
+/// An open connection counted against the connection limits.
+#[derive(Debug)]
+pub(crate) struct ConnectionSlot {
+    quotas: Arc<ConnectionQuotas>,
+    ip: IpAddr,
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        let mut open = self.quotas.open();
+        open.total -= 1;
+        if let Some(count) = open.per_ip.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                open.per_ip.remove(&self.ip);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_open_connections() {
+        let local: IpAddr = "127.0.0.1".parse().unwrap();
+        let remote: IpAddr = "10.0.0.1".parse().unwrap();
+        let config = ServerConfig {
+            max_connections: 3,
+            max_connections_per_ip: 1,
+            max_connections_per_ip_overrides: HashMap::from([(local, 2)]),
+            ..ServerConfig::default()
+        };
+        let quotas = Arc::new(ConnectionQuotas::new(&config));
+
+        let first = quotas.acquire(local, 0).unwrap();
+        let _second = quotas.acquire(local, 0).unwrap();
+        assert_eq!(quotas.acquire(local, 0).unwrap_err(), ConnectionRejection::MaxConnectionsPerIp);
+        let _third = quotas.acquire(remote, 0).unwrap();
+        assert_eq!(quotas.acquire("10.0.0.2".parse().unwrap(), 0).unwrap_err(), ConnectionRejection::MaxConnections);
+
+        drop(first);
+        assert!(quotas.acquire(local, 0).is_ok());
+
+        let config = ServerConfig { max_connection_creation_rate: 1.0, quota_window_num: 2, ..ServerConfig::default() };
+        let quotas = Arc::new(ConnectionQuotas::new(&config));
+        assert!(quotas.acquire(local, 0).is_ok());
+        assert_eq!(quotas.acquire(local, 0).unwrap_err(), ConnectionRejection::CreationRate);
+        assert!(quotas.acquire(local, 5000).is_ok());
+    }
+}
@@ -0,0 +1,161 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::quota::{QuotaEntity, Rate, REQUEST_PERCENTAGE};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+/// How a DescribeClientQuotas filter component matches an entity type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum QuotaMatch {
+    /// The named entity.
+    Exact(String),
+    /// The default entity.
+    Default,
+    /// Any entity of the type, named or default.
+    Any,
+}
+
+/// Enforces the client quotas set with AlterClientQuotas. Quotas apply to
+/// users, client ids, or both, and the most specific entity with a quota
+/// wins, in the order of Kafka's `ClientQuotaManager`.
+#[derive(Debug)]
+pub(crate) struct QuotaManager {
+    window_ms: i64,
+    num_windows: usize,
+    quotas: RwLock<HashMap<QuotaEntity, HashMap<String, f64>>>,
+    /// The request handling time of the clients sharing a quota, keyed by
+    /// the user and client id of the quota's entity, as a percentage of one
+    /// thread.
+    request_rates: Mutex<HashMap<(String, String), Rate>>,
+}
+
+impl QuotaManager {
+    pub(crate) fn new(config: &ServerConfig, quotas: HashMap<QuotaEntity, HashMap<String, f64>>) -> Self {
+        Self {
+            window_ms: config.quota_window_size_seconds as i64 * 1000,
+            num_windows: config.quota_window_num,
+            quotas: RwLock::new(quotas),
+            request_rates: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records the time spent handling a request of the client and returns
+    /// how long it must be throttled for, 0 if it is within its quota.
+    /// Throttling never exceeds one sample window.
+    pub(crate) fn record_request_time(&self, user: &str, client_id: &str, elapsed: Duration, now_ms: i64) -> i64 {
+        let Some((entity, quota)) = self.quota(user, client_id, REQUEST_PERCENTAGE) else {
+            return 0;
+        };
+        let key = (
+            entity.user.map(|_| user.to_owned()).unwrap_or_default(),
+            entity.client_id.map(|_| client_id.to_owned()).unwrap_or_default(),
+        );
+        let mut rates = self.request_rates.lock().expect("quota rates lock poisoned");
+        rates.retain(|_, rate| !rate.is_idle(now_ms));
+        let rate = rates.entry(key).or_insert_with(|| Rate::new(self.window_ms, self.num_windows));
+        rate.record(elapsed.as_secs_f64() * 100.0, now_ms);
+        rate.throttle_time_ms(quota, now_ms).min(self.window_ms)
+    }
+
+    /// The quota `key` of the client and the entity it is set on, trying the
+    /// user and client id together, then the user, then the client id, each
+    /// named before default.
+    pub(crate) fn quota(&self, user: &str, client_id: &str, key: &str) -> Option<(QuotaEntity, f64)> {
+        let named = |name: &str| Some(Some(name.to_owned()));
+        let candidates = [
+            (named(user), named(client_id)),
+            (named(user), Some(None)),
+            (named(user), None),
+            (Some(None), named(client_id)),
+            (Some(None), Some(None)),
+            (Some(None), None),
+            (None, named(client_id)),
+            (None, Some(None)),
+        ];
+        let quotas = self.quotas.read().expect("quotas lock poisoned");
+        candidates.into_iter().find_map(|(user, client_id)| {
+            let entity = QuotaEntity { user, client_id };
+            let value = *quotas.get(&entity)?.get(key)?;
+            Some((entity, value))
+        })
+    }
+
+    /// The quotas of the entities matching every `(entity type, match)`
+    /// filter component. Unless `strict`, entities may have components the
+    /// filter does not mention.
+    pub(crate) fn describe(
+        &self,
+        filter: &[(String, QuotaMatch)],
+        strict: bool,
+    ) -> BTreeMap<QuotaEntity, HashMap<String, f64>> {
+        let quotas = self.quotas.read().expect("quotas lock poisoned");
+        quotas
+            .iter()
+            .filter(|(entity, _)| {
+                let components = entity.components();
+                let matches = filter.iter().all(|(entity_type, quota_match)| {
+                    components.iter().any(|(component_type, name)| {
+                        component_type == entity_type
+                            && match quota_match {
+                                QuotaMatch::Exact(expected) => *name == Some(expected.as_str()),
+                                QuotaMatch::Default => name.is_none(),
+                                QuotaMatch::Any => true,
+                            }
+                    })
+                });
+                matches && (!strict || components.len() == filter.len())
+            })
+            .map(|(entity, values)| (entity.clone(), values.clone()))
+            .collect()
+    }
+
+    /// Sets the quota `key` of the entity, or removes it for `None`.
+    pub(crate) fn set_quota(&self, entity: &QuotaEntity, key: &str, value: Option<f64>) {
+        let mut quotas = self.quotas.write().expect("quotas lock poisoned");
+        match value {
+            Some(value) => {
+                quotas.entry(entity.clone()).or_default().insert(key.to_owned(), value);
+            }
+            None => {
+                if let Some(values) = quotas.get_mut(entity) {
+                    values.remove(key);
+                    if values.is_empty() {
+                        quotas.remove(entity);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_most_specific_quota_is_enforced() {
+        let manager = QuotaManager::new(&ServerConfig::default(), HashMap::new());
+        let default_user = QuotaEntity { user: Some(None), client_id: None };
+        let alice_app = QuotaEntity { user: Some(Some("alice".to_owned())), client_id: Some(Some("app".to_owned())) };
+        manager.set_quota(&default_user, REQUEST_PERCENTAGE, Some(10.0));
+        manager.set_quota(&alice_app, REQUEST_PERCENTAGE, Some(50.0));
+
+        assert_eq!(manager.quota("alice", "app", REQUEST_PERCENTAGE), Some((alice_app.clone(), 50.0)));
+        assert_eq!(manager.quota("alice", "other", REQUEST_PERCENTAGE), Some((default_user.clone(), 10.0)));
+        assert_eq!(manager.quota("alice", "app", "producer_byte_rate"), None);
+
+        // 2s of handling time measured over the 10s window is 20% of a thread.
+        assert_eq!(manager.record_request_time("bob", "tool", Duration::from_secs(2), 0), 1000);
+        assert_eq!(manager.record_request_time("alice", "app", Duration::from_secs(2), 0), 0);
+
+        let filter = [("user".to_owned(), QuotaMatch::Default)];
+        let described = manager.describe(&filter, true);
+        assert_eq!(described.len(), 1);
+        assert!(described.contains_key(&default_user));
+        let filter = [("user".to_owned(), QuotaMatch::Any)];
+        assert_eq!(manager.describe(&filter, false).len(), 2);
+
+        manager.set_quota(&default_user, REQUEST_PERCENTAGE, None);
+        assert_eq!(manager.quota("alice", "other", REQUEST_PERCENTAGE), None);
+    }
+}
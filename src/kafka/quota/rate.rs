@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+
+/// A rate per second measured over a sliding window of samples, like the
+/// sensors of Kafka's client quotas: values are summed into samples of
+/// `window_ms`, and only the last `num_windows` samples count.
+#[derive(Debug, Clone)]
+pub(crate) struct Rate {
+    window_ms: i64,
+    num_windows: usize,
+    samples: VecDeque<Sample>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    start_ms: i64,
+    value: f64,
+}
+
+impl Rate {
+    pub(crate) fn new(window_ms: i64, num_windows: usize) -> Self {
+        Self { window_ms, num_windows, samples: VecDeque::new() }
+    }
+
+    pub(crate) fn record(&mut self, value: f64, now_ms: i64) {
+        self.purge(now_ms);
+        match self.samples.back_mut() {
+            Some(sample) if now_ms < sample.start_ms + self.window_ms => sample.value += value,
+            _ => self.samples.push_back(Sample { start_ms: now_ms, value }),
+        }
+    }
+
+    /// The rate per second at `now_ms`.
+    pub(crate) fn measure(&mut self, now_ms: i64) -> f64 {
+        self.purge(now_ms);
+        let total: f64 = self.samples.iter().map(|sample| sample.value).sum();
+        total * 1000.0 / self.elapsed_ms(now_ms) as f64
+    }
+
+    /// How long the client must wait for the rate to fall back to `bound`,
+    /// 0 if it is within it.
+    pub(crate) fn throttle_time_ms(&mut self, bound: f64, now_ms: i64) -> i64 {
+        let rate = self.measure(now_ms);
+        if rate <= bound {
+            return 0;
+        }
+        ((rate - bound) / bound * self.elapsed_ms(now_ms) as f64) as i64
+    }
+
+    /// Whether no sample is left in the window.
+    pub(crate) fn is_idle(&mut self, now_ms: i64) -> bool {
+        self.purge(now_ms);
+        self.samples.is_empty()
+    }
+
+    /// The time the rate is measured over: since the oldest sample, but at
+    /// least all but one full window, so a burst at startup is not measured
+    /// over a few milliseconds.
+    fn elapsed_ms(&self, now_ms: i64) -> i64 {
+        let elapsed = self.samples.front().map_or(0, |sample| now_ms - sample.start_ms);
+        let min_elapsed = (self.num_windows as i64 - 1) * self.window_ms;
+        elapsed.max(min_elapsed).max(1)
+    }
+
+    fn purge(&mut self, now_ms: i64) {
+        let expire_ms = self.window_ms * self.num_windows as i64;
+        while self.samples.front().is_some_and(|sample| sample.start_ms + expire_ms <= now_ms) {
+            self.samples.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measures_over_sliding_window() {
+        let mut rate = Rate::new(1000, 11);
+        for second in 0..10 {
+            rate.record(100.0, second * 1000);
+        }
+        assert_eq!(rate.measure(10_000), 100.0);
+        assert_eq!(rate.throttle_time_ms(100.0, 10_000), 0);
+        assert_eq!(rate.throttle_time_ms(50.0, 10_000), 10_000);
+
+        // The first samples have left the window by now.
+        assert_eq!(rate.measure(15_000), 50.0);
+        assert!(rate.is_idle(20_000));
+    }
+}
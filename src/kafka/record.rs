@@ -0,0 +1,6 @@
+mod batch;
+pub(crate) use batch::*;
+mod records;
+pub(crate) use records::*;
+mod control;
+pub(crate) use control::*;
@@ -0,0 +1,115 @@
+use binrw::{binrw, BinRead};
+use crc::{Crc, CRC_32_ISCSI};
+use std::io::Cursor;
+
+pub(crate) const CRC32C: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+/// Size of the `base_offset` and `batch_length` fields that prefix every batch.
+pub(crate) const LOG_OVERHEAD: usize = 12;
+/// Size of the full v2 batch header, including [`LOG_OVERHEAD`].
+pub(crate) const RECORD_BATCH_OVERHEAD: usize = 61;
+/// Offset of the `crc` field.
+pub(crate) const CRC_OFFSET: usize = 17;
+/// Offset of the first byte covered by the batch CRC (the `attributes` field).
+pub(crate) const CRC_START: usize = 21;
+const CURRENT_MAGIC: i8 = 2;
+const LOG_APPEND_TIME_FLAG: i16 = 0x08;
+pub(crate) const TRANSACTIONAL_FLAG: i16 = 0x10;
+pub(crate) const CONTROL_FLAG: i16 = 0x20;
+/// Set by the log cleaner once `base_timestamp` holds the delete horizon.
+pub(crate) const DELETE_HORIZON_FLAG: i16 = 0x40;
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug, Clone)]
+pub(crate) struct RecordBatchHeader {
+    pub(crate) base_offset: i64,
+    pub(crate) batch_length: i32,
+    pub(crate) partition_leader_epoch: i32,
+    pub(crate) magic: i8,
+    pub(crate) crc: u32,
+    pub(crate) attributes: i16,
+    pub(crate) last_offset_delta: i32,
+    pub(crate) base_timestamp: i64,
+    pub(crate) max_timestamp: i64,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    pub(crate) base_sequence: i32,
+    pub(crate) records_count: i32,
+}
+
+impl RecordBatchHeader {
+    pub(crate) fn is_control(&self) -> bool {
+        self.attributes & CONTROL_FLAG != 0
+    }
+
+    /// Whether the batch was written inside a transaction, including the
+    /// marker that ends it.
+    pub(crate) fn is_transactional(&self) -> bool {
+        self.attributes & TRANSACTIONAL_FLAG != 0
+    }
+
+    /// Whether the broker assigned `max_timestamp` to every record on append,
+    /// overriding the per-record timestamps.
+    pub(crate) fn is_log_append_time(&self) -> bool {
+        self.attributes & LOG_APPEND_TIME_FLAG != 0
+    }
+
+    /// When the log cleaner may drop the batch's tombstones, as set by the
+    /// first cleaning pass that kept them (KIP-534).
+    pub(crate) fn delete_horizon_ms(&self) -> Option<i64> {
+        (self.attributes & DELETE_HORIZON_FLAG != 0).then_some(self.base_timestamp)
+    }
+
+    pub(crate) fn last_offset(&self) -> i64 {
+        self.base_offset + self.last_offset_delta as i64
+    }
+
+    /// Total size of the batch on disk, including [`LOG_OVERHEAD`].
+    pub(crate) fn size_in_bytes(&self) -> usize {
+        LOG_OVERHEAD + self.batch_length as usize
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum RecordBatchError {
+    #[error("batch length {0} is smaller than the minimum batch header")]
+    TooSmall(i32),
+    #[error("batch of {needed} bytes exceeds the {available} bytes available")]
+    Truncated { needed: usize, available: usize },
+    #[error("unsupported magic byte {0}")]
+    UnsupportedMagic(i8),
+    #[error("batch crc {stored:#010x} does not match computed {computed:#010x}")]
+    CorruptMessage { stored: u32, computed: u32 },
+    #[error("unsupported compression codec {0}")]
+    UnsupportedCompression(i16),
+    #[error(transparent)]
+    Parse(#[from] binrw::Error),
+}
+
+/// Parses the header at the start of `bytes` and checks that the whole batch
+/// is present and its CRC matches.
+pub(crate) fn validate_batch(bytes: &[u8]) -> Result<RecordBatchHeader, RecordBatchError> {
+    if bytes.len() < RECORD_BATCH_OVERHEAD {
+        return Err(RecordBatchError::Truncated { needed: RECORD_BATCH_OVERHEAD, available: bytes.len() });
+    }
+
+    let header = RecordBatchHeader::read(&mut Cursor::new(bytes))?;
+
+    if (header.batch_length as i64) < (RECORD_BATCH_OVERHEAD - LOG_OVERHEAD) as i64 {
+        return Err(RecordBatchError::TooSmall(header.batch_length));
+    }
+    if header.size_in_bytes() > bytes.len() {
+        return Err(RecordBatchError::Truncated { needed: header.size_in_bytes(), available: bytes.len() });
+    }
+    if header.magic != CURRENT_MAGIC {
+        return Err(RecordBatchError::UnsupportedMagic(header.magic));
+    }
+
+    let computed = CRC32C.checksum(&bytes[CRC_START..header.size_in_bytes()]);
+    if computed != header.crc {
+        return Err(RecordBatchError::CorruptMessage { stored: header.crc, computed });
+    }
+
+    Ok(header)
+}
@@ -0,0 +1,161 @@
+use crate::kafka::record::{Record, RecordBatch, RecordBatchError, CONTROL_FLAG, TRANSACTIONAL_FLAG};
+use crate::kafka::types::{CompactArray, TagBuffer};
+use binrw::{binrw, BinRead, BinWrite};
+use std::io::Cursor;
+
+const CONTROL_RECORD_KEY_VERSION: i16 = 0;
+const END_TXN_MARKER_VALUE_VERSION: i16 = 0;
+const LEADER_CHANGE_CONTROL_TYPE: i16 = 2;
+const LEADER_CHANGE_MESSAGE_VERSION: i16 = 0;
+
+/// The type of a control record, stored in its key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ControlRecordType {
+    Abort,
+    Commit,
+}
+
+/// The control record that ends a transaction on a partition, written by the
+/// transaction coordinator with the producer's id and epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct EndTransactionMarker {
+    pub(crate) control_type: ControlRecordType,
+    pub(crate) coordinator_epoch: i32,
+}
+
+impl EndTransactionMarker {
+    pub(crate) fn new(commit: bool, coordinator_epoch: i32) -> Self {
+        let control_type = if commit { ControlRecordType::Commit } else { ControlRecordType::Abort };
+        Self { control_type, coordinator_epoch }
+    }
+
+    pub(crate) fn is_commit(&self) -> bool {
+        self.control_type == ControlRecordType::Commit
+    }
+
+    /// The transactional control batch holding only this marker.
+    pub(crate) fn to_batch(self, producer_id: i64, producer_epoch: i16, timestamp: i64) -> RecordBatch {
+        let key = ControlRecordKey {
+            version: CONTROL_RECORD_KEY_VERSION,
+            control_type: match self.control_type {
+                ControlRecordType::Abort => 0,
+                ControlRecordType::Commit => 1,
+            },
+        };
+        let value = EndTxnMarkerValue { version: END_TXN_MARKER_VALUE_VERSION, coordinator_epoch: self.coordinator_epoch };
+        let record = Record::new(Some(encode(&key)), Some(encode(&value)));
+
+        let mut batch = RecordBatch::new(vec![record], timestamp);
+        batch.header.attributes = TRANSACTIONAL_FLAG | CONTROL_FLAG;
+        batch.header.producer_id = producer_id;
+        batch.header.producer_epoch = producer_epoch;
+        batch
+    }
+
+    /// Decodes the marker of a control batch. Returns `None` for control
+    /// records other than transaction markers.
+    pub(crate) fn parse(batch: &RecordBatch) -> Result<Option<Self>, RecordBatchError> {
+        let Some(record) = batch.records.first() else {
+            return Ok(None);
+        };
+        let key = ControlRecordKey::read(&mut Cursor::new(record.key.as_deref().unwrap_or_default()))?;
+        let control_type = match key.control_type {
+            0 => ControlRecordType::Abort,
+            1 => ControlRecordType::Commit,
+            _ => return Ok(None),
+        };
+        let value = EndTxnMarkerValue::read(&mut Cursor::new(record.value.as_deref().unwrap_or_default()))?;
+        Ok(Some(Self { control_type, coordinator_epoch: value.coordinator_epoch }))
+    }
+}
+
+/// The control record that starts each epoch of the metadata log, naming
+/// its leader, the voters and those that granted the leader their vote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LeaderChangeMessage {
+    pub(crate) leader_id: i32,
+    pub(crate) voters: Vec<i32>,
+    pub(crate) granting_voters: Vec<i32>,
+}
+
+impl LeaderChangeMessage {
+    /// The control batch holding only this message, in the leader's epoch.
+    pub(crate) fn to_batch(&self, leader_epoch: i32, timestamp: i64) -> RecordBatch {
+        let key = ControlRecordKey { version: CONTROL_RECORD_KEY_VERSION, control_type: LEADER_CHANGE_CONTROL_TYPE };
+        let voters = |ids: &[i32]| {
+            ids.iter().map(|&voter_id| Voter { voter_id, _tagged_fields: TagBuffer }).collect::<Vec<_>>()
+        };
+        let value = LeaderChangeMessageValue {
+            version: LEADER_CHANGE_MESSAGE_VERSION,
+            leader_id: self.leader_id,
+            voters: voters(&self.voters).into(),
+            granting_voters: voters(&self.granting_voters).into(),
+            _tagged_fields: TagBuffer,
+        };
+        let record = Record::new(Some(encode(&key)), Some(encode(&value)));
+
+        let mut batch = RecordBatch::new(vec![record], timestamp);
+        batch.header.attributes = CONTROL_FLAG;
+        batch.header.partition_leader_epoch = leader_epoch;
+        batch
+    }
+}
+
+fn encode(value: &impl for<'a> BinWrite<Args<'a> = ()>) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    value.write_be(&mut writer).expect("writing to a Vec cannot fail");
+    writer.into_inner()
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct ControlRecordKey {
+    version: i16,
+    control_type: i16,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct EndTxnMarkerValue {
+    version: i16,
+    coordinator_epoch: i32,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct LeaderChangeMessageValue {
+    version: i16,
+    leader_id: i32,
+    voters: CompactArray<Voter>,
+    granting_voters: CompactArray<Voter>,
+    _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug, Clone)]
+struct Voter {
+    voter_id: i32,
+    _tagged_fields: TagBuffer,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::record::validate_batch;
+
+    #[test]
+    fn test_marker_round_trip() {
+        let bytes = EndTransactionMarker::new(true, 3).to_batch(42, 7, 1_000).to_bytes();
+        let header = validate_batch(&bytes).unwrap();
+        assert!(header.is_control() && header.is_transactional());
+        assert_eq!((header.producer_id, header.producer_epoch), (42, 7));
+
+        let batch = RecordBatch::parse(header, &bytes).unwrap();
+        let marker = EndTransactionMarker::parse(&batch).unwrap().unwrap();
+        assert_eq!(marker, EndTransactionMarker { control_type: ControlRecordType::Commit, coordinator_epoch: 3 });
+    }
+}
@@ -0,0 +1,192 @@
+use crate::kafka::record::{
+    RecordBatchError, RecordBatchHeader, CRC32C, CRC_OFFSET, CRC_START, DELETE_HORIZON_FLAG, LOG_OVERHEAD,
+    RECORD_BATCH_OVERHEAD,
+};
+use crate::kafka::types::{VarInt, VarLong};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::io::{Cursor, Read, Seek, Write};
+
+const COMPRESSION_CODEC_MASK: i16 = 0x07;
+
+/// A single record inside a v2 record batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Record {
+    pub(crate) attributes: i8,
+    pub(crate) timestamp_delta: i64,
+    pub(crate) offset_delta: i32,
+    pub(crate) key: Option<Vec<u8>>,
+    pub(crate) value: Option<Vec<u8>>,
+    pub(crate) headers: Vec<RecordHeader>,
+}
+
+impl Record {
+    /// A record to be placed in a batch by [`RecordBatch::new`].
+    pub(crate) fn new(key: Option<Vec<u8>>, value: Option<Vec<u8>>) -> Self {
+        Self { attributes: 0, timestamp_delta: 0, offset_delta: 0, key, value, headers: Vec::new() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RecordHeader {
+    pub(crate) key: String,
+    pub(crate) value: Option<Vec<u8>>,
+}
+
+impl BinRead for Record {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(reader: &mut R, endian: Endian, _args: Self::Args<'_>) -> BinResult<Self> {
+        let length = *VarInt::read(reader)?;
+        let start = reader.stream_position()?;
+
+        let attributes = i8::read_options(reader, endian, ())?;
+        let timestamp_delta = *VarLong::read(reader)?;
+        let offset_delta = *VarInt::read(reader)?;
+        let key = read_var_bytes(reader)?;
+        let value = read_var_bytes(reader)?;
+
+        let header_count = *VarInt::read(reader)?;
+        let mut headers = Vec::with_capacity(header_count.max(0) as usize);
+        for _ in 0..header_count {
+            let key = read_var_bytes(reader)?.unwrap_or_default();
+            let key = String::from_utf8(key).map_err(|err| binrw::Error::Custom {
+                pos: reader.stream_position().expect("Should be able to read stream position"),
+                err: Box::new(err),
+            })?;
+            headers.push(RecordHeader { key, value: read_var_bytes(reader)? });
+        }
+
+        let consumed = reader.stream_position()? - start;
+        if consumed != length as u64 {
+            return Err(binrw::Error::AssertFail {
+                pos: start,
+                message: format!("record length {length} does not match {consumed} bytes read"),
+            });
+        }
+
+        Ok(Self { attributes, timestamp_delta, offset_delta, key, value, headers })
+    }
+}
+
+impl BinWrite for Record {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, endian: Endian, _args: Self::Args<'_>) -> BinResult<()> {
+        let mut body = Cursor::new(Vec::new());
+        self.attributes.write_options(&mut body, endian, ())?;
+        VarLong(self.timestamp_delta).write(&mut body)?;
+        VarInt(self.offset_delta).write(&mut body)?;
+        write_var_bytes(&mut body, self.key.as_deref())?;
+        write_var_bytes(&mut body, self.value.as_deref())?;
+        VarInt(self.headers.len() as i32).write(&mut body)?;
+        for header in &self.headers {
+            write_var_bytes(&mut body, Some(header.key.as_bytes()))?;
+            write_var_bytes(&mut body, header.value.as_deref())?;
+        }
+
+        let body = body.into_inner();
+        VarInt(body.len() as i32).write(writer)?;
+        writer.write_all(&body)?;
+        Ok(())
+    }
+}
+
+fn read_var_bytes<R: Read + Seek>(reader: &mut R) -> BinResult<Option<Vec<u8>>> {
+    let length = *VarInt::read(reader)?;
+    if length < 0 {
+        return Ok(None);
+    }
+    let mut bytes = vec![0u8; length as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+fn write_var_bytes<W: Write + Seek>(writer: &mut W, bytes: Option<&[u8]>) -> BinResult<()> {
+    match bytes {
+        None => VarInt(-1).write(writer),
+        Some(bytes) => {
+            VarInt(bytes.len() as i32).write(writer)?;
+            writer.write_all(bytes)?;
+            Ok(())
+        }
+    }
+}
+
+/// A batch header together with its decoded records.
+#[derive(Debug, Clone)]
+pub(crate) struct RecordBatch {
+    pub(crate) header: RecordBatchHeader,
+    pub(crate) records: Vec<Record>,
+}
+
+impl RecordBatch {
+    /// An uncompressed batch of `records`, without a producer, with every
+    /// record timestamped `timestamp`. Offset deltas follow the record order.
+    pub(crate) fn new(records: Vec<Record>, timestamp: i64) -> Self {
+        let header = RecordBatchHeader {
+            base_offset: 0,
+            batch_length: 0,
+            partition_leader_epoch: 0,
+            magic: 2,
+            crc: 0,
+            attributes: 0,
+            last_offset_delta: records.len() as i32 - 1,
+            base_timestamp: timestamp,
+            max_timestamp: timestamp,
+            producer_id: -1,
+            producer_epoch: -1,
+            base_sequence: -1,
+            records_count: records.len() as i32,
+        };
+        let records = records
+            .into_iter()
+            .enumerate()
+            .map(|(offset_delta, record)| Record { timestamp_delta: 0, offset_delta: offset_delta as i32, ..record })
+            .collect();
+        Self { header, records }
+    }
+
+    /// Decodes the records of an already validated batch.
+    pub(crate) fn parse(header: RecordBatchHeader, bytes: &[u8]) -> Result<Self, RecordBatchError> {
+        let codec = header.attributes & COMPRESSION_CODEC_MASK;
+        if codec != 0 {
+            return Err(RecordBatchError::UnsupportedCompression(codec));
+        }
+
+        let mut reader = Cursor::new(&bytes[RECORD_BATCH_OVERHEAD..header.size_in_bytes()]);
+        let records = (0..header.records_count)
+            .map(|_| Record::read_be(&mut reader))
+            .collect::<BinResult<Vec<_>>>()?;
+
+        Ok(Self { header, records })
+    }
+
+    /// Encodes the batch, recomputing its length, record count and CRC. All
+    /// other header fields, including `last_offset_delta`, are kept as is.
+    /// Makes `delete_horizon_ms` the base timestamp, rebasing the record
+    /// timestamp deltas on it so every record keeps its timestamp.
+    pub(crate) fn set_delete_horizon(&mut self, delete_horizon_ms: i64) {
+        let shift = delete_horizon_ms - self.header.base_timestamp;
+        for record in &mut self.records {
+            record.timestamp_delta -= shift;
+        }
+        self.header.base_timestamp = delete_horizon_ms;
+        self.header.attributes |= DELETE_HORIZON_FLAG;
+    }
+
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let header = RecordBatchHeader { records_count: self.records.len() as i32, ..self.header.clone() };
+        let mut writer = Cursor::new(Vec::with_capacity(RECORD_BATCH_OVERHEAD));
+        header.write(&mut writer).expect("writing to a Vec cannot fail");
+        for record in &self.records {
+            record.write_be(&mut writer).expect("writing to a Vec cannot fail");
+        }
+
+        let mut bytes = writer.into_inner();
+        let batch_length = (bytes.len() - LOG_OVERHEAD) as i32;
+        bytes[8..12].copy_from_slice(&batch_length.to_be_bytes());
+        let crc = CRC32C.checksum(&bytes[CRC_START..]);
+        bytes[CRC_OFFSET..CRC_START].copy_from_slice(&crc.to_be_bytes());
+        bytes
+    }
+}
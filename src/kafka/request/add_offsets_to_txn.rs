@@ -0,0 +1,16 @@
+use crate::kafka::types::{KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestAddOffsetsToTxn {
+    #[brw(args(v.flexible))]
+    pub(crate) transactional_id: KafkaString,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,29 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+/// The single-transaction form sent by producers, v3 and below.
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestAddPartitionsToTxn {
+    #[brw(args(v.flexible))]
+    pub(crate) transactional_id: KafkaString,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<AddPartitionsToTxnTopic>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct AddPartitionsToTxnTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) partitions: KafkaArray<i32>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
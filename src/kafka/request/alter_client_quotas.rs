@@ -0,0 +1,55 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestAlterClientQuotas {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) entries: KafkaArray<AlterClientQuotasEntry>,
+    #[br(map = |validate_only: u8| validate_only != 0)]
+    #[bw(map = |validate_only: &bool| u8::from(*validate_only))]
+    pub(crate) validate_only: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct AlterClientQuotasEntry {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) entity: KafkaArray<AlterClientQuotasEntityData>,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) ops: KafkaArray<QuotaOpData>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct AlterClientQuotasEntityData {
+    #[brw(args(v.flexible))]
+    pub(crate) entity_type: KafkaString,
+    /// `None` for the default entity of the type.
+    #[brw(args(v.flexible))]
+    pub(crate) entity_name: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct QuotaOpData {
+    #[brw(args(v.flexible))]
+    pub(crate) key: KafkaString,
+    pub(crate) value: f64,
+    /// Whether the quota is removed, ignoring `value`.
+    #[br(map = |remove: u8| remove != 0)]
+    #[bw(map = |remove: &bool| u8::from(*remove))]
+    pub(crate) remove: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,41 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestAlterConfigs {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) resources: KafkaArray<AlterConfigsResource>,
+    #[br(map = |validate_only: u8| validate_only != 0)]
+    #[bw(map = |validate_only: &bool| u8::from(*validate_only))]
+    pub(crate) validate_only: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct AlterConfigsResource {
+    pub(crate) resource_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name: KafkaString,
+    /// The complete set of dynamic configs of the resource.
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) configs: KafkaArray<AlterableConfig>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct AlterableConfig {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) value: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
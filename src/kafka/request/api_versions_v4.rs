@@ -1,5 +1,5 @@
 use binrw::{binread};
-use crate::kafka::types::{CompactArray, CompactString, ErrorCode, TagBuffer};
+use crate::kafka::types::{CompactString, TagBuffer};
 
 #[binread]
 #[br(big)]
@@ -0,0 +1,20 @@
+use crate::kafka::types::{MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestBrokerHeartbeat {
+    pub(crate) broker_id: i32,
+    pub(crate) broker_epoch: i64,
+    /// The highest metadata offset the broker has applied.
+    pub(crate) current_metadata_offset: i64,
+    #[br(map = |x: u8| x != 0)]
+    #[bw(map = |x: &bool| u8::from(*x))]
+    pub(crate) want_fence: bool,
+    #[br(map = |x: u8| x != 0)]
+    #[bw(map = |x: &bool| u8::from(*x))]
+    pub(crate) want_shut_down: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
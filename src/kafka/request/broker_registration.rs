@@ -0,0 +1,57 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer, Uuid};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestBrokerRegistration {
+    pub(crate) broker_id: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) cluster_id: KafkaString,
+    /// Identifies this run of the broker, a restarted broker registers a new
+    /// incarnation.
+    pub(crate) incarnation_id: Uuid,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) listeners: KafkaArray<BrokerRegistrationRequestListener>,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) features: KafkaArray<BrokerRegistrationRequestFeature>,
+    #[brw(args(v.flexible))]
+    pub(crate) rack: KafkaNullableString,
+    #[brw(if(v.version >= 1))]
+    #[br(map = |x: u8| x != 0)]
+    #[bw(map = |x: &bool| u8::from(*x))]
+    pub(crate) is_migrating_zk_broker: bool,
+    #[brw(if(v.version >= 2), args(v.flexible, ()))]
+    pub(crate) log_dirs: KafkaArray<Uuid>,
+    /// The epoch before a clean shutdown, or -1.
+    #[brw(if(v.version >= 3))]
+    pub(crate) previous_broker_epoch: i64,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct BrokerRegistrationRequestListener {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) host: KafkaString,
+    pub(crate) port: u16,
+    pub(crate) security_protocol: i16,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct BrokerRegistrationRequestFeature {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    pub(crate) min_supported_version: i16,
+    pub(crate) max_supported_version: i16,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,15 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestConsumerGroupDescribe {
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) group_ids: KafkaArray<KafkaString>,
+    #[br(map = |include: u8| include != 0)]
+    #[bw(map = |include: &bool| u8::from(*include))]
+    pub(crate) include_authorized_operations: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,45 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer, Uuid};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestConsumerGroupHeartbeat {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    /// Empty on the first heartbeat of a member that lets the coordinator
+    /// generate its id.
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    /// 0 to join, -1 to leave, -2 for a static member leaving temporarily.
+    pub(crate) member_epoch: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) instance_id: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) rack_id: KafkaNullableString,
+    /// -1 if unchanged since the last heartbeat.
+    pub(crate) rebalance_timeout_ms: i32,
+    /// Null if unchanged since the last heartbeat.
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) subscribed_topic_names: KafkaNullableArray<KafkaString>,
+    /// Null if unchanged since the last heartbeat.
+    #[brw(args(v.flexible))]
+    pub(crate) server_assignor: KafkaNullableString,
+    /// The partitions the member owns. Null if unchanged since the last
+    /// heartbeat.
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topic_partitions: KafkaNullableArray<ConsumerGroupHeartbeatRequestTopicPartitions>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct ConsumerGroupHeartbeatRequestTopicPartitions {
+    pub(crate) topic_id: Uuid,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) partitions: KafkaArray<i32>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
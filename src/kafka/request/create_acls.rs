@@ -0,0 +1,31 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestCreateAcls {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) creations: KafkaArray<AclCreation>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct AclCreation {
+    pub(crate) resource_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name: KafkaString,
+    #[brw(if(v.version >= 1, 3))]
+    pub(crate) resource_pattern_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) principal: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) host: KafkaString,
+    pub(crate) operation: i8,
+    pub(crate) permission_type: i8,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,31 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestDeleteAcls {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) filters: KafkaArray<DeleteAclsFilter>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct DeleteAclsFilter {
+    pub(crate) resource_type_filter: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name_filter: KafkaNullableString,
+    #[brw(if(v.version >= 1, 3))]
+    pub(crate) pattern_type_filter: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) principal_filter: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) host_filter: KafkaNullableString,
+    pub(crate) operation: i8,
+    pub(crate) permission_type: i8,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
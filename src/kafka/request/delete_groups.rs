@@ -0,0 +1,12 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestDeleteGroups {
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) groups_names: KafkaArray<KafkaString>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
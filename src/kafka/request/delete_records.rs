@@ -0,0 +1,39 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+/// Deletes everything below the partition's high watermark.
+pub(crate) const HIGH_WATERMARK: i64 = -1;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestDeleteRecords {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<DeleteRecordsTopic>,
+    pub(crate) timeout_ms: i32,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct DeleteRecordsTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<DeleteRecordsPartition>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct DeleteRecordsPartition {
+    pub(crate) partition_index: i32,
+    /// The new log start offset, or [`HIGH_WATERMARK`].
+    pub(crate) offset: i64,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,32 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestDescribeClientQuotas {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) components: KafkaArray<ComponentData>,
+    /// Whether matching entities must have no components besides the
+    /// filtered ones.
+    #[br(map = |strict: u8| strict != 0)]
+    #[bw(map = |strict: &bool| u8::from(*strict))]
+    pub(crate) strict: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct ComponentData {
+    #[brw(args(v.flexible))]
+    pub(crate) entity_type: KafkaString,
+    /// 0 matches the entity named `match_name`, 1 the default entity, 2 any
+    /// entity.
+    pub(crate) match_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) match_name: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
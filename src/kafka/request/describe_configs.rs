@@ -0,0 +1,34 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestDescribeConfigs {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) resources: KafkaArray<DescribeConfigsResource>,
+    #[brw(if(v.version >= 1))]
+    #[br(map = |include: u8| include != 0)]
+    #[bw(map = |include: &bool| u8::from(*include))]
+    pub(crate) include_synonyms: bool,
+    #[brw(if(v.version >= 3))]
+    #[br(map = |include: u8| include != 0)]
+    #[bw(map = |include: &bool| u8::from(*include))]
+    pub(crate) include_documentation: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct DescribeConfigsResource {
+    pub(crate) resource_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name: KafkaString,
+    /// Null to describe every config of the resource.
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) configuration_keys: KafkaNullableArray<KafkaString>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,24 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestDescribeProducers {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<DescribeProducersRequestTopic>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct DescribeProducersRequestTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) partition_indexes: KafkaArray<i32>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
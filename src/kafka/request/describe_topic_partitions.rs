@@ -0,0 +1,44 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestDescribeTopicPartitions {
+    /// The topics to describe, all topics if empty.
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<DescribeTopicPartitionsRequestTopic>,
+    /// The most partitions included in the response.
+    pub(crate) response_partition_limit: i32,
+    /// A nullable struct, prefixed by -1 if null and 1 otherwise.
+    #[br(temp)]
+    #[bw(calc = if cursor.is_some() { 1 } else { -1 })]
+    cursor_marker: i8,
+    /// The first partition to describe, from the previous response.
+    #[br(if(cursor_marker >= 0), args(v))]
+    #[bw(args(v))]
+    pub(crate) cursor: Option<DescribeTopicPartitionsRequestCursor>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct DescribeTopicPartitionsRequestTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct DescribeTopicPartitionsRequestCursor {
+    #[brw(args(v.flexible))]
+    pub(crate) topic_name: KafkaString,
+    pub(crate) partition_index: i32,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
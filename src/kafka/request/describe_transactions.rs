@@ -0,0 +1,12 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestDescribeTransactions {
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) transactional_ids: KafkaArray<KafkaString>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,18 @@
+use crate::kafka::types::{KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestEndTxn {
+    #[brw(args(v.flexible))]
+    pub(crate) transactional_id: KafkaString,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    /// True to commit the transaction, false to abort it.
+    #[br(map = |committed: u8| committed != 0)]
+    #[bw(map = |committed: &bool| u8::from(*committed))]
+    pub(crate) committed: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,26 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, repr = i8)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum CoordinatorType {
+    #[default]
+    Group = 0,
+    Transaction = 1,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestFindCoordinator {
+    /// The group id or transactional id, replaced by `coordinator_keys` in v4.
+    #[brw(if(v.version <= 3), args(v.flexible))]
+    pub(crate) key: KafkaString,
+    #[brw(if(v.version >= 1))]
+    pub(crate) key_type: CoordinatorType,
+    #[brw(if(v.version >= 4), args(v.flexible, (v.flexible,)))]
+    pub(crate) coordinator_keys: KafkaArray<KafkaString>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -1,14 +1,30 @@
-use binrw::{binread, binwrite, BinRead, BinResult, Endian};
-use std::io::{Read, Seek};
+use binrw::{binread, BinRead, BinResult, Endian};
+use std::io::{Read, Seek, SeekFrom};
 use binrw::meta::{EndianKind, ReadEndian};
 use binrw::io::TakeSeekExt;
+use crate::kafka::proto::API_REGISTRY;
 use crate::kafka::request::api_versions_v4::KafkaRequestApiVersionsV4;
-use crate::kafka::types::{ApiKey, NullableString, TagBuffer};
+use crate::kafka::request::{
+    KafkaRequestAddOffsetsToTxn, KafkaRequestAddPartitionsToTxn, KafkaRequestAlterClientQuotas,
+    KafkaRequestAlterConfigs, KafkaRequestBrokerHeartbeat, KafkaRequestBrokerRegistration,
+    KafkaRequestConsumerGroupDescribe, KafkaRequestConsumerGroupHeartbeat, KafkaRequestCreateAcls,
+    KafkaRequestDeleteAcls, KafkaRequestDeleteGroups, KafkaRequestDeleteRecords, KafkaRequestDescribeAcls,
+    KafkaRequestDescribeClientQuotas, KafkaRequestDescribeConfigs, KafkaRequestDescribeGroups,
+    KafkaRequestDescribeProducers, KafkaRequestDescribeTopicPartitions, KafkaRequestDescribeTransactions,
+    KafkaRequestEndTxn, KafkaRequestFindCoordinator, KafkaRequestHeartbeat, KafkaRequestIncrementalAlterConfigs,
+    KafkaRequestInitProducerId, KafkaRequestJoinGroup, KafkaRequestLeaveGroup, KafkaRequestListGroups,
+    KafkaRequestListOffsets, KafkaRequestListTransactions, KafkaRequestOffsetCommit, KafkaRequestOffsetDelete,
+    KafkaRequestOffsetFetch, KafkaRequestOffsetForLeaderEpoch, KafkaRequestSaslAuthenticate, KafkaRequestSaslHandshake,
+    KafkaRequestSyncGroup, KafkaRequestTxnOffsetCommit,
+};
+use crate::kafka::types::{ApiKey, MessageVersion, NullableString, TagBuffer};
 
 #[derive(Debug)]
 pub(crate) struct KafkaRequest {
+    /// The size of the request after its length prefix.
+    pub(crate) size: usize,
     pub(crate) header: KafkaRequestHeader,
-    pub(crate) body: KafkaRequestApiVersionsV4,
+    pub(crate) body: KafkaRequestBody,
 }
 
 impl ReadEndian for KafkaRequest {
@@ -27,8 +43,8 @@ impl BinRead for KafkaRequest {
         let mut reader = reader.take_seek(message_size.try_into().unwrap());
 
         let header = KafkaRequestHeader::read_options(&mut reader, endian, ())?;
-        let body = KafkaRequestApiVersionsV4::read_options(&mut reader, endian, ())?;
-        
+        let body = KafkaRequestBody::read_options(&mut reader, endian, (header.api_key(), header.api_version()))?;
+
         if reader.limit() != 0 {
             Err(binrw::Error::AssertFail {
                 pos: reader.stream_position()?,
@@ -38,13 +54,11 @@ impl BinRead for KafkaRequest {
                 ),
             })
         } else {
-            Ok(Self { header, body })
+            Ok(Self { size: message_size as usize, header, body })
         }
     }
 }
 
-#[binread]
-#[br(big)]
 #[derive(Debug)]
 pub(crate) enum KafkaRequestHeader {
     V2(KafkaRequestHeaderV2),
@@ -52,6 +66,67 @@ pub(crate) enum KafkaRequestHeader {
     V0(KafkaRequestHeaderV0),
 }
 
+impl KafkaRequestHeader {
+    pub(crate) fn api_key(&self) -> ApiKey {
+        match self {
+            KafkaRequestHeader::V2(header) => header.request_api_key,
+            KafkaRequestHeader::V1(header) => header.request_api_key,
+            KafkaRequestHeader::V0(header) => header.request_api_key,
+        }
+    }
+
+    pub(crate) fn api_version(&self) -> i16 {
+        match self {
+            KafkaRequestHeader::V2(header) => header.request_api_version,
+            KafkaRequestHeader::V1(header) => header.request_api_version,
+            KafkaRequestHeader::V0(header) => header.request_api_version,
+        }
+    }
+
+    pub(crate) fn correlation_id(&self) -> i32 {
+        match self {
+            KafkaRequestHeader::V2(header) => header.correlation_id,
+            KafkaRequestHeader::V1(header) => header.correlation_id,
+            KafkaRequestHeader::V0(header) => header.correlation_id,
+        }
+    }
+
+    pub(crate) fn client_id(&self) -> Option<&str> {
+        match self {
+            KafkaRequestHeader::V2(header) => header.client_id.as_deref(),
+            KafkaRequestHeader::V1(header) => header.client_id.as_deref(),
+            KafkaRequestHeader::V0(_) => None,
+        }
+    }
+
+    pub(crate) fn message_version(&self) -> MessageVersion {
+        MessageVersion::new(self.api_key(), self.api_version())
+    }
+}
+
+impl ReadEndian for KafkaRequestHeader {
+    const ENDIAN: EndianKind = EndianKind::Endian(Endian::Big);
+}
+
+impl BinRead for KafkaRequestHeader {
+    type Args<'a> = ();
+
+    /// Peeks at the api key and version to pick the header version: flexible
+    /// requests use v2, all others v1.
+    fn read_options<R: Read + Seek>(reader: &mut R, endian: Endian, _args: Self::Args<'_>) -> BinResult<Self> {
+        let start = reader.stream_position()?;
+        let api_key = ApiKey::read_options(reader, endian, ())?;
+        let api_version = i16::read_options(reader, endian, ())?;
+        reader.seek(SeekFrom::Start(start))?;
+
+        if MessageVersion::new(api_key, api_version).flexible {
+            Ok(Self::V2(KafkaRequestHeaderV2::read_options(reader, endian, ())?))
+        } else {
+            Ok(Self::V1(KafkaRequestHeaderV1::read_options(reader, endian, ())?))
+        }
+    }
+}
+
 #[binread]
 #[br(big)]
 #[derive(Debug)]
@@ -81,3 +156,262 @@ pub(crate) struct  KafkaRequestHeaderV2 {
     pub(crate) client_id: NullableString,
     _tagged_fields: TagBuffer,
 }
+
+/// The request body, decoded according to the api key and version in the
+/// header. Versions outside [`API_REGISTRY`] are not decoded.
+#[derive(Debug)]
+pub(crate) enum KafkaRequestBody {
+    /// ApiVersions v0-v2 have an empty body.
+    ApiVersions(Option<KafkaRequestApiVersionsV4>),
+    ListOffsets(KafkaRequestListOffsets),
+    DeleteRecords(KafkaRequestDeleteRecords),
+    FindCoordinator(KafkaRequestFindCoordinator),
+    JoinGroup(KafkaRequestJoinGroup),
+    SyncGroup(KafkaRequestSyncGroup),
+    Heartbeat(KafkaRequestHeartbeat),
+    LeaveGroup(KafkaRequestLeaveGroup),
+    OffsetCommit(KafkaRequestOffsetCommit),
+    OffsetFetch(KafkaRequestOffsetFetch),
+    ConsumerGroupHeartbeat(KafkaRequestConsumerGroupHeartbeat),
+    ConsumerGroupDescribe(KafkaRequestConsumerGroupDescribe),
+    DescribeGroups(KafkaRequestDescribeGroups),
+    ListGroups(KafkaRequestListGroups),
+    DeleteGroups(KafkaRequestDeleteGroups),
+    OffsetDelete(KafkaRequestOffsetDelete),
+    InitProducerId(KafkaRequestInitProducerId),
+    AddPartitionsToTxn(KafkaRequestAddPartitionsToTxn),
+    AddOffsetsToTxn(KafkaRequestAddOffsetsToTxn),
+    EndTxn(KafkaRequestEndTxn),
+    TxnOffsetCommit(KafkaRequestTxnOffsetCommit),
+    DescribeProducers(KafkaRequestDescribeProducers),
+    DescribeTransactions(KafkaRequestDescribeTransactions),
+    ListTransactions(KafkaRequestListTransactions),
+    SaslHandshake(KafkaRequestSaslHandshake),
+    SaslAuthenticate(KafkaRequestSaslAuthenticate),
+    DescribeAcls(KafkaRequestDescribeAcls),
+    CreateAcls(KafkaRequestCreateAcls),
+    DeleteAcls(KafkaRequestDeleteAcls),
+    DescribeConfigs(KafkaRequestDescribeConfigs),
+    AlterConfigs(KafkaRequestAlterConfigs),
+    IncrementalAlterConfigs(KafkaRequestIncrementalAlterConfigs),
+    DescribeClientQuotas(KafkaRequestDescribeClientQuotas),
+    AlterClientQuotas(KafkaRequestAlterClientQuotas),
+    OffsetForLeaderEpoch(KafkaRequestOffsetForLeaderEpoch),
+    DescribeTopicPartitions(KafkaRequestDescribeTopicPartitions),
+    BrokerRegistration(KafkaRequestBrokerRegistration),
+    BrokerHeartbeat(KafkaRequestBrokerHeartbeat),
+    Unsupported,
+}
+
+impl BinRead for KafkaRequestBody {
+    type Args<'a> = (ApiKey, i16);
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (api_key, api_version): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let supported = API_REGISTRY.get(&api_key).is_some_and(|versions| versions.contains(&api_version));
+        if !supported {
+            reader.seek(SeekFrom::End(0))?;
+            return Ok(Self::Unsupported);
+        }
+
+        let version = MessageVersion::new(api_key, api_version);
+        let body = match api_key {
+            ApiKey::ApiVersions if api_version < 3 => Self::ApiVersions(None),
+            ApiKey::ApiVersions => Self::ApiVersions(Some(KafkaRequestApiVersionsV4::read_options(reader, endian, ())?)),
+            ApiKey::ListOffsets => Self::ListOffsets(KafkaRequestListOffsets::read_options(reader, endian, (version,))?),
+            ApiKey::DeleteRecords => {
+                Self::DeleteRecords(KafkaRequestDeleteRecords::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::FindCoordinator => {
+                Self::FindCoordinator(KafkaRequestFindCoordinator::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::JoinGroup => Self::JoinGroup(KafkaRequestJoinGroup::read_options(reader, endian, (version,))?),
+            ApiKey::SyncGroup => Self::SyncGroup(KafkaRequestSyncGroup::read_options(reader, endian, (version,))?),
+            ApiKey::Heartbeat => Self::Heartbeat(KafkaRequestHeartbeat::read_options(reader, endian, (version,))?),
+            ApiKey::LeaveGroup => Self::LeaveGroup(KafkaRequestLeaveGroup::read_options(reader, endian, (version,))?),
+            ApiKey::OffsetCommit => {
+                Self::OffsetCommit(KafkaRequestOffsetCommit::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::OffsetFetch => {
+                Self::OffsetFetch(KafkaRequestOffsetFetch::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::ConsumerGroupHeartbeat => Self::ConsumerGroupHeartbeat(
+                KafkaRequestConsumerGroupHeartbeat::read_options(reader, endian, (version,))?,
+            ),
+            ApiKey::ConsumerGroupDescribe => Self::ConsumerGroupDescribe(
+                KafkaRequestConsumerGroupDescribe::read_options(reader, endian, (version,))?,
+            ),
+            ApiKey::DescribeGroups => {
+                Self::DescribeGroups(KafkaRequestDescribeGroups::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::ListGroups => Self::ListGroups(KafkaRequestListGroups::read_options(reader, endian, (version,))?),
+            ApiKey::DeleteGroups => {
+                Self::DeleteGroups(KafkaRequestDeleteGroups::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::OffsetDelete => {
+                Self::OffsetDelete(KafkaRequestOffsetDelete::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::InitProducerId => {
+                Self::InitProducerId(KafkaRequestInitProducerId::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::AddPartitionsToTxn => {
+                Self::AddPartitionsToTxn(KafkaRequestAddPartitionsToTxn::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::AddOffsetsToTxn => {
+                Self::AddOffsetsToTxn(KafkaRequestAddOffsetsToTxn::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::EndTxn => Self::EndTxn(KafkaRequestEndTxn::read_options(reader, endian, (version,))?),
+            ApiKey::TxnOffsetCommit => {
+                Self::TxnOffsetCommit(KafkaRequestTxnOffsetCommit::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::DescribeProducers => {
+                Self::DescribeProducers(KafkaRequestDescribeProducers::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::DescribeTransactions => {
+                Self::DescribeTransactions(KafkaRequestDescribeTransactions::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::ListTransactions => {
+                Self::ListTransactions(KafkaRequestListTransactions::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::SaslHandshake => {
+                Self::SaslHandshake(KafkaRequestSaslHandshake::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::SaslAuthenticate => {
+                Self::SaslAuthenticate(KafkaRequestSaslAuthenticate::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::DescribeAcls => {
+                Self::DescribeAcls(KafkaRequestDescribeAcls::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::CreateAcls => Self::CreateAcls(KafkaRequestCreateAcls::read_options(reader, endian, (version,))?),
+            ApiKey::DeleteAcls => Self::DeleteAcls(KafkaRequestDeleteAcls::read_options(reader, endian, (version,))?),
+            ApiKey::DescribeConfigs => {
+                Self::DescribeConfigs(KafkaRequestDescribeConfigs::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::AlterConfigs => {
+                Self::AlterConfigs(KafkaRequestAlterConfigs::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::IncrementalAlterConfigs => {
+                Self::IncrementalAlterConfigs(KafkaRequestIncrementalAlterConfigs::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::DescribeClientQuotas => {
+                Self::DescribeClientQuotas(KafkaRequestDescribeClientQuotas::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::AlterClientQuotas => {
+                Self::AlterClientQuotas(KafkaRequestAlterClientQuotas::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::OffsetForLeaderEpoch => {
+                Self::OffsetForLeaderEpoch(KafkaRequestOffsetForLeaderEpoch::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::DescribeTopicPartitions => {
+                Self::DescribeTopicPartitions(KafkaRequestDescribeTopicPartitions::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::BrokerRegistration => {
+                Self::BrokerRegistration(KafkaRequestBrokerRegistration::read_options(reader, endian, (version,))?)
+            }
+            ApiKey::BrokerHeartbeat => {
+                Self::BrokerHeartbeat(KafkaRequestBrokerHeartbeat::read_options(reader, endian, (version,))?)
+            }
+            _ => {
+                reader.seek(SeekFrom::End(0))?;
+                Self::Unsupported
+            }
+        };
+        Ok(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::request::{IsolationLevel, EARLIEST_TIMESTAMP, LATEST_TIMESTAMP};
+    use crate::kafka::types::Uuid;
+    use std::io::Cursor;
+
+    fn framed(message: Vec<u8>) -> Cursor<Vec<u8>> {
+        Cursor::new([(message.len() as i32).to_be_bytes().to_vec(), message].concat())
+    }
+
+    #[test]
+    fn test_non_flexible_list_offsets() {
+        let mut message = vec![0, 2, 0, 1, 0, 0, 0, 7, 0, 1, b'c'];
+        message.extend_from_slice(&(-1i32).to_be_bytes());
+        message.extend_from_slice(&[0, 0, 0, 1, 0, 3, b'f', b'o', b'o', 0, 0, 0, 1, 0, 0, 0, 4]);
+        message.extend_from_slice(&EARLIEST_TIMESTAMP.to_be_bytes());
+
+        let request = KafkaRequest::read_be(&mut framed(message)).unwrap();
+
+        assert!(matches!(request.header, KafkaRequestHeader::V1(_)));
+        assert_eq!(request.header.correlation_id(), 7);
+        let KafkaRequestBody::ListOffsets(body) = request.body else { panic!("expected ListOffsets") };
+        assert_eq!(body.isolation_level, IsolationLevel::ReadUncommitted);
+        assert_eq!(*body.topics[0].name, "foo");
+        assert_eq!(body.topics[0].partitions[0].partition_index, 4);
+        assert_eq!(body.topics[0].partitions[0].current_leader_epoch, -1);
+        assert_eq!(body.topics[0].partitions[0].timestamp, EARLIEST_TIMESTAMP);
+    }
+
+    #[test]
+    fn test_flexible_list_offsets() {
+        let mut message = vec![0, 2, 0, 7, 0, 0, 0, 9, 0xFF, 0xFF, 0];
+        message.extend_from_slice(&(-1i32).to_be_bytes());
+        message.extend_from_slice(&[1, 2, 4, b'f', b'o', b'o', 2, 0, 0, 0, 0, 0, 0, 0, 5]);
+        message.extend_from_slice(&LATEST_TIMESTAMP.to_be_bytes());
+        message.extend_from_slice(&[0, 0, 0]);
+
+        let request = KafkaRequest::read_be(&mut framed(message)).unwrap();
+
+        assert!(matches!(request.header, KafkaRequestHeader::V2(_)));
+        assert_eq!(request.header.client_id(), None);
+        let KafkaRequestBody::ListOffsets(body) = request.body else { panic!("expected ListOffsets") };
+        assert_eq!(body.isolation_level, IsolationLevel::ReadCommitted);
+        assert_eq!(body.topics[0].partitions[0].current_leader_epoch, 5);
+        assert_eq!(body.topics[0].partitions[0].timestamp, LATEST_TIMESTAMP);
+    }
+
+    #[test]
+    fn test_describe_topic_partitions_with_cursor() {
+        let mut message = vec![0, 75, 0, 0, 0, 0, 0, 3, 0xFF, 0xFF, 0, 2, 4, b'f', b'o', b'o', 0];
+        message.extend_from_slice(&10i32.to_be_bytes());
+        message.extend_from_slice(&[1, 4, b'f', b'o', b'o', 0, 0, 0, 3, 0, 0]);
+
+        let request = KafkaRequest::read_be(&mut framed(message)).unwrap();
+
+        let KafkaRequestBody::DescribeTopicPartitions(body) = request.body else {
+            panic!("expected DescribeTopicPartitions")
+        };
+        assert_eq!(*body.topics[0].name, "foo");
+        assert_eq!(body.response_partition_limit, 10);
+        let cursor = body.cursor.unwrap();
+        assert_eq!((cursor.topic_name.as_str(), cursor.partition_index), ("foo", 3));
+    }
+
+    #[test]
+    fn test_broker_registration_v3() {
+        let mut message = vec![0, 62, 0, 3, 0, 0, 0, 1, 0xFF, 0xFF, 0, 0, 0, 0, 2, 2, b'c'];
+        message.extend_from_slice(&[7; 16]);
+        message.extend_from_slice(&[2, 3, b'P', b'L', 2, b'h', 0x23, 0x84, 0, 0, 0, 1, 0, 0, 2]);
+        message.extend_from_slice(&[9; 16]);
+        message.extend_from_slice(&(-1i64).to_be_bytes());
+        message.push(0);
+
+        let request = KafkaRequest::read_be(&mut framed(message)).unwrap();
+
+        let KafkaRequestBody::BrokerRegistration(body) = request.body else { panic!("expected BrokerRegistration") };
+        assert_eq!((body.broker_id, body.incarnation_id), (2, Uuid([7; 16])));
+        let listener = &body.listeners[0];
+        assert_eq!((listener.name.as_str(), listener.host.as_str(), listener.port), ("PL", "h", 9092));
+        assert!(body.features.is_empty());
+        assert_eq!(*body.rack, None);
+        assert_eq!(*body.log_dirs, vec![Uuid([9; 16])]);
+        assert_eq!(body.previous_broker_epoch, -1);
+    }
+
+    #[test]
+    fn test_unsupported_version_is_skipped() {
+        let request = KafkaRequest::read_be(&mut framed(vec![0, 2, 0, 0, 0, 0, 0, 1, 0xFF, 0xFF, 1, 2, 3])).unwrap();
+        assert!(matches!(request.body, KafkaRequestBody::Unsupported));
+    }
+}
@@ -0,0 +1,17 @@
+use crate::kafka::types::{KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestHeartbeat {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    pub(crate) generation_id: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(if(v.version >= 3), args(v.flexible))]
+    pub(crate) group_instance_id: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,42 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestIncrementalAlterConfigs {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) resources: KafkaArray<IncrementalAlterConfigsResource>,
+    #[br(map = |validate_only: u8| validate_only != 0)]
+    #[bw(map = |validate_only: &bool| u8::from(*validate_only))]
+    pub(crate) validate_only: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct IncrementalAlterConfigsResource {
+    pub(crate) resource_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) configs: KafkaArray<IncrementalAlterableConfig>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct IncrementalAlterableConfig {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    /// 0 to set, 1 to delete, 2 to append to and 3 to subtract from a list.
+    pub(crate) config_operation: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) value: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,19 @@
+use crate::kafka::types::{KafkaNullableString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestInitProducerId {
+    /// `None` for idempotent producers outside transactions.
+    #[brw(args(v.flexible))]
+    pub(crate) transactional_id: KafkaNullableString,
+    pub(crate) transaction_timeout_ms: i32,
+    /// The current producer id and epoch when bumping the epoch (KIP-360).
+    #[brw(if(v.version >= 3, -1))]
+    pub(crate) producer_id: i64,
+    #[brw(if(v.version >= 3, -1))]
+    pub(crate) producer_epoch: i16,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
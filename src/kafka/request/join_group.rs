@@ -0,0 +1,39 @@
+use crate::kafka::types::{KafkaArray, KafkaBytes, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestJoinGroup {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    pub(crate) session_timeout_ms: i32,
+    /// Defaults to the session timeout before v1.
+    #[brw(if(v.version >= 1, -1))]
+    pub(crate) rebalance_timeout_ms: i32,
+    /// Empty on the first join of a member.
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(if(v.version >= 5), args(v.flexible))]
+    pub(crate) group_instance_id: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) protocol_type: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) protocols: KafkaArray<JoinGroupRequestProtocol>,
+    #[brw(if(v.version >= 8), args(v.flexible))]
+    pub(crate) reason: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct JoinGroupRequestProtocol {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) metadata: KafkaBytes,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
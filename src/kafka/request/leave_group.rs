@@ -0,0 +1,31 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestLeaveGroup {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    /// The single leaving member, replaced by `members` in v3.
+    #[brw(if(v.version <= 2), args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(if(v.version >= 3), args(v.flexible, (v,)))]
+    pub(crate) members: KafkaArray<LeaveGroupRequestMember>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LeaveGroupRequestMember {
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) group_instance_id: KafkaNullableString,
+    #[brw(if(v.version >= 5), args(v.flexible))]
+    pub(crate) reason: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,16 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestListGroups {
+    /// Only groups in these states are listed, all groups if empty.
+    #[brw(if(v.version >= 4), args(v.flexible, (v.flexible,)))]
+    pub(crate) states_filter: KafkaArray<KafkaString>,
+    /// Only groups of these types are listed, all groups if empty.
+    #[brw(if(v.version >= 5), args(v.flexible, (v.flexible,)))]
+    pub(crate) types_filter: KafkaArray<KafkaString>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
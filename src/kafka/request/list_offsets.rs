@@ -0,0 +1,56 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+pub(crate) const LATEST_TIMESTAMP: i64 = -1;
+pub(crate) const EARLIEST_TIMESTAMP: i64 = -2;
+pub(crate) const MAX_TIMESTAMP: i64 = -3;
+pub(crate) const EARLIEST_LOCAL_TIMESTAMP: i64 = -4;
+pub(crate) const LATEST_TIERED_TIMESTAMP: i64 = -5;
+
+#[binrw]
+#[brw(big, repr = i8)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum IsolationLevel {
+    #[default]
+    ReadUncommitted = 0,
+    ReadCommitted = 1,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestListOffsets {
+    /// The broker id of a follower, or -1 for consumers.
+    pub(crate) replica_id: i32,
+    #[brw(if(v.version >= 2))]
+    pub(crate) isolation_level: IsolationLevel,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<ListOffsetsTopic>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct ListOffsetsTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<ListOffsetsPartition>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct ListOffsetsPartition {
+    pub(crate) partition_index: i32,
+    #[brw(if(v.version >= 4, -1))]
+    pub(crate) current_leader_epoch: i32,
+    /// A timestamp in milliseconds, or one of the special timestamps above.
+    pub(crate) timestamp: i64,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
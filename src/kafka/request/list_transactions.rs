@@ -0,0 +1,20 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestListTransactions {
+    /// Only transactions in these states are listed, all if empty.
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) state_filters: KafkaArray<KafkaString>,
+    /// Only transactions of these producers are listed, all if empty.
+    #[brw(args(v.flexible, ()))]
+    pub(crate) producer_id_filters: KafkaArray<i64>,
+    /// From v1, only transactions running at least this long are listed,
+    /// all if -1.
+    #[brw(if(v.version >= 1, -1))]
+    pub(crate) duration_filter: i64,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
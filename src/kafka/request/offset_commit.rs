@@ -0,0 +1,50 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestOffsetCommit {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    /// The generation of a classic group, -1 for commits from outside the
+    /// group. The member epoch for consumer groups.
+    pub(crate) generation_id_or_member_epoch: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(if(v.version >= 7), args(v.flexible))]
+    pub(crate) group_instance_id: KafkaNullableString,
+    /// Ignored, offsets are kept for `offsets.retention.minutes`.
+    #[brw(if(v.version >= 2 && v.version <= 4, -1))]
+    pub(crate) retention_time_ms: i64,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<OffsetCommitRequestTopic>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetCommitRequestTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<OffsetCommitRequestPartition>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetCommitRequestPartition {
+    pub(crate) partition_index: i32,
+    pub(crate) committed_offset: i64,
+    #[brw(if(v.version >= 6, -1))]
+    pub(crate) committed_leader_epoch: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) committed_metadata: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
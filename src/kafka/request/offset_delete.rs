@@ -0,0 +1,22 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestOffsetDelete {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<OffsetDeleteRequestTopic>,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OffsetDeleteRequestTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) partitions: KafkaArray<i32>,
+}
@@ -0,0 +1,50 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestOffsetFetch {
+    /// The single group fetched before v8.
+    #[brw(if(v.version <= 7), args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    /// Null fetches every committed offset. Only nullable from v2.
+    #[brw(if(v.version <= 7), args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaNullableArray<OffsetFetchRequestTopic>,
+    #[brw(if(v.version >= 8), args(v.flexible, (v,)))]
+    pub(crate) groups: KafkaArray<OffsetFetchRequestGroup>,
+    #[brw(if(v.version >= 7))]
+    #[br(map = |stable: u8| stable != 0)]
+    #[bw(map = |stable: &bool| u8::from(*stable))]
+    pub(crate) require_stable: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetFetchRequestGroup {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    #[brw(if(v.version >= 9), args(v.flexible))]
+    pub(crate) member_id: KafkaNullableString,
+    #[brw(if(v.version >= 9, -1))]
+    pub(crate) member_epoch: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaNullableArray<OffsetFetchRequestTopic>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetFetchRequestTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) partition_indexes: KafkaArray<i32>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
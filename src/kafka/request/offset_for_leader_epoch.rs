@@ -0,0 +1,41 @@
+use crate::kafka::types::{KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestOffsetForLeaderEpoch {
+    /// The broker id of a follower, -1 for consumers and -2 for debugging.
+    #[brw(if(v.version >= 3, -2))]
+    pub(crate) replica_id: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<OffsetForLeaderTopic>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetForLeaderTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) topic: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<OffsetForLeaderPartition>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetForLeaderPartition {
+    pub(crate) partition: i32,
+    /// The epoch the client believes the leader is on, -1 to skip fencing.
+    #[brw(if(v.version >= 2, -1))]
+    pub(crate) current_leader_epoch: i32,
+    /// The epoch to look up the end offset of.
+    pub(crate) leader_epoch: i32,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
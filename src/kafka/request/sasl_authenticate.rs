@@ -0,0 +1,13 @@
+use crate::kafka::types::{KafkaBytes, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestSaslAuthenticate {
+    /// The next token of the mechanism's exchange.
+    #[brw(args(v.flexible))]
+    pub(crate) auth_bytes: KafkaBytes,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
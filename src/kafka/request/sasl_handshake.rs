@@ -0,0 +1,10 @@
+use crate::kafka::types::{KafkaString, MessageVersion};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestSaslHandshake {
+    #[brw(args(v.flexible))]
+    pub(crate) mechanism: KafkaString,
+}
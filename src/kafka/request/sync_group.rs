@@ -0,0 +1,36 @@
+use crate::kafka::types::{KafkaArray, KafkaBytes, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestSyncGroup {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    pub(crate) generation_id: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(if(v.version >= 3), args(v.flexible))]
+    pub(crate) group_instance_id: KafkaNullableString,
+    #[brw(if(v.version >= 5), args(v.flexible))]
+    pub(crate) protocol_type: KafkaNullableString,
+    #[brw(if(v.version >= 5), args(v.flexible))]
+    pub(crate) protocol_name: KafkaNullableString,
+    /// Only sent by the leader.
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) assignments: KafkaArray<SyncGroupRequestAssignment>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct SyncGroupRequestAssignment {
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) assignment: KafkaBytes,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,51 @@
+use crate::kafka::types::{KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct KafkaRequestTxnOffsetCommit {
+    #[brw(args(v.flexible))]
+    pub(crate) transactional_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    /// The member's generation from v3 (KIP-447), -1 before.
+    #[brw(if(v.version >= 3, -1))]
+    pub(crate) generation_id: i32,
+    #[brw(if(v.version >= 3), args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(if(v.version >= 3), args(v.flexible))]
+    pub(crate) group_instance_id: KafkaNullableString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<TxnOffsetCommitRequestTopic>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct TxnOffsetCommitRequestTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<TxnOffsetCommitRequestPartition>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct TxnOffsetCommitRequestPartition {
+    pub(crate) partition_index: i32,
+    pub(crate) committed_offset: i64,
+    #[brw(if(v.version >= 2, -1))]
+    pub(crate) committed_leader_epoch: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) committed_metadata: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -3,8 +3,86 @@ pub(crate) use api_versions_v3::*;
 mod api_versions_v4;
 pub(crate) use api_versions_v4::*;
 mod generic_response;
+mod kafka_response;
 mod response_header_v0;
+mod response_header_v1;
 mod common;
+mod list_offsets;
+mod delete_records;
+mod find_coordinator;
+mod join_group;
+mod sync_group;
+mod heartbeat;
+mod leave_group;
+mod offset_commit;
+mod offset_fetch;
+mod consumer_group_heartbeat;
+mod consumer_group_describe;
+mod describe_groups;
+mod list_groups;
+mod delete_groups;
+mod offset_delete;
+mod init_producer_id;
+mod add_partitions_to_txn;
+mod add_offsets_to_txn;
+mod end_txn;
+mod txn_offset_commit;
+mod describe_producers;
+mod describe_transactions;
+mod list_transactions;
+mod sasl_handshake;
+mod sasl_authenticate;
+mod describe_acls;
+mod create_acls;
+mod delete_acls;
+mod describe_configs;
+mod alter_configs;
+mod incremental_alter_configs;
+mod describe_client_quotas;
+mod alter_client_quotas;
+mod offset_for_leader_epoch;
+mod describe_topic_partitions;
+mod broker_registration;
+mod broker_heartbeat;
 
 pub(crate) use generic_response::*;
-pub(crate) use response_header_v0::*;
\ No newline at end of file
+pub(crate) use kafka_response::*;
+pub(crate) use response_header_v0::*;
+pub(crate) use response_header_v1::*;
+pub(crate) use list_offsets::*;
+pub(crate) use delete_records::*;
+pub(crate) use find_coordinator::*;
+pub(crate) use join_group::*;
+pub(crate) use sync_group::*;
+pub(crate) use heartbeat::*;
+pub(crate) use leave_group::*;
+pub(crate) use offset_commit::*;
+pub(crate) use offset_fetch::*;
+pub(crate) use consumer_group_heartbeat::*;
+pub(crate) use consumer_group_describe::*;
+pub(crate) use describe_groups::*;
+pub(crate) use list_groups::*;
+pub(crate) use delete_groups::*;
+pub(crate) use offset_delete::*;
+pub(crate) use init_producer_id::*;
+pub(crate) use add_partitions_to_txn::*;
+pub(crate) use add_offsets_to_txn::*;
+pub(crate) use end_txn::*;
+pub(crate) use txn_offset_commit::*;
+pub(crate) use describe_producers::*;
+pub(crate) use describe_transactions::*;
+pub(crate) use list_transactions::*;
+pub(crate) use sasl_handshake::*;
+pub(crate) use sasl_authenticate::*;
+pub(crate) use describe_acls::*;
+pub(crate) use create_acls::*;
+pub(crate) use delete_acls::*;
+pub(crate) use describe_configs::*;
+pub(crate) use alter_configs::*;
+pub(crate) use incremental_alter_configs::*;
+pub(crate) use describe_client_quotas::*;
+pub(crate) use alter_client_quotas::*;
+pub(crate) use offset_for_leader_epoch::*;
+pub(crate) use describe_topic_partitions::*;
+pub(crate) use broker_registration::*;
+pub(crate) use broker_heartbeat::*;
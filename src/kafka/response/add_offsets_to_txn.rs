@@ -0,0 +1,12 @@
+use crate::kafka::types::{ErrorCode, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseAddOffsetsToTxn {
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
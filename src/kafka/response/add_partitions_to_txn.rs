@@ -0,0 +1,35 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseAddPartitionsToTxn {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) results: KafkaArray<AddPartitionsToTxnTopicResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AddPartitionsToTxnTopicResult {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) results: KafkaArray<AddPartitionsToTxnPartitionResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AddPartitionsToTxnPartitionResult {
+    pub(crate) partition_index: i32,
+    pub(crate) partition_error_code: ErrorCode,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
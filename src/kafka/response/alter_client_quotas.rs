@@ -0,0 +1,27 @@
+use crate::kafka::response::QuotaEntityData;
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseAlterClientQuotas {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) entries: KafkaArray<AlterClientQuotasEntryResponse>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AlterClientQuotasEntryResponse {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) entity: KafkaArray<QuotaEntityData>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
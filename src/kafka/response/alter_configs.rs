@@ -0,0 +1,29 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseAlterConfigs {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) responses: KafkaArray<AlterConfigsResourceResponse>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+/// The result of altering one resource, in AlterConfigs and
+/// IncrementalAlterConfigs responses.
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AlterConfigsResourceResponse {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    pub(crate) resource_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name: KafkaString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,21 @@
+use crate::kafka::types::{ErrorCode, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseBrokerHeartbeat {
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    #[br(map = |x: u8| x != 0)]
+    #[bw(map = |x: &bool| u8::from(*x))]
+    pub(crate) is_caught_up: bool,
+    #[br(map = |x: u8| x != 0)]
+    #[bw(map = |x: &bool| u8::from(*x))]
+    pub(crate) is_fenced: bool,
+    #[br(map = |x: u8| x != 0)]
+    #[bw(map = |x: &bool| u8::from(*x))]
+    pub(crate) should_shut_down: bool,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
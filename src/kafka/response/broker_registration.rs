@@ -0,0 +1,14 @@
+use crate::kafka::types::{ErrorCode, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseBrokerRegistration {
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    /// -1 on error.
+    pub(crate) broker_epoch: i64,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,89 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer, Uuid};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseConsumerGroupDescribe {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) groups: KafkaArray<ConsumerGroupDescribeResponseGroup>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConsumerGroupDescribeResponseGroup {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) group_state: KafkaString,
+    pub(crate) group_epoch: i32,
+    pub(crate) assignment_epoch: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) assignor_name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) members: KafkaArray<ConsumerGroupDescribeResponseMember>,
+    /// `i32::MIN` unless requested.
+    pub(crate) authorized_operations: i32,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConsumerGroupDescribeResponseMember {
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) instance_id: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) rack_id: KafkaNullableString,
+    pub(crate) member_epoch: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) client_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) client_host: KafkaString,
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) subscribed_topic_names: KafkaArray<KafkaString>,
+    #[brw(args(v.flexible))]
+    pub(crate) subscribed_topic_regex: KafkaNullableString,
+    #[brw(args(v))]
+    pub(crate) assignment: ConsumerGroupDescribeResponseAssignment,
+    #[brw(args(v))]
+    pub(crate) target_assignment: ConsumerGroupDescribeResponseAssignment,
+    /// 0 for classic and 1 for consumer protocol members.
+    #[brw(if(v.version >= 1, -1))]
+    pub(crate) member_type: i8,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConsumerGroupDescribeResponseAssignment {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topic_partitions: KafkaArray<ConsumerGroupDescribeResponseTopicPartitions>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConsumerGroupDescribeResponseTopicPartitions {
+    pub(crate) topic_id: Uuid,
+    #[brw(args(v.flexible))]
+    pub(crate) topic_name: KafkaString,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) partitions: KafkaArray<i32>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
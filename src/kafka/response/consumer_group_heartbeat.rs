@@ -0,0 +1,47 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, MessageVersion, TagBuffer, Uuid};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseConsumerGroupHeartbeat {
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaNullableString,
+    pub(crate) member_epoch: i32,
+    pub(crate) heartbeat_interval_ms: i32,
+    /// A nullable struct, prefixed by -1 if null and 1 otherwise.
+    #[br(temp)]
+    #[bw(calc = if assignment.is_some() { 1 } else { -1 })]
+    assignment_marker: i8,
+    /// Null if the assignment did not change.
+    #[br(if(assignment_marker >= 0), args(v))]
+    #[bw(args(v))]
+    pub(crate) assignment: Option<ConsumerGroupHeartbeatResponseAssignment>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConsumerGroupHeartbeatResponseAssignment {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topic_partitions: KafkaArray<ConsumerGroupHeartbeatResponseTopicPartitions>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConsumerGroupHeartbeatResponseTopicPartitions {
+    pub(crate) topic_id: Uuid,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) partitions: KafkaArray<i32>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
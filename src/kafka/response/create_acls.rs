@@ -0,0 +1,25 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseCreateAcls {
+    pub(crate) throttle_time_ms: i32,
+    /// One result per creation, in request order.
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) results: KafkaArray<AclCreationResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AclCreationResult {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
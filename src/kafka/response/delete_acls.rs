@@ -0,0 +1,49 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDeleteAcls {
+    pub(crate) throttle_time_ms: i32,
+    /// One result per filter, in request order.
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) filter_results: KafkaArray<DeleteAclsFilterResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeleteAclsFilterResult {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) matching_acls: KafkaArray<DeleteAclsMatchingAcl>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeleteAclsMatchingAcl {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    pub(crate) resource_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name: KafkaString,
+    #[brw(if(v.version >= 1, 3))]
+    pub(crate) pattern_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) principal: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) host: KafkaString,
+    pub(crate) operation: i8,
+    pub(crate) permission_type: i8,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
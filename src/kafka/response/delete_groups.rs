@@ -0,0 +1,24 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDeleteGroups {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) results: KafkaArray<DeleteGroupsResponseResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeleteGroupsResponseResult {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    pub(crate) error_code: ErrorCode,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,43 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDeleteRecords {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<DeleteRecordsTopicResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeleteRecordsTopicResult {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<DeleteRecordsPartitionResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct DeleteRecordsPartitionResult {
+    pub(crate) partition_index: i32,
+    /// The log start offset after the deletion, or -1 on error.
+    pub(crate) low_watermark: i64,
+    pub(crate) error_code: ErrorCode,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+impl DeleteRecordsPartitionResult {
+    pub(crate) fn new(partition_index: i32, low_watermark: i64, error_code: ErrorCode) -> Self {
+        Self { partition_index, low_watermark, error_code, _tagged_fields: TagBuffer }
+    }
+}
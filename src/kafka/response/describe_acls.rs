@@ -0,0 +1,45 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDescribeAcls {
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) resources: KafkaArray<DescribeAclsResource>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeAclsResource {
+    pub(crate) resource_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name: KafkaString,
+    #[brw(if(v.version >= 1, 3))]
+    pub(crate) pattern_type: i8,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) acls: KafkaArray<AclDescription>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AclDescription {
+    #[brw(args(v.flexible))]
+    pub(crate) principal: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) host: KafkaString,
+    pub(crate) operation: i8,
+    pub(crate) permission_type: i8,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,57 @@
+use crate::kafka::types::{
+    ErrorCode, KafkaArray, KafkaNullableArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer,
+};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDescribeClientQuotas {
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    /// `None` if the request failed.
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) entries: KafkaNullableArray<DescribeClientQuotasEntry>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeClientQuotasEntry {
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) entity: KafkaArray<QuotaEntityData>,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) values: KafkaArray<QuotaValueData>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+/// One component of a quota entity, in DescribeClientQuotas and
+/// AlterClientQuotas responses.
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct QuotaEntityData {
+    #[brw(args(v.flexible))]
+    pub(crate) entity_type: KafkaString,
+    /// `None` for the default entity of the type.
+    #[brw(args(v.flexible))]
+    pub(crate) entity_name: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct QuotaValueData {
+    #[brw(args(v.flexible))]
+    pub(crate) key: KafkaString,
+    pub(crate) value: f64,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,73 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDescribeConfigs {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) results: KafkaArray<DescribeConfigsResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeConfigsResult {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    pub(crate) resource_type: i8,
+    #[brw(args(v.flexible))]
+    pub(crate) resource_name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) configs: KafkaArray<DescribeConfigsResourceResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeConfigsResourceResult {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) value: KafkaNullableString,
+    #[br(map = |read_only: u8| read_only != 0)]
+    #[bw(map = |read_only: &bool| u8::from(*read_only))]
+    pub(crate) read_only: bool,
+    /// Version 0 only tells whether the config has its default value.
+    #[brw(if(v.version == 0))]
+    #[br(map = |is_default: u8| is_default != 0)]
+    #[bw(map = |is_default: &bool| u8::from(*is_default))]
+    pub(crate) is_default: bool,
+    #[brw(if(v.version >= 1))]
+    pub(crate) config_source: i8,
+    #[br(map = |is_sensitive: u8| is_sensitive != 0)]
+    #[bw(map = |is_sensitive: &bool| u8::from(*is_sensitive))]
+    pub(crate) is_sensitive: bool,
+    #[brw(if(v.version >= 1), args(v.flexible, (v,)))]
+    pub(crate) synonyms: KafkaArray<DescribeConfigsSynonym>,
+    #[brw(if(v.version >= 3))]
+    pub(crate) config_type: i8,
+    #[brw(if(v.version >= 3), args(v.flexible))]
+    pub(crate) documentation: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeConfigsSynonym {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) value: KafkaNullableString,
+    pub(crate) source: i8,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,58 @@
+use crate::kafka::types::{
+    ErrorCode, KafkaArray, KafkaBytes, KafkaNullableString, KafkaString, MessageVersion, TagBuffer,
+};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDescribeGroups {
+    #[brw(if(v.version >= 1))]
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) groups: KafkaArray<DescribeGroupsResponseGroup>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeGroupsResponseGroup {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) group_state: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) protocol_type: KafkaString,
+    /// The selected protocol while the group is stable.
+    #[brw(args(v.flexible))]
+    pub(crate) protocol_data: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) members: KafkaArray<DescribeGroupsResponseMember>,
+    #[brw(if(v.version >= 3))]
+    pub(crate) authorized_operations: i32,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeGroupsResponseMember {
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(if(v.version >= 4), args(v.flexible))]
+    pub(crate) group_instance_id: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) client_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) client_host: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) member_metadata: KafkaBytes,
+    #[brw(args(v.flexible))]
+    pub(crate) member_assignment: KafkaBytes,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,75 @@
+use crate::kafka::types::{
+    ErrorCode, KafkaArray, KafkaNullableArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer, Uuid,
+};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDescribeTopicPartitions {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<DescribeTopicPartitionsResponseTopic>,
+    /// A nullable struct, prefixed by -1 if null and 1 otherwise.
+    #[br(temp)]
+    #[bw(calc = if next_cursor.is_some() { 1 } else { -1 })]
+    next_cursor_marker: i8,
+    /// Where the next request continues, null if every partition was
+    /// described.
+    #[br(if(next_cursor_marker >= 0), args(v))]
+    #[bw(args(v))]
+    pub(crate) next_cursor: Option<DescribeTopicPartitionsResponseCursor>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeTopicPartitionsResponseTopic {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaNullableString,
+    pub(crate) topic_id: Uuid,
+    #[br(map = |internal: u8| internal != 0)]
+    #[bw(map = |internal: &bool| u8::from(*internal))]
+    pub(crate) is_internal: bool,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<DescribeTopicPartitionsResponsePartition>,
+    pub(crate) topic_authorized_operations: i32,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeTopicPartitionsResponsePartition {
+    pub(crate) error_code: ErrorCode,
+    pub(crate) partition_index: i32,
+    pub(crate) leader_id: i32,
+    pub(crate) leader_epoch: i32,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) replica_nodes: KafkaArray<i32>,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) isr_nodes: KafkaArray<i32>,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) eligible_leader_replicas: KafkaNullableArray<i32>,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) last_known_elr: KafkaNullableArray<i32>,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) offline_replicas: KafkaArray<i32>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeTopicPartitionsResponseCursor {
+    #[brw(args(v.flexible))]
+    pub(crate) topic_name: KafkaString,
+    pub(crate) partition_index: i32,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,45 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseDescribeTransactions {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) transaction_states: KafkaArray<DescribeTransactionsResponseState>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeTransactionsResponseState {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) transactional_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) transaction_state: KafkaString,
+    pub(crate) transaction_timeout_ms: i32,
+    pub(crate) transaction_start_time_ms: i64,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    /// The partitions of the ongoing transaction.
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<DescribeTransactionsResponseTopic>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DescribeTransactionsResponseTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) topic: KafkaString,
+    #[brw(args(v.flexible, ()))]
+    pub(crate) partitions: KafkaArray<i32>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,41 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseFindCoordinator {
+    #[brw(if(v.version >= 1))]
+    pub(crate) throttle_time_ms: i32,
+    #[brw(if(v.version <= 3))]
+    pub(crate) error_code: ErrorCode,
+    #[brw(if(v.version >= 1 && v.version <= 3), args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(if(v.version <= 3))]
+    pub(crate) node_id: i32,
+    #[brw(if(v.version <= 3), args(v.flexible))]
+    pub(crate) host: KafkaString,
+    #[brw(if(v.version <= 3))]
+    pub(crate) port: i32,
+    #[brw(if(v.version >= 4), args(v.flexible, (v,)))]
+    pub(crate) coordinators: KafkaArray<Coordinator>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Coordinator {
+    #[brw(args(v.flexible))]
+    pub(crate) key: KafkaString,
+    pub(crate) node_id: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) host: KafkaString,
+    pub(crate) port: i32,
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
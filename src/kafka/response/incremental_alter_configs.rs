@@ -0,0 +1,14 @@
+use crate::kafka::response::AlterConfigsResourceResponse;
+use crate::kafka::types::{KafkaArray, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseIncrementalAlterConfigs {
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) responses: KafkaArray<AlterConfigsResourceResponse>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
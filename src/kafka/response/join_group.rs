@@ -0,0 +1,45 @@
+use crate::kafka::types::{
+    ErrorCode, KafkaArray, KafkaBytes, KafkaNullableString, KafkaString, MessageVersion, TagBuffer,
+};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseJoinGroup {
+    #[brw(if(v.version >= 2))]
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    pub(crate) generation_id: i32,
+    #[brw(if(v.version >= 7), args(v.flexible))]
+    pub(crate) protocol_type: KafkaNullableString,
+    /// Only nullable from v7, older versions need an empty string instead.
+    #[brw(args(v.flexible))]
+    pub(crate) protocol_name: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) leader: KafkaString,
+    #[brw(if(v.version >= 9))]
+    #[br(map = |skip: u8| skip != 0)]
+    #[bw(map = |skip: &bool| u8::from(*skip))]
+    pub(crate) skip_assignment: bool,
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) members: KafkaArray<JoinGroupResponseMember>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct JoinGroupResponseMember {
+    #[brw(args(v.flexible))]
+    pub(crate) member_id: KafkaString,
+    #[brw(if(v.version >= 5), args(v.flexible))]
+    pub(crate) group_instance_id: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) metadata: KafkaBytes,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,196 @@
+use crate::kafka::proto::ApiVersionsResponse;
+use crate::kafka::request::generic_request::KafkaRequestHeader;
+use crate::kafka::response::{
+    KafkaGenericResponse, KafkaResponseAddOffsetsToTxn, KafkaResponseAddPartitionsToTxn, KafkaResponseAlterClientQuotas,
+    KafkaResponseAlterConfigs, KafkaResponseBrokerHeartbeat, KafkaResponseBrokerRegistration,
+    KafkaResponseConsumerGroupDescribe, KafkaResponseConsumerGroupHeartbeat, KafkaResponseCreateAcls,
+    KafkaResponseDeleteAcls, KafkaResponseDeleteGroups, KafkaResponseDeleteRecords, KafkaResponseDescribeAcls,
+    KafkaResponseDescribeClientQuotas, KafkaResponseDescribeConfigs, KafkaResponseDescribeGroups,
+    KafkaResponseDescribeProducers, KafkaResponseDescribeTopicPartitions, KafkaResponseDescribeTransactions,
+    KafkaResponseEndTxn, KafkaResponseFindCoordinator, KafkaResponseHeaderV0, KafkaResponseHeaderV1,
+    KafkaResponseHeartbeat, KafkaResponseIncrementalAlterConfigs, KafkaResponseInitProducerId, KafkaResponseJoinGroup,
+    KafkaResponseLeaveGroup, KafkaResponseListGroups, KafkaResponseListOffsets, KafkaResponseListTransactions,
+    KafkaResponseOffsetCommit, KafkaResponseOffsetDelete, KafkaResponseOffsetFetch, KafkaResponseOffsetForLeaderEpoch,
+    KafkaResponseSaslAuthenticate, KafkaResponseSaslHandshake, KafkaResponseSyncGroup, KafkaResponseTxnOffsetCommit,
+};
+use crate::kafka::types::{ApiKey, MessageVersion};
+use binrw::meta::{EndianKind, WriteEndian};
+use binrw::{BinResult, BinWrite, Endian};
+use std::io::{Seek, Write};
+
+pub(crate) type KafkaResponse = KafkaGenericResponse<KafkaResponseHeader, KafkaResponseBody>;
+
+#[derive(Debug)]
+pub(crate) enum KafkaResponseHeader {
+    V0(KafkaResponseHeaderV0),
+    V1(KafkaResponseHeaderV1),
+}
+
+impl KafkaResponseHeader {
+    /// Flexible responses use header v1, except ApiVersions which always
+    /// uses v0 so clients can parse it before knowing the broker's versions.
+    pub(crate) fn for_request(header: &KafkaRequestHeader) -> Self {
+        let correlation_id = header.correlation_id();
+        if header.api_key() != ApiKey::ApiVersions && header.message_version().flexible {
+            Self::V1(KafkaResponseHeaderV1::new(correlation_id))
+        } else {
+            Self::V0(KafkaResponseHeaderV0::new(correlation_id))
+        }
+    }
+}
+
+impl BinWrite for KafkaResponseHeader {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _: Endian, _: Self::Args<'_>) -> BinResult<()> {
+        match self {
+            KafkaResponseHeader::V0(header) => header.write_be(writer),
+            KafkaResponseHeader::V1(header) => header.write_be(writer),
+        }
+    }
+}
+
+impl WriteEndian for KafkaResponseHeader {
+    const ENDIAN: EndianKind = EndianKind::Endian(Endian::Big);
+}
+
+/// A response body together with the version it is encoded with.
+#[derive(Debug)]
+pub(crate) enum KafkaResponseBody {
+    ApiVersions(ApiVersionsResponse),
+    ListOffsets(MessageVersion, KafkaResponseListOffsets),
+    DeleteRecords(MessageVersion, KafkaResponseDeleteRecords),
+    FindCoordinator(MessageVersion, KafkaResponseFindCoordinator),
+    JoinGroup(MessageVersion, KafkaResponseJoinGroup),
+    SyncGroup(MessageVersion, KafkaResponseSyncGroup),
+    Heartbeat(MessageVersion, KafkaResponseHeartbeat),
+    LeaveGroup(MessageVersion, KafkaResponseLeaveGroup),
+    OffsetCommit(MessageVersion, KafkaResponseOffsetCommit),
+    OffsetFetch(MessageVersion, KafkaResponseOffsetFetch),
+    ConsumerGroupHeartbeat(MessageVersion, KafkaResponseConsumerGroupHeartbeat),
+    ConsumerGroupDescribe(MessageVersion, KafkaResponseConsumerGroupDescribe),
+    DescribeGroups(MessageVersion, KafkaResponseDescribeGroups),
+    ListGroups(MessageVersion, KafkaResponseListGroups),
+    DeleteGroups(MessageVersion, KafkaResponseDeleteGroups),
+    OffsetDelete(MessageVersion, KafkaResponseOffsetDelete),
+    InitProducerId(MessageVersion, KafkaResponseInitProducerId),
+    AddPartitionsToTxn(MessageVersion, KafkaResponseAddPartitionsToTxn),
+    AddOffsetsToTxn(MessageVersion, KafkaResponseAddOffsetsToTxn),
+    EndTxn(MessageVersion, KafkaResponseEndTxn),
+    TxnOffsetCommit(MessageVersion, KafkaResponseTxnOffsetCommit),
+    DescribeProducers(MessageVersion, KafkaResponseDescribeProducers),
+    DescribeTransactions(MessageVersion, KafkaResponseDescribeTransactions),
+    ListTransactions(MessageVersion, KafkaResponseListTransactions),
+    SaslHandshake(MessageVersion, KafkaResponseSaslHandshake),
+    SaslAuthenticate(MessageVersion, KafkaResponseSaslAuthenticate),
+    DescribeAcls(MessageVersion, KafkaResponseDescribeAcls),
+    CreateAcls(MessageVersion, KafkaResponseCreateAcls),
+    DeleteAcls(MessageVersion, KafkaResponseDeleteAcls),
+    DescribeConfigs(MessageVersion, KafkaResponseDescribeConfigs),
+    AlterConfigs(MessageVersion, KafkaResponseAlterConfigs),
+    IncrementalAlterConfigs(MessageVersion, KafkaResponseIncrementalAlterConfigs),
+    DescribeClientQuotas(MessageVersion, KafkaResponseDescribeClientQuotas),
+    AlterClientQuotas(MessageVersion, KafkaResponseAlterClientQuotas),
+    OffsetForLeaderEpoch(MessageVersion, KafkaResponseOffsetForLeaderEpoch),
+    DescribeTopicPartitions(MessageVersion, KafkaResponseDescribeTopicPartitions),
+    BrokerRegistration(MessageVersion, KafkaResponseBrokerRegistration),
+    BrokerHeartbeat(MessageVersion, KafkaResponseBrokerHeartbeat),
+}
+
+impl KafkaResponseBody {
+    /// Tells the client how long it is throttled for. The SASL responses
+    /// have no throttle time.
+    pub(crate) fn set_throttle_time_ms(&mut self, throttle_time_ms: i32) {
+        match self {
+            KafkaResponseBody::ApiVersions(ApiVersionsResponse::V3(body)) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::ApiVersions(ApiVersionsResponse::V4(body)) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::ListOffsets(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DeleteRecords(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::FindCoordinator(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::JoinGroup(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::SyncGroup(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::Heartbeat(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::LeaveGroup(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::OffsetCommit(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::OffsetFetch(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::ConsumerGroupHeartbeat(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::ConsumerGroupDescribe(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DescribeGroups(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::ListGroups(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DeleteGroups(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::OffsetDelete(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::InitProducerId(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::AddPartitionsToTxn(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::AddOffsetsToTxn(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::EndTxn(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::TxnOffsetCommit(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DescribeProducers(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DescribeTransactions(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::ListTransactions(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DescribeAcls(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::CreateAcls(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DeleteAcls(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DescribeConfigs(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::AlterConfigs(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::IncrementalAlterConfigs(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DescribeClientQuotas(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::AlterClientQuotas(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::OffsetForLeaderEpoch(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::DescribeTopicPartitions(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::BrokerRegistration(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::BrokerHeartbeat(_, body) => body.throttle_time_ms = throttle_time_ms,
+            KafkaResponseBody::SaslHandshake(..) | KafkaResponseBody::SaslAuthenticate(..) => {}
+        }
+    }
+}
+
+impl BinWrite for KafkaResponseBody {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _: Endian, _: Self::Args<'_>) -> BinResult<()> {
+        match self {
+            KafkaResponseBody::ApiVersions(body) => body.write_be(writer),
+            KafkaResponseBody::ListOffsets(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DeleteRecords(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::FindCoordinator(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::JoinGroup(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::SyncGroup(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::Heartbeat(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::LeaveGroup(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::OffsetCommit(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::OffsetFetch(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::ConsumerGroupHeartbeat(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::ConsumerGroupDescribe(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DescribeGroups(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::ListGroups(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DeleteGroups(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::OffsetDelete(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::InitProducerId(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::AddPartitionsToTxn(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::AddOffsetsToTxn(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::EndTxn(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::TxnOffsetCommit(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DescribeProducers(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DescribeTransactions(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::ListTransactions(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::SaslHandshake(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::SaslAuthenticate(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DescribeAcls(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::CreateAcls(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DeleteAcls(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DescribeConfigs(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::AlterConfigs(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::IncrementalAlterConfigs(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DescribeClientQuotas(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::AlterClientQuotas(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::OffsetForLeaderEpoch(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::DescribeTopicPartitions(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::BrokerRegistration(version, body) => body.write_be_args(writer, (*version,)),
+            KafkaResponseBody::BrokerHeartbeat(version, body) => body.write_be_args(writer, (*version,)),
+        }
+    }
+}
+
+impl WriteEndian for KafkaResponseBody {
+    const ENDIAN: EndianKind = EndianKind::Endian(Endian::Big);
+}
@@ -0,0 +1,31 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseListGroups {
+    #[brw(if(v.version >= 1))]
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) groups: KafkaArray<ListGroupsResponseGroup>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ListGroupsResponseGroup {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    #[brw(args(v.flexible))]
+    pub(crate) protocol_type: KafkaString,
+    #[brw(if(v.version >= 4), args(v.flexible))]
+    pub(crate) group_state: KafkaString,
+    #[brw(if(v.version >= 5), args(v.flexible))]
+    pub(crate) group_type: KafkaString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,53 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseListOffsets {
+    #[brw(if(v.version >= 2))]
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<ListOffsetsTopicResponse>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ListOffsetsTopicResponse {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<ListOffsetsPartitionResponse>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct ListOffsetsPartitionResponse {
+    pub(crate) partition_index: i32,
+    pub(crate) error_code: ErrorCode,
+    pub(crate) timestamp: i64,
+    pub(crate) offset: i64,
+    #[brw(if(v.version >= 4, -1))]
+    pub(crate) leader_epoch: i32,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+impl ListOffsetsPartitionResponse {
+    pub(crate) fn new(partition_index: i32, error_code: ErrorCode) -> Self {
+        Self {
+            partition_index,
+            error_code,
+            timestamp: -1,
+            offset: -1,
+            leader_epoch: -1,
+            _tagged_fields: TagBuffer,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseListTransactions {
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    /// The state filters that name no transaction state.
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) unknown_state_filters: KafkaArray<KafkaString>,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) transaction_states: KafkaArray<ListTransactionsResponseState>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ListTransactionsResponseState {
+    #[brw(args(v.flexible))]
+    pub(crate) transactional_id: KafkaString,
+    pub(crate) producer_id: i64,
+    #[brw(args(v.flexible))]
+    pub(crate) transaction_state: KafkaString,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,30 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseOffsetDelete {
+    pub(crate) error_code: ErrorCode,
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<OffsetDeleteResponseTopic>,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OffsetDeleteResponseTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<OffsetDeleteResponsePartition>,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OffsetDeleteResponsePartition {
+    pub(crate) partition_index: i32,
+    pub(crate) error_code: ErrorCode,
+}
@@ -0,0 +1,59 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaNullableString, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseOffsetFetch {
+    #[brw(if(v.version >= 3))]
+    pub(crate) throttle_time_ms: i32,
+    #[brw(if(v.version <= 7), args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<OffsetFetchResponseTopic>,
+    #[brw(if(v.version >= 2 && v.version <= 7))]
+    pub(crate) error_code: ErrorCode,
+    #[brw(if(v.version >= 8), args(v.flexible, (v,)))]
+    pub(crate) groups: KafkaArray<OffsetFetchResponseGroup>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OffsetFetchResponseGroup {
+    #[brw(args(v.flexible))]
+    pub(crate) group_id: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<OffsetFetchResponseTopic>,
+    pub(crate) error_code: ErrorCode,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OffsetFetchResponseTopic {
+    #[brw(args(v.flexible))]
+    pub(crate) name: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<OffsetFetchResponsePartition>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OffsetFetchResponsePartition {
+    pub(crate) partition_index: i32,
+    /// -1 if the group has no committed offset for the partition.
+    pub(crate) committed_offset: i64,
+    #[brw(if(v.version >= 5))]
+    pub(crate) committed_leader_epoch: i32,
+    #[brw(args(v.flexible))]
+    pub(crate) metadata: KafkaNullableString,
+    pub(crate) error_code: ErrorCode,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
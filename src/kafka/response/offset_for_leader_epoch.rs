@@ -0,0 +1,46 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseOffsetForLeaderEpoch {
+    #[brw(if(v.version >= 2))]
+    pub(crate) throttle_time_ms: i32,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) topics: KafkaArray<OffsetForLeaderTopicResult>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OffsetForLeaderTopicResult {
+    #[brw(args(v.flexible))]
+    pub(crate) topic: KafkaString,
+    #[brw(args(v.flexible, (v,)))]
+    pub(crate) partitions: KafkaArray<EpochEndOffset>,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone)]
+pub(crate) struct EpochEndOffset {
+    pub(crate) error_code: ErrorCode,
+    pub(crate) partition: i32,
+    #[br(if(v.version >= 1, -1))]
+    #[bw(if(v.version >= 1))]
+    pub(crate) leader_epoch: i32,
+    pub(crate) end_offset: i64,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
+
+impl EpochEndOffset {
+    pub(crate) fn new(partition: i32, error_code: ErrorCode) -> Self {
+        Self { error_code, partition, leader_epoch: -1, end_offset: -1, _tagged_fields: TagBuffer }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::kafka::types::TagBuffer;
+use binrw::binwrite;
+
+#[binwrite]
+#[bw(big)]
+#[derive(Debug)]
+pub(crate) struct KafkaResponseHeaderV1 {
+    pub(crate) correlation_id: i32,
+    _tagged_fields: TagBuffer,
+}
+
+impl KafkaResponseHeaderV1 {
+    pub(crate) fn new(correlation_id: i32) -> Self {
+        Self { correlation_id, _tagged_fields: TagBuffer }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::kafka::types::{ErrorCode, KafkaBytes, KafkaNullableString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseSaslAuthenticate {
+    pub(crate) error_code: ErrorCode,
+    #[brw(args(v.flexible))]
+    pub(crate) error_message: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) auth_bytes: KafkaBytes,
+    /// From v1, how long the session stays valid before the client must
+    /// re-authenticate, 0 for no limit.
+    #[brw(if(v.version >= 1))]
+    pub(crate) session_lifetime_ms: i64,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
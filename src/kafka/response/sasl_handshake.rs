@@ -0,0 +1,12 @@
+use crate::kafka::types::{ErrorCode, KafkaArray, KafkaString, MessageVersion};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseSaslHandshake {
+    pub(crate) error_code: ErrorCode,
+    /// The mechanisms enabled on the broker.
+    #[brw(args(v.flexible, (v.flexible,)))]
+    pub(crate) mechanisms: KafkaArray<KafkaString>,
+}
@@ -0,0 +1,19 @@
+use crate::kafka::types::{ErrorCode, KafkaBytes, KafkaNullableString, MessageVersion, TagBuffer};
+use binrw::binrw;
+
+#[binrw]
+#[brw(big, import(v: MessageVersion))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KafkaResponseSyncGroup {
+    #[brw(if(v.version >= 1))]
+    pub(crate) throttle_time_ms: i32,
+    pub(crate) error_code: ErrorCode,
+    #[brw(if(v.version >= 5), args(v.flexible))]
+    pub(crate) protocol_type: KafkaNullableString,
+    #[brw(if(v.version >= 5), args(v.flexible))]
+    pub(crate) protocol_name: KafkaNullableString,
+    #[brw(args(v.flexible))]
+    pub(crate) assignment: KafkaBytes,
+    #[brw(if(v.flexible))]
+    pub(crate) _tagged_fields: TagBuffer,
+}
@@ -0,0 +1,116 @@
+mod acl;
+pub(crate) use acl::*;
+mod authorizer;
+pub(crate) use authorizer::*;
+mod credentials;
+pub(crate) use credentials::*;
+mod sasl;
+pub(crate) use sasl::*;
+mod scram;
+pub(crate) use scram::*;
+mod tls;
+pub(crate) use tls::*;
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The identity requests on a connection are made as.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct KafkaPrincipal {
+    pub(crate) principal_type: String,
+    pub(crate) name: String,
+}
+
+impl KafkaPrincipal {
+    pub(crate) const USER_TYPE: &str = "User";
+
+    pub(crate) fn user(name: impl Into<String>) -> Self {
+        Self { principal_type: Self::USER_TYPE.to_owned(), name: name.into() }
+    }
+
+    /// The principal of connections on listeners without authentication.
+    pub(crate) fn anonymous() -> Self {
+        Self::user("ANONYMOUS")
+    }
+}
+
+impl Display for KafkaPrincipal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.principal_type, self.name)
+    }
+}
+
+/// The protocol spoken on a listener, from `listener.security.protocol.map`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SecurityProtocol {
+    #[default]
+    Plaintext,
+    SaslPlaintext,
+    Ssl,
+    SaslSsl,
+}
+
+impl SecurityProtocol {
+    /// Whether connections must authenticate with SASL before other requests.
+    pub(crate) fn is_sasl(self) -> bool {
+        matches!(self, SecurityProtocol::SaslPlaintext | SecurityProtocol::SaslSsl)
+    }
+
+    /// Whether connections are wrapped in TLS.
+    pub(crate) fn is_tls(self) -> bool {
+        matches!(self, SecurityProtocol::Ssl | SecurityProtocol::SaslSsl)
+    }
+
+    /// The id of the protocol in broker registrations.
+    pub(crate) fn id(self) -> i16 {
+        match self {
+            SecurityProtocol::Plaintext => 0,
+            SecurityProtocol::Ssl => 1,
+            SecurityProtocol::SaslPlaintext => 2,
+            SecurityProtocol::SaslSsl => 3,
+        }
+    }
+}
+
+impl FromStr for SecurityProtocol {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "PLAINTEXT" => Ok(SecurityProtocol::Plaintext),
+            "SASL_PLAINTEXT" => Ok(SecurityProtocol::SaslPlaintext),
+            "SSL" => Ok(SecurityProtocol::Ssl),
+            "SASL_SSL" => Ok(SecurityProtocol::SaslSsl),
+            _ => Err(format!("unknown security protocol {name:?}")),
+        }
+    }
+}
+
+/// Whether TLS listeners ask clients for a certificate, `ssl.client.auth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SslClientAuth {
+    #[default]
+    None,
+    /// Clients may authenticate with a certificate.
+    Requested,
+    /// Clients without a valid certificate are rejected.
+    Required,
+}
+
+impl FromStr for SslClientAuth {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "none" => Ok(SslClientAuth::None),
+            "requested" => Ok(SslClientAuth::Requested),
+            "required" => Ok(SslClientAuth::Required),
+            _ => Err(format!("unknown client auth {name:?}")),
+        }
+    }
+}
+
+/// Compares secrets without leaking the position of the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,286 @@
+/// The name of the single cluster resource.
+pub(crate) const CLUSTER_RESOURCE_NAME: &str = "kafka-cluster";
+/// A literal resource name matching every resource of its type.
+pub(crate) const WILDCARD_RESOURCE: &str = "*";
+pub(crate) const WILDCARD_PRINCIPAL: &str = "User:*";
+pub(crate) const WILDCARD_HOST: &str = "*";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ResourceType {
+    /// Only in filters.
+    Any = 1,
+    Topic = 2,
+    Group = 3,
+    Cluster = 4,
+    TransactionalId = 5,
+    DelegationToken = 6,
+    User = 7,
+}
+
+impl ResourceType {
+    pub(crate) fn from_code(code: i8) -> Option<Self> {
+        match code {
+            1 => Some(ResourceType::Any),
+            2 => Some(ResourceType::Topic),
+            3 => Some(ResourceType::Group),
+            4 => Some(ResourceType::Cluster),
+            5 => Some(ResourceType::TransactionalId),
+            6 => Some(ResourceType::DelegationToken),
+            7 => Some(ResourceType::User),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn code(self) -> i8 {
+        self as i8
+    }
+
+    /// The operations that apply to resources of this type, reported in
+    /// `authorized_operations` fields.
+    fn operations(self) -> &'static [AclOperation] {
+        use AclOperation::*;
+        match self {
+            ResourceType::Topic => &[Read, Write, Create, Delete, Alter, Describe, DescribeConfigs, AlterConfigs],
+            ResourceType::Group => &[Read, Describe, Delete],
+            ResourceType::Cluster => {
+                &[Create, ClusterAction, DescribeConfigs, AlterConfigs, IdempotentWrite, Alter, Describe]
+            }
+            ResourceType::TransactionalId => &[Describe, Write],
+            ResourceType::DelegationToken => &[Describe],
+            ResourceType::User => &[CreateTokens, DescribeTokens],
+            ResourceType::Any => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum PatternType {
+    /// Only in filters, matching ACLs of any pattern type.
+    Any = 1,
+    /// Only in filters, matching the ACLs that apply to the named resource.
+    Match = 2,
+    Literal = 3,
+    /// Matches resources whose name starts with the pattern.
+    Prefixed = 4,
+}
+
+impl PatternType {
+    pub(crate) fn from_code(code: i8) -> Option<Self> {
+        match code {
+            1 => Some(PatternType::Any),
+            2 => Some(PatternType::Match),
+            3 => Some(PatternType::Literal),
+            4 => Some(PatternType::Prefixed),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn code(self) -> i8 {
+        self as i8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum AclOperation {
+    /// Only in filters.
+    Any = 1,
+    All = 2,
+    Read = 3,
+    Write = 4,
+    Create = 5,
+    Delete = 6,
+    Alter = 7,
+    Describe = 8,
+    ClusterAction = 9,
+    DescribeConfigs = 10,
+    AlterConfigs = 11,
+    IdempotentWrite = 12,
+    CreateTokens = 13,
+    DescribeTokens = 14,
+}
+
+impl AclOperation {
+    pub(crate) fn from_code(code: i8) -> Option<Self> {
+        use AclOperation::*;
+        [
+            Any, All, Read, Write, Create, Delete, Alter, Describe, ClusterAction, DescribeConfigs, AlterConfigs,
+            IdempotentWrite, CreateTokens, DescribeTokens,
+        ]
+        .into_iter()
+        .find(|operation| operation.code() == code)
+    }
+
+    pub(crate) fn code(self) -> i8 {
+        self as i8
+    }
+
+    /// Whether an ACL granting `self` also grants `operation`. Every
+    /// operation that changes a resource implies describing it.
+    pub(crate) fn implies(self, operation: AclOperation) -> bool {
+        use AclOperation::*;
+        self == All
+            || self == operation
+            || operation == Describe && matches!(self, Read | Write | Delete | Alter)
+            || operation == DescribeConfigs && self == AlterConfigs
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum AclPermissionType {
+    /// Only in filters.
+    Any = 1,
+    Deny = 2,
+    Allow = 3,
+}
+
+impl AclPermissionType {
+    pub(crate) fn from_code(code: i8) -> Option<Self> {
+        match code {
+            1 => Some(AclPermissionType::Any),
+            2 => Some(AclPermissionType::Deny),
+            3 => Some(AclPermissionType::Allow),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn code(self) -> i8 {
+        self as i8
+    }
+}
+
+/// An access control entry bound to a resource pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct StandardAcl {
+    pub(crate) resource_type: ResourceType,
+    pub(crate) resource_name: String,
+    pub(crate) pattern_type: PatternType,
+    /// A principal such as `User:alice`, or `User:*` for everyone.
+    pub(crate) principal: String,
+    /// A client IP address, or `*` for any host.
+    pub(crate) host: String,
+    pub(crate) operation: AclOperation,
+    pub(crate) permission_type: AclPermissionType,
+}
+
+impl StandardAcl {
+    /// An ACL from its wire codes, `None` if any code is unknown.
+    pub(crate) fn from_codes(
+        resource_type: i8,
+        resource_name: String,
+        pattern_type: i8,
+        principal: String,
+        host: String,
+        operation: i8,
+        permission_type: i8,
+    ) -> Option<Self> {
+        Some(Self {
+            resource_type: ResourceType::from_code(resource_type)?,
+            resource_name,
+            pattern_type: PatternType::from_code(pattern_type)?,
+            principal,
+            host,
+            operation: AclOperation::from_code(operation)?,
+            permission_type: AclPermissionType::from_code(permission_type)?,
+        })
+    }
+
+    /// Checks that the ACL is concrete enough to be stored.
+    pub(crate) fn validate(&self) -> Result<(), &'static str> {
+        if self.resource_type == ResourceType::Any {
+            return Err("resource type ANY is only valid in filters");
+        }
+        if !matches!(self.pattern_type, PatternType::Literal | PatternType::Prefixed) {
+            return Err("pattern type must be LITERAL or PREFIXED");
+        }
+        if self.resource_name.is_empty() {
+            return Err("resource name must not be empty");
+        }
+        if self.operation == AclOperation::Any {
+            return Err("operation ANY is only valid in filters");
+        }
+        if self.permission_type == AclPermissionType::Any {
+            return Err("permission type ANY is only valid in filters");
+        }
+        if !self.principal.split_once(':').is_some_and(|(kind, name)| !kind.is_empty() && !name.is_empty()) {
+            return Err("principal must be of the form Type:name");
+        }
+        if self.resource_type == ResourceType::Cluster && self.resource_name != CLUSTER_RESOURCE_NAME {
+            return Err("the cluster resource is named kafka-cluster");
+        }
+        Ok(())
+    }
+
+    /// Whether the ACL's resource pattern covers the named resource.
+    pub(crate) fn matches_resource(&self, resource_type: ResourceType, resource_name: &str) -> bool {
+        self.resource_type == resource_type
+            && match self.pattern_type {
+                PatternType::Literal => self.resource_name == resource_name || self.resource_name == WILDCARD_RESOURCE,
+                PatternType::Prefixed => resource_name.starts_with(&self.resource_name),
+                PatternType::Any | PatternType::Match => false,
+            }
+    }
+}
+
+/// Selects ACLs in DescribeAcls and DeleteAcls. `None` names, principals
+/// and hosts match any value.
+#[derive(Debug, Clone)]
+pub(crate) struct AclFilter {
+    pub(crate) resource_type: ResourceType,
+    pub(crate) resource_name: Option<String>,
+    pub(crate) pattern_type: PatternType,
+    pub(crate) principal: Option<String>,
+    pub(crate) host: Option<String>,
+    pub(crate) operation: AclOperation,
+    pub(crate) permission_type: AclPermissionType,
+}
+
+impl AclFilter {
+    /// A filter from its wire codes, `None` if any code is unknown.
+    pub(crate) fn from_codes(
+        resource_type: i8,
+        resource_name: Option<String>,
+        pattern_type: i8,
+        principal: Option<String>,
+        host: Option<String>,
+        operation: i8,
+        permission_type: i8,
+    ) -> Option<Self> {
+        Some(Self {
+            resource_type: ResourceType::from_code(resource_type)?,
+            resource_name,
+            pattern_type: PatternType::from_code(pattern_type)?,
+            principal,
+            host,
+            operation: AclOperation::from_code(operation)?,
+            permission_type: AclPermissionType::from_code(permission_type)?,
+        })
+    }
+
+    pub(crate) fn matches(&self, acl: &StandardAcl) -> bool {
+        let name_matches = self.resource_name.as_ref().is_none_or(|name| *name == acl.resource_name);
+        let resource_matches = match (self.pattern_type, &self.resource_name) {
+            (PatternType::Match, Some(name)) => acl.matches_resource(acl.resource_type, name),
+            (PatternType::Any | PatternType::Match, _) => name_matches,
+            (pattern_type, _) => pattern_type == acl.pattern_type && name_matches,
+        };
+        (self.resource_type == ResourceType::Any || self.resource_type == acl.resource_type)
+            && resource_matches
+            && self.principal.as_ref().is_none_or(|principal| *principal == acl.principal)
+            && self.host.as_ref().is_none_or(|host| *host == acl.host)
+            && (self.operation == AclOperation::Any || self.operation == acl.operation)
+            && (self.permission_type == AclPermissionType::Any || self.permission_type == acl.permission_type)
+    }
+}
+
+/// The bitfield of `authorized_operations` response fields: bit `n` is set
+/// if the operation with code `n` is allowed.
+pub(crate) fn operations_bitfield(
+    resource_type: ResourceType,
+    mut allowed: impl FnMut(AclOperation) -> bool,
+) -> i32 {
+    resource_type
+        .operations()
+        .iter()
+        .filter(|operation| allowed(**operation))
+        .fold(0, |bits, operation| bits | 1 << operation.code())
+}
@@ -0,0 +1,230 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::security::{
+    operations_bitfield, AclFilter, AclOperation, AclPermissionType, KafkaPrincipal, ResourceType, StandardAcl,
+    WILDCARD_HOST, WILDCARD_PRINCIPAL, WILDCARD_RESOURCE,
+};
+use crate::kafka::types::Uuid;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+/// Authorizes requests against the ACLs of the metadata log, following the
+/// rules of Kafka's `StandardAuthorizer`: super users may do anything, a
+/// matching DENY wins over any ALLOW, and resources without ACLs are open
+/// only if `allow.everyone.if.no.acl.found` is set.
+#[derive(Debug)]
+pub(crate) struct Authorizer {
+    /// Without `authorizer.class.name`, every request is allowed.
+    enabled: bool,
+    super_users: HashSet<String>,
+    allow_everyone_if_no_acl_found: bool,
+    acls: RwLock<HashMap<Uuid, StandardAcl>>,
+}
+
+impl Authorizer {
+    pub(crate) fn new(config: &ServerConfig, acls: HashMap<Uuid, StandardAcl>) -> Self {
+        Self {
+            enabled: config.authorizer_class_name.is_some(),
+            super_users: config.super_users.iter().cloned().collect(),
+            allow_everyone_if_no_acl_found: config.allow_everyone_if_no_acl_found,
+            acls: RwLock::new(acls),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether `principal`, connecting from `host`, may perform `operation`
+    /// on the named resource.
+    pub(crate) fn authorize(
+        &self,
+        principal: &KafkaPrincipal,
+        host: &str,
+        operation: AclOperation,
+        resource_type: ResourceType,
+        resource_name: &str,
+    ) -> bool {
+        let principal = principal.to_string();
+        if !self.enabled || self.super_users.contains(&principal) {
+            return true;
+        }
+
+        let acls = self.acls.read().expect("acls lock poisoned");
+        let mut resource_acls =
+            acls.values().filter(|acl| acl.matches_resource(resource_type, resource_name)).peekable();
+        if resource_acls.peek().is_none() {
+            return self.allow_everyone_if_no_acl_found;
+        }
+
+        let mut allowed = false;
+        for acl in resource_acls.filter(|acl| applies_to(acl, &principal, host)) {
+            if denies(acl, operation) {
+                return false;
+            }
+            allowed |= allows(acl, operation);
+        }
+        allowed
+    }
+
+    /// Whether `principal` may perform `operation` on some resource of the
+    /// type, unless a wildcard ACL denies it on all of them.
+    pub(crate) fn authorize_any(
+        &self,
+        principal: &KafkaPrincipal,
+        host: &str,
+        operation: AclOperation,
+        resource_type: ResourceType,
+    ) -> bool {
+        let principal = principal.to_string();
+        if !self.enabled || self.super_users.contains(&principal) {
+            return true;
+        }
+
+        let acls = self.acls.read().expect("acls lock poisoned");
+        let mut allowed = false;
+        for acl in acls.values().filter(|acl| acl.resource_type == resource_type && applies_to(acl, &principal, host)) {
+            if denies(acl, operation) && acl.matches_resource(resource_type, WILDCARD_RESOURCE) {
+                return false;
+            }
+            allowed |= allows(acl, operation);
+        }
+        allowed
+    }
+
+    /// The `authorized_operations` bitfield of the named resource.
+    pub(crate) fn authorized_operations(
+        &self,
+        principal: &KafkaPrincipal,
+        host: &str,
+        resource_type: ResourceType,
+        resource_name: &str,
+    ) -> i32 {
+        operations_bitfield(resource_type, |operation| {
+            self.authorize(principal, host, operation, resource_type, resource_name)
+        })
+    }
+
+    /// The id of an existing ACL identical to `acl`.
+    pub(crate) fn find_acl(&self, acl: &StandardAcl) -> Option<Uuid> {
+        let acls = self.acls.read().expect("acls lock poisoned");
+        acls.iter().find(|(_, existing)| *existing == acl).map(|(id, _)| *id)
+    }
+
+    pub(crate) fn matching_acls(&self, filter: &AclFilter) -> Vec<(Uuid, StandardAcl)> {
+        let acls = self.acls.read().expect("acls lock poisoned");
+        let mut matching: Vec<_> =
+            acls.iter().filter(|(_, acl)| filter.matches(acl)).map(|(id, acl)| (*id, acl.clone())).collect();
+        matching.sort_by_key(|(id, _)| *id);
+        matching
+    }
+
+    pub(crate) fn add_acl(&self, id: Uuid, acl: StandardAcl) {
+        self.acls.write().expect("acls lock poisoned").insert(id, acl);
+    }
+
+    pub(crate) fn remove_acl(&self, id: &Uuid) {
+        self.acls.write().expect("acls lock poisoned").remove(id);
+    }
+}
+
+/// Whether the ACL's principal and host cover the connection.
+fn applies_to(acl: &StandardAcl, principal: &str, host: &str) -> bool {
+    (acl.principal == principal || acl.principal == WILDCARD_PRINCIPAL)
+        && (acl.host == host || acl.host == WILDCARD_HOST)
+}
+
+fn denies(acl: &StandardAcl, operation: AclOperation) -> bool {
+    acl.permission_type == AclPermissionType::Deny && (acl.operation == operation || acl.operation == AclOperation::All)
+}
+
+fn allows(acl: &StandardAcl, operation: AclOperation) -> bool {
+    acl.permission_type == AclPermissionType::Allow && acl.operation.implies(operation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::security::PatternType;
+
+    fn acl(
+        pattern_type: PatternType,
+        name: &str,
+        operation: AclOperation,
+        permission: AclPermissionType,
+    ) -> StandardAcl {
+        StandardAcl {
+            resource_type: ResourceType::Topic,
+            resource_name: name.to_owned(),
+            pattern_type,
+            principal: "User:alice".to_owned(),
+            host: WILDCARD_HOST.to_owned(),
+            operation,
+            permission_type: permission,
+        }
+    }
+
+    fn authorizer(acls: Vec<StandardAcl>) -> Authorizer {
+        let config = ServerConfig {
+            authorizer_class_name: Some("StandardAuthorizer".to_owned()),
+            super_users: vec!["User:admin".to_owned()],
+            ..ServerConfig::default()
+        };
+        Authorizer::new(&config, acls.into_iter().map(|acl| (Uuid::random(), acl)).collect())
+    }
+
+    #[test]
+    fn test_deny_wins_and_allows_imply_describe() {
+        let authorizer = authorizer(vec![
+            acl(PatternType::Prefixed, "orders", AclOperation::Write, AclPermissionType::Allow),
+            acl(PatternType::Literal, "orders-secret", AclOperation::All, AclPermissionType::Deny),
+        ]);
+        let alice = KafkaPrincipal::user("alice");
+        let topic = |operation, name| authorizer.authorize(&alice, "127.0.0.1", operation, ResourceType::Topic, name);
+
+        assert!(topic(AclOperation::Write, "orders-eu"));
+        assert!(topic(AclOperation::Describe, "orders-eu"));
+        assert!(!topic(AclOperation::Read, "orders-eu"));
+        assert!(!topic(AclOperation::Write, "orders-secret"));
+        assert!(!topic(AclOperation::Write, "payments"));
+        assert!(!authorizer.authorize(
+            &KafkaPrincipal::user("bob"),
+            "127.0.0.1",
+            AclOperation::Write,
+            ResourceType::Topic,
+            "orders-eu"
+        ));
+        assert!(authorizer.authorize(
+            &KafkaPrincipal::user("admin"),
+            "127.0.0.1",
+            AclOperation::Write,
+            ResourceType::Topic,
+            "orders-secret"
+        ));
+
+        let bits = authorizer.authorized_operations(&alice, "127.0.0.1", ResourceType::Topic, "orders-eu");
+        assert_eq!(bits, 1 << AclOperation::Write.code() | 1 << AclOperation::Describe.code());
+    }
+
+    #[test]
+    fn test_filters_match_by_pattern() {
+        let authorizer = authorizer(vec![
+            acl(PatternType::Prefixed, "orders", AclOperation::Read, AclPermissionType::Allow),
+            acl(PatternType::Literal, "*", AclOperation::Describe, AclPermissionType::Allow),
+            acl(PatternType::Literal, "payments", AclOperation::Read, AclPermissionType::Allow),
+        ]);
+        let filter = |pattern_type, name: Option<&str>| AclFilter {
+            resource_type: ResourceType::Topic,
+            resource_name: name.map(str::to_owned),
+            pattern_type,
+            principal: None,
+            host: None,
+            operation: AclOperation::Any,
+            permission_type: AclPermissionType::Any,
+        };
+
+        assert_eq!(authorizer.matching_acls(&filter(PatternType::Any, None)).len(), 3);
+        assert_eq!(authorizer.matching_acls(&filter(PatternType::Match, Some("orders-eu"))).len(), 2);
+        assert_eq!(authorizer.matching_acls(&filter(PatternType::Literal, Some("orders"))).len(), 0);
+        assert_eq!(authorizer.matching_acls(&filter(PatternType::Prefixed, Some("orders"))).len(), 1);
+    }
+}
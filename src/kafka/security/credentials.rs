@@ -0,0 +1,55 @@
+use crate::kafka::config::parse_properties;
+use crate::kafka::security::{constant_time_eq, ScramCredential, ScramMechanism};
+use anyhow::Context;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The credentials SASL clients are authenticated against.
+#[derive(Debug, Default)]
+pub(crate) struct CredentialStore {
+    /// PLAIN passwords, only known for users of the credentials file.
+    passwords: HashMap<String, String>,
+    scram: HashMap<(ScramMechanism, String), ScramCredential>,
+}
+
+impl CredentialStore {
+    /// Loads a credentials file of `username=password` lines. Each user can
+    /// authenticate with PLAIN and both SCRAM mechanisms.
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let mut store = Self::default();
+        for (username, password) in parse_properties(&text) {
+            store.add_user(&username, &password);
+        }
+        Ok(store)
+    }
+
+    pub(crate) fn add_user(&mut self, username: &str, password: &str) {
+        for mechanism in ScramMechanism::ALL {
+            let credential = ScramCredential::generate(mechanism, password);
+            self.scram.insert((mechanism, username.to_owned()), credential);
+        }
+        self.passwords.insert(username.to_owned(), password.to_owned());
+    }
+
+    /// Sets the SCRAM credential of `username`, replacing one derived from
+    /// the credentials file.
+    pub(crate) fn set_scram_credential(
+        &mut self,
+        mechanism: ScramMechanism,
+        username: String,
+        credential: ScramCredential,
+    ) {
+        self.scram.insert((mechanism, username), credential);
+    }
+
+    pub(crate) fn verify_password(&self, username: &str, password: &str) -> bool {
+        self.passwords
+            .get(username)
+            .is_some_and(|expected| constant_time_eq(expected.as_bytes(), password.as_bytes()))
+    }
+
+    pub(crate) fn scram_credential(&self, mechanism: ScramMechanism, username: &str) -> Option<&ScramCredential> {
+        self.scram.get(&(mechanism, username.to_owned()))
+    }
+}
@@ -0,0 +1,132 @@
+use crate::kafka::security::{CredentialStore, ScramMechanism, ScramServer};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum SaslMechanism {
+    Plain,
+    Scram(ScramMechanism),
+}
+
+impl SaslMechanism {
+    pub(crate) fn mechanism_name(self) -> &'static str {
+        match self {
+            SaslMechanism::Plain => "PLAIN",
+            SaslMechanism::Scram(mechanism) => mechanism.mechanism_name(),
+        }
+    }
+}
+
+impl FromStr for SaslMechanism {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if name == "PLAIN" {
+            return Ok(SaslMechanism::Plain);
+        }
+        ScramMechanism::ALL
+            .into_iter()
+            .find(|mechanism| mechanism.mechanism_name() == name)
+            .map(SaslMechanism::Scram)
+            .ok_or_else(|| format!("unknown SASL mechanism {name:?}"))
+    }
+}
+
+impl Display for SaslMechanism {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.mechanism_name())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum SaslError {
+    #[error("Invalid SASL message: {0}")]
+    InvalidMessage(&'static str),
+    #[error("Authentication failed: Invalid username or password")]
+    InvalidCredentials,
+}
+
+/// The outcome of one SaslAuthenticate token.
+#[derive(Debug)]
+pub(crate) enum SaslStep {
+    /// The exchange continues with this challenge.
+    Challenge(Vec<u8>),
+    /// `username` is authenticated, `response` being the final server message.
+    Complete { username: String, response: Vec<u8> },
+}
+
+/// The server side of the mechanism picked in SaslHandshake.
+#[derive(Debug, Clone)]
+pub(crate) enum SaslServer {
+    Plain,
+    Scram(ScramServer),
+}
+
+impl SaslServer {
+    pub(crate) fn new(mechanism: SaslMechanism) -> Self {
+        match mechanism {
+            SaslMechanism::Plain => SaslServer::Plain,
+            SaslMechanism::Scram(mechanism) => SaslServer::Scram(ScramServer::new(mechanism)),
+        }
+    }
+
+    /// Evaluates the next client token of the exchange.
+    pub(crate) fn evaluate(&mut self, credentials: &CredentialStore, token: &[u8]) -> Result<SaslStep, SaslError> {
+        match self {
+            SaslServer::Plain => evaluate_plain(credentials, token),
+            SaslServer::Scram(server) => server.evaluate(credentials, token),
+        }
+    }
+}
+
+/// PLAIN (RFC 4616) sends `[authzid] NUL authcid NUL passwd` in one token.
+fn evaluate_plain(credentials: &CredentialStore, token: &[u8]) -> Result<SaslStep, SaslError> {
+    let token = std::str::from_utf8(token).map_err(|_| SaslError::InvalidMessage("token is not UTF-8"))?;
+    let mut parts = token.split('\0');
+    let (Some(authzid), Some(username), Some(password), None) = (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(SaslError::InvalidMessage("expected authzid, username and password separated by NUL"));
+    };
+    if username.is_empty() || password.is_empty() {
+        return Err(SaslError::InvalidMessage("username and password must not be empty"));
+    }
+    if !authzid.is_empty() && authzid != username {
+        return Err(SaslError::InvalidMessage("authzid does not match the username"));
+    }
+    if !credentials.verify_password(username, password) {
+        return Err(SaslError::InvalidCredentials);
+    }
+    Ok(SaslStep::Complete { username: username.to_owned(), response: Vec::new() })
+}
+
+/// Where a connection is in authenticating.
+#[derive(Debug, Clone, Default)]
+pub(crate) enum SaslSession {
+    /// Waiting for SaslHandshake to pick a mechanism.
+    #[default]
+    Handshake,
+    /// Exchanging SaslAuthenticate tokens.
+    Authenticate(SaslServer),
+    /// Authenticated, or on a listener without SASL.
+    Complete,
+    /// Authentication failed, the connection is closed after the response.
+    Failed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_checks_the_password() {
+        let mut store = CredentialStore::default();
+        store.add_user("alice", "secret");
+        let mut server = SaslServer::new(SaslMechanism::Plain);
+
+        let step = server.evaluate(&store, b"\0alice\0secret").unwrap();
+        assert!(matches!(step, SaslStep::Complete { username, .. } if username == "alice"));
+        assert!(matches!(server.evaluate(&store, b"alice\0alice\0wrong"), Err(SaslError::InvalidCredentials)));
+        assert!(matches!(server.evaluate(&store, b"bob\0alice\0secret"), Err(SaslError::InvalidMessage(_))));
+        assert!(matches!(server.evaluate(&store, b"alice\0secret"), Err(SaslError::InvalidMessage(_))));
+    }
+}
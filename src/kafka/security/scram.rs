@@ -0,0 +1,310 @@
+use crate::kafka::security::{constant_time_eq, CredentialStore, SaslError, SaslStep};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt::{Display, Formatter};
+
+/// The fewest PBKDF2 iterations accepted for a SCRAM credential.
+pub(crate) const MIN_SCRAM_ITERATIONS: i32 = 4096;
+
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ScramMechanism {
+    Sha256,
+    Sha512,
+}
+
+impl ScramMechanism {
+    pub(crate) const ALL: [ScramMechanism; 2] = [ScramMechanism::Sha256, ScramMechanism::Sha512];
+
+    /// The mechanism of its metadata record type id.
+    pub(crate) fn from_type(id: i8) -> Option<Self> {
+        match id {
+            1 => Some(ScramMechanism::Sha256),
+            2 => Some(ScramMechanism::Sha512),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn mechanism_name(self) -> &'static str {
+        match self {
+            ScramMechanism::Sha256 => "SCRAM-SHA-256",
+            ScramMechanism::Sha512 => "SCRAM-SHA-512",
+        }
+    }
+
+    fn hash(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            ScramMechanism::Sha256 => Sha256::digest(data).to_vec(),
+            ScramMechanism::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+
+    fn hmac(self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        match self {
+            ScramMechanism::Sha256 => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+                mac.update(data);
+                mac.finalize().into_bytes().to_vec()
+            }
+            ScramMechanism::Sha512 => {
+                let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes keys of any length");
+                mac.update(data);
+                mac.finalize().into_bytes().to_vec()
+            }
+        }
+    }
+
+    fn salted_password(self, password: &str, salt: &[u8], iterations: u32) -> Vec<u8> {
+        let password = password.as_bytes();
+        match self {
+            ScramMechanism::Sha256 => pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(password, salt, iterations).to_vec(),
+            ScramMechanism::Sha512 => pbkdf2::pbkdf2_hmac_array::<Sha512, 64>(password, salt, iterations).to_vec(),
+        }
+    }
+}
+
+impl Display for ScramMechanism {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.mechanism_name())
+    }
+}
+
+/// What the broker stores of a SCRAM password (RFC 5802 section 3).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScramCredential {
+    pub(crate) salt: Vec<u8>,
+    pub(crate) stored_key: Vec<u8>,
+    pub(crate) server_key: Vec<u8>,
+    pub(crate) iterations: i32,
+}
+
+impl ScramCredential {
+    pub(crate) fn new(mechanism: ScramMechanism, password: &str, salt: Vec<u8>, iterations: i32) -> Self {
+        let salted_password = mechanism.salted_password(password, &salt, iterations.max(1) as u32);
+        let client_key = mechanism.hmac(&salted_password, b"Client Key");
+        Self {
+            stored_key: mechanism.hash(&client_key),
+            server_key: mechanism.hmac(&salted_password, b"Server Key"),
+            salt,
+            iterations,
+        }
+    }
+
+    /// A credential for `password` with a random salt.
+    pub(crate) fn generate(mechanism: ScramMechanism, password: &str) -> Self {
+        Self::new(mechanism, password, random_bytes(SALT_LENGTH), MIN_SCRAM_ITERATIONS)
+    }
+}
+
+/// The server side of a SCRAM exchange: client-first, server-first,
+/// client-final, server-final.
+#[derive(Debug, Clone)]
+pub(crate) struct ScramServer {
+    mechanism: ScramMechanism,
+    state: ScramState,
+}
+
+#[derive(Debug, Clone)]
+enum ScramState {
+    ReceiveClientFirst,
+    ReceiveClientFinal(Box<ServerFirst>),
+    Complete,
+}
+
+/// What the server remembers between its first message and the client's
+/// final message.
+#[derive(Debug, Clone)]
+struct ServerFirst {
+    username: String,
+    gs2_header: String,
+    client_first_bare: String,
+    server_first: String,
+    nonce: String,
+    credential: ScramCredential,
+}
+
+impl ScramServer {
+    pub(crate) fn new(mechanism: ScramMechanism) -> Self {
+        Self { mechanism, state: ScramState::ReceiveClientFirst }
+    }
+
+    pub(crate) fn evaluate(&mut self, credentials: &CredentialStore, message: &[u8]) -> Result<SaslStep, SaslError> {
+        let message = std::str::from_utf8(message).map_err(|_| SaslError::InvalidMessage("message is not UTF-8"))?;
+        match std::mem::replace(&mut self.state, ScramState::Complete) {
+            ScramState::ReceiveClientFirst => {
+                let server_first = self.client_first(credentials, message)?;
+                let challenge = server_first.server_first.clone().into_bytes();
+                self.state = ScramState::ReceiveClientFinal(Box::new(server_first));
+                Ok(SaslStep::Challenge(challenge))
+            }
+            ScramState::ReceiveClientFinal(server_first) => self.client_final(&server_first, message),
+            ScramState::Complete => Err(SaslError::InvalidMessage("exchange is already complete")),
+        }
+    }
+
+    fn client_first(&self, credentials: &CredentialStore, message: &str) -> Result<ServerFirst, SaslError> {
+        // gs2-header: a channel binding flag and an optional authzid.
+        let mut parts = message.splitn(3, ',');
+        let (Some(channel_binding), Some(authzid), Some(client_first_bare)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(SaslError::InvalidMessage("client-first message has no gs2 header"));
+        };
+        if channel_binding != "n" && channel_binding != "y" {
+            return Err(SaslError::InvalidMessage("channel binding is not supported"));
+        }
+
+        let mut fields = client_first_bare.split(',');
+        let username = fields
+            .next()
+            .and_then(|field| attribute(field, 'n'))
+            .ok_or(SaslError::InvalidMessage("client-first message has no username"))?;
+        let username = decode_saslname(username)?;
+        let client_nonce = fields
+            .next()
+            .and_then(|field| attribute(field, 'r'))
+            .filter(|nonce| !nonce.is_empty())
+            .ok_or(SaslError::InvalidMessage("client-first message has no nonce"))?;
+        if !authzid.is_empty() {
+            let authzid = attribute(authzid, 'a').ok_or(SaslError::InvalidMessage("invalid authzid"))?;
+            if decode_saslname(authzid)? != username {
+                return Err(SaslError::InvalidMessage("authzid does not match the username"));
+            }
+        }
+
+        let credential = credentials
+            .scram_credential(self.mechanism, &username)
+            .ok_or(SaslError::InvalidCredentials)?
+            .clone();
+        let nonce = format!("{client_nonce}{}", BASE64.encode(random_bytes(NONCE_LENGTH)));
+        let server_first = format!("r={nonce},s={},i={}", BASE64.encode(&credential.salt), credential.iterations);
+        Ok(ServerFirst {
+            username,
+            gs2_header: message[..message.len() - client_first_bare.len()].to_owned(),
+            client_first_bare: client_first_bare.to_owned(),
+            server_first,
+            nonce,
+            credential,
+        })
+    }
+
+    fn client_final(&self, server_first: &ServerFirst, message: &str) -> Result<SaslStep, SaslError> {
+        let (without_proof, proof) =
+            message.rsplit_once(",p=").ok_or(SaslError::InvalidMessage("client-final message has no proof"))?;
+        let mut fields = without_proof.split(',');
+        let channel_binding = fields
+            .next()
+            .and_then(|field| attribute(field, 'c'))
+            .and_then(|value| BASE64.decode(value).ok())
+            .ok_or(SaslError::InvalidMessage("client-final message has no channel binding"))?;
+        if channel_binding != server_first.gs2_header.as_bytes() {
+            return Err(SaslError::InvalidMessage("channel binding does not match the gs2 header"));
+        }
+        if fields.next().and_then(|field| attribute(field, 'r')) != Some(server_first.nonce.as_str()) {
+            return Err(SaslError::InvalidMessage("nonce does not match"));
+        }
+        let proof = BASE64.decode(proof).map_err(|_| SaslError::InvalidMessage("proof is not base64"))?;
+
+        let auth_message =
+            format!("{},{},{without_proof}", server_first.client_first_bare, server_first.server_first);
+        let credential = &server_first.credential;
+        let client_signature = self.mechanism.hmac(&credential.stored_key, auth_message.as_bytes());
+        if proof.len() != client_signature.len() {
+            return Err(SaslError::InvalidCredentials);
+        }
+        let client_key: Vec<u8> = proof.iter().zip(&client_signature).map(|(p, s)| p ^ s).collect();
+        if !constant_time_eq(&self.mechanism.hash(&client_key), &credential.stored_key) {
+            return Err(SaslError::InvalidCredentials);
+        }
+
+        let server_signature = self.mechanism.hmac(&credential.server_key, auth_message.as_bytes());
+        Ok(SaslStep::Complete {
+            username: server_first.username.clone(),
+            response: format!("v={}", BASE64.encode(server_signature)).into_bytes(),
+        })
+    }
+}
+
+/// The value of a `name=value` message field, if the field is `name`.
+fn attribute(field: &str, name: char) -> Option<&str> {
+    field.strip_prefix(name)?.strip_prefix('=')
+}
+
+/// Undoes the `=2C` and `=3D` escaping of `,` and `=` in usernames.
+fn decode_saslname(name: &str) -> Result<String, SaslError> {
+    let mut decoded = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(pos) = rest.find('=') {
+        decoded.push_str(&rest[..pos]);
+        match rest.get(pos..pos + 3) {
+            Some("=2C") => decoded.push(','),
+            Some("=3D") => decoded.push('='),
+            _ => return Err(SaslError::InvalidMessage("invalid escape in username")),
+        }
+        rest = &rest[pos + 3..];
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0; length];
+    getrandom::fill(&mut bytes).expect("the OS random number generator is available");
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays the client side of an exchange, returning the server's final
+    /// message.
+    fn authenticate(store: &CredentialStore, mechanism: ScramMechanism, password: &str) -> Result<SaslStep, SaslError> {
+        let mut server = ScramServer::new(mechanism);
+        let client_first_bare = "n=alice,r=clientnonce";
+        let SaslStep::Challenge(server_first) = server.evaluate(store, format!("n,,{client_first_bare}").as_bytes())?
+        else {
+            panic!("expected a server-first challenge");
+        };
+        let server_first = String::from_utf8(server_first).unwrap();
+        let fields: Vec<_> = server_first.split(',').collect();
+        let nonce = attribute(fields[0], 'r').unwrap();
+        assert!(nonce.starts_with("clientnonce"));
+        let salt = BASE64.decode(attribute(fields[1], 's').unwrap()).unwrap();
+        let iterations: i32 = attribute(fields[2], 'i').unwrap().parse().unwrap();
+
+        let client = ScramCredential::new(mechanism, password, salt, iterations);
+        let salted_password = mechanism.salted_password(password, &client.salt, iterations as u32);
+        let client_key = mechanism.hmac(&salted_password, b"Client Key");
+        let without_proof = format!("c={},r={nonce}", BASE64.encode("n,,"));
+        let auth_message = format!("{client_first_bare},{server_first},{without_proof}");
+        let client_signature = mechanism.hmac(&client.stored_key, auth_message.as_bytes());
+        let proof: Vec<u8> = client_key.iter().zip(&client_signature).map(|(k, s)| k ^ s).collect();
+        server.evaluate(store, format!("{without_proof},p={}", BASE64.encode(proof)).as_bytes())
+    }
+
+    #[test]
+    fn test_scram_exchange_verifies_the_proof() {
+        let mut store = CredentialStore::default();
+        store.add_user("alice", "secret");
+
+        for mechanism in ScramMechanism::ALL {
+            let SaslStep::Complete { username, response } = authenticate(&store, mechanism, "secret").unwrap() else {
+                panic!("expected the exchange to complete");
+            };
+            assert_eq!(username, "alice");
+            assert!(response.starts_with(b"v="));
+
+            assert!(matches!(authenticate(&store, mechanism, "wrong"), Err(SaslError::InvalidCredentials)));
+        }
+    }
+
+    #[test]
+    fn test_saslname_escapes_are_decoded() {
+        assert_eq!(decode_saslname("a=2Cb=3Dc").unwrap(), "a,b=c");
+        assert!(decode_saslname("a=b").is_err());
+    }
+}
@@ -0,0 +1,287 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::security::{KafkaPrincipal, SslClientAuth};
+use anyhow::Context;
+use rustls::pki_types::CertificateDer;
+use rustls::server::WebPkiClientVerifier;
+use rustls::RootCertStore;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio_rustls::TlsAcceptor;
+use tracing::{error, info};
+use x509_parser::objects::{oid2abbrev, oid_registry};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+/// The modification time and length of a file, to notice it was replaced.
+type FileVersion = Option<(SystemTime, u64)>;
+
+/// The rustls config of SSL and SASL_SSL listeners, rebuilt when the keystore
+/// or truststore changes on disk.
+#[derive(Debug)]
+pub(crate) struct TlsContext {
+    keystore: PathBuf,
+    truststore: Option<PathBuf>,
+    client_auth: SslClientAuth,
+    reload_check_interval: Duration,
+    server_config: RwLock<Arc<rustls::ServerConfig>>,
+    file_versions: Mutex<Vec<FileVersion>>,
+}
+
+impl TlsContext {
+    pub(crate) fn load(config: &ServerConfig) -> anyhow::Result<Self> {
+        let keystore =
+            config.ssl_keystore_location.clone().context("ssl.keystore.location is required for SSL listeners")?;
+        let truststore = config.ssl_truststore_location.clone();
+        let server_config = build_server_config(&keystore, truststore.as_deref(), config.ssl_client_auth)?;
+        let context = Self {
+            keystore,
+            truststore,
+            client_auth: config.ssl_client_auth,
+            reload_check_interval: Duration::from_millis(config.ssl_reload_check_interval_ms),
+            server_config: RwLock::new(Arc::new(server_config)),
+            file_versions: Mutex::new(Vec::new()),
+        };
+        *context.file_versions.lock().expect("TLS file versions lock poisoned") = context.file_versions();
+        Ok(context)
+    }
+
+    /// An acceptor with the current certificates.
+    pub(crate) fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.server_config.read().expect("TLS config lock poisoned").clone())
+    }
+
+    /// Checks the keystore and truststore for changes until the broker stops.
+    pub(crate) async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.reload_check_interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.reload_if_changed() {
+                error!(error = %err, "Reloading TLS certificates failed");
+            }
+        }
+    }
+
+    /// Rebuilds the rustls config if the keystore or truststore changed since
+    /// they were last read, returning whether it did. Connections already
+    /// established keep their certificates.
+    pub(crate) fn reload_if_changed(&self) -> anyhow::Result<bool> {
+        let versions = self.file_versions();
+        {
+            let mut last = self.file_versions.lock().expect("TLS file versions lock poisoned");
+            if *last == versions {
+                return Ok(false);
+            }
+            // A broken file is reported once, and read again when it changes.
+            *last = versions;
+        }
+        let server_config = build_server_config(&self.keystore, self.truststore.as_deref(), self.client_auth)?;
+        *self.server_config.write().expect("TLS config lock poisoned") = Arc::new(server_config);
+        info!(keystore = %self.keystore.display(), "Reloaded TLS certificates");
+        Ok(true)
+    }
+
+    fn file_versions(&self) -> Vec<FileVersion> {
+        std::iter::once(&self.keystore)
+            .chain(&self.truststore)
+            .map(|path| {
+                let metadata = std::fs::metadata(path).ok()?;
+                Some((metadata.modified().ok()?, metadata.len()))
+            })
+            .collect()
+    }
+}
+
+/// Builds the config from a PEM keystore holding the private key and the
+/// certificate chain, and a PEM truststore of the CAs client certificates
+/// must be signed by.
+fn build_server_config(
+    keystore: &Path,
+    truststore: Option<&Path>,
+    client_auth: SslClientAuth,
+) -> anyhow::Result<rustls::ServerConfig> {
+    let certs = read_certs(keystore)?;
+    let pem = std::fs::read(keystore).with_context(|| format!("reading {}", keystore.display()))?;
+    let key = rustls_pemfile::private_key(&mut pem.as_slice())
+        .with_context(|| format!("reading {}", keystore.display()))?
+        .with_context(|| format!("{} has no private key", keystore.display()))?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ServerConfig::builder_with_provider(provider.clone()).with_safe_default_protocol_versions()?;
+    let builder = match (client_auth, truststore) {
+        (SslClientAuth::None, _) => builder.with_no_client_auth(),
+        (_, None) => anyhow::bail!("ssl.client.auth requires ssl.truststore.location"),
+        (_, Some(truststore)) => {
+            let mut roots = RootCertStore::empty();
+            for cert in read_certs(truststore)? {
+                roots.add(cert).with_context(|| format!("invalid CA certificate in {}", truststore.display()))?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = match client_auth {
+                SslClientAuth::Requested => verifier.allow_unauthenticated(),
+                _ => verifier,
+            };
+            builder.with_client_cert_verifier(verifier.build()?)
+        }
+    };
+    builder.with_single_cert(certs, key).with_context(|| format!("invalid keystore {}", keystore.display()))
+}
+
+fn read_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let pem = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let certs = rustls_pemfile::certs(&mut pem.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("reading {}", path.display()))?;
+    anyhow::ensure!(!certs.is_empty(), "{} has no certificates", path.display());
+    Ok(certs)
+}
+
+/// The principal of a TLS client: the subject of its certificate, or
+/// ANONYMOUS if it sent none.
+pub(crate) fn peer_principal(connection: &rustls::ServerConnection) -> KafkaPrincipal {
+    connection
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .and_then(certificate_subject)
+        .map(KafkaPrincipal::user)
+        .unwrap_or_else(KafkaPrincipal::anonymous)
+}
+
+/// The subject of a certificate as an RFC 2253 distinguished name, most
+/// specific name first, like `CN=alice,O=Example`.
+fn certificate_subject(cert: &CertificateDer<'_>) -> Option<String> {
+    let (_, cert) = X509Certificate::from_der(cert).ok()?;
+    let mut rdns = Vec::new();
+    for rdn in cert.subject().iter_rdn() {
+        let mut attributes = Vec::new();
+        for attribute in rdn.iter() {
+            let oid = attribute.attr_type();
+            let name = oid2abbrev(oid, oid_registry()).map(str::to_owned).unwrap_or_else(|_| oid.to_id_string());
+            attributes.push(format!("{name}={}", escape_dn_value(attribute.as_str().ok()?)));
+        }
+        rdns.push(attributes.join("+"));
+    }
+    rdns.reverse();
+    Some(rdns.join(","))
+}
+
+fn escape_dn_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let last = value.chars().count().saturating_sub(1);
+    for (i, c) in value.chars().enumerate() {
+        let leading = i == 0 && (c == ' ' || c == '#');
+        let trailing = i == last && c == ' ';
+        if leading || trailing || matches!(c, ',' | '+' | '"' | '\\' | '<' | '>' | ';') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, IsCa, KeyPair};
+    use rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer, ServerName};
+    use tokio_rustls::TlsConnector;
+
+    struct Issued {
+        cert: Certificate,
+        key: KeyPair,
+    }
+
+    fn ca(name: &str) -> Issued {
+        let mut params = CertificateParams::default();
+        params.distinguished_name.push(DnType::CommonName, name);
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let key = KeyPair::generate().unwrap();
+        Issued { cert: params.self_signed(&key).unwrap(), key }
+    }
+
+    fn issue(ca: &Issued, common_name: &str) -> Issued {
+        let mut params = CertificateParams::new(vec!["localhost".to_owned()]).unwrap();
+        params.distinguished_name = DistinguishedName::new();
+        params.distinguished_name.push(DnType::OrganizationName, "Example, Inc");
+        params.distinguished_name.push(DnType::CommonName, common_name);
+        let key = KeyPair::generate().unwrap();
+        Issued { cert: params.signed_by(&key, &ca.cert, &ca.key).unwrap(), key }
+    }
+
+    fn write_keystore(path: &Path, issued: &Issued) {
+        std::fs::write(path, format!("{}{}", issued.cert.pem(), issued.key.serialize_pem())).unwrap();
+    }
+
+    /// Connects a client over an in-memory stream, returning the principal
+    /// the server sees and the server certificate the client got.
+    async fn handshake(context: &TlsContext, ca: &Issued, client: Option<&Issued>) -> (KafkaPrincipal, Vec<u8>) {
+        let mut roots = RootCertStore::empty();
+        roots.add(ca.cert.der().clone()).unwrap();
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots);
+        let client_config = match client {
+            Some(client) => {
+                let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(client.key.serialize_der()));
+                builder.with_client_auth_cert(vec![client.cert.der().clone()], key).unwrap()
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let (client_stream, server_stream) = tokio::io::duplex(64 * 1024);
+        let connector = TlsConnector::from(Arc::new(client_config));
+        let server_name = ServerName::try_from("localhost").unwrap();
+        let (server, client) =
+            tokio::join!(context.acceptor().accept(server_stream), connector.connect(server_name, client_stream));
+        let server = server.unwrap();
+        let client = client.unwrap();
+        let server_cert = client.get_ref().1.peer_certificates().unwrap()[0].to_vec();
+        (peer_principal(server.get_ref().1), server_cert)
+    }
+
+    fn config(dir: &Path, client_auth: SslClientAuth) -> ServerConfig {
+        ServerConfig {
+            ssl_keystore_location: Some(dir.join("server.pem")),
+            ssl_truststore_location: Some(dir.join("ca.pem")),
+            ssl_client_auth: client_auth,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_certificate_subject_becomes_the_principal() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = ca("test-ca");
+        std::fs::write(dir.path().join("ca.pem"), ca.cert.pem()).unwrap();
+        write_keystore(&dir.path().join("server.pem"), &issue(&ca, "broker"));
+
+        let context = TlsContext::load(&config(dir.path(), SslClientAuth::Requested)).unwrap();
+        let (principal, _) = handshake(&context, &ca, Some(&issue(&ca, "alice"))).await;
+        assert_eq!(principal.to_string(), "User:CN=alice,O=Example\\, Inc");
+
+        let (principal, _) = handshake(&context, &ca, None).await;
+        assert_eq!(principal, KafkaPrincipal::anonymous());
+    }
+
+    #[tokio::test]
+    async fn test_changed_keystore_is_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = ca("test-ca");
+        std::fs::write(dir.path().join("ca.pem"), ca.cert.pem()).unwrap();
+        let keystore = dir.path().join("server.pem");
+        let first = issue(&ca, "broker");
+        write_keystore(&keystore, &first);
+
+        let context = TlsContext::load(&config(dir.path(), SslClientAuth::None)).unwrap();
+        assert!(!context.reload_if_changed().unwrap());
+        let (_, server_cert) = handshake(&context, &ca, None).await;
+        assert_eq!(server_cert, first.cert.der().to_vec());
+
+        let second = issue(&ca, "broker-renewed");
+        write_keystore(&keystore, &second);
+        assert!(context.reload_if_changed().unwrap());
+        let (_, server_cert) = handshake(&context, &ca, None).await;
+        assert_eq!(server_cert, second.cert.der().to_vec());
+    }
+}
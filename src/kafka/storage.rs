@@ -0,0 +1,49 @@
+mod checkpoint;
+pub(crate) use checkpoint::*;
+mod log_config;
+pub(crate) use log_config::*;
+mod partition_metadata;
+pub(crate) use partition_metadata::*;
+mod index;
+pub(crate) use index::*;
+mod segment;
+pub(crate) use segment::*;
+mod leader_epoch;
+pub(crate) use leader_epoch::*;
+mod producer_state;
+pub(crate) use producer_state::*;
+mod partition_log;
+pub(crate) use partition_log::*;
+mod log_manager;
+pub(crate) use log_manager::*;
+mod log_cleaner;
+pub(crate) use log_cleaner::*;
+
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct TopicPartition {
+    pub(crate) topic: String,
+    pub(crate) partition: i32,
+}
+
+impl TopicPartition {
+    pub(crate) fn new(topic: impl Into<String>, partition: i32) -> Self {
+        Self { topic: topic.into(), partition }
+    }
+
+    /// Parses a partition directory name of the form `<topic>-<partition>`.
+    pub(crate) fn from_dir_name(name: &str) -> Option<Self> {
+        let (topic, partition) = name.rsplit_once('-')?;
+        if topic.is_empty() {
+            return None;
+        }
+        Some(Self::new(topic, partition.parse().ok()?))
+    }
+}
+
+impl Display for TopicPartition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.topic, self.partition)
+    }
+}
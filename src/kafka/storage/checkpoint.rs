@@ -0,0 +1,220 @@
+use crate::kafka::storage::TopicPartition;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub(crate) const RECOVERY_POINT_CHECKPOINT_FILE: &str = "recovery-point-offset-checkpoint";
+pub(crate) const LOG_START_OFFSET_CHECKPOINT_FILE: &str = "log-start-offset-checkpoint";
+pub(crate) const LEADER_EPOCH_CHECKPOINT_FILE: &str = "leader-epoch-checkpoint";
+pub(crate) const QUORUM_STATE_FILE: &str = "quorum-state";
+/// Written to a log directory once its logs are flushed and checkpointed on
+/// shutdown, and removed once they are loaded again.
+pub(crate) const CLEAN_SHUTDOWN_FILE: &str = ".kafka_cleanshutdown";
+
+const CHECKPOINT_VERSION: i32 = 0;
+
+/// A `topic partition offset` checkpoint file in Kafka's text format: a
+/// version line, an entry count line, then one line per partition.
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetCheckpointFile {
+    path: PathBuf,
+}
+
+impl OffsetCheckpointFile {
+    pub(crate) fn new(dir: &Path, name: &str) -> Self {
+        Self { path: dir.join(name) }
+    }
+
+    /// Reads the checkpoint. A missing file is an empty checkpoint.
+    pub(crate) fn read(&self) -> io::Result<HashMap<TopicPartition, i64>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut lines = text.lines();
+        let version: i32 = parse_field(lines.next(), "version")?;
+        if version != CHECKPOINT_VERSION {
+            return Err(invalid_data(format!("unsupported checkpoint version {version}")));
+        }
+        let expected: usize = parse_field(lines.next(), "entry count")?;
+
+        let mut offsets = HashMap::with_capacity(expected);
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let mut fields = line.split_whitespace();
+            let topic = fields.next().ok_or_else(|| invalid_data(format!("malformed entry {line:?}")))?;
+            let partition = parse_field(fields.next(), "partition")?;
+            let offset = parse_field(fields.next(), "offset")?;
+            offsets.insert(TopicPartition::new(topic, partition), offset);
+        }
+
+        if offsets.len() != expected {
+            return Err(invalid_data(format!("expected {expected} entries but found {}", offsets.len())));
+        }
+        Ok(offsets)
+    }
+
+    /// Atomically replaces the checkpoint via a temporary file and rename.
+    pub(crate) fn write(&self, offsets: impl IntoIterator<Item = (TopicPartition, i64)>) -> io::Result<()> {
+        let offsets = offsets.into_iter().collect::<Vec<_>>();
+        let mut text = format!("{CHECKPOINT_VERSION}\n{}\n", offsets.len());
+        for (tp, offset) in offsets {
+            text.push_str(&format!("{} {} {offset}\n", tp.topic, tp.partition));
+        }
+        write_atomically(&self.path, &text)
+    }
+}
+
+/// The `leader-epoch-checkpoint` file in a partition directory: a version
+/// line, an entry count line, then one `epoch start_offset` line per epoch.
+#[derive(Debug, Clone)]
+pub(crate) struct LeaderEpochCheckpointFile {
+    path: PathBuf,
+}
+
+impl LeaderEpochCheckpointFile {
+    pub(crate) fn new(dir: &Path) -> Self {
+        Self { path: dir.join(LEADER_EPOCH_CHECKPOINT_FILE) }
+    }
+
+    /// Reads the `(epoch, start offset)` entries. A missing file is `None`.
+    pub(crate) fn read(&self) -> io::Result<Option<Vec<(i32, i64)>>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let mut lines = text.lines();
+        let version: i32 = parse_field(lines.next(), "version")?;
+        if version != CHECKPOINT_VERSION {
+            return Err(invalid_data(format!("unsupported checkpoint version {version}")));
+        }
+        let expected: usize = parse_field(lines.next(), "entry count")?;
+
+        let mut entries = Vec::with_capacity(expected);
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let mut fields = line.split_whitespace();
+            entries.push((parse_field(fields.next(), "epoch")?, parse_field(fields.next(), "start offset")?));
+        }
+
+        if entries.len() != expected {
+            return Err(invalid_data(format!("expected {expected} entries but found {}", entries.len())));
+        }
+        Ok(Some(entries))
+    }
+
+    pub(crate) fn write(&self, entries: &[(i32, i64)]) -> io::Result<()> {
+        let mut text = format!("{CHECKPOINT_VERSION}\n{}\n", entries.len());
+        for (epoch, start_offset) in entries {
+            text.push_str(&format!("{epoch} {start_offset}\n"));
+        }
+        write_atomically(&self.path, &text)
+    }
+}
+
+/// The latest leader epoch of the metadata log, with its leader and the
+/// voter it was granted by as Kafka's Raft voters store them. Ids are -1
+/// when unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ElectionState {
+    pub(crate) epoch: i32,
+    pub(crate) leader_id: i32,
+    pub(crate) voted_id: i32,
+}
+
+/// The `quorum-state` file in the metadata log directory: a version line,
+/// then an `epoch leader_id voted_id` line. Kafka writes the same state as
+/// JSON.
+#[derive(Debug, Clone)]
+pub(crate) struct QuorumStateFile {
+    path: PathBuf,
+}
+
+impl QuorumStateFile {
+    pub(crate) fn new(dir: &Path) -> Self {
+        Self { path: dir.join(QUORUM_STATE_FILE) }
+    }
+
+    /// Reads the election state. A missing file is `None`.
+    pub(crate) fn read(&self) -> io::Result<Option<ElectionState>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let mut lines = text.lines();
+        let version: i32 = parse_field(lines.next(), "version")?;
+        if version != CHECKPOINT_VERSION {
+            return Err(invalid_data(format!("unsupported quorum state version {version}")));
+        }
+        let mut fields = lines.next().unwrap_or_default().split_whitespace();
+        Ok(Some(ElectionState {
+            epoch: parse_field(fields.next(), "epoch")?,
+            leader_id: parse_field(fields.next(), "leader id")?,
+            voted_id: parse_field(fields.next(), "voted id")?,
+        }))
+    }
+
+    pub(crate) fn write(&self, state: &ElectionState) -> io::Result<()> {
+        let ElectionState { epoch, leader_id, voted_id } = state;
+        write_atomically(&self.path, &format!("{CHECKPOINT_VERSION}\n{epoch} {leader_id} {voted_id}\n"))
+    }
+}
+
+/// Replaces the file at `path` via a temporary file and rename.
+fn write_atomically(path: &Path, text: &str) -> io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(text.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+fn parse_field<T: std::str::FromStr>(field: Option<&str>, name: &str) -> io::Result<T> {
+    field
+        .and_then(|field| field.trim().parse().ok())
+        .ok_or_else(|| invalid_data(format!("missing or malformed {name}")))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint = OffsetCheckpointFile::new(dir.path(), RECOVERY_POINT_CHECKPOINT_FILE);
+        let foo = TopicPartition::new("foo", 0);
+        let bar = TopicPartition::new("bar-baz", 3);
+
+        checkpoint.write([(foo.clone(), 42), (bar.clone(), 7)]).unwrap();
+
+        let offsets = checkpoint.read().unwrap();
+        assert_eq!(offsets.len(), 2);
+        assert_eq!(offsets[&foo], 42);
+        assert_eq!(offsets[&bar], 7);
+    }
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint = OffsetCheckpointFile::new(dir.path(), RECOVERY_POINT_CHECKPOINT_FILE);
+        assert!(checkpoint.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_count_mismatch_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(RECOVERY_POINT_CHECKPOINT_FILE), "0\n2\nfoo 0 1\n").unwrap();
+        let checkpoint = OffsetCheckpointFile::new(dir.path(), RECOVERY_POINT_CHECKPOINT_FILE);
+        assert!(checkpoint.read().is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const OFFSET_ENTRY_SIZE: usize = 8;
+const TIME_ENTRY_SIZE: usize = 12;
+const TXN_ENTRY_SIZE: usize = 34;
+const ABORTED_TXN_VERSION: i16 = 0;
+
+/// Sparse mapping from offsets to byte positions in a segment's `.log` file.
+/// Entries are stored as big-endian `(relative_offset: i32, position: i32)`.
+#[derive(Debug)]
+pub(crate) struct OffsetIndex {
+    path: PathBuf,
+    base_offset: i64,
+    entries: Vec<(i32, u32)>,
+}
+
+impl OffsetIndex {
+    pub(crate) fn empty(path: PathBuf, base_offset: i64) -> Self {
+        Self { path, base_offset, entries: Vec::new() }
+    }
+
+    /// Loads and sanity checks the index. Entries must have strictly
+    /// increasing offsets, non-decreasing positions, and must not point past
+    /// `log_size`.
+    pub(crate) fn load(path: PathBuf, base_offset: i64, log_size: u64) -> io::Result<Self> {
+        let bytes = fs::read(&path)?;
+        if bytes.len() % OFFSET_ENTRY_SIZE != 0 {
+            return Err(corrupt(&path, format!("size {} is not a multiple of {OFFSET_ENTRY_SIZE}", bytes.len())));
+        }
+
+        let entries = bytes
+            .chunks_exact(OFFSET_ENTRY_SIZE)
+            .map(|entry| (read_i32(&entry[0..4]), read_i32(&entry[4..8]) as u32))
+            .collect::<Vec<_>>();
+
+        for pair in entries.windows(2) {
+            if pair[1].0 <= pair[0].0 || pair[1].1 < pair[0].1 {
+                return Err(corrupt(&path, format!("entry {:?} does not follow {:?}", pair[1], pair[0])));
+            }
+        }
+        if let Some(&(relative_offset, position)) = entries.last() {
+            if relative_offset < 0 || position as u64 >= log_size {
+                return Err(corrupt(&path, format!("last entry ({relative_offset}, {position}) is out of range")));
+            }
+        }
+
+        Ok(Self { path, base_offset, entries })
+    }
+
+    pub(crate) fn append(&mut self, offset: i64, position: u32) {
+        self.entries.push(((offset - self.base_offset) as i32, position));
+    }
+
+    /// Returns the position of the batch holding the largest indexed offset
+    /// at or below `offset`, or 0 if there is none.
+    pub(crate) fn lookup(&self, offset: i64) -> u32 {
+        let relative_offset = offset - self.base_offset;
+        let slot = self.entries.partition_point(|&(entry, _)| (entry as i64) <= relative_offset);
+        slot.checked_sub(1).map_or(0, |slot| self.entries[slot].1)
+    }
+
+    /// Returns the position of the last entry, or 0 for an empty index.
+    pub(crate) fn last_position(&self) -> u32 {
+        self.entries.last().map_or(0, |&(_, position)| position)
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(self.entries.len() * OFFSET_ENTRY_SIZE);
+        for &(relative_offset, position) in &self.entries {
+            bytes.extend_from_slice(&relative_offset.to_be_bytes());
+            bytes.extend_from_slice(&(position as i32).to_be_bytes());
+        }
+        write_file(&self.path, &bytes)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Sparse mapping from timestamps to offsets. Entries are stored as
+/// big-endian `(timestamp: i64, relative_offset: i32)`.
+#[derive(Debug)]
+pub(crate) struct TimeIndex {
+    path: PathBuf,
+    base_offset: i64,
+    entries: Vec<(i64, i32)>,
+}
+
+impl TimeIndex {
+    pub(crate) fn empty(path: PathBuf, base_offset: i64) -> Self {
+        Self { path, base_offset, entries: Vec::new() }
+    }
+
+    pub(crate) fn load(path: PathBuf, base_offset: i64) -> io::Result<Self> {
+        let bytes = fs::read(&path)?;
+        if bytes.len() % TIME_ENTRY_SIZE != 0 {
+            return Err(corrupt(&path, format!("size {} is not a multiple of {TIME_ENTRY_SIZE}", bytes.len())));
+        }
+
+        let entries = bytes
+            .chunks_exact(TIME_ENTRY_SIZE)
+            .map(|entry| (read_i64(&entry[0..8]), read_i32(&entry[8..12])))
+            .collect::<Vec<_>>();
+
+        for pair in entries.windows(2) {
+            if pair[1].0 < pair[0].0 || pair[1].1 < pair[0].1 {
+                return Err(corrupt(&path, format!("entry {:?} does not follow {:?}", pair[1], pair[0])));
+            }
+        }
+
+        Ok(Self { path, base_offset, entries })
+    }
+
+    pub(crate) fn last_timestamp(&self) -> Option<i64> {
+        self.entries.last().map(|&(timestamp, _)| timestamp)
+    }
+
+    /// Returns an offset from which every record with a timestamp at or above
+    /// `timestamp` follows: the offset of the last entry with a smaller
+    /// timestamp, or the base offset if there is none.
+    pub(crate) fn lookup(&self, timestamp: i64) -> i64 {
+        let slot = self.entries.partition_point(|&(entry, _)| entry < timestamp);
+        slot.checked_sub(1).map_or(self.base_offset, |slot| self.base_offset + self.entries[slot].1 as i64)
+    }
+
+    /// Appends an entry unless the timestamp does not advance the index.
+    pub(crate) fn maybe_append(&mut self, timestamp: i64, offset: i64) {
+        if timestamp < 0 {
+            return;
+        }
+        if let Some(&(last_timestamp, _)) = self.entries.last() {
+            if timestamp <= last_timestamp {
+                return;
+            }
+        }
+        self.entries.push((timestamp, (offset - self.base_offset) as i32));
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(self.entries.len() * TIME_ENTRY_SIZE);
+        for &(timestamp, relative_offset) in &self.entries {
+            bytes.extend_from_slice(&timestamp.to_be_bytes());
+            bytes.extend_from_slice(&relative_offset.to_be_bytes());
+        }
+        write_file(&self.path, &bytes)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// A transaction aborted on a partition, from the producer's first batch in
+/// the transaction to the abort marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AbortedTxn {
+    pub(crate) producer_id: i64,
+    pub(crate) first_offset: i64,
+    pub(crate) last_offset: i64,
+    /// The last stable offset right after the abort marker was written.
+    pub(crate) last_stable_offset: i64,
+}
+
+/// The transactions aborted in a segment, in the order of their markers.
+/// Entries are stored as big-endian `(version: i16, producer_id: i64,
+/// first_offset: i64, last_offset: i64, last_stable_offset: i64)`. Unlike
+/// the sparse indexes they cannot be rebuilt from the segment alone, so every
+/// entry is written through as it is appended.
+#[derive(Debug)]
+pub(crate) struct TransactionIndex {
+    path: PathBuf,
+    entries: Vec<AbortedTxn>,
+}
+
+impl TransactionIndex {
+    /// Loads the index, which only exists once the segment holds an abort
+    /// marker. Entries after a partial or unreadable one are dropped.
+    pub(crate) fn load(path: PathBuf) -> io::Result<Self> {
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+
+        let mut entries = Vec::with_capacity(bytes.len() / TXN_ENTRY_SIZE);
+        for entry in bytes.chunks_exact(TXN_ENTRY_SIZE) {
+            let version = i16::from_be_bytes(entry[0..2].try_into().expect("slice of 2 bytes"));
+            if version != ABORTED_TXN_VERSION {
+                break;
+            }
+            entries.push(AbortedTxn {
+                producer_id: read_i64(&entry[2..10]),
+                first_offset: read_i64(&entry[10..18]),
+                last_offset: read_i64(&entry[18..26]),
+                last_stable_offset: read_i64(&entry[26..34]),
+            });
+        }
+
+        let index = Self { path, entries };
+        if index.entries.len() * TXN_ENTRY_SIZE != bytes.len() {
+            warn!(index = %index.path.display(), entries = index.entries.len(), "Dropping unreadable transaction index tail");
+            index.rewrite()?;
+        }
+        Ok(index)
+    }
+
+    pub(crate) fn entries(&self) -> &[AbortedTxn] {
+        &self.entries
+    }
+
+    pub(crate) fn append(&mut self, txn: AbortedTxn) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(&encode_aborted_txn(&txn))?;
+        file.sync_data()?;
+        self.entries.push(txn);
+        Ok(())
+    }
+
+    /// Drops the entries whose abort marker is at or after `offset`.
+    pub(crate) fn truncate_to(&mut self, offset: i64) -> io::Result<()> {
+        let len = self.entries.len();
+        self.entries.retain(|txn| txn.last_offset < offset);
+        if self.entries.len() == len {
+            return Ok(());
+        }
+        self.rewrite()
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn rewrite(&self) -> io::Result<()> {
+        let bytes = self.entries.iter().flat_map(encode_aborted_txn).collect::<Vec<_>>();
+        write_file(&self.path, &bytes)
+    }
+}
+
+fn encode_aborted_txn(txn: &AbortedTxn) -> [u8; TXN_ENTRY_SIZE] {
+    let mut bytes = [0u8; TXN_ENTRY_SIZE];
+    bytes[0..2].copy_from_slice(&ABORTED_TXN_VERSION.to_be_bytes());
+    bytes[2..10].copy_from_slice(&txn.producer_id.to_be_bytes());
+    bytes[10..18].copy_from_slice(&txn.first_offset.to_be_bytes());
+    bytes[18..26].copy_from_slice(&txn.last_offset.to_be_bytes());
+    bytes[26..34].copy_from_slice(&txn.last_stable_offset.to_be_bytes());
+    bytes
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+fn read_i32(bytes: &[u8]) -> i32 {
+    i32::from_be_bytes(bytes.try_into().expect("slice of 4 bytes"))
+}
+
+fn read_i64(bytes: &[u8]) -> i64 {
+    i64::from_be_bytes(bytes.try_into().expect("slice of 8 bytes"))
+}
+
+fn corrupt(path: &Path, message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("corrupt index {}: {message}", path.display()))
+}
@@ -0,0 +1,134 @@
+use crate::kafka::storage::LeaderEpochCheckpointFile;
+use std::io;
+use std::path::Path;
+
+/// The epoch and end offset OffsetForLeaderEpoch answers with when the log
+/// has no epoch at or below the requested one.
+pub(crate) const UNDEFINED_EPOCH: i32 = -1;
+pub(crate) const UNDEFINED_EPOCH_OFFSET: i64 = -1;
+
+/// The first offset of every leader epoch in a partition log, like Kafka's
+/// `LeaderEpochFileCache`. A follower compares its epochs with the leader's
+/// to find where their logs diverge. Every change is written through to the
+/// partition's `leader-epoch-checkpoint`.
+#[derive(Debug)]
+pub(crate) struct LeaderEpochCache {
+    file: LeaderEpochCheckpointFile,
+    /// `(epoch, start offset)`, both increasing.
+    entries: Vec<(i32, i64)>,
+}
+
+impl LeaderEpochCache {
+    /// Loads the checkpoint of the partition in `dir`, along with whether
+    /// there was one. Without it, the epochs have to be rebuilt from the log.
+    pub(crate) fn load(dir: &Path) -> io::Result<(Self, bool)> {
+        let file = LeaderEpochCheckpointFile::new(dir);
+        let entries = file.read()?;
+        let found = entries.is_some();
+        Ok((Self { file, entries: entries.unwrap_or_default() }, found))
+    }
+
+    pub(crate) fn latest_epoch(&self) -> Option<i32> {
+        self.entries.last().map(|&(epoch, _)| epoch)
+    }
+
+    /// The epoch of the batch at `offset`: the latest one starting at or
+    /// before it. `None` if the cache has no such epoch.
+    pub(crate) fn epoch_for_offset(&self, offset: i64) -> Option<i32> {
+        let covering = self.entries.partition_point(|&(_, start_offset)| start_offset <= offset);
+        covering.checked_sub(1).map(|index| self.entries[index].0)
+    }
+
+    /// Records that `epoch` starts at `start_offset`, if it is newer than
+    /// the latest epoch. Batches of older epochs, or without an epoch, leave
+    /// the cache unchanged.
+    pub(crate) fn assign(&mut self, epoch: i32, start_offset: i64) -> io::Result<()> {
+        if epoch < 0 || self.latest_epoch().is_some_and(|latest| epoch <= latest) {
+            return Ok(());
+        }
+        self.entries.push((epoch, start_offset));
+        self.file.write(&self.entries)
+    }
+
+    /// Drops the epochs that start at or after `end_offset`, after the log
+    /// was truncated to it.
+    pub(crate) fn truncate_from_end(&mut self, end_offset: i64) -> io::Result<()> {
+        let kept = self.entries.partition_point(|&(_, start_offset)| start_offset < end_offset);
+        if kept == self.entries.len() {
+            return Ok(());
+        }
+        self.entries.truncate(kept);
+        self.file.write(&self.entries)
+    }
+
+    /// Drops the epochs that end at or before the new log start offset, and
+    /// moves the start of the first remaining epoch up to it.
+    pub(crate) fn truncate_from_start(&mut self, log_start_offset: i64) -> io::Result<()> {
+        let covering = self.entries.partition_point(|&(_, start_offset)| start_offset <= log_start_offset);
+        if covering == 0 {
+            return Ok(());
+        }
+        let (first_epoch, first_start_offset) = self.entries[covering - 1];
+        if covering == 1 && first_start_offset == log_start_offset {
+            return Ok(());
+        }
+        self.entries.drain(..covering - 1);
+        self.entries[0] = (first_epoch, log_start_offset);
+        self.file.write(&self.entries)
+    }
+
+    /// The largest epoch at or below `requested_epoch` and the offset its
+    /// log ends at: the start of the next epoch, or `log_end_offset` for the
+    /// latest. An epoch older than every epoch in the log ends where the log
+    /// begins.
+    pub(crate) fn end_offset_for(&self, requested_epoch: i32, log_end_offset: i64) -> (i32, i64) {
+        if requested_epoch == UNDEFINED_EPOCH {
+            return (UNDEFINED_EPOCH, UNDEFINED_EPOCH_OFFSET);
+        }
+        if self.latest_epoch() == Some(requested_epoch) {
+            return (requested_epoch, log_end_offset);
+        }
+        let higher = self.entries.partition_point(|&(epoch, _)| epoch <= requested_epoch);
+        let Some(&(_, higher_start_offset)) = self.entries.get(higher) else {
+            return (UNDEFINED_EPOCH, UNDEFINED_EPOCH_OFFSET);
+        };
+        match higher.checked_sub(1).map(|floor| self.entries[floor]) {
+            Some((floor_epoch, _)) => (floor_epoch, higher_start_offset),
+            None => (requested_epoch, higher_start_offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_end_offsets_follow_epoch_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut cache, found) = LeaderEpochCache::load(dir.path()).unwrap();
+        assert!(!found);
+        cache.assign(1, 0).unwrap();
+        cache.assign(1, 5).unwrap();
+        cache.assign(3, 10).unwrap();
+        cache.assign(5, 20).unwrap();
+
+        assert_eq!(cache.end_offset_for(5, 30), (5, 30));
+        assert_eq!(cache.end_offset_for(3, 30), (3, 20));
+        // Epoch 2 was never written, so a follower on it diverged at 10.
+        assert_eq!(cache.end_offset_for(2, 30), (1, 10));
+        assert_eq!(cache.end_offset_for(0, 30), (0, 0));
+        assert_eq!(cache.end_offset_for(6, 30), (UNDEFINED_EPOCH, UNDEFINED_EPOCH_OFFSET));
+
+        assert_eq!(cache.epoch_for_offset(0), Some(1));
+        assert_eq!(cache.epoch_for_offset(19), Some(3));
+        assert_eq!(cache.epoch_for_offset(30), Some(5));
+
+        cache.truncate_from_start(12).unwrap();
+        cache.truncate_from_end(20).unwrap();
+        let (cache, found) = LeaderEpochCache::load(dir.path()).unwrap();
+        assert!(found);
+        assert_eq!(cache.entries, [(3, 12)]);
+        assert_eq!(cache.epoch_for_offset(11), None);
+    }
+}
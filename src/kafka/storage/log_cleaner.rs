@@ -0,0 +1,742 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::metadata::CLUSTER_METADATA_TOPIC;
+use crate::kafka::record::{Record, RecordBatch, RecordBatchError, RecordBatchHeader};
+use crate::kafka::storage::{
+    segment_file, AbortedTxn, LogConfig, LogManager, LogSegment, PartitionLog, TopicPartition, CLEANED_FILE_SUFFIX,
+    LOG_FILE_SUFFIX,
+};
+use std::collections::{HashMap, HashSet};
+use std::iter::Peekable;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+pub(crate) const CLEANER_OFFSET_CHECKPOINT_FILE: &str = "cleaner-offset-checkpoint";
+
+const THROTTLE_CHECK_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Compacts logs with `cleanup.policy=compact`, keeping only the latest
+/// record for every key and dropping the data of aborted transactions, then
+/// the markers of transactions with no data left. The active segment is
+/// never cleaned.
+#[derive(Debug)]
+pub(crate) struct LogCleaner {
+    log_manager: Arc<LogManager>,
+    backoff: Duration,
+    throttler: Throttler,
+    /// First offset not yet covered by a cleaning pass, per partition.
+    first_dirty_offsets: HashMap<TopicPartition, i64>,
+}
+
+#[derive(Debug, Default)]
+struct CleanerStats {
+    segments: usize,
+    bytes_read: u64,
+    bytes_written: u64,
+    records_read: u64,
+    records_retained: u64,
+    tombstones_removed: u64,
+    aborted_batches_removed: u64,
+    markers_removed: u64,
+}
+
+impl LogCleaner {
+    pub(crate) fn new(log_manager: Arc<LogManager>, config: &ServerConfig) -> Self {
+        let first_dirty_offsets = log_manager.read_checkpoints(CLEANER_OFFSET_CHECKPOINT_FILE);
+        Self {
+            log_manager,
+            backoff: Duration::from_millis(config.log_cleaner_backoff_ms),
+            throttler: Throttler::new(config.log_cleaner_io_max_bytes_per_second),
+            first_dirty_offsets,
+        }
+    }
+
+    /// Runs the cleaner on a dedicated thread, backing off whenever no log
+    /// is dirty enough to clean.
+    pub(crate) fn start(mut self) -> io::Result<thread::JoinHandle<()>> {
+        thread::Builder::new().name("kafka-log-cleaner-thread-0".to_owned()).spawn(move || loop {
+            match self.clean_filthiest_log() {
+                Ok(true) => {}
+                Ok(false) => thread::sleep(self.backoff),
+                Err(err) => {
+                    error!(error = %err, "Log cleaning failed");
+                    thread::sleep(self.backoff);
+                }
+            }
+        })
+    }
+
+    /// Cleans the compacted log with the highest dirty ratio above its
+    /// `min.cleanable.dirty.ratio`. Returns whether a log was cleaned.
+    pub(crate) fn clean_filthiest_log(&mut self) -> io::Result<bool> {
+        let mut filthiest: Option<(f64, TopicPartition, Arc<Mutex<PartitionLog>>, LogConfig)> = None;
+
+        for (topic_partition, log) in self.log_manager.logs() {
+            if topic_partition.topic == CLUSTER_METADATA_TOPIC {
+                continue;
+            }
+            let config = self.log_manager.log_config(&topic_partition.topic);
+            if !config.cleanup_policy.compact {
+                continue;
+            }
+
+            let ratio = {
+                let log = log.lock().expect("partition log lock poisoned");
+                dirty_ratio(&log, self.first_dirty_offset(&topic_partition, &log))
+            };
+            if ratio > 0.0 && ratio >= config.min_cleanable_dirty_ratio
+                && filthiest.as_ref().is_none_or(|(max, ..)| ratio > *max)
+            {
+                filthiest = Some((ratio, topic_partition, log, config));
+            }
+        }
+
+        let Some((ratio, topic_partition, log, config)) = filthiest else {
+            return Ok(false);
+        };
+        info!(partition = %topic_partition, dirty_ratio = ratio, "Cleaning log");
+        self.clean(&topic_partition, &log, &config)?;
+        Ok(true)
+    }
+
+    fn first_dirty_offset(&self, topic_partition: &TopicPartition, log: &PartitionLog) -> i64 {
+        let active_base_offset = log.active_segment().base_offset();
+        self.first_dirty_offsets
+            .get(topic_partition)
+            .copied()
+            .unwrap_or(log.log_start_offset())
+            .clamp(log.log_start_offset().min(active_base_offset), active_base_offset)
+    }
+
+    fn clean(&mut self, topic_partition: &TopicPartition, log: &Mutex<PartitionLog>, config: &LogConfig) -> io::Result<()> {
+        let started = Instant::now();
+        let mut stats = CleanerStats::default();
+
+        let (offset_map, end_offset, base_offsets, mut txns) = {
+            let log = log.lock().expect("partition log lock poisoned");
+            let first_dirty_offset = self.first_dirty_offset(topic_partition, &log);
+            let end_offset = log.active_segment().base_offset();
+            let base_offsets = log
+                .segments()
+                .map(LogSegment::base_offset)
+                .filter(|&base_offset| base_offset < end_offset)
+                .collect::<Vec<_>>();
+            // Every pass walks the log from its start, so a marker is only
+            // found empty once none of its transaction's data is left.
+            let txns = CleanedTxnMetadata::new(log.aborted_transactions(log.log_start_offset(), end_offset));
+            (build_offset_map(&log, first_dirty_offset, end_offset)?, end_offset, base_offsets, txns)
+        };
+
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+        let retention = TombstoneRetention { now_ms, delete_retention_ms: config.delete_retention_ms };
+
+        for base_offset in base_offsets {
+            let bytes_read = {
+                let mut log = log.lock().expect("partition log lock poisoned");
+                let Some(segment) = log.segments().find(|segment| segment.base_offset() == base_offset) else {
+                    continue;
+                };
+                let cleaned = PathBuf::from(format!(
+                    "{}.{CLEANED_FILE_SUFFIX}",
+                    segment_file(log.dir(), base_offset, LOG_FILE_SUFFIX).display()
+                ));
+                let bytes_read = segment.size();
+                clean_segment(segment, &cleaned, &offset_map, &mut txns, retention, &mut stats)?;
+                log.replace_segment(base_offset, &cleaned, txns.take_retained_aborted())?;
+                bytes_read
+            };
+            stats.segments += 1;
+            self.throttler.maybe_throttle(bytes_read);
+        }
+
+        self.first_dirty_offsets.insert(topic_partition.clone(), end_offset);
+        self.log_manager.write_checkpoints(CLEANER_OFFSET_CHECKPOINT_FILE, |log| {
+            Ok(self.first_dirty_offsets.get(log.topic_partition()).copied().unwrap_or(log.log_start_offset()))
+        })?;
+
+        info!(
+            partition = %topic_partition,
+            segments = stats.segments,
+            keys = offset_map.len(),
+            bytes_read = stats.bytes_read,
+            bytes_written = stats.bytes_written,
+            records_read = stats.records_read,
+            records_retained = stats.records_retained,
+            tombstones_removed = stats.tombstones_removed,
+            aborted_batches_removed = stats.aborted_batches_removed,
+            markers_removed = stats.markers_removed,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Log cleaning complete"
+        );
+        Ok(())
+    }
+}
+
+/// Dirty bytes over all bytes below the active segment.
+fn dirty_ratio(log: &PartitionLog, first_dirty_offset: i64) -> f64 {
+    let active_base_offset = log.active_segment().base_offset();
+    let (mut clean, mut dirty) = (0u64, 0u64);
+    for segment in log.segments().filter(|segment| segment.base_offset() < active_base_offset) {
+        if segment.base_offset() >= first_dirty_offset {
+            dirty += segment.size();
+        } else {
+            clean += segment.size();
+        }
+    }
+    if dirty == 0 {
+        0.0
+    } else {
+        dirty as f64 / (clean + dirty) as f64
+    }
+}
+
+/// Maps every key in `[first_dirty_offset, end_offset)` to the offset of its
+/// latest record, ignoring aborted transactions.
+fn build_offset_map(log: &PartitionLog, first_dirty_offset: i64, end_offset: i64) -> io::Result<HashMap<Vec<u8>, i64>> {
+    let mut offset_map = HashMap::new();
+    let mut txns = CleanedTxnMetadata::new(log.aborted_transactions(first_dirty_offset, end_offset));
+    for batch in log.batches(first_dirty_offset) {
+        let batch = batch.map_err(io::Error::other)?;
+        if batch.header.base_offset >= end_offset {
+            break;
+        }
+        if batch.header.is_control() {
+            txns.end_txn(&batch.header);
+            continue;
+        }
+        if txns.is_aborted(&batch.header) {
+            continue;
+        }
+        let Some(batch) = parse_batch(batch.header, &batch.bytes)? else {
+            continue;
+        };
+        for record in batch.records {
+            let offset = batch.header.base_offset + record.offset_delta as i64;
+            if let Some(key) = record.key {
+                if offset >= first_dirty_offset {
+                    offset_map.insert(key, offset);
+                }
+            }
+        }
+    }
+    Ok(offset_map)
+}
+
+/// When tombstones and transaction markers may be dropped. Like Kafka since
+/// KIP-534, the first pass that keeps a batch's tombstones, or finds its
+/// marker's transaction empty, stamps it with a delete horizon of
+/// `delete.retention.ms` from then. They are dropped by the first pass after
+/// that horizon, however old their own timestamps are, so consumers always
+/// get `delete.retention.ms` to see them.
+#[derive(Debug, Clone, Copy)]
+struct TombstoneRetention {
+    now_ms: i64,
+    delete_retention_ms: i64,
+}
+
+/// Writes the records of `segment` worth keeping to `cleaned`, dropping the
+/// batches of aborted transactions and the markers of transactions with no
+/// data left once past their delete horizon. Other control batches and
+/// batches the cleaner cannot decode are copied unchanged.
+fn clean_segment(
+    segment: &LogSegment,
+    cleaned: &PathBuf,
+    offset_map: &HashMap<Vec<u8>, i64>,
+    txns: &mut CleanedTxnMetadata,
+    retention: TombstoneRetention,
+    stats: &mut CleanerStats,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(cleaned)?);
+
+    for batch in segment.batches(0) {
+        let batch = batch.map_err(io::Error::other)?;
+        stats.bytes_read += batch.bytes.len() as u64;
+
+        if batch.header.is_control() {
+            let delete_horizon_ms = batch.header.delete_horizon_ms();
+            let bytes = if !txns.end_txn(&batch.header) {
+                batch.bytes
+            } else if delete_horizon_ms.is_some_and(|horizon| horizon <= retention.now_ms) {
+                stats.markers_removed += 1;
+                continue;
+            } else if delete_horizon_ms.is_none() {
+                let mut parsed = RecordBatch::parse(batch.header, &batch.bytes).map_err(io::Error::other)?;
+                parsed.set_delete_horizon(retention.now_ms + retention.delete_retention_ms);
+                parsed.to_bytes()
+            } else {
+                batch.bytes
+            };
+            writer.write_all(&bytes)?;
+            stats.bytes_written += bytes.len() as u64;
+            continue;
+        }
+        if txns.is_aborted(&batch.header) {
+            stats.aborted_batches_removed += 1;
+            continue;
+        }
+
+        let Some(mut parsed) = parse_batch(batch.header.clone(), &batch.bytes)? else {
+            writer.write_all(&batch.bytes)?;
+            stats.bytes_written += batch.bytes.len() as u64;
+            continue;
+        };
+
+        let delete_horizon_ms = batch.header.delete_horizon_ms();
+        let remove_tombstones = delete_horizon_ms.is_some_and(|horizon| horizon <= retention.now_ms);
+        let read = parsed.records.len();
+        parsed.records.retain(|record| should_retain(&batch.header, record, offset_map, remove_tombstones, stats));
+        stats.records_read += read as u64;
+        stats.records_retained += parsed.records.len() as u64;
+
+        let starts_horizon = delete_horizon_ms.is_none() && parsed.records.iter().any(is_tombstone);
+        if starts_horizon {
+            parsed.set_delete_horizon(retention.now_ms + retention.delete_retention_ms);
+        }
+        let bytes = if parsed.records.len() == read && !starts_horizon {
+            batch.bytes
+        } else if parsed.records.is_empty() && batch.header.producer_id < 0 {
+            continue;
+        } else {
+            // Empty batches of idempotent producers are kept to preserve
+            // their sequence numbers.
+            parsed.to_bytes()
+        };
+        writer.write_all(&bytes)?;
+        stats.bytes_written += bytes.len() as u64;
+    }
+
+    writer.into_inner().map_err(io::IntoInnerError::into_error)?.sync_all()
+}
+
+fn should_retain(
+    header: &RecordBatchHeader,
+    record: &Record,
+    offset_map: &HashMap<Vec<u8>, i64>,
+    remove_tombstones: bool,
+    stats: &mut CleanerStats,
+) -> bool {
+    let Some(key) = &record.key else {
+        return true;
+    };
+    let offset = header.base_offset + record.offset_delta as i64;
+    if offset_map.get(key).is_some_and(|&latest| latest > offset) {
+        return false;
+    }
+    if remove_tombstones && record.value.is_none() {
+        stats.tombstones_removed += 1;
+        return false;
+    }
+    true
+}
+
+/// A keyed record without a value, which deletes the key once compacted.
+fn is_tombstone(record: &Record) -> bool {
+    record.key.is_some() && record.value.is_none()
+}
+
+/// Follows the transactions of the batches visited in offset order, like
+/// Kafka's `CleanedTransactionMetadata`: picks out the data of aborted
+/// transactions, and the markers whose transaction has no data left.
+struct CleanedTxnMetadata {
+    aborted: Peekable<std::vec::IntoIter<AbortedTxn>>,
+    /// Producers whose current transaction was aborted, and whether any of
+    /// its data was seen.
+    ongoing_aborted: HashMap<i64, (AbortedTxn, bool)>,
+    /// Producers whose current transaction has data and was not aborted.
+    ongoing_committed: HashSet<i64>,
+    /// The aborted transactions whose markers the segment being cleaned
+    /// keeps, to index with it.
+    retained_aborted: Vec<AbortedTxn>,
+}
+
+impl CleanedTxnMetadata {
+    fn new(aborted: Vec<AbortedTxn>) -> Self {
+        Self {
+            aborted: aborted.into_iter().peekable(),
+            ongoing_aborted: HashMap::new(),
+            ongoing_committed: HashSet::new(),
+            retained_aborted: Vec::new(),
+        }
+    }
+
+    /// Whether the data batch belongs to an aborted transaction.
+    fn is_aborted(&mut self, header: &RecordBatchHeader) -> bool {
+        self.consume_aborted_up_to(header.last_offset());
+        if !header.is_transactional() {
+            return false;
+        }
+        match self.ongoing_aborted.get_mut(&header.producer_id) {
+            Some((_, seen_data)) => {
+                *seen_data = true;
+                true
+            }
+            None => {
+                self.ongoing_committed.insert(header.producer_id);
+                false
+            }
+        }
+    }
+
+    /// Ends the producer's transaction at its marker. Returns whether none of
+    /// the transaction's data is left, so the marker may be dropped as well.
+    /// The marker of an aborted transaction whose data was just seen stays,
+    /// and so does its transaction in the index, until a later pass finds
+    /// the data gone.
+    fn end_txn(&mut self, header: &RecordBatchHeader) -> bool {
+        self.consume_aborted_up_to(header.last_offset());
+        if !header.is_transactional() {
+            return false;
+        }
+        match self.ongoing_aborted.remove(&header.producer_id) {
+            Some((txn, true)) => {
+                self.retained_aborted.push(txn);
+                false
+            }
+            Some((_, false)) => true,
+            None => !self.ongoing_committed.remove(&header.producer_id),
+        }
+    }
+
+    /// The aborted transactions to index with the segment just cleaned.
+    fn take_retained_aborted(&mut self) -> Vec<AbortedTxn> {
+        std::mem::take(&mut self.retained_aborted)
+    }
+
+    fn consume_aborted_up_to(&mut self, offset: i64) {
+        while let Some(txn) = self.aborted.next_if(|txn| txn.first_offset <= offset) {
+            self.ongoing_aborted.insert(txn.producer_id, (txn, false));
+        }
+    }
+}
+
+fn parse_batch(header: RecordBatchHeader, bytes: &[u8]) -> io::Result<Option<RecordBatch>> {
+    match RecordBatch::parse(header, bytes) {
+        Ok(batch) => Ok(Some(batch)),
+        Err(RecordBatchError::UnsupportedCompression(codec)) => {
+            warn!(codec, "Leaving compressed batch uncleaned");
+            Ok(None)
+        }
+        Err(err) => Err(io::Error::other(err)),
+    }
+}
+
+/// Caps the cleaner's I/O rate by sleeping whenever the observed rate over
+/// the current check interval exceeds `bytes_per_second`.
+#[derive(Debug)]
+struct Throttler {
+    bytes_per_second: f64,
+    period_start: Instant,
+    observed_bytes: f64,
+}
+
+impl Throttler {
+    fn new(bytes_per_second: f64) -> Self {
+        Self { bytes_per_second, period_start: Instant::now(), observed_bytes: 0.0 }
+    }
+
+    fn maybe_throttle(&mut self, bytes: u64) {
+        self.observed_bytes += bytes as f64;
+        let elapsed = self.period_start.elapsed();
+        if elapsed < THROTTLE_CHECK_INTERVAL {
+            return;
+        }
+
+        let target = Duration::from_secs_f64(self.observed_bytes / self.bytes_per_second);
+        if target > elapsed {
+            thread::sleep(target - elapsed);
+        }
+        self.period_start = Instant::now();
+        self.observed_bytes = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::record::{EndTransactionMarker, CONTROL_FLAG, TRANSACTIONAL_FLAG};
+    use crate::kafka::storage::LOG_START_OFFSET_CHECKPOINT_FILE;
+    use std::fs;
+
+    fn keyed_batch(base_offset: i64, timestamp: i64, records: &[(&str, Option<&str>)]) -> Vec<u8> {
+        let header = RecordBatchHeader {
+            base_offset,
+            batch_length: 0,
+            partition_leader_epoch: 0,
+            magic: 2,
+            crc: 0,
+            attributes: 0,
+            last_offset_delta: records.len() as i32 - 1,
+            base_timestamp: timestamp,
+            max_timestamp: timestamp,
+            producer_id: -1,
+            producer_epoch: -1,
+            base_sequence: -1,
+            records_count: 0,
+        };
+        let records = records
+            .iter()
+            .enumerate()
+            .map(|(delta, (key, value))| Record {
+                attributes: 0,
+                timestamp_delta: 0,
+                offset_delta: delta as i32,
+                key: Some(key.as_bytes().to_vec()),
+                value: value.map(|value| value.as_bytes().to_vec()),
+                headers: Vec::new(),
+            })
+            .collect();
+        RecordBatch { header, records }.to_bytes()
+    }
+
+    fn read_all(log: &PartitionLog) -> Vec<(i64, String, Option<String>)> {
+        log.batches(0)
+            .flat_map(|batch| {
+                let batch = batch.unwrap();
+                let batch = RecordBatch::parse(batch.header, &batch.bytes).unwrap();
+                batch.records.into_iter().map(move |record| {
+                    (
+                        batch.header.base_offset + record.offset_delta as i64,
+                        String::from_utf8(record.key.unwrap()).unwrap(),
+                        record.value.map(|value| String::from_utf8(value).unwrap()),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_keeps_latest_record_per_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let partition_dir = dir.path().join("changelog-0");
+        fs::create_dir(&partition_dir).unwrap();
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64;
+        let old = now - 10_000;
+        fs::write(
+            segment_file(&partition_dir, 0, LOG_FILE_SUFFIX),
+            [
+                keyed_batch(0, old, &[("a", Some("1")), ("b", Some("1"))]),
+                keyed_batch(2, old, &[("c", Some("1"))]),
+            ]
+            .concat(),
+        )
+        .unwrap();
+        fs::write(
+            segment_file(&partition_dir, 3, LOG_FILE_SUFFIX),
+            [keyed_batch(3, old, &[("a", Some("2")), ("b", None)]), keyed_batch(5, now, &[("c", None)])].concat(),
+        )
+        .unwrap();
+        fs::write(segment_file(&partition_dir, 6, LOG_FILE_SUFFIX), keyed_batch(6, now, &[("a", Some("3"))])).unwrap();
+
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        log_manager.update_topic_config(
+            "changelog",
+            &HashMap::from([
+                ("cleanup.policy".to_owned(), "compact".to_owned()),
+                ("delete.retention.ms".to_owned(), "5000".to_owned()),
+            ]),
+        );
+
+        let mut cleaner = LogCleaner::new(log_manager.clone(), &config);
+        assert!(cleaner.clean_filthiest_log().unwrap());
+        assert!(!cleaner.clean_filthiest_log().unwrap());
+
+        let log = log_manager.get_log(&TopicPartition::new("changelog", 0)).unwrap();
+        let log = log.lock().unwrap();
+        assert_eq!(
+            read_all(&log),
+            vec![
+                (3, "a".to_owned(), Some("2".to_owned())),
+                // Old as it is, the tombstone is kept until it was seen by
+                // a cleaning pass delete.retention.ms ago.
+                (4, "b".to_owned(), None),
+                (5, "c".to_owned(), None),
+                (6, "a".to_owned(), Some("3".to_owned())),
+            ]
+        );
+        assert_eq!(log.log_end_offset(), 7);
+
+        let checkpoint = log_manager.read_checkpoints(CLEANER_OFFSET_CHECKPOINT_FILE);
+        assert_eq!(checkpoint[&TopicPartition::new("changelog", 0)], 6);
+        assert!(log_manager.read_checkpoints(LOG_START_OFFSET_CHECKPOINT_FILE).contains_key(log.topic_partition()));
+    }
+
+    #[test]
+    fn test_drops_aborted_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        log_manager.update_topic_config("changelog", &HashMap::from([("cleanup.policy".to_owned(), "compact".to_owned())]));
+        let topic_partition = TopicPartition::new("changelog", 0);
+        let log = log_manager.get_or_create_log(&topic_partition).unwrap();
+        let log_config = LogConfig { segment_bytes: 1024, ..log_manager.log_config("changelog") };
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64;
+        let transactional = |producer_id: i64, key: &str| {
+            let mut batch = RecordBatch::new(vec![Record::new(Some(key.as_bytes().to_vec()), Some(vec![1]))], now);
+            batch.header.attributes = TRANSACTIONAL_FLAG;
+            batch.header.producer_id = producer_id;
+            batch.header.producer_epoch = 0;
+            batch.to_bytes()
+        };
+        let marker =
+            |producer_id: i64, commit: bool| EndTransactionMarker::new(commit, 0).to_batch(producer_id, 0, now).to_bytes();
+        {
+            let mut log = log.lock().unwrap();
+            for bytes in [transactional(7, "a"), transactional(8, "b"), marker(7, false), marker(8, true)] {
+                log.append(bytes, &log_config).unwrap();
+            }
+            assert_eq!(log.aborted_transactions(0, 4).len(), 1);
+            // Roll past the transactions so they become cleanable.
+            log.append(keyed_batch(0, now, &[("x".repeat(1024).as_str(), Some("1"))]), &log_config).unwrap();
+            log.append(keyed_batch(0, now, &[("c", Some("1"))]), &log_config).unwrap();
+        }
+
+        let mut cleaner = LogCleaner::new(log_manager.clone(), &config);
+        assert!(cleaner.clean_filthiest_log().unwrap());
+
+        let log = log.lock().unwrap();
+        let data = log
+            .batches(0)
+            .map(|batch| batch.unwrap())
+            .filter(|batch| !batch.header.is_control())
+            .map(|batch| batch.header.base_offset)
+            .collect::<Vec<_>>();
+        assert_eq!(data, vec![1, 4, 5]);
+        assert_eq!(log.aborted_transactions(0, 4).len(), 1);
+    }
+
+    #[test]
+    fn test_drops_markers_of_cleaned_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        let topic_partition = TopicPartition::new("changelog", 0);
+        let log = log_manager.get_or_create_log(&topic_partition).unwrap();
+        let log_config =
+            LogConfig { segment_bytes: 1024, delete_retention_ms: 0, ..log_manager.log_config("changelog") };
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64;
+        let transactional = |producer_id: i64, key: &str| {
+            let mut batch = RecordBatch::new(vec![Record::new(Some(key.as_bytes().to_vec()), Some(vec![1]))], now);
+            batch.header.attributes = TRANSACTIONAL_FLAG;
+            batch.header.producer_id = producer_id;
+            batch.header.producer_epoch = 0;
+            batch.to_bytes()
+        };
+        let marker =
+            |producer_id: i64, commit: bool| EndTransactionMarker::new(commit, 0).to_batch(producer_id, 0, now).to_bytes();
+        {
+            let mut log = log.lock().unwrap();
+            for bytes in [transactional(7, "a"), transactional(8, "b"), marker(7, false), marker(8, true)] {
+                log.append(bytes, &log_config).unwrap();
+            }
+            log.append(keyed_batch(0, now, &[("x".repeat(1024).as_str(), Some("1"))]), &log_config).unwrap();
+            log.append(keyed_batch(0, now, &[("c", Some("1"))]), &log_config).unwrap();
+        }
+        let markers = |log: &PartitionLog| {
+            log.batches(0)
+                .map(|batch| batch.unwrap().header)
+                .filter(RecordBatchHeader::is_control)
+                .map(|header| (header.base_offset, header.delete_horizon_ms().is_some()))
+                .collect::<Vec<_>>()
+        };
+        let mut cleaner = LogCleaner::new(log_manager.clone(), &config);
+
+        // The first pass drops the aborted data, but keeps its marker.
+        cleaner.clean(&topic_partition, &log, &log_config).unwrap();
+        assert_eq!(markers(&log.lock().unwrap()), vec![(2, false), (3, false)]);
+        assert_eq!(log.lock().unwrap().aborted_transactions(0, 4).len(), 1);
+
+        // The next pass finds the aborted transaction empty, unindexes it and
+        // stamps its marker with a delete horizon.
+        cleaner.clean(&topic_partition, &log, &log_config).unwrap();
+        assert_eq!(markers(&log.lock().unwrap()), vec![(2, true), (3, false)]);
+        assert!(log.lock().unwrap().aborted_transactions(0, 4).is_empty());
+
+        // Past the horizon, the marker goes. The commit marker stays with its
+        // data.
+        cleaner.clean(&topic_partition, &log, &log_config).unwrap();
+        assert_eq!(markers(&log.lock().unwrap()), vec![(3, false)]);
+        let data = log.lock().unwrap().batches(0).map(|batch| batch.unwrap().header.base_offset).collect::<Vec<_>>();
+        assert_eq!(data, vec![1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_tombstones_outlive_the_first_cleaning() {
+        let dir = tempfile::tempdir().unwrap();
+        let partition_dir = dir.path().join("changelog-0");
+        fs::create_dir(&partition_dir).unwrap();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64;
+        let old = now - 60_000;
+        fs::write(segment_file(&partition_dir, 0, LOG_FILE_SUFFIX), keyed_batch(0, old, &[("a", None)])).unwrap();
+        fs::write(segment_file(&partition_dir, 1, LOG_FILE_SUFFIX), keyed_batch(1, now, &[("b", Some("1"))])).unwrap();
+
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        let topic_partition = TopicPartition::new("changelog", 0);
+        let log = log_manager.get_log(&topic_partition).unwrap();
+        let log_config = LogConfig { delete_retention_ms: 0, ..log_manager.log_config("changelog") };
+        let mut cleaner = LogCleaner::new(log_manager.clone(), &config);
+
+        // The first pass keeps the tombstone, however old, and stamps its
+        // batch with the delete horizon without changing its timestamp.
+        cleaner.clean(&topic_partition, &log, &log_config).unwrap();
+        {
+            let log = log.lock().unwrap();
+            assert_eq!(read_all(&log), vec![(0, "a".to_owned(), None), (1, "b".to_owned(), Some("1".to_owned()))]);
+            let batch = log.batches(0).next().unwrap().unwrap();
+            assert!(batch.header.delete_horizon_ms().is_some_and(|horizon| horizon >= now));
+            let batch = RecordBatch::parse(batch.header, &batch.bytes).unwrap();
+            assert_eq!(batch.header.base_timestamp + batch.records[0].timestamp_delta, old);
+        }
+
+        // The next pass past the horizon drops it.
+        cleaner.clean(&topic_partition, &log, &log_config).unwrap();
+        assert_eq!(read_all(&log.lock().unwrap()), vec![(1, "b".to_owned(), Some("1".to_owned()))]);
+    }
+
+    #[test]
+    fn test_cleaned_txn_metadata() {
+        let header = |base_offset: i64, producer_id: i64, attributes: i16| RecordBatchHeader {
+            base_offset,
+            batch_length: 0,
+            partition_leader_epoch: 0,
+            magic: 2,
+            crc: 0,
+            attributes,
+            last_offset_delta: 0,
+            base_timestamp: 0,
+            max_timestamp: 0,
+            producer_id,
+            producer_epoch: 0,
+            base_sequence: 0,
+            records_count: 1,
+        };
+        let aborted = AbortedTxn { producer_id: 7, first_offset: 1, last_offset: 3, last_stable_offset: 4 };
+        let mut txns = CleanedTxnMetadata::new(vec![aborted]);
+        let marker = TRANSACTIONAL_FLAG | CONTROL_FLAG;
+
+        // Producer 7 wrote before its aborted transaction started.
+        assert!(!txns.is_aborted(&header(0, 7, TRANSACTIONAL_FLAG)));
+        assert!(txns.is_aborted(&header(1, 7, TRANSACTIONAL_FLAG)));
+        // Producer 8's transaction overlaps but is not aborted, and
+        // non-transactional batches never are.
+        assert!(!txns.is_aborted(&header(2, 8, TRANSACTIONAL_FLAG)));
+        assert!(!txns.is_aborted(&header(2, 7, 0)));
+        // The abort marker ends the transaction. It stays, and stays
+        // indexed, as its data was only just seen.
+        assert!(!txns.end_txn(&header(3, 7, marker)));
+        assert_eq!(txns.take_retained_aborted(), vec![aborted]);
+        assert!(!txns.is_aborted(&header(4, 7, TRANSACTIONAL_FLAG)));
+        // Producer 8's commit marker still has data, producer 9's has none.
+        assert!(!txns.end_txn(&header(5, 8, marker)));
+        assert!(txns.end_txn(&header(6, 9, marker)));
+        assert!(txns.take_retained_aborted().is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use tracing::warn;
+
+pub(crate) const SEGMENT_BYTES_CONFIG: &str = "segment.bytes";
+pub(crate) const RETENTION_MS_CONFIG: &str = "retention.ms";
+pub(crate) const RETENTION_BYTES_CONFIG: &str = "retention.bytes";
+pub(crate) const CLEANUP_POLICY_CONFIG: &str = "cleanup.policy";
+pub(crate) const DELETE_RETENTION_MS_CONFIG: &str = "delete.retention.ms";
+pub(crate) const MIN_CLEANABLE_DIRTY_RATIO_CONFIG: &str = "min.cleanable.dirty.ratio";
+pub(crate) const COMPRESSION_TYPE_CONFIG: &str = "compression.type";
+
+/// Settings of a single partition log: broker defaults with any topic
+/// overrides applied. A negative retention means unlimited.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LogConfig {
+    pub(crate) segment_bytes: u64,
+    pub(crate) retention_ms: i64,
+    pub(crate) retention_bytes: i64,
+    pub(crate) cleanup_policy: CleanupPolicy,
+    pub(crate) delete_retention_ms: i64,
+    pub(crate) min_cleanable_dirty_ratio: f64,
+    pub(crate) compression_type: CompressionType,
+}
+
+impl LogConfig {
+    /// Applies topic-level overrides. Unknown keys are ignored and malformed
+    /// values keep the broker default.
+    pub(crate) fn with_overrides(&self, overrides: &HashMap<String, String>) -> Self {
+        let mut config = self.clone();
+        for (name, value) in overrides {
+            let result = match name.as_str() {
+                SEGMENT_BYTES_CONFIG => set(&mut config.segment_bytes, value),
+                RETENTION_MS_CONFIG => set(&mut config.retention_ms, value),
+                RETENTION_BYTES_CONFIG => set(&mut config.retention_bytes, value),
+                CLEANUP_POLICY_CONFIG => set(&mut config.cleanup_policy, value),
+                DELETE_RETENTION_MS_CONFIG => set(&mut config.delete_retention_ms, value),
+                MIN_CLEANABLE_DIRTY_RATIO_CONFIG => set(&mut config.min_cleanable_dirty_ratio, value),
+                COMPRESSION_TYPE_CONFIG => set(&mut config.compression_type, value),
+                _ => continue,
+            };
+            if let Err(err) = result {
+                warn!(config = %name, value = %value, error = %err, "Ignoring malformed topic config");
+            }
+        }
+        config
+    }
+}
+
+fn set<T>(target: &mut T, value: &str) -> Result<(), String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    *target = value.parse().map_err(|err: T::Err| err.to_string())?;
+    Ok(())
+}
+
+/// The `cleanup.policy` of a topic: `delete`, `compact` or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CleanupPolicy {
+    pub(crate) delete: bool,
+    pub(crate) compact: bool,
+}
+
+impl Default for CleanupPolicy {
+    fn default() -> Self {
+        Self { delete: true, compact: false }
+    }
+}
+
+impl FromStr for CleanupPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut policy = Self { delete: false, compact: false };
+        for part in value.split(',').map(str::trim) {
+            match part {
+                "delete" => policy.delete = true,
+                "compact" => policy.compact = true,
+                other => return Err(format!("unknown cleanup policy {other:?}")),
+            }
+        }
+        Ok(policy)
+    }
+}
+
+impl Display for CleanupPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.delete, self.compact) {
+            (true, true) => write!(f, "delete,compact"),
+            (false, true) => write!(f, "compact"),
+            _ => write!(f, "delete"),
+        }
+    }
+}
+
+/// The `compression.type` of a topic. `producer` keeps the codec the
+/// producer chose.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum CompressionType {
+    #[default]
+    Producer,
+    Uncompressed,
+    Gzip,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+impl CompressionType {
+    pub(crate) const NAMES: &[&str] = &["producer", "uncompressed", "gzip", "snappy", "lz4", "zstd"];
+}
+
+impl FromStr for CompressionType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "producer" => Ok(CompressionType::Producer),
+            "uncompressed" => Ok(CompressionType::Uncompressed),
+            "gzip" => Ok(CompressionType::Gzip),
+            "snappy" => Ok(CompressionType::Snappy),
+            "lz4" => Ok(CompressionType::Lz4),
+            "zstd" => Ok(CompressionType::Zstd),
+            other => Err(format!("unknown compression type {other:?}")),
+        }
+    }
+}
+
+impl Display for CompressionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CompressionType::Producer => "producer",
+            CompressionType::Uncompressed => "uncompressed",
+            CompressionType::Gzip => "gzip",
+            CompressionType::Snappy => "snappy",
+            CompressionType::Lz4 => "lz4",
+            CompressionType::Zstd => "zstd",
+        };
+        f.write_str(name)
+    }
+}
@@ -0,0 +1,347 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::metadata::CLUSTER_METADATA_TOPIC;
+use crate::kafka::storage::{
+    LogConfig, OffsetCheckpointFile, PartitionLog, PartitionMetadataFile, TopicPartition, CLEAN_SHUTDOWN_FILE,
+    LOG_START_OFFSET_CHECKPOINT_FILE, RECOVERY_POINT_CHECKPOINT_FILE,
+};
+use crate::kafka::types::Uuid;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+/// A topic as known from the local partition logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TopicMetadata {
+    pub(crate) topic_id: Uuid,
+    pub(crate) num_partitions: i32,
+}
+
+/// Owns every partition log across the configured log directories.
+#[derive(Debug)]
+pub(crate) struct LogManager {
+    config: ServerConfig,
+    /// The static broker defaults with any dynamic broker overrides applied.
+    default_log_config: RwLock<LogConfig>,
+    /// Topic-level overrides, applied on top of the defaults on every lookup
+    /// so that changed defaults take effect immediately.
+    topic_overrides: RwLock<HashMap<String, HashMap<String, String>>>,
+    topic_ids: RwLock<HashMap<String, Uuid>>,
+    logs: RwLock<BTreeMap<TopicPartition, Arc<Mutex<PartitionLog>>>>,
+}
+
+impl LogManager {
+    /// Loads and recovers all partition logs, then checkpoints the recovered
+    /// log end offsets as the new recovery points. Logs in a directory shut
+    /// down cleanly are not recovered. Topics without an id are assigned one.
+    pub(crate) fn startup(config: ServerConfig) -> io::Result<Self> {
+        let mut logs = BTreeMap::new();
+        let mut topic_ids = HashMap::new();
+        let mut without_topic_id = Vec::new();
+
+        for log_dir in &config.log_dirs {
+            fs::create_dir_all(log_dir)?;
+            let recovery_points = read_checkpoint(log_dir, RECOVERY_POINT_CHECKPOINT_FILE);
+            let log_start_offsets = read_checkpoint(log_dir, LOG_START_OFFSET_CHECKPOINT_FILE);
+            let clean_shutdown_file = log_dir.join(CLEAN_SHUTDOWN_FILE);
+            let clean_shutdown = clean_shutdown_file.exists();
+
+            for (dir, topic_partition) in partition_dirs(log_dir)? {
+                match PartitionMetadataFile::new(&dir).read() {
+                    Ok(Some(topic_id)) => {
+                        topic_ids.entry(topic_partition.topic.clone()).or_insert(topic_id);
+                    }
+                    Ok(None) => without_topic_id.push((dir.clone(), topic_partition.topic.clone())),
+                    Err(err) => {
+                        warn!(partition = %topic_partition, error = %err, "Ignoring unreadable partition metadata");
+                        without_topic_id.push((dir.clone(), topic_partition.topic.clone()));
+                    }
+                }
+                let recovery_point = recovery_points.get(&topic_partition).copied().unwrap_or(0);
+                let log_start_offset = log_start_offsets.get(&topic_partition).copied().unwrap_or(0);
+                let log = PartitionLog::load(
+                    dir,
+                    topic_partition.clone(),
+                    recovery_point,
+                    log_start_offset,
+                    clean_shutdown,
+                    &config,
+                )?;
+                info!(partition = %topic_partition, recovery_point, log_start_offset = log.log_start_offset(),
+                    log_end_offset = log.log_end_offset(), recovered_segments = log.recovered_segments(),
+                    "Loaded partition log");
+                logs.insert(topic_partition, Arc::new(Mutex::new(log)));
+            }
+            // A crash from now on leaves logs that need recovery.
+            if clean_shutdown {
+                fs::remove_file(clean_shutdown_file)?;
+            }
+        }
+
+        for (dir, topic) in without_topic_id {
+            let topic_id = *topic_ids.entry(topic).or_insert_with(Uuid::random);
+            PartitionMetadataFile::new(&dir).write(topic_id)?;
+        }
+
+        let manager = Self {
+            default_log_config: RwLock::new(config.default_log_config()),
+            config,
+            topic_overrides: RwLock::new(HashMap::new()),
+            topic_ids: RwLock::new(topic_ids),
+            logs: RwLock::new(logs),
+        };
+        manager.flush_logs()?;
+        manager.checkpoint_recovery_points()?;
+        manager.checkpoint_log_start_offsets()?;
+        Ok(manager)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.logs.read().expect("log map lock poisoned").len()
+    }
+
+    pub(crate) fn get_log(&self, topic_partition: &TopicPartition) -> Option<Arc<Mutex<PartitionLog>>> {
+        self.logs.read().expect("log map lock poisoned").get(topic_partition).cloned()
+    }
+
+    /// Returns the log of `topic_partition`, creating an empty one in the log
+    /// directory holding the fewest partitions if there is none.
+    pub(crate) fn get_or_create_log(&self, topic_partition: &TopicPartition) -> io::Result<Arc<Mutex<PartitionLog>>> {
+        if let Some(log) = self.get_log(topic_partition) {
+            return Ok(log);
+        }
+
+        let mut logs = self.logs.write().expect("log map lock poisoned");
+        if let Some(log) = logs.get(topic_partition) {
+            return Ok(log.clone());
+        }
+        let log_dir = self
+            .config
+            .log_dirs
+            .iter()
+            .min_by_key(|log_dir| {
+                logs.values()
+                    .filter(|log| log.lock().expect("partition log lock poisoned").dir().parent() == Some(log_dir.as_path()))
+                    .count()
+            })
+            .expect("at least one log directory is configured");
+
+        let dir = log_dir.join(topic_partition.to_string());
+        fs::create_dir_all(&dir)?;
+        let topic_id = *self
+            .topic_ids
+            .write()
+            .expect("topic id lock poisoned")
+            .entry(topic_partition.topic.clone())
+            .or_insert_with(Uuid::random);
+        PartitionMetadataFile::new(&dir).write(topic_id)?;
+        let log = Arc::new(Mutex::new(PartitionLog::load(dir, topic_partition.clone(), 0, 0, false, &self.config)?));
+        info!(partition = %topic_partition, log_dir = %log_dir.display(), "Created partition log");
+        logs.insert(topic_partition.clone(), log.clone());
+        Ok(log)
+    }
+
+    /// A snapshot of all logs, safe to iterate while logs are added or removed.
+    pub(crate) fn logs(&self) -> Vec<(TopicPartition, Arc<Mutex<PartitionLog>>)> {
+        let logs = self.logs.read().expect("log map lock poisoned");
+        logs.iter().map(|(tp, log)| (tp.clone(), log.clone())).collect()
+    }
+
+    /// Every topic with at least one local partition, by name. Topics are
+    /// assumed to hold partitions `0..num_partitions`.
+    pub(crate) fn topics(&self) -> BTreeMap<String, TopicMetadata> {
+        let partitions = self.logs.read().expect("log map lock poisoned").keys().cloned().collect::<Vec<_>>();
+        let topic_ids = self.topic_ids.read().expect("topic id lock poisoned");
+
+        let mut topics = BTreeMap::new();
+        for topic_partition in &partitions {
+            let Some(&topic_id) = topic_ids.get(&topic_partition.topic) else {
+                continue;
+            };
+            let metadata = topics
+                .entry(topic_partition.topic.clone())
+                .or_insert(TopicMetadata { topic_id, num_partitions: 0 });
+            metadata.num_partitions = metadata.num_partitions.max(topic_partition.partition + 1);
+        }
+        topics
+    }
+
+    /// Replaces the topic-level overrides applied on top of the broker
+    /// defaults for every partition of `topic`.
+    pub(crate) fn update_topic_config(&self, topic: &str, overrides: &HashMap<String, String>) {
+        self.topic_overrides.write().expect("topic config lock poisoned").insert(topic.to_owned(), overrides.clone());
+        info!(topic, config = ?self.log_config(topic), "Updated topic log config");
+    }
+
+    /// Replaces the dynamic broker overrides, keyed by topic config name,
+    /// applied on top of the static broker defaults.
+    pub(crate) fn update_default_log_config(&self, overrides: &HashMap<String, String>) {
+        let config = self.config.default_log_config().with_overrides(overrides);
+        info!(?config, "Updated default log config");
+        *self.default_log_config.write().expect("default log config lock poisoned") = config;
+    }
+
+    pub(crate) fn log_config(&self, topic: &str) -> LogConfig {
+        let defaults = self.default_log_config.read().expect("default log config lock poisoned");
+        match self.topic_overrides.read().expect("topic config lock poisoned").get(topic) {
+            Some(overrides) => defaults.with_overrides(overrides),
+            None => defaults.clone(),
+        }
+    }
+
+    /// Applies retention to every log and checkpoints the log start offsets
+    /// if any segment was deleted. The cluster metadata log is left alone.
+    pub(crate) fn cleanup_logs(&self) -> io::Result<()> {
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+
+        let mut deleted = 0;
+        for (topic_partition, log) in self.logs().iter().filter(|(tp, _)| tp.topic != CLUSTER_METADATA_TOPIC) {
+            let config = self.log_config(&topic_partition.topic);
+            deleted += log.lock().expect("partition log lock poisoned").delete_old_segments(&config, now_ms)?;
+        }
+
+        if deleted > 0 {
+            info!(deleted, "Log retention deleted segments");
+            self.checkpoint_log_start_offsets()?;
+        }
+        Ok(())
+    }
+
+    /// Runs [`Self::cleanup_logs`] every `log.retention.check.interval.ms`.
+    pub(crate) async fn run_retention(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(Duration::from_millis(self.config.log_retention_check_interval_ms));
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.cleanup_logs() {
+                error!(error = %err, "Log retention failed");
+            }
+        }
+    }
+
+    /// Flushes every log, checkpoints the recovery points and log start
+    /// offsets and marks every log directory as shut down cleanly, so that
+    /// the next startup has nothing to recover. Nothing may be appended
+    /// afterwards.
+    pub(crate) fn shutdown(&self) -> io::Result<()> {
+        self.flush_logs()?;
+        self.checkpoint_recovery_points()?;
+        self.checkpoint_log_start_offsets()?;
+        for log_dir in &self.config.log_dirs {
+            fs::write(log_dir.join(CLEAN_SHUTDOWN_FILE), [])?;
+        }
+        info!(partitions = self.len(), "Flushed and checkpointed logs");
+        Ok(())
+    }
+
+    /// Flushes every log up to its log end offset, its new recovery point.
+    fn flush_logs(&self) -> io::Result<()> {
+        for (_, log) in self.logs() {
+            log.lock().expect("partition log lock poisoned").flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes `recovery-point-offset-checkpoint` in every log directory.
+    pub(crate) fn checkpoint_recovery_points(&self) -> io::Result<()> {
+        self.write_checkpoints(RECOVERY_POINT_CHECKPOINT_FILE, |log| Ok(log.recovery_point()))
+    }
+
+    /// Writes `log-start-offset-checkpoint` in every log directory.
+    pub(crate) fn checkpoint_log_start_offsets(&self) -> io::Result<()> {
+        self.write_checkpoints(LOG_START_OFFSET_CHECKPOINT_FILE, |log| Ok(log.log_start_offset()))
+    }
+
+    /// Reads the checkpoint `file_name` from every log directory.
+    pub(crate) fn read_checkpoints(&self, file_name: &str) -> HashMap<TopicPartition, i64> {
+        self.config.log_dirs.iter().flat_map(|log_dir| read_checkpoint(log_dir, file_name)).collect()
+    }
+
+    /// Writes the checkpoint `file_name` in every log directory, with one
+    /// entry per log holding the offset returned by `offset`.
+    pub(crate) fn write_checkpoints(
+        &self,
+        file_name: &str,
+        mut offset: impl FnMut(&mut PartitionLog) -> io::Result<i64>,
+    ) -> io::Result<()> {
+        let logs = self.logs.read().expect("log map lock poisoned");
+
+        for log_dir in &self.config.log_dirs {
+            let mut offsets = Vec::new();
+            for log in logs.values() {
+                let mut log = log.lock().expect("partition log lock poisoned");
+                if log.dir().parent() == Some(log_dir.as_path()) {
+                    offsets.push((log.topic_partition().clone(), offset(&mut log)?));
+                }
+            }
+            OffsetCheckpointFile::new(log_dir, file_name).write(offsets)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_checkpoint(log_dir: &Path, file_name: &str) -> HashMap<TopicPartition, i64> {
+    OffsetCheckpointFile::new(log_dir, file_name).read().unwrap_or_else(|err| {
+        warn!(log_dir = %log_dir.display(), file = file_name, error = %err, "Ignoring unreadable checkpoint");
+        HashMap::new()
+    })
+}
+
+fn partition_dirs(log_dir: &Path) -> io::Result<Vec<(PathBuf, TopicPartition)>> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(log_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name();
+        match name.to_str().and_then(TopicPartition::from_dir_name) {
+            Some(topic_partition) => dirs.push((entry.path(), topic_partition)),
+            None => info!(dir = %entry.path().display(), "Skipping non-partition directory"),
+        }
+    }
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::record::{Record, RecordBatch};
+
+    fn append(log_manager: &LogManager, topic_partition: &TopicPartition) {
+        let log = log_manager.get_or_create_log(topic_partition).unwrap();
+        let batch = RecordBatch::new(vec![Record::new(None, Some(b"value".to_vec()))], 1_000);
+        log.lock().unwrap().append(batch.to_bytes(), &log_manager.log_config(&topic_partition.topic)).unwrap();
+    }
+
+    #[test]
+    fn test_restart_after_shutdown_recovers_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let topic_partition = TopicPartition::new("orders", 0);
+        let log_manager = LogManager::startup(config.clone()).unwrap();
+        append(&log_manager, &topic_partition);
+        append(&log_manager, &topic_partition);
+        log_manager.shutdown().unwrap();
+        drop(log_manager);
+        assert!(dir.path().join(CLEAN_SHUTDOWN_FILE).exists());
+
+        let log_manager = LogManager::startup(config.clone()).unwrap();
+        let log = log_manager.get_log(&topic_partition).unwrap();
+        let log = log.lock().unwrap();
+        assert_eq!((log.log_end_offset(), log.recovered_segments()), (2, 0));
+        drop(log);
+        assert_eq!(log_manager.read_checkpoints(RECOVERY_POINT_CHECKPOINT_FILE)[&topic_partition], 2);
+        assert!(!dir.path().join(CLEAN_SHUTDOWN_FILE).exists());
+
+        // Without a clean shutdown, the active segment is recovered.
+        append(&log_manager, &topic_partition);
+        drop(log_manager);
+        let log_manager = LogManager::startup(config).unwrap();
+        let log = log_manager.get_log(&topic_partition).unwrap();
+        let log = log.lock().unwrap();
+        assert_eq!((log.log_end_offset(), log.recovered_segments()), (3, 1));
+    }
+}
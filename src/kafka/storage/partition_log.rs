@@ -0,0 +1,821 @@
+use crate::kafka::config::ServerConfig;
+use crate::kafka::record::{validate_batch, EndTransactionMarker, RecordBatch, RecordBatchError, RecordBatchHeader};
+use crate::kafka::storage::{
+    segment_file, AbortedTxn, ActiveProducer, FileBatch, LeaderEpochCache, LogConfig, LogSegment, ProducerStateError,
+    ProducerStateManager,
+    TimestampAndOffset, TopicPartition, CLEANED_FILE_SUFFIX, DELETED_FILE_SUFFIX, INDEX_FILE_SUFFIX, LOG_FILE_SUFFIX,
+    SWAP_FILE_SUFFIX, TIME_INDEX_FILE_SUFFIX,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Suffixes of files left behind by an interrupted operation.
+const TEMP_FILE_SUFFIXES: [&str; 3] = [DELETED_FILE_SUFFIX, CLEANED_FILE_SUFFIX, "tmp"];
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AppendError {
+    #[error(transparent)]
+    InvalidBatch(#[from] RecordBatchError),
+    #[error(transparent)]
+    ProducerState(#[from] ProducerStateError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// The on-disk log of a single partition: an ordered set of segments.
+#[derive(Debug)]
+pub(crate) struct PartitionLog {
+    topic_partition: TopicPartition,
+    dir: PathBuf,
+    segments: BTreeMap<i64, LogSegment>,
+    log_start_offset: i64,
+    log_end_offset: i64,
+    recovery_point: i64,
+    index_interval_bytes: usize,
+    producer_state: ProducerStateManager,
+    leader_epochs: LeaderEpochCache,
+    /// The bytes appended since the log was loaded.
+    bytes_in: u64,
+    /// The segments scanned and rebuilt when the log was loaded.
+    recovered_segments: usize,
+}
+
+impl PartitionLog {
+    /// Loads the partition in `dir`, recovering every segment that may hold
+    /// offsets at or beyond `recovery_point`, or none after a clean shutdown.
+    /// A segment with a missing or corrupt index is recovered regardless. If
+    /// a segment had to be truncated, all later segments are discarded.
+    pub(crate) fn load(
+        dir: PathBuf,
+        topic_partition: TopicPartition,
+        recovery_point: i64,
+        log_start_offset: i64,
+        clean_shutdown: bool,
+        config: &ServerConfig,
+    ) -> io::Result<Self> {
+        remove_temp_files(&dir)?;
+        complete_swaps(&dir)?;
+
+        let mut segments = BTreeMap::new();
+        for base_offset in segment_base_offsets(&dir)? {
+            segments.insert(base_offset, LogSegment::open(&dir, base_offset, config.log_index_interval_bytes)?);
+        }
+        if segments.is_empty() {
+            // The checkpointed start offset may have been moved past 0 by
+            // retention or DeleteRecords.
+            let base_offset = log_start_offset.max(0);
+            segments.insert(base_offset, LogSegment::open(&dir, base_offset, config.log_index_interval_bytes)?);
+        }
+
+        let base_offsets = segments.keys().copied().collect::<Vec<_>>();
+        let mut recovered_segments = 0;
+        for (i, base_offset) in base_offsets.iter().enumerate() {
+            let next_base_offset = base_offsets.get(i + 1).copied().unwrap_or(i64::MAX);
+            let segment = segments.get_mut(base_offset).expect("segment exists");
+            if (clean_shutdown || next_base_offset <= recovery_point) && !segment.needs_index_rebuild() {
+                continue;
+            }
+
+            recovered_segments += 1;
+            let truncated = segment.recover()?;
+            if truncated > 0 {
+                warn!(partition = %topic_partition, segment = base_offset, truncated, "Truncated corrupt segment tail");
+                for later in base_offsets[i + 1..].iter() {
+                    let segment = segments.remove(later).expect("segment exists");
+                    warn!(partition = %topic_partition, segment = later, "Deleting segment after corruption");
+                    segment.delete()?;
+                }
+                break;
+            }
+        }
+
+        let active = segments.values().next_back().expect("log has an active segment");
+        let log_end_offset = active.read_next_offset()?;
+        let first_base_offset = *segments.keys().next().expect("log has an active segment");
+        let (producer_state, snapshot_offset) = ProducerStateManager::load(&dir, log_end_offset)?;
+        let (mut leader_epochs, found_leader_epochs) = LeaderEpochCache::load(&dir)?;
+        leader_epochs.truncate_from_end(log_end_offset)?;
+
+        let mut log = Self {
+            topic_partition,
+            dir,
+            segments,
+            log_start_offset: log_start_offset.clamp(first_base_offset, log_end_offset),
+            log_end_offset,
+            recovery_point: log_end_offset,
+            index_interval_bytes: config.log_index_interval_bytes,
+            producer_state,
+            leader_epochs,
+            bytes_in: 0,
+            recovered_segments,
+        };
+        log.rebuild_producer_state(snapshot_offset.unwrap_or(log.log_start_offset))?;
+        log.rebuild_leader_epochs(if found_leader_epochs { recovery_point } else { log.log_start_offset })?;
+        Ok(log)
+    }
+
+    /// Assigns the epochs of the batches from `start_offset` on, which may
+    /// not have made it to the checkpoint before a crash. Stops at the first
+    /// unreadable batch, like [`Self::rebuild_producer_state`].
+    fn rebuild_leader_epochs(&mut self, start_offset: i64) -> io::Result<()> {
+        let mut epochs = Vec::new();
+        for batch in self.batches(start_offset.max(self.log_start_offset)) {
+            match batch {
+                Ok(batch) => epochs.push((batch.header.partition_leader_epoch, batch.header.base_offset)),
+                Err(err) => {
+                    warn!(partition = %self.topic_partition, error = %err, "Stopped rebuilding leader epochs");
+                    break;
+                }
+            }
+        }
+        for (epoch, start_offset) in epochs {
+            self.leader_epochs.assign(epoch, start_offset)?;
+        }
+        self.leader_epochs.truncate_from_start(self.log_start_offset)
+    }
+
+    /// Replays the producer batches from `start_offset` to the log end on top
+    /// of the loaded snapshot, indexing the aborts that did not make it to the
+    /// transaction index before a crash. Segments below the recovery point
+    /// were not validated on load, so replay stops at the first unreadable
+    /// batch.
+    fn rebuild_producer_state(&mut self, start_offset: i64) -> io::Result<()> {
+        let mut batches = Vec::new();
+        for batch in self.batches(start_offset) {
+            let batch = batch.and_then(|batch| Ok((end_transaction_marker(&batch.header, &batch.bytes)?, batch.header)));
+            match batch {
+                Ok((marker, header)) if header.base_offset >= start_offset => batches.push((header, marker)),
+                Ok(_) => {}
+                Err(err) => {
+                    warn!(partition = %self.topic_partition, error = %err, "Stopped rebuilding producer state");
+                    break;
+                }
+            }
+        }
+
+        for (header, marker) in batches {
+            let Some(marker) = marker else {
+                self.producer_state.update(&header);
+                continue;
+            };
+            let Some(aborted) = self.producer_state.complete_txn(&header, &marker) else {
+                continue;
+            };
+            let segment = self
+                .segments
+                .range_mut(..=header.base_offset)
+                .next_back()
+                .map(|(_, segment)| segment)
+                .expect("replayed batch lies in a segment");
+            if !segment.aborted_txns().contains(&aborted) {
+                segment.append_aborted_txn(aborted)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn topic_partition(&self) -> &TopicPartition {
+        &self.topic_partition
+    }
+
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The first offset still exposed to consumers.
+    pub(crate) fn log_start_offset(&self) -> i64 {
+        self.log_start_offset
+    }
+
+    pub(crate) fn log_end_offset(&self) -> i64 {
+        self.log_end_offset
+    }
+
+    /// The end of the offsets a `read_committed` consumer may see: the first
+    /// offset of the oldest open transaction, or the log end offset.
+    pub(crate) fn last_stable_offset(&self) -> i64 {
+        self.producer_state.first_unstable_offset().map_or(self.log_end_offset, |offset| offset.min(self.log_end_offset))
+    }
+
+    /// The idempotent and transactional producers that wrote to the partition.
+    pub(crate) fn active_producers(&self) -> Vec<ActiveProducer> {
+        self.producer_state.active_producers()
+    }
+
+    /// The aborted transactions with data in `[start_offset, end_offset)`,
+    /// ordered by first offset, which `read_committed` reads must skip.
+    pub(crate) fn aborted_transactions(&self, start_offset: i64, end_offset: i64) -> Vec<AbortedTxn> {
+        let first = self.segments.range(..=start_offset).next_back().map_or(i64::MIN, |(&base_offset, _)| base_offset);
+        let mut aborted = self
+            .segments
+            .range(first..)
+            .flat_map(|(_, segment)| segment.aborted_txns())
+            .filter(|txn| txn.last_offset >= start_offset && txn.first_offset < end_offset)
+            .copied()
+            .collect::<Vec<_>>();
+        aborted.sort_by_key(|txn| txn.first_offset);
+        aborted
+    }
+
+    pub(crate) fn bytes_in(&self) -> u64 {
+        self.bytes_in
+    }
+
+    /// Total size in bytes of all segments.
+    pub(crate) fn size(&self) -> u64 {
+        self.segments.values().map(LogSegment::size).sum()
+    }
+
+    pub(crate) fn segments(&self) -> impl DoubleEndedIterator<Item = &LogSegment> + '_ {
+        self.segments.values()
+    }
+
+    pub(crate) fn active_segment(&self) -> &LogSegment {
+        self.segments.values().next_back().expect("log has an active segment")
+    }
+
+    /// Replaces the segment at `base_offset` with the fully written file at
+    /// `cleaned`. The file is first renamed to `.swap`, which startup treats
+    /// as a committed replacement, so a crash cannot lose both versions. The
+    /// new segment indexes the `aborted` transactions, those whose markers it
+    /// kept.
+    pub(crate) fn replace_segment(
+        &mut self,
+        base_offset: i64,
+        cleaned: &Path,
+        aborted: Vec<AbortedTxn>,
+    ) -> io::Result<()> {
+        let swap = PathBuf::from(format!("{}.{SWAP_FILE_SUFFIX}", segment_file(&self.dir, base_offset, LOG_FILE_SUFFIX).display()));
+        fs::rename(cleaned, &swap)?;
+
+        if let Some(segment) = self.segments.remove(&base_offset) {
+            segment.delete()?;
+        }
+        fs::rename(&swap, segment_file(&self.dir, base_offset, LOG_FILE_SUFFIX))?;
+
+        let mut segment = LogSegment::open(&self.dir, base_offset, self.index_interval_bytes)?;
+        segment.recover()?;
+        for txn in aborted {
+            segment.append_aborted_txn(txn)?;
+        }
+        self.segments.insert(base_offset, segment);
+        Ok(())
+    }
+
+    /// Appends a batch at the log end offset, assigning its offsets, and rolls
+    /// a new segment first if the batch would overflow `segment.bytes`.
+    /// Returns the offset of the first record. A retried batch of an
+    /// idempotent producer is not appended again, the offset it was first
+    /// appended at is returned instead. A transaction marker closes the
+    /// producer's transaction, indexing it if it was aborted.
+    pub(crate) fn append(&mut self, mut bytes: Vec<u8>, config: &LogConfig) -> Result<i64, AppendError> {
+        let mut header = validate_batch(&bytes)?;
+        let marker = end_transaction_marker(&header, &bytes)?;
+        if let Some(duplicate) = self.producer_state.check(&header)? {
+            return Ok(duplicate.first_offset);
+        }
+        // The base offset is not covered by the batch CRC.
+        let base_offset = self.log_end_offset;
+        header.base_offset = base_offset;
+        bytes[..8].copy_from_slice(&base_offset.to_be_bytes());
+
+        let active = self.active_segment();
+        if active.size() > 0 && active.size() + bytes.len() as u64 > config.segment_bytes {
+            self.roll()?;
+        }
+        let active = self.segments.values_mut().next_back().expect("log has an active segment");
+        active.append(&header, &bytes)?;
+        self.log_end_offset = header.last_offset() + 1;
+        self.bytes_in += bytes.len() as u64;
+        match marker {
+            Some(marker) => {
+                if let Some(aborted) = self.producer_state.complete_txn(&header, &marker) {
+                    active.append_aborted_txn(aborted)?;
+                }
+            }
+            None => self.producer_state.update(&header),
+        }
+        self.leader_epochs.assign(header.partition_leader_epoch, base_offset)?;
+        Ok(base_offset)
+    }
+
+    /// Iterates the batches that contain offsets at or after `start_offset`.
+    pub(crate) fn batches(&self, start_offset: i64) -> impl Iterator<Item = Result<FileBatch, RecordBatchError>> + '_ {
+        let first = self.segments.range(..=start_offset).next_back().map_or(i64::MIN, |(&base_offset, _)| base_offset);
+        self.segments
+            .range(first..)
+            .flat_map(|(_, segment)| segment.batches(0))
+            .filter(move |batch| batch.as_ref().map_or(true, |batch| batch.header.last_offset() >= start_offset))
+    }
+
+    /// Finds the first record at or after the log start offset whose
+    /// timestamp is at least `timestamp`.
+    pub(crate) fn offset_for_timestamp(&self, timestamp: i64) -> io::Result<Option<TimestampAndOffset>> {
+        let mut segments = self.segments.values().peekable();
+        while let Some(segment) = segments.next() {
+            let next_base_offset = segments.peek().map_or(i64::MAX, |next| next.base_offset());
+            if segment.max_timestamp() < timestamp || next_base_offset <= self.log_start_offset {
+                continue;
+            }
+            if let Some(found) = segment.find_offset_by_timestamp(timestamp, self.log_start_offset)? {
+                return Ok(Some(found));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds the record with the largest timestamp, the earliest one if
+    /// several share it.
+    pub(crate) fn offset_of_max_timestamp(&self) -> io::Result<Option<TimestampAndOffset>> {
+        let mut latest: Option<&LogSegment> = None;
+        for segment in self.segments.values() {
+            if segment.max_timestamp() > latest.map_or(-1, LogSegment::max_timestamp) {
+                latest = Some(segment);
+            }
+        }
+        match latest {
+            Some(segment) => segment.find_offset_by_timestamp(segment.max_timestamp(), self.log_start_offset),
+            None => Ok(None),
+        }
+    }
+
+    /// Deletes the oldest segments that breach `retention.ms` or
+    /// `retention.bytes` (for the `delete` cleanup policy), or lie entirely
+    /// below the log start offset. Returns the number of segments deleted.
+    pub(crate) fn delete_old_segments(&mut self, config: &LogConfig, now_ms: i64) -> io::Result<usize> {
+        let retention_ms = config.retention_ms;
+        let mut deleted = 0;
+
+        if config.cleanup_policy.delete && retention_ms >= 0 {
+            deleted += self.delete_oldest_segments("retention.ms", |segment, _| {
+                Ok(now_ms - segment.largest_timestamp()? > retention_ms)
+            })?;
+        }
+
+        if config.cleanup_policy.delete && config.retention_bytes >= 0 {
+            let mut excess = self.size() as i64 - config.retention_bytes;
+            deleted += self.delete_oldest_segments("retention.bytes", |segment, _| {
+                if excess - segment.size() as i64 >= 0 {
+                    excess -= segment.size() as i64;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            })?;
+        }
+
+        deleted += self.delete_segments_below_log_start_offset()?;
+        Ok(deleted)
+    }
+
+    /// Moves the log start offset forward to `offset`, capped at the log end
+    /// offset, and deletes the segments that now lie entirely below it.
+    /// Returns the number of segments deleted.
+    pub(crate) fn increment_log_start_offset(&mut self, offset: i64) -> io::Result<usize> {
+        self.log_start_offset = self.log_start_offset.max(offset.min(self.log_end_offset));
+        self.leader_epochs.truncate_from_start(self.log_start_offset)?;
+        self.delete_segments_below_log_start_offset()
+    }
+
+    fn delete_segments_below_log_start_offset(&mut self) -> io::Result<usize> {
+        let log_start_offset = self.log_start_offset;
+        self.delete_oldest_segments("log start offset", |_, next_base_offset| {
+            Ok(next_base_offset.is_some_and(|next| next <= log_start_offset))
+        })
+    }
+
+    /// Deletes segments from the oldest while `should_delete` holds. An empty
+    /// active segment is never deleted, a non-empty one is first replaced by
+    /// a fresh segment at the log end offset.
+    fn delete_oldest_segments(
+        &mut self,
+        reason: &str,
+        mut should_delete: impl FnMut(&LogSegment, Option<i64>) -> io::Result<bool>,
+    ) -> io::Result<usize> {
+        let mut deletable = Vec::new();
+        let mut segments = self.segments.values().peekable();
+        while let Some(segment) = segments.next() {
+            let next_base_offset = segments.peek().map(|next| next.base_offset());
+            if next_base_offset.is_none() && segment.size() == 0 {
+                break;
+            }
+            if !should_delete(segment, next_base_offset)? {
+                break;
+            }
+            deletable.push(segment.base_offset());
+        }
+
+        if deletable.is_empty() {
+            return Ok(0);
+        }
+        if deletable.len() == self.segments.len() {
+            self.roll()?;
+        }
+
+        for base_offset in &deletable {
+            let segment = self.segments.remove(base_offset).expect("segment exists");
+            info!(partition = %self.topic_partition, segment = base_offset, reason, "Deleting segment");
+            segment.delete()?;
+        }
+
+        let first_base_offset = *self.segments.keys().next().expect("log has an active segment");
+        self.log_start_offset = self.log_start_offset.max(first_base_offset);
+        self.producer_state.truncate_head(self.log_start_offset)?;
+        self.leader_epochs.truncate_from_start(self.log_start_offset)?;
+        Ok(deletable.len())
+    }
+
+    /// Starts a new empty active segment at the log end offset, snapshotting
+    /// the producer state there.
+    fn roll(&mut self) -> io::Result<()> {
+        self.producer_state.take_snapshot(self.log_end_offset)?;
+        let segment = LogSegment::open(&self.dir, self.log_end_offset, self.index_interval_bytes)?;
+        self.segments.insert(self.log_end_offset, segment);
+        Ok(())
+    }
+
+    /// The largest epoch at or below `epoch` and the offset its part of the
+    /// log ends at, as returned by OffsetForLeaderEpoch.
+    pub(crate) fn end_offset_for_epoch(&self, epoch: i32) -> (i32, i64) {
+        self.leader_epochs.end_offset_for(epoch, self.log_end_offset)
+    }
+
+    /// The leader epoch the batch at `offset` was written in. For the log end
+    /// offset, the latest epoch.
+    pub(crate) fn epoch_for_offset(&self, offset: i64) -> Option<i32> {
+        self.leader_epochs.epoch_for_offset(offset)
+    }
+
+    /// The epoch of the last batch appended by a newer leader, `None` if no
+    /// batch carries an epoch.
+    pub(crate) fn latest_epoch(&self) -> Option<i32> {
+        self.leader_epochs.latest_epoch()
+    }
+
+    /// The offset below which all data is known to be flushed to disk.
+    pub(crate) fn recovery_point(&self) -> i64 {
+        self.recovery_point
+    }
+
+    pub(crate) fn recovered_segments(&self) -> usize {
+        self.recovered_segments
+    }
+
+    /// Fsyncs every segment that may hold data past the recovery point,
+    /// advances the recovery point to the log end offset and snapshots the
+    /// producer state there.
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        let first_dirty = self.segments.range(..=self.recovery_point).next_back().map_or(0, |(&base_offset, _)| base_offset);
+        for segment in self.segments.range(first_dirty..).map(|(_, segment)| segment) {
+            segment.flush()?;
+        }
+        self.recovery_point = self.log_end_offset;
+        self.producer_state.take_snapshot(self.log_end_offset)
+    }
+}
+
+/// Decodes the marker of a transactional control batch.
+fn end_transaction_marker(header: &RecordBatchHeader, bytes: &[u8]) -> Result<Option<EndTransactionMarker>, RecordBatchError> {
+    if !header.is_control() || !header.is_transactional() {
+        return Ok(None);
+    }
+    EndTransactionMarker::parse(&RecordBatch::parse(header.clone(), bytes)?)
+}
+
+fn segment_base_offsets(dir: &Path) -> io::Result<Vec<i64>> {
+    let mut base_offsets = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(LOG_FILE_SUFFIX) {
+            continue;
+        }
+        match path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse().ok()) {
+            Some(base_offset) => base_offsets.push(base_offset),
+            None => warn!(file = %path.display(), "Ignoring log file with unexpected name"),
+        }
+    }
+    base_offsets.sort_unstable();
+    Ok(base_offsets)
+}
+
+/// Finishes segment replacements interrupted after the new file was renamed
+/// to `.swap`: the old segment is dropped and the swap file takes its place.
+fn complete_swaps(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(SWAP_FILE_SUFFIX) {
+            continue;
+        }
+        let Some(base_offset) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.strip_suffix(&format!(".{LOG_FILE_SUFFIX}")))
+            .and_then(|stem| stem.parse::<i64>().ok())
+        else {
+            warn!(file = %path.display(), "Ignoring swap file with unexpected name");
+            continue;
+        };
+
+        info!(file = %path.display(), "Completing interrupted segment swap");
+        for suffix in [INDEX_FILE_SUFFIX, TIME_INDEX_FILE_SUFFIX] {
+            match fs::remove_file(segment_file(dir, base_offset, suffix)) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+        fs::rename(&path, segment_file(dir, base_offset, LOG_FILE_SUFFIX))?;
+    }
+    Ok(())
+}
+
+fn remove_temp_files(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_temp = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| TEMP_FILE_SUFFIXES.contains(&ext));
+        if is_temp {
+            info!(file = %path.display(), "Removing leftover temporary file");
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::record::{Record, RecordBatch, RecordBatchHeader, CRC32C};
+    use crate::kafka::storage::PRODUCER_SNAPSHOT_FILE_SUFFIX;
+    use binrw::BinWrite;
+    use std::io::Cursor;
+
+    fn batch(base_offset: i64, records: i32) -> Vec<u8> {
+        let header = RecordBatchHeader {
+            base_offset,
+            batch_length: 49 + 8,
+            partition_leader_epoch: 0,
+            magic: 2,
+            crc: 0,
+            attributes: 0,
+            last_offset_delta: records - 1,
+            base_timestamp: 1_000,
+            max_timestamp: 1_000 + base_offset,
+            producer_id: -1,
+            producer_epoch: -1,
+            base_sequence: -1,
+            records_count: records,
+        };
+        let mut writer = Cursor::new(Vec::new());
+        header.write(&mut writer).unwrap();
+        let mut bytes = writer.into_inner();
+        bytes.extend_from_slice(&[0xAB; 8]);
+        let crc = CRC32C.checksum(&bytes[21..]);
+        bytes[17..21].copy_from_slice(&crc.to_be_bytes());
+        bytes
+    }
+
+    fn timestamped_batch(base_offset: i64, timestamps: &[i64]) -> Vec<u8> {
+        let header = RecordBatchHeader {
+            base_offset,
+            batch_length: 0,
+            partition_leader_epoch: 0,
+            magic: 2,
+            crc: 0,
+            attributes: 0,
+            last_offset_delta: timestamps.len() as i32 - 1,
+            base_timestamp: timestamps[0],
+            max_timestamp: *timestamps.iter().max().unwrap(),
+            producer_id: -1,
+            producer_epoch: -1,
+            base_sequence: -1,
+            records_count: 0,
+        };
+        let records = timestamps
+            .iter()
+            .enumerate()
+            .map(|(delta, timestamp)| Record {
+                attributes: 0,
+                timestamp_delta: timestamp - timestamps[0],
+                offset_delta: delta as i32,
+                key: None,
+                value: Some(vec![0xAB]),
+                headers: Vec::new(),
+            })
+            .collect();
+        RecordBatch { header, records }.to_bytes()
+    }
+
+    fn write_segment(dir: &Path, base_offset: i64, batches: &[Vec<u8>]) {
+        fs::write(segment_file(dir, base_offset, LOG_FILE_SUFFIX), batches.concat()).unwrap();
+    }
+
+    fn load(dir: &Path, recovery_point: i64) -> PartitionLog {
+        let config = ServerConfig { log_index_interval_bytes: 1, ..ServerConfig::default() };
+        PartitionLog::load(dir.to_path_buf(), TopicPartition::new("foo", 0), recovery_point, 0, false, &config).unwrap()
+    }
+
+    fn segmented_log(dir: &Path) -> PartitionLog {
+        write_segment(dir, 0, &[batch(0, 3)]);
+        write_segment(dir, 3, &[batch(3, 2)]);
+        write_segment(dir, 5, &[batch(5, 1)]);
+        load(dir, 0)
+    }
+
+    #[test]
+    fn test_truncated_tail_is_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut partial = batch(5, 2);
+        partial.truncate(30);
+        write_segment(dir.path(), 0, &[batch(0, 3), batch(3, 2), partial]);
+
+        let log = load(dir.path(), 0);
+
+        assert_eq!(log.log_end_offset(), 5);
+        let size = fs::metadata(segment_file(dir.path(), 0, LOG_FILE_SUFFIX)).unwrap().len();
+        assert_eq!(size, 2 * batch(0, 1).len() as u64);
+    }
+
+    #[test]
+    fn test_crc_mismatch_truncates_and_drops_later_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut corrupt = batch(3, 2);
+        *corrupt.last_mut().unwrap() ^= 0xFF;
+        write_segment(dir.path(), 0, &[batch(0, 3), corrupt]);
+        write_segment(dir.path(), 5, &[batch(5, 5)]);
+
+        let log = load(dir.path(), 0);
+
+        assert_eq!(log.log_end_offset(), 3);
+        assert!(!segment_file(dir.path(), 5, LOG_FILE_SUFFIX).exists());
+    }
+
+    #[test]
+    fn test_segments_below_recovery_point_are_not_scanned() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut corrupt = batch(3, 2);
+        *corrupt.last_mut().unwrap() ^= 0xFF;
+        write_segment(dir.path(), 0, &[batch(0, 3), corrupt]);
+        write_segment(dir.path(), 5, &[batch(5, 5)]);
+        fs::write(segment_file(dir.path(), 0, INDEX_FILE_SUFFIX), []).unwrap();
+        fs::write(segment_file(dir.path(), 0, TIME_INDEX_FILE_SUFFIX), []).unwrap();
+
+        let log = load(dir.path(), 5);
+
+        assert_eq!(log.log_end_offset(), 10);
+        assert!(segment_file(dir.path(), 5, LOG_FILE_SUFFIX).exists());
+    }
+
+    #[test]
+    fn test_missing_index_is_rebuilt() {
+        let dir = tempfile::tempdir().unwrap();
+        write_segment(dir.path(), 0, &[batch(0, 3), batch(3, 2), batch(5, 1)]);
+        write_segment(dir.path(), 6, &[batch(6, 1)]);
+
+        load(dir.path(), 7);
+
+        let index = fs::read(segment_file(dir.path(), 0, INDEX_FILE_SUFFIX)).unwrap();
+        assert_eq!(index.len(), 2 * 8);
+    }
+
+    #[test]
+    fn test_empty_dir_gets_active_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = load(dir.path(), 0);
+        assert_eq!(log.log_end_offset(), 0);
+        assert!(segment_file(dir.path(), 0, LOG_FILE_SUFFIX).exists());
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig::default();
+        let topic_partition = TopicPartition::new("foo", 0);
+        let log = PartitionLog::load(dir.path().to_path_buf(), topic_partition, 0, 7, false, &config).unwrap();
+        assert_eq!((log.log_start_offset(), log.log_end_offset()), (7, 7));
+        assert!(segment_file(dir.path(), 7, LOG_FILE_SUFFIX).exists());
+    }
+
+    #[test]
+    fn test_retention_ms_deletes_expired_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = segmented_log(dir.path());
+        let config = LogConfig { retention_ms: 2, retention_bytes: -1, ..ServerConfig::default().default_log_config() };
+
+        assert_eq!(log.delete_old_segments(&config, 1_006).unwrap(), 2);
+
+        assert_eq!(log.log_start_offset(), 5);
+        assert!(!segment_file(dir.path(), 0, LOG_FILE_SUFFIX).exists());
+        assert!(!segment_file(dir.path(), 3, LOG_FILE_SUFFIX).exists());
+    }
+
+    #[test]
+    fn test_retention_bytes_keeps_newest_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = segmented_log(dir.path());
+        let config = LogConfig {
+            retention_ms: -1,
+            retention_bytes: batch(0, 1).len() as i64,
+            ..ServerConfig::default().default_log_config()
+        };
+
+        assert_eq!(log.delete_old_segments(&config, 1_006).unwrap(), 2);
+
+        assert_eq!(log.log_start_offset(), 5);
+        assert_eq!(log.size(), batch(0, 1).len() as u64);
+    }
+
+    #[test]
+    fn test_expiring_active_segment_rolls_new_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = segmented_log(dir.path());
+        let config = LogConfig { retention_ms: 0, retention_bytes: -1, ..ServerConfig::default().default_log_config() };
+
+        assert_eq!(log.delete_old_segments(&config, 2_000).unwrap(), 3);
+
+        assert_eq!(log.log_start_offset(), 6);
+        assert_eq!(log.log_end_offset(), 6);
+        assert!(segment_file(dir.path(), 6, LOG_FILE_SUFFIX).exists());
+        assert_eq!(log.delete_old_segments(&config, 2_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_offset_lookup_by_timestamp() {
+        let dir = tempfile::tempdir().unwrap();
+        write_segment(dir.path(), 0, &[timestamped_batch(0, &[100, 300]), timestamped_batch(2, &[200])]);
+        write_segment(dir.path(), 3, &[timestamped_batch(3, &[500, 400])]);
+        let log = load(dir.path(), 0);
+
+        let found = log.offset_for_timestamp(150).unwrap().unwrap();
+        assert_eq!((found.offset, found.timestamp), (1, 300));
+        let found = log.offset_for_timestamp(450).unwrap().unwrap();
+        assert_eq!((found.offset, found.timestamp), (3, 500));
+        assert_eq!(log.offset_for_timestamp(600).unwrap(), None);
+
+        let found = log.offset_of_max_timestamp().unwrap().unwrap();
+        assert_eq!((found.offset, found.timestamp), (3, 500));
+    }
+
+    #[test]
+    fn test_increment_log_start_offset_deletes_covered_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = segmented_log(dir.path());
+
+        assert_eq!(log.increment_log_start_offset(4).unwrap(), 1);
+        assert_eq!(log.log_start_offset(), 4);
+        assert!(!segment_file(dir.path(), 0, LOG_FILE_SUFFIX).exists());
+        assert!(segment_file(dir.path(), 3, LOG_FILE_SUFFIX).exists());
+
+        assert_eq!(log.increment_log_start_offset(2).unwrap(), 0);
+        assert_eq!(log.log_start_offset(), 4);
+        assert_eq!(log.batches(0).next().unwrap().unwrap().header.base_offset, 3);
+    }
+
+    #[test]
+    fn test_append_assigns_offsets_and_rolls_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = load(dir.path(), 0);
+        let config = LogConfig { segment_bytes: 100, ..ServerConfig::default().default_log_config() };
+
+        assert_eq!(log.append(timestamped_batch(0, &[100, 200]), &config).unwrap(), 0);
+        assert_eq!(log.append(timestamped_batch(0, &[300]), &config).unwrap(), 2);
+        assert_eq!(log.log_end_offset(), 3);
+        assert_eq!(log.segments().map(LogSegment::base_offset).collect::<Vec<_>>(), vec![0, 2]);
+
+        let offsets = log.batches(0).map(|batch| batch.unwrap().header.base_offset).collect::<Vec<_>>();
+        assert_eq!(offsets, vec![0, 2]);
+        drop(log);
+        assert_eq!(load(dir.path(), 0).log_end_offset(), 3);
+    }
+
+    #[test]
+    fn test_producer_retries_are_deduplicated_across_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LogConfig { segment_bytes: 150, ..ServerConfig::default().default_log_config() };
+        let produce = |base_sequence: i32| {
+            let mut batch = RecordBatch::new(vec![Record::new(None, Some(vec![1])), Record::new(None, Some(vec![2]))], 1_000);
+            batch.header.producer_id = 42;
+            batch.header.producer_epoch = 0;
+            batch.header.base_sequence = base_sequence;
+            batch.to_bytes()
+        };
+
+        let mut log = load(dir.path(), 0);
+        assert_eq!(log.append(produce(0), &config).unwrap(), 0);
+        assert_eq!(log.append(produce(2), &config).unwrap(), 2);
+        assert_eq!(log.append(produce(0), &config).unwrap(), 0);
+        assert!(matches!(
+            log.append(produce(6), &config),
+            Err(AppendError::ProducerState(ProducerStateError::OutOfOrderSequence { expected: 4, .. }))
+        ));
+        assert_eq!(log.log_end_offset(), 4);
+        assert!(segment_file(dir.path(), 2, PRODUCER_SNAPSHOT_FILE_SUFFIX).exists());
+        drop(log);
+
+        // The roll snapshotted the first batch, the second one is replayed.
+        let mut log = load(dir.path(), 0);
+        assert_eq!(log.append(produce(2), &config).unwrap(), 2);
+        assert_eq!(log.append(produce(4), &config).unwrap(), 4);
+    }
+}
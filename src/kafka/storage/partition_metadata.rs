@@ -0,0 +1,78 @@
+use crate::kafka::types::Uuid;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub(crate) const PARTITION_METADATA_FILE: &str = "partition.metadata";
+
+const PARTITION_METADATA_VERSION: i32 = 0;
+
+/// The `partition.metadata` file in a partition directory, recording the id
+/// of the partition's topic in Kafka's `key: value` text format.
+#[derive(Debug, Clone)]
+pub(crate) struct PartitionMetadataFile {
+    path: PathBuf,
+}
+
+impl PartitionMetadataFile {
+    pub(crate) fn new(dir: &Path) -> Self {
+        Self { path: dir.join(PARTITION_METADATA_FILE) }
+    }
+
+    /// Reads the topic id. A missing file has none.
+    pub(crate) fn read(&self) -> io::Result<Option<Uuid>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let mut lines = text.lines();
+        let version = field(lines.next(), "version")?;
+        if version.parse() != Ok(PARTITION_METADATA_VERSION) {
+            return Err(invalid_data(format!("unsupported partition metadata version {version}")));
+        }
+        let topic_id = field(lines.next(), "topic_id")?;
+        Uuid::from_base64(topic_id)
+            .map(Some)
+            .ok_or_else(|| invalid_data(format!("malformed topic id {topic_id:?}")))
+    }
+
+    /// Atomically replaces the file via a temporary file and rename.
+    pub(crate) fn write(&self, topic_id: Uuid) -> io::Result<()> {
+        let text = format!("version: {PARTITION_METADATA_VERSION}\ntopic_id: {}\n", topic_id.to_base64());
+
+        let tmp_path = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(text.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+fn field<'a>(line: Option<&'a str>, name: &str) -> io::Result<&'a str> {
+    line.and_then(|line| line.split_once(':'))
+        .filter(|(key, _)| key.trim() == name)
+        .map(|(_, value)| value.trim())
+        .ok_or_else(|| invalid_data(format!("missing or malformed {name}")))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = PartitionMetadataFile::new(dir.path());
+        assert_eq!(file.read().unwrap(), None);
+
+        let topic_id = Uuid::random();
+        file.write(topic_id).unwrap();
+        assert_eq!(file.read().unwrap(), Some(topic_id));
+    }
+}
@@ -0,0 +1,502 @@
+use crate::kafka::record::{EndTransactionMarker, RecordBatchHeader, CRC32C};
+use crate::kafka::storage::{segment_file, AbortedTxn};
+use binrw::{binrw, BinRead, BinWrite};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, Cursor, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+pub(crate) const PRODUCER_SNAPSHOT_FILE_SUFFIX: &str = "snapshot";
+
+/// The producer id of batches written without idempotence.
+pub(crate) const NO_PRODUCER_ID: i64 = -1;
+/// The sequence of batches that are not deduplicated, such as the offset
+/// commits the group coordinator writes inside transactions.
+pub(crate) const NO_SEQUENCE: i32 = -1;
+/// Batches whose sequences are remembered per producer, matching the most
+/// in-flight requests an idempotent producer allows.
+const NUM_BATCHES_TO_RETAIN: usize = 5;
+const SNAPSHOT_VERSION: i16 = 1;
+/// Offset of the first snapshot byte covered by the CRC, after the version and CRC.
+const SNAPSHOT_CRC_START: usize = 6;
+
+/// The sequences and offsets of one batch appended by a producer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BatchMetadata {
+    pub(crate) first_seq: i32,
+    pub(crate) last_seq: i32,
+    pub(crate) first_offset: i64,
+    pub(crate) last_offset: i64,
+    pub(crate) timestamp: i64,
+}
+
+impl BatchMetadata {
+    fn of(header: &RecordBatchHeader) -> Self {
+        Self {
+            first_seq: header.base_sequence,
+            last_seq: last_sequence(header),
+            first_offset: header.base_offset,
+            last_offset: header.last_offset(),
+            timestamp: header.max_timestamp,
+        }
+    }
+}
+
+/// What a partition knows about one producer: its epoch, its most recent
+/// batches, newest last, and the transaction it has open on the partition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProducerStateEntry {
+    pub(crate) producer_epoch: i16,
+    pub(crate) batches: VecDeque<BatchMetadata>,
+    /// The epoch of the transaction coordinator that wrote the last marker.
+    pub(crate) coordinator_epoch: i32,
+    pub(crate) current_txn_first_offset: Option<i64>,
+}
+
+/// A producer with state on a partition, as reported by DescribeProducers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ActiveProducer {
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    /// The sequence of the last record appended, -1 if none is remembered.
+    pub(crate) last_sequence: i32,
+    pub(crate) last_timestamp: i64,
+    pub(crate) coordinator_epoch: i32,
+    /// The first offset of the producer's open transaction, -1 if none.
+    pub(crate) current_txn_start_offset: i64,
+}
+
+impl ProducerStateEntry {
+    fn new(producer_epoch: i16) -> Self {
+        Self { producer_epoch, batches: VecDeque::new(), coordinator_epoch: -1, current_txn_first_offset: None }
+    }
+
+    /// Moves to `producer_epoch`, forgetting the batches of older epochs.
+    fn update_epoch(&mut self, producer_epoch: i16) {
+        if producer_epoch != self.producer_epoch {
+            self.producer_epoch = producer_epoch;
+            self.batches.clear();
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ProducerStateError {
+    #[error("producer {producer_id} epoch {epoch} is older than the current epoch {current_epoch}")]
+    InvalidProducerEpoch { producer_id: i64, epoch: i16, current_epoch: i16 },
+    #[error("out of order sequence {sequence} from producer {producer_id}, expected {expected}")]
+    OutOfOrderSequence { producer_id: i64, sequence: i32, expected: i32 },
+}
+
+/// The idempotent and transactional producer state of a partition,
+/// snapshotted to `<offset>.snapshot` files next to the segments so it can be
+/// rebuilt on startup from the latest snapshot and the batches appended after
+/// it.
+#[derive(Debug)]
+pub(crate) struct ProducerStateManager {
+    dir: PathBuf,
+    producers: HashMap<i64, ProducerStateEntry>,
+    /// Producer ids of the open transactions, by their first offset.
+    ongoing_txns: BTreeMap<i64, i64>,
+    /// The log end offset the state was last snapshotted at.
+    last_snapshot_offset: Option<i64>,
+}
+
+impl ProducerStateManager {
+    /// Loads the latest readable snapshot at or below `log_end_offset`,
+    /// deleting newer snapshots left behind by a truncated log. Returns the
+    /// manager and the offset replay must continue from, if a snapshot was
+    /// found.
+    pub(crate) fn load(dir: &Path, log_end_offset: i64) -> io::Result<(Self, Option<i64>)> {
+        let mut manager = Self {
+            dir: dir.to_path_buf(),
+            producers: HashMap::new(),
+            ongoing_txns: BTreeMap::new(),
+            last_snapshot_offset: None,
+        };
+        for offset in snapshot_offsets(dir)?.into_iter().rev() {
+            let path = segment_file(dir, offset, PRODUCER_SNAPSHOT_FILE_SUFFIX);
+            if offset > log_end_offset {
+                fs::remove_file(&path)?;
+                continue;
+            }
+            match read_snapshot(&path) {
+                Ok(producers) => {
+                    manager.ongoing_txns = producers
+                        .iter()
+                        .filter_map(|(&producer_id, entry)| Some((entry.current_txn_first_offset?, producer_id)))
+                        .collect();
+                    manager.producers = producers;
+                    manager.last_snapshot_offset = Some(offset);
+                    return Ok((manager, Some(offset)));
+                }
+                Err(err) => {
+                    warn!(file = %path.display(), error = %err, "Deleting unreadable producer snapshot");
+                    fs::remove_file(&path)?;
+                }
+            }
+        }
+        Ok((manager, None))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn producer(&self, producer_id: i64) -> Option<&ProducerStateEntry> {
+        self.producers.get(&producer_id)
+    }
+
+    /// Every producer with state on the partition, by producer id.
+    pub(crate) fn active_producers(&self) -> Vec<ActiveProducer> {
+        let mut producers = self
+            .producers
+            .iter()
+            .map(|(&producer_id, entry)| ActiveProducer {
+                producer_id,
+                producer_epoch: entry.producer_epoch,
+                last_sequence: entry.batches.back().map_or(NO_SEQUENCE, |batch| batch.last_seq),
+                last_timestamp: entry.batches.back().map_or(-1, |batch| batch.timestamp),
+                coordinator_epoch: entry.coordinator_epoch,
+                current_txn_start_offset: entry.current_txn_first_offset.unwrap_or(-1),
+            })
+            .collect::<Vec<_>>();
+        producers.sort_by_key(|producer| producer.producer_id);
+        producers
+    }
+
+    /// The first offset of the oldest open transaction, below which every
+    /// offset is stable.
+    pub(crate) fn first_unstable_offset(&self) -> Option<i64> {
+        self.ongoing_txns.keys().next().copied()
+    }
+
+    /// Checks the epoch and sequence of a batch about to be appended. Returns
+    /// the batch it duplicates, if it is a retry of one of the producer's
+    /// recent batches. Producers without state may start at any sequence.
+    /// Markers and batches without a sequence only have their epoch checked.
+    pub(crate) fn check(&self, header: &RecordBatchHeader) -> Result<Option<BatchMetadata>, ProducerStateError> {
+        if header.producer_id == NO_PRODUCER_ID {
+            return Ok(None);
+        }
+        let Some(entry) = self.producers.get(&header.producer_id) else {
+            return Ok(None);
+        };
+
+        let out_of_order = |expected| ProducerStateError::OutOfOrderSequence {
+            producer_id: header.producer_id,
+            sequence: header.base_sequence,
+            expected,
+        };
+        if header.producer_epoch < entry.producer_epoch {
+            return Err(ProducerStateError::InvalidProducerEpoch {
+                producer_id: header.producer_id,
+                epoch: header.producer_epoch,
+                current_epoch: entry.producer_epoch,
+            });
+        }
+        if header.is_control() || header.base_sequence == NO_SEQUENCE {
+            return Ok(None);
+        }
+        // A new epoch restarts the sequence.
+        if header.producer_epoch > entry.producer_epoch {
+            return if header.base_sequence == 0 { Ok(None) } else { Err(out_of_order(0)) };
+        }
+
+        let last_seq = last_sequence(header);
+        if let Some(duplicate) =
+            entry.batches.iter().find(|batch| batch.first_seq == header.base_sequence && batch.last_seq == last_seq)
+        {
+            return Ok(Some(*duplicate));
+        }
+        match entry.batches.back() {
+            Some(last) if !in_sequence(last.last_seq, header.base_sequence) => {
+                Err(out_of_order(next_sequence(last.last_seq)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Records a data batch appended at its final offsets. A transactional
+    /// batch opens a transaction unless the producer already has one open.
+    pub(crate) fn update(&mut self, header: &RecordBatchHeader) {
+        if header.producer_id == NO_PRODUCER_ID || header.is_control() {
+            return;
+        }
+        let entry = self
+            .producers
+            .entry(header.producer_id)
+            .or_insert_with(|| ProducerStateEntry::new(header.producer_epoch));
+        entry.update_epoch(header.producer_epoch);
+        if header.base_sequence != NO_SEQUENCE {
+            entry.batches.push_back(BatchMetadata::of(header));
+            if entry.batches.len() > NUM_BATCHES_TO_RETAIN {
+                entry.batches.pop_front();
+            }
+        }
+        if header.is_transactional() && entry.current_txn_first_offset.is_none() {
+            entry.current_txn_first_offset = Some(header.base_offset);
+            self.ongoing_txns.insert(header.base_offset, header.producer_id);
+        }
+    }
+
+    /// Records a transaction marker appended at its final offset, closing the
+    /// producer's open transaction. Returns the transaction if the marker
+    /// aborted it.
+    pub(crate) fn complete_txn(&mut self, header: &RecordBatchHeader, marker: &EndTransactionMarker) -> Option<AbortedTxn> {
+        let entry = self
+            .producers
+            .entry(header.producer_id)
+            .or_insert_with(|| ProducerStateEntry::new(header.producer_epoch));
+        entry.update_epoch(header.producer_epoch);
+        entry.coordinator_epoch = marker.coordinator_epoch;
+        let first_offset = entry.current_txn_first_offset.take()?;
+        self.ongoing_txns.remove(&first_offset);
+        if marker.is_commit() {
+            return None;
+        }
+        Some(AbortedTxn {
+            producer_id: header.producer_id,
+            first_offset,
+            last_offset: header.last_offset(),
+            last_stable_offset: self.first_unstable_offset().unwrap_or(header.last_offset() + 1),
+        })
+    }
+
+    /// Forgets producers whose last batch lies below `log_start_offset`,
+    /// unless they have a transaction open, and deletes the snapshots below it.
+    pub(crate) fn truncate_head(&mut self, log_start_offset: i64) -> io::Result<()> {
+        self.producers.retain(|_, entry| {
+            entry.current_txn_first_offset.is_some()
+                || entry.batches.back().is_some_and(|batch| batch.last_offset >= log_start_offset)
+        });
+        for offset in snapshot_offsets(&self.dir)?.into_iter().filter(|offset| *offset < log_start_offset) {
+            fs::remove_file(segment_file(&self.dir, offset, PRODUCER_SNAPSHOT_FILE_SUFFIX))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the state as of `log_end_offset`, unless it was already
+    /// snapshotted there.
+    pub(crate) fn take_snapshot(&mut self, log_end_offset: i64) -> io::Result<()> {
+        if self.last_snapshot_offset == Some(log_end_offset) {
+            return Ok(());
+        }
+        let mut entries = self
+            .producers
+            .iter()
+            .map(|(&producer_id, entry)| {
+                let last = entry.batches.back();
+                SnapshotEntry {
+                    producer_id,
+                    producer_epoch: entry.producer_epoch,
+                    last_sequence: last.map_or(NO_SEQUENCE, |last| last.last_seq),
+                    last_offset: last.map_or(-1, |last| last.last_offset),
+                    offset_delta: last.map_or(0, |last| (last.last_offset - last.first_offset) as i32),
+                    timestamp: last.map_or(-1, |last| last.timestamp),
+                    coordinator_epoch: entry.coordinator_epoch,
+                    current_txn_first_offset: entry.current_txn_first_offset.unwrap_or(-1),
+                }
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.producer_id);
+        let snapshot = Snapshot { version: SNAPSHOT_VERSION, crc: 0, entry_count: entries.len() as i32, entries };
+
+        let mut writer = Cursor::new(Vec::new());
+        snapshot.write(&mut writer).map_err(io::Error::other)?;
+        let mut bytes = writer.into_inner();
+        let crc = CRC32C.checksum(&bytes[SNAPSHOT_CRC_START..]);
+        bytes[2..SNAPSHOT_CRC_START].copy_from_slice(&crc.to_be_bytes());
+
+        let path = segment_file(&self.dir, log_end_offset, PRODUCER_SNAPSHOT_FILE_SUFFIX);
+        let tmp_path = path.with_extension("tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        self.last_snapshot_offset = Some(log_end_offset);
+        Ok(())
+    }
+}
+
+/// Kafka's producer snapshot format: a version and a CRC32C of the rest,
+/// then the last batch and open transaction of every producer. Producers
+/// without a batch are stored with a last offset of -1.
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct Snapshot {
+    version: i16,
+    crc: u32,
+    entry_count: i32,
+    #[br(count = entry_count)]
+    entries: Vec<SnapshotEntry>,
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Debug)]
+struct SnapshotEntry {
+    producer_id: i64,
+    producer_epoch: i16,
+    last_sequence: i32,
+    last_offset: i64,
+    offset_delta: i32,
+    timestamp: i64,
+    coordinator_epoch: i32,
+    current_txn_first_offset: i64,
+}
+
+fn read_snapshot(path: &Path) -> io::Result<HashMap<i64, ProducerStateEntry>> {
+    let bytes = fs::read(path)?;
+    let snapshot = Snapshot::read(&mut Cursor::new(&bytes)).map_err(io::Error::other)?;
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(io::Error::other(format!("unsupported producer snapshot version {}", snapshot.version)));
+    }
+    let computed = CRC32C.checksum(&bytes[SNAPSHOT_CRC_START..]);
+    if computed != snapshot.crc {
+        let message = format!("snapshot crc {:#010x} does not match computed {computed:#010x}", snapshot.crc);
+        return Err(io::Error::other(message));
+    }
+
+    let producers = snapshot
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let offset_delta = entry.offset_delta;
+            let batch = (entry.last_offset >= 0).then(|| BatchMetadata {
+                first_seq: wrap_sequence(entry.last_sequence as i64 - offset_delta as i64),
+                last_seq: entry.last_sequence,
+                first_offset: entry.last_offset - offset_delta as i64,
+                last_offset: entry.last_offset,
+                timestamp: entry.timestamp,
+            });
+            let state = ProducerStateEntry {
+                producer_epoch: entry.producer_epoch,
+                batches: batch.into_iter().collect(),
+                coordinator_epoch: entry.coordinator_epoch,
+                current_txn_first_offset: (entry.current_txn_first_offset >= 0).then_some(entry.current_txn_first_offset),
+            };
+            (entry.producer_id, state)
+        })
+        .collect();
+    Ok(producers)
+}
+
+fn snapshot_offsets(dir: &Path) -> io::Result<Vec<i64>> {
+    let mut offsets = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(PRODUCER_SNAPSHOT_FILE_SUFFIX) {
+            continue;
+        }
+        match path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse().ok()) {
+            Some(offset) => offsets.push(offset),
+            None => warn!(file = %path.display(), "Ignoring snapshot file with unexpected name"),
+        }
+    }
+    offsets.sort_unstable();
+    Ok(offsets)
+}
+
+/// Sequences wrap around to 0 after `i32::MAX`.
+fn next_sequence(sequence: i32) -> i32 {
+    if sequence == i32::MAX { 0 } else { sequence + 1 }
+}
+
+fn in_sequence(last_seq: i32, next_seq: i32) -> bool {
+    next_seq == next_sequence(last_seq)
+}
+
+fn last_sequence(header: &RecordBatchHeader) -> i32 {
+    wrap_sequence(header.base_sequence as i64 + header.last_offset_delta as i64)
+}
+
+fn wrap_sequence(sequence: i64) -> i32 {
+    sequence.rem_euclid(i32::MAX as i64 + 1) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::record::{CONTROL_FLAG, TRANSACTIONAL_FLAG};
+
+    fn header(producer_epoch: i16, base_sequence: i32, base_offset: i64, records: i32) -> RecordBatchHeader {
+        RecordBatchHeader {
+            base_offset,
+            batch_length: 0,
+            partition_leader_epoch: 0,
+            magic: 2,
+            crc: 0,
+            attributes: 0,
+            last_offset_delta: records - 1,
+            base_timestamp: 1_000,
+            max_timestamp: 1_000,
+            producer_id: 7,
+            producer_epoch,
+            base_sequence,
+            records_count: records,
+        }
+    }
+
+    #[test]
+    fn test_sequence_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut state, _) = ProducerStateManager::load(dir.path(), 0).unwrap();
+        // Unknown producers may start anywhere.
+        assert!(state.check(&header(0, 5, 0, 2)).unwrap().is_none());
+        state.update(&header(0, 5, 0, 2));
+
+        let duplicate = state.check(&header(0, 5, 0, 2)).unwrap().unwrap();
+        assert_eq!((duplicate.first_offset, duplicate.last_offset), (0, 1));
+        assert!(matches!(
+            state.check(&header(0, 8, 0, 1)),
+            Err(ProducerStateError::OutOfOrderSequence { expected: 7, .. })
+        ));
+        assert!(state.check(&header(0, 7, 0, 1)).unwrap().is_none());
+        assert!(matches!(state.check(&header(1, 3, 0, 1)), Err(ProducerStateError::OutOfOrderSequence { .. })));
+        state.update(&header(1, 0, 2, 1));
+        assert!(matches!(state.check(&header(0, 7, 0, 1)), Err(ProducerStateError::InvalidProducerEpoch { .. })));
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut state, _) = ProducerStateManager::load(dir.path(), 0).unwrap();
+        state.update(&header(3, 10, 0, 4));
+        state.take_snapshot(4).unwrap();
+        state.take_snapshot(9).unwrap();
+
+        let (loaded, offset) = ProducerStateManager::load(dir.path(), 5).unwrap();
+        assert_eq!(offset, Some(4));
+        assert_eq!(loaded.producer(7), state.producer(7));
+        // The snapshot beyond the log end was deleted.
+        assert_eq!(snapshot_offsets(dir.path()).unwrap(), vec![4]);
+    }
+
+    #[test]
+    fn test_transactions_hold_back_last_stable_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut state, _) = ProducerStateManager::load(dir.path(), 0).unwrap();
+        let transactional = |base_offset| RecordBatchHeader { attributes: TRANSACTIONAL_FLAG, ..header(0, 0, base_offset, 1) };
+        let marker = |base_offset| RecordBatchHeader {
+            attributes: TRANSACTIONAL_FLAG | CONTROL_FLAG,
+            base_sequence: NO_SEQUENCE,
+            ..header(1, 0, base_offset, 1)
+        };
+        state.update(&transactional(3));
+        state.update(&RecordBatchHeader { producer_id: 8, ..transactional(4) });
+        state.update(&RecordBatchHeader { base_sequence: 1, ..transactional(5) });
+        assert_eq!(state.first_unstable_offset(), Some(3));
+
+        state.take_snapshot(6).unwrap();
+        let (mut state, _) = ProducerStateManager::load(dir.path(), 6).unwrap();
+        assert_eq!(state.producer(7).unwrap().current_txn_first_offset, Some(3));
+
+        let aborted = state.complete_txn(&marker(6), &EndTransactionMarker::new(false, 0)).unwrap();
+        assert_eq!(aborted, AbortedTxn { producer_id: 7, first_offset: 3, last_offset: 6, last_stable_offset: 4 });
+        assert_eq!(state.producer(7).unwrap().producer_epoch, 1);
+        assert_eq!(state.first_unstable_offset(), Some(4));
+        let committed = RecordBatchHeader { producer_id: 8, ..marker(7) };
+        assert_eq!(state.complete_txn(&committed, &EndTransactionMarker::new(true, 0)), None);
+        assert_eq!(state.first_unstable_offset(), None);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::kafka::record::{validate_batch, RecordBatch, RecordBatchError, RecordBatchHeader, LOG_OVERHEAD};
+use crate::kafka::storage::{AbortedTxn, OffsetIndex, TimeIndex, TransactionIndex};
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::warn;
+
+pub(crate) const LOG_FILE_SUFFIX: &str = "log";
+pub(crate) const INDEX_FILE_SUFFIX: &str = "index";
+pub(crate) const TIME_INDEX_FILE_SUFFIX: &str = "timeindex";
+pub(crate) const TXN_INDEX_FILE_SUFFIX: &str = "txnindex";
+pub(crate) const DELETED_FILE_SUFFIX: &str = "deleted";
+pub(crate) const CLEANED_FILE_SUFFIX: &str = "cleaned";
+pub(crate) const SWAP_FILE_SUFFIX: &str = "swap";
+
+pub(crate) fn segment_file(dir: &Path, base_offset: i64, suffix: &str) -> PathBuf {
+    dir.join(format!("{base_offset:020}.{suffix}"))
+}
+
+/// One `.log` file together with its offset, time and transaction indexes.
+#[derive(Debug)]
+pub(crate) struct LogSegment {
+    base_offset: i64,
+    log_path: PathBuf,
+    log: File,
+    size: u64,
+    offset_index: OffsetIndex,
+    time_index: TimeIndex,
+    txn_index: TransactionIndex,
+    index_interval_bytes: usize,
+    needs_index_rebuild: bool,
+    max_timestamp: i64,
+    offset_of_max_timestamp: i64,
+    bytes_since_last_index_entry: usize,
+}
+
+impl LogSegment {
+    /// Opens (or creates) the segment starting at `base_offset`. Missing or
+    /// corrupt indexes are not an error, they are flagged for rebuild.
+    pub(crate) fn open(dir: &Path, base_offset: i64, index_interval_bytes: usize) -> io::Result<Self> {
+        let log_path = segment_file(dir, base_offset, LOG_FILE_SUFFIX);
+        let log = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&log_path)?;
+        let size = log.metadata()?.len();
+
+        let index_path = segment_file(dir, base_offset, INDEX_FILE_SUFFIX);
+        let time_index_path = segment_file(dir, base_offset, TIME_INDEX_FILE_SUFFIX);
+        let loaded = OffsetIndex::load(index_path.clone(), base_offset, size)
+            .and_then(|offset_index| Ok((offset_index, TimeIndex::load(time_index_path.clone(), base_offset)?)));
+
+        let (offset_index, time_index, needs_index_rebuild) = match loaded {
+            Ok((offset_index, time_index)) => (offset_index, time_index, false),
+            Err(err) => {
+                if size > 0 {
+                    warn!(segment = %log_path.display(), error = %err, "Index needs rebuild");
+                }
+                (OffsetIndex::empty(index_path, base_offset), TimeIndex::empty(time_index_path, base_offset), size > 0)
+            }
+        };
+        let max_timestamp = time_index.last_timestamp().unwrap_or(-1);
+        let txn_index = TransactionIndex::load(segment_file(dir, base_offset, TXN_INDEX_FILE_SUFFIX))?;
+
+        Ok(Self {
+            base_offset,
+            log_path,
+            log,
+            size,
+            offset_index,
+            time_index,
+            txn_index,
+            index_interval_bytes,
+            needs_index_rebuild,
+            max_timestamp,
+            offset_of_max_timestamp: base_offset,
+            bytes_since_last_index_entry: 0,
+        })
+    }
+
+    pub(crate) fn base_offset(&self) -> i64 {
+        self.base_offset
+    }
+
+    pub(crate) fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub(crate) fn needs_index_rebuild(&self) -> bool {
+        self.needs_index_rebuild
+    }
+
+    /// The largest record timestamp in the segment, or -1 if no batch carries
+    /// a timestamp.
+    pub(crate) fn max_timestamp(&self) -> i64 {
+        self.max_timestamp
+    }
+
+    /// The largest record timestamp in the segment, falling back to the file
+    /// modification time when no batch carries a timestamp.
+    pub(crate) fn largest_timestamp(&self) -> io::Result<i64> {
+        if self.max_timestamp >= 0 {
+            return Ok(self.max_timestamp);
+        }
+        let modified = self.log.metadata()?.modified()?;
+        Ok(modified.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_millis() as i64))
+    }
+
+    /// Iterates the batches stored at or after byte `position`.
+    pub(crate) fn batches(&self, position: u64) -> SegmentBatches<'_> {
+        SegmentBatches { log: &self.log, position, end: self.size }
+    }
+
+    /// Finds the first record at or after `start_offset` whose timestamp is at
+    /// least `timestamp`, starting the scan from the indexes. Compressed
+    /// batches are not decoded, their base offset and max timestamp stand in
+    /// for the record.
+    pub(crate) fn find_offset_by_timestamp(&self, timestamp: i64, start_offset: i64) -> io::Result<Option<TimestampAndOffset>> {
+        let offset = self.time_index.lookup(timestamp).max(start_offset);
+        for batch in self.batches(self.offset_index.lookup(offset) as u64) {
+            let batch = batch.map_err(io::Error::other)?;
+            let header = &batch.header;
+            if header.last_offset() < start_offset || header.max_timestamp < timestamp {
+                continue;
+            }
+
+            let leader_epoch = header.partition_leader_epoch;
+            let records = match RecordBatch::parse(header.clone(), &batch.bytes) {
+                Ok(parsed) => parsed.records,
+                Err(RecordBatchError::UnsupportedCompression(_)) => {
+                    let offset = header.base_offset.max(start_offset);
+                    return Ok(Some(TimestampAndOffset { timestamp: header.max_timestamp, offset, leader_epoch }));
+                }
+                Err(err) => return Err(io::Error::other(err)),
+            };
+            for record in records {
+                let offset = header.base_offset + record.offset_delta as i64;
+                let record_timestamp = if header.is_log_append_time() {
+                    header.max_timestamp
+                } else {
+                    header.base_timestamp + record.timestamp_delta
+                };
+                if offset >= start_offset && record_timestamp >= timestamp {
+                    return Ok(Some(TimestampAndOffset { timestamp: record_timestamp, offset, leader_epoch }));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// The transactions aborted by markers in this segment.
+    pub(crate) fn aborted_txns(&self) -> &[AbortedTxn] {
+        self.txn_index.entries()
+    }
+
+    pub(crate) fn append_aborted_txn(&mut self, txn: AbortedTxn) -> io::Result<()> {
+        self.txn_index.append(txn)
+    }
+
+    /// Re-validates every batch in the segment, rebuilding the offset and
+    /// time indexes and truncating the log at the first invalid batch, along
+    /// with the aborts indexed past it. Returns the number of bytes truncated.
+    pub(crate) fn recover(&mut self) -> io::Result<u64> {
+        self.offset_index.reset();
+        self.time_index.reset();
+
+        let mut valid_bytes = 0u64;
+        let mut next_offset = self.base_offset;
+        let mut bytes_since_last_entry = 0usize;
+        let mut max_timestamp = -1i64;
+        let mut offset_of_max_timestamp = self.base_offset;
+
+        let batches = SegmentBatches { log: &self.log, position: 0, end: self.size };
+        for batch in batches {
+            let header = match batch {
+                Ok(batch) if batch.header.base_offset < next_offset
+                    || batch.header.last_offset() - self.base_offset > i32::MAX as i64 =>
+                {
+                    warn!(segment = %self.log_path.display(), position = valid_bytes, base_offset = batch.header.base_offset,
+                        expected = next_offset, "Batch offset out of range, truncating");
+                    break;
+                }
+                Ok(batch) => batch.header,
+                Err(err) => {
+                    warn!(segment = %self.log_path.display(), position = valid_bytes, error = %err,
+                        "Found invalid batch, truncating");
+                    break;
+                }
+            };
+
+            if header.max_timestamp > max_timestamp {
+                max_timestamp = header.max_timestamp;
+                offset_of_max_timestamp = header.last_offset();
+            }
+            if bytes_since_last_entry > self.index_interval_bytes {
+                self.offset_index.append(header.last_offset(), valid_bytes as u32);
+                self.time_index.maybe_append(max_timestamp, offset_of_max_timestamp);
+                bytes_since_last_entry = 0;
+            }
+
+            bytes_since_last_entry += header.size_in_bytes();
+            valid_bytes += header.size_in_bytes() as u64;
+            next_offset = header.last_offset() + 1;
+        }
+        self.time_index.maybe_append(max_timestamp, offset_of_max_timestamp);
+        self.max_timestamp = max_timestamp;
+        self.offset_of_max_timestamp = offset_of_max_timestamp;
+        self.bytes_since_last_index_entry = bytes_since_last_entry;
+
+        let truncated = self.size - valid_bytes;
+        if truncated > 0 {
+            self.log.set_len(valid_bytes)?;
+            self.size = valid_bytes;
+        }
+        self.log.sync_all()?;
+        self.offset_index.flush()?;
+        self.time_index.flush()?;
+        self.txn_index.truncate_to(next_offset)?;
+        self.needs_index_rebuild = false;
+
+        Ok(truncated)
+    }
+
+    /// Writes a batch, whose offsets are already assigned, at the end of the
+    /// log and indexes it every `index.interval.bytes`.
+    pub(crate) fn append(&mut self, header: &RecordBatchHeader, bytes: &[u8]) -> io::Result<()> {
+        self.log.write_all_at(bytes, self.size)?;
+
+        if header.max_timestamp > self.max_timestamp {
+            self.max_timestamp = header.max_timestamp;
+            self.offset_of_max_timestamp = header.last_offset();
+        }
+        if self.bytes_since_last_index_entry > self.index_interval_bytes {
+            self.offset_index.append(header.last_offset(), self.size as u32);
+            self.time_index.maybe_append(self.max_timestamp, self.offset_of_max_timestamp);
+            self.bytes_since_last_index_entry = 0;
+        }
+        self.bytes_since_last_index_entry += bytes.len();
+        self.size += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Returns the offset following the last batch, walking batches forward
+    /// from the last index entry.
+    pub(crate) fn read_next_offset(&self) -> io::Result<i64> {
+        let mut next_offset = self.base_offset;
+        for batch in self.batches(self.offset_index.last_position() as u64) {
+            next_offset = batch.map_err(io::Error::other)?.header.last_offset() + 1;
+        }
+        Ok(next_offset)
+    }
+
+    /// Fsyncs the log and rewrites both index files.
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        self.log.sync_all()?;
+        self.offset_index.flush()?;
+        self.time_index.flush()
+    }
+
+    /// Removes the log and index files of this segment. Files are renamed to
+    /// `.deleted` first so a crash mid-way leaves nothing that looks live.
+    pub(crate) fn delete(self) -> io::Result<()> {
+        let mut renamed = Vec::with_capacity(4);
+        let paths = [self.log_path.as_path(), self.offset_index.path(), self.time_index.path(), self.txn_index.path()];
+        for path in paths {
+            let deleted = PathBuf::from(format!("{}.{DELETED_FILE_SUFFIX}", path.display()));
+            match fs::rename(path, &deleted) {
+                Ok(()) => renamed.push(deleted),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        for path in renamed {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// A record located by timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TimestampAndOffset {
+    pub(crate) timestamp: i64,
+    pub(crate) offset: i64,
+    pub(crate) leader_epoch: i32,
+}
+
+#[derive(Debug)]
+pub(crate) struct FileBatch {
+    pub(crate) header: RecordBatchHeader,
+    pub(crate) bytes: Vec<u8>,
+}
+
+/// Reads validated batches sequentially from a segment file. Iteration stops
+/// after the first error.
+#[derive(Debug)]
+pub(crate) struct SegmentBatches<'a> {
+    log: &'a File,
+    position: u64,
+    end: u64,
+}
+
+impl SegmentBatches<'_> {
+    fn read_batch(&self) -> Result<FileBatch, RecordBatchError> {
+        let available = (self.end - self.position) as usize;
+        if available < LOG_OVERHEAD {
+            return Err(RecordBatchError::Truncated { needed: LOG_OVERHEAD, available });
+        }
+
+        let mut overhead = [0u8; LOG_OVERHEAD];
+        self.log.read_exact_at(&mut overhead, self.position).map_err(binrw::Error::Io)?;
+        let batch_length = i32::from_be_bytes(overhead[8..12].try_into().expect("slice of 4 bytes"));
+        if batch_length < 0 || LOG_OVERHEAD + batch_length as usize > available {
+            return Err(RecordBatchError::Truncated { needed: LOG_OVERHEAD + batch_length.max(0) as usize, available });
+        }
+
+        let mut bytes = vec![0u8; LOG_OVERHEAD + batch_length as usize];
+        self.log.read_exact_at(&mut bytes, self.position).map_err(binrw::Error::Io)?;
+
+        let header = validate_batch(&bytes)?;
+        Ok(FileBatch { header, bytes })
+    }
+}
+
+impl Iterator for SegmentBatches<'_> {
+    type Item = Result<FileBatch, RecordBatchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.end {
+            return None;
+        }
+        let batch = self.read_batch();
+        match &batch {
+            Ok(batch) => self.position += batch.bytes.len() as u64,
+            Err(_) => self.position = self.end,
+        }
+        Some(batch)
+    }
+}
@@ -8,11 +8,28 @@ mod error_codes;
 pub(crate) use error_codes::*;
 mod api_keys;
 pub(crate) use api_keys::*;
+mod message_version;
+pub(crate) use message_version::*;
 mod unsigned_varint;
 pub(crate) use unsigned_varint::*;
+mod varint;
+pub(crate) use varint::*;
 
 mod compact_array;
 pub(crate) use compact_array::*;
+mod kafka_array;
+pub(crate) use kafka_array::*;
+mod kafka_string;
+pub(crate) use kafka_string::*;
+mod kafka_nullable_array;
+pub(crate) use kafka_nullable_array::*;
+mod kafka_nullable_string;
+pub(crate) use kafka_nullable_string::*;
+mod kafka_bytes;
+pub(crate) use kafka_bytes::*;
+mod uuid;
+pub(crate) use uuid::*;
 
 pub(crate) mod helper;
-mod compact_nullable_string;
\ No newline at end of file
+mod compact_nullable_string;
+pub(crate) use compact_nullable_string::*;
@@ -5,7 +5,93 @@ use binrw::binrw;
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub(crate) enum ApiKey {
     Produce = 0,
+    ListOffsets = 2,
+    OffsetCommit = 8,
+    OffsetFetch = 9,
+    FindCoordinator = 10,
+    JoinGroup = 11,
+    Heartbeat = 12,
+    LeaveGroup = 13,
+    SyncGroup = 14,
+    DescribeGroups = 15,
+    ListGroups = 16,
+    SaslHandshake = 17,
     ApiVersions = 18,
+    DeleteRecords = 21,
+    OffsetForLeaderEpoch = 23,
+    InitProducerId = 22,
+    AddPartitionsToTxn = 24,
+    AddOffsetsToTxn = 25,
+    EndTxn = 26,
+    TxnOffsetCommit = 28,
+    DescribeAcls = 29,
+    CreateAcls = 30,
+    DeleteAcls = 31,
+    DescribeConfigs = 32,
+    AlterConfigs = 33,
+    SaslAuthenticate = 36,
     CreateTopics = 19,
+    DeleteGroups = 42,
+    IncrementalAlterConfigs = 44,
+    OffsetDelete = 47,
+    DescribeClientQuotas = 48,
+    AlterClientQuotas = 49,
+    DescribeProducers = 61,
+    BrokerRegistration = 62,
+    BrokerHeartbeat = 63,
+    DescribeTransactions = 65,
+    ListTransactions = 66,
+    ConsumerGroupHeartbeat = 68,
+    ConsumerGroupDescribe = 69,
     DescribeTopicPartitions = 75,
-}
\ No newline at end of file
+}
+
+impl ApiKey {
+    /// The first version using the flexible encoding with tagged fields.
+    pub(crate) fn first_flexible_version(&self) -> i16 {
+        match self {
+            ApiKey::Produce => 9,
+            ApiKey::ListOffsets => 6,
+            ApiKey::OffsetCommit => 8,
+            ApiKey::OffsetFetch => 6,
+            ApiKey::FindCoordinator => 3,
+            ApiKey::JoinGroup => 6,
+            ApiKey::Heartbeat => 4,
+            ApiKey::LeaveGroup => 4,
+            ApiKey::SyncGroup => 4,
+            ApiKey::DescribeGroups => 5,
+            ApiKey::ListGroups => 3,
+            // SaslHandshake has no flexible versions.
+            ApiKey::SaslHandshake => i16::MAX,
+            ApiKey::ApiVersions => 3,
+            ApiKey::CreateTopics => 5,
+            ApiKey::DeleteRecords => 2,
+            ApiKey::OffsetForLeaderEpoch => 4,
+            ApiKey::InitProducerId => 2,
+            ApiKey::AddPartitionsToTxn => 3,
+            ApiKey::AddOffsetsToTxn => 3,
+            ApiKey::EndTxn => 3,
+            ApiKey::TxnOffsetCommit => 3,
+            ApiKey::DescribeAcls => 2,
+            ApiKey::CreateAcls => 2,
+            ApiKey::DeleteAcls => 2,
+            ApiKey::DescribeConfigs => 4,
+            ApiKey::AlterConfigs => 2,
+            ApiKey::SaslAuthenticate => 2,
+            ApiKey::DeleteGroups => 2,
+            ApiKey::IncrementalAlterConfigs => 1,
+            // OffsetDelete has no flexible versions.
+            ApiKey::OffsetDelete => i16::MAX,
+            ApiKey::DescribeClientQuotas => 1,
+            ApiKey::AlterClientQuotas => 1,
+            ApiKey::DescribeProducers => 0,
+            ApiKey::BrokerRegistration => 0,
+            ApiKey::BrokerHeartbeat => 0,
+            ApiKey::DescribeTransactions => 0,
+            ApiKey::ListTransactions => 0,
+            ApiKey::ConsumerGroupHeartbeat => 0,
+            ApiKey::ConsumerGroupDescribe => 0,
+            ApiKey::DescribeTopicPartitions => 0,
+        }
+    }
+}
@@ -1 +1,2 @@
-pub(crate) mod pos_marker;
\ No newline at end of file
+pub(crate) mod length;
+pub(crate) mod pos_marker;
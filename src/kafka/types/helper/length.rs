@@ -0,0 +1,89 @@
+use crate::kafka::types::UnsignedVarInt;
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::io::{Read, Seek, Write};
+
+/// Reads the length prefix of a string: an `i16`, or a compact length when
+/// `flexible`. `None` is a null string.
+pub(crate) fn read_string_length<R: Read + Seek>(reader: &mut R, flexible: bool) -> BinResult<Option<usize>> {
+    if flexible {
+        return read_compact_length(reader);
+    }
+    let length = i16::read_options(reader, Endian::Big, ())?;
+    check_length(reader, length.into())
+}
+
+/// Reads the length prefix of an array or byte string: an `i32`, or a
+/// compact length when `flexible`. `None` is a null value.
+pub(crate) fn read_length<R: Read + Seek>(reader: &mut R, flexible: bool) -> BinResult<Option<usize>> {
+    if flexible {
+        return read_compact_length(reader);
+    }
+    let length = i32::read_options(reader, Endian::Big, ())?;
+    check_length(reader, length)
+}
+
+pub(crate) fn write_string_length<W: Write + Seek>(writer: &mut W, flexible: bool, length: Option<usize>) -> BinResult<()> {
+    if flexible {
+        return write_compact_length(writer, length);
+    }
+    let length = match length {
+        None => -1,
+        Some(length) => i16::try_from(length).map_err(|err| binrw::Error::Custom {
+            pos: writer.stream_position().expect("Should be able to read stream position"),
+            err: Box::new(err),
+        })?,
+    };
+    length.write_options(writer, Endian::Big, ())
+}
+
+pub(crate) fn write_length<W: Write + Seek>(writer: &mut W, flexible: bool, length: Option<usize>) -> BinResult<()> {
+    if flexible {
+        return write_compact_length(writer, length);
+    }
+    let length = match length {
+        None => -1,
+        Some(length) => i32::try_from(length).map_err(|err| binrw::Error::Custom {
+            pos: writer.stream_position().expect("Should be able to read stream position"),
+            err: Box::new(err),
+        })?,
+    };
+    length.write_options(writer, Endian::Big, ())
+}
+
+fn read_compact_length<R: Read + Seek>(reader: &mut R) -> BinResult<Option<usize>> {
+    let length = *UnsignedVarInt::read(reader)?;
+    Ok(length.checked_sub(1).map(|length| length as usize))
+}
+
+fn write_compact_length<W: Write + Seek>(writer: &mut W, length: Option<usize>) -> BinResult<()> {
+    let length = match length {
+        None => UnsignedVarInt(0),
+        Some(length) => UnsignedVarInt::try_from(length + 1).map_err(|err| binrw::Error::Custom {
+            pos: writer.stream_position().expect("Should be able to read stream position"),
+            err: Box::new(err),
+        })?,
+    };
+    length.write(writer)
+}
+
+fn check_length<R: Seek>(reader: &mut R, length: i32) -> BinResult<Option<usize>> {
+    match length {
+        -1 => Ok(None),
+        length if length < -1 => Err(binrw::Error::AssertFail {
+            pos: reader.stream_position()?,
+            message: format!("Invalid length: {length}"),
+        }),
+        length => Ok(Some(length as usize)),
+    }
+}
+
+/// Reads exactly `length` bytes without trusting `length` for the up-front
+/// allocation.
+pub(crate) fn read_bytes<R: Read + Seek>(reader: &mut R, length: usize) -> BinResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let read = reader.take(length as u64).read_to_end(&mut bytes)?;
+    if read != length {
+        return Err(binrw::Error::Io(std::io::ErrorKind::UnexpectedEof.into()));
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,112 @@
+use crate::kafka::types::helper::length::{read_length, write_length};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::io::{Read, Seek, Write};
+use std::ops::Deref;
+
+/// A non-null `ARRAY`, encoded as `COMPACT_ARRAY` in flexible versions. The
+/// arguments are whether the message version is flexible and the arguments
+/// passed to every element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KafkaArray<T>(pub(crate) Vec<T>);
+
+impl<T> BinRead for KafkaArray<T>
+where
+    T: BinRead,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = (bool, T::Args<'a>);
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (flexible, args): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let Some(length) = read_length(reader, flexible)? else {
+            return Err(binrw::Error::AssertFail { pos, message: "Array must not be null".to_owned() });
+        };
+        // Every element takes at least one byte, so this bounds the allocation.
+        let mut entries = Vec::with_capacity(length.min(1024));
+        for _ in 0..length {
+            entries.push(T::read_options(reader, endian, args.clone())?);
+        }
+        Ok(Self(entries))
+    }
+}
+
+impl<T> BinWrite for KafkaArray<T>
+where
+    T: BinWrite,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = (bool, T::Args<'a>);
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (flexible, args): Self::Args<'_>,
+    ) -> BinResult<()> {
+        write_length(writer, flexible, Some(self.0.len()))?;
+        for entry in &self.0 {
+            entry.write_options(writer, endian, args.clone())?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Default for KafkaArray<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T> Deref for KafkaArray<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<Vec<T>> for KafkaArray<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> FromIterator<T> for KafkaArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> IntoIterator for KafkaArray<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_encoding_follows_flexibility() {
+        let array = KafkaArray(vec![1i32, 2]);
+        for (flexible, prefix) in [(false, vec![0, 0, 0, 2]), (true, vec![3])] {
+            let mut writer = Cursor::new(Vec::new());
+            array.write_options(&mut writer, Endian::Big, (flexible, ())).unwrap();
+            let expected = [prefix, vec![0, 0, 0, 1, 0, 0, 0, 2]].concat();
+            assert_eq!(writer.get_ref(), &expected);
+
+            let mut reader = Cursor::new(expected);
+            let read = KafkaArray::<i32>::read_options(&mut reader, Endian::Big, (flexible, ())).unwrap();
+            assert_eq!(read, array);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::kafka::types::helper::length::{read_bytes, read_length, write_length};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::io::{Read, Seek, Write};
+use std::ops::Deref;
+
+/// Non-null `BYTES`, encoded as `COMPACT_BYTES` in flexible versions. The
+/// argument is whether the message version is flexible.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct KafkaBytes(pub(crate) Vec<u8>);
+
+impl BinRead for KafkaBytes {
+    type Args<'a> = (bool,);
+
+    fn read_options<R: Read + Seek>(reader: &mut R, _endian: Endian, (flexible,): Self::Args<'_>) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let Some(length) = read_length(reader, flexible)? else {
+            return Err(binrw::Error::AssertFail { pos, message: "Bytes must not be null".to_owned() });
+        };
+        Ok(Self(read_bytes(reader, length)?))
+    }
+}
+
+impl BinWrite for KafkaBytes {
+    type Args<'a> = (bool,);
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _endian: Endian, (flexible,): Self::Args<'_>) -> BinResult<()> {
+        write_length(writer, flexible, Some(self.0.len()))?;
+        writer.write_all(&self.0)?;
+        Ok(())
+    }
+}
+
+impl Deref for KafkaBytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for KafkaBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::kafka::types::helper::length::{read_length, write_length};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::io::{Read, Seek, Write};
+use std::ops::Deref;
+
+/// A nullable `ARRAY`, encoded as `COMPACT_ARRAY` in flexible versions. The
+/// arguments are whether the message version is flexible and the arguments
+/// passed to every element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KafkaNullableArray<T>(pub(crate) Option<Vec<T>>);
+
+impl<T> BinRead for KafkaNullableArray<T>
+where
+    T: BinRead,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = (bool, T::Args<'a>);
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (flexible, args): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let Some(length) = read_length(reader, flexible)? else {
+            return Ok(Self(None));
+        };
+        // Every element takes at least one byte, so this bounds the allocation.
+        let mut entries = Vec::with_capacity(length.min(1024));
+        for _ in 0..length {
+            entries.push(T::read_options(reader, endian, args.clone())?);
+        }
+        Ok(Self(Some(entries)))
+    }
+}
+
+impl<T> BinWrite for KafkaNullableArray<T>
+where
+    T: BinWrite,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = (bool, T::Args<'a>);
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (flexible, args): Self::Args<'_>,
+    ) -> BinResult<()> {
+        write_length(writer, flexible, self.0.as_ref().map(Vec::len))?;
+        for entry in self.0.iter().flatten() {
+            entry.write_options(writer, endian, args.clone())?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Default for KafkaNullableArray<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Deref for KafkaNullableArray<T> {
+    type Target = Option<Vec<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<Option<Vec<T>>> for KafkaNullableArray<T> {
+    fn from(value: Option<Vec<T>>) -> Self {
+        Self(value)
+    }
+}
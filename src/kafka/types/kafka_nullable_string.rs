@@ -0,0 +1,49 @@
+use crate::kafka::types::helper::length::{read_bytes, read_string_length, write_string_length};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::io::{Read, Seek, Write};
+use std::ops::Deref;
+
+/// A `NULLABLE_STRING`, encoded as `COMPACT_NULLABLE_STRING` in flexible
+/// versions. The argument is whether the message version is flexible.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub(crate) struct KafkaNullableString(pub(crate) Option<String>);
+
+impl BinRead for KafkaNullableString {
+    type Args<'a> = (bool,);
+
+    fn read_options<R: Read + Seek>(reader: &mut R, _endian: Endian, (flexible,): Self::Args<'_>) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let Some(length) = read_string_length(reader, flexible)? else {
+            return Ok(Self(None));
+        };
+        let string = String::from_utf8(read_bytes(reader, length)?)
+            .map_err(|err| binrw::Error::Custom { pos, err: Box::new(err) })?;
+        Ok(Self(Some(string)))
+    }
+}
+
+impl BinWrite for KafkaNullableString {
+    type Args<'a> = (bool,);
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _endian: Endian, (flexible,): Self::Args<'_>) -> BinResult<()> {
+        write_string_length(writer, flexible, self.0.as_ref().map(String::len))?;
+        if let Some(string) = &self.0 {
+            writer.write_all(string.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl Deref for KafkaNullableString {
+    type Target = Option<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Option<String>> for KafkaNullableString {
+    fn from(value: Option<String>) -> Self {
+        Self(value)
+    }
+}
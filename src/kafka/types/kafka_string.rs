@@ -0,0 +1,85 @@
+use crate::kafka::types::helper::length::{read_bytes, read_string_length, write_string_length};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Seek, Write};
+use std::ops::Deref;
+
+/// A non-null `STRING`, encoded as `COMPACT_STRING` in flexible versions.
+/// The argument is whether the message version is flexible.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub(crate) struct KafkaString(pub(crate) String);
+
+impl BinRead for KafkaString {
+    type Args<'a> = (bool,);
+
+    fn read_options<R: Read + Seek>(reader: &mut R, _endian: Endian, (flexible,): Self::Args<'_>) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let Some(length) = read_string_length(reader, flexible)? else {
+            return Err(binrw::Error::AssertFail { pos, message: "String must not be null".to_owned() });
+        };
+        let string = String::from_utf8(read_bytes(reader, length)?)
+            .map_err(|err| binrw::Error::Custom { pos, err: Box::new(err) })?;
+        Ok(Self(string))
+    }
+}
+
+impl BinWrite for KafkaString {
+    type Args<'a> = (bool,);
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _endian: Endian, (flexible,): Self::Args<'_>) -> BinResult<()> {
+        write_string_length(writer, flexible, Some(self.0.len()))?;
+        writer.write_all(self.0.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl Deref for KafkaString {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for KafkaString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for KafkaString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for KafkaString {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_encoding_follows_flexibility() {
+        for (flexible, expected) in [(false, vec![0, 2, b'h', b'i']), (true, vec![3, b'h', b'i'])] {
+            let mut writer = Cursor::new(Vec::new());
+            KafkaString::from("hi").write_options(&mut writer, Endian::Big, (flexible,)).unwrap();
+            assert_eq!(writer.get_ref(), &expected);
+
+            let mut reader = Cursor::new(expected);
+            let read = KafkaString::read_options(&mut reader, Endian::Big, (flexible,)).unwrap();
+            assert_eq!(*read, "hi");
+        }
+    }
+
+    #[test]
+    fn test_null_is_rejected() {
+        let mut reader = Cursor::new(vec![0xFF, 0xFF]);
+        assert!(KafkaString::read_options(&mut reader, Endian::Big, (false,)).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use crate::kafka::types::ApiKey;
+
+/// The version a message is encoded with. Flexible versions (KIP-482) use
+/// compact strings and arrays and carry tagged fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MessageVersion {
+    pub(crate) version: i16,
+    pub(crate) flexible: bool,
+}
+
+impl MessageVersion {
+    pub(crate) fn new(api_key: ApiKey, version: i16) -> Self {
+        Self { version, flexible: version >= api_key.first_flexible_version() }
+    }
+}
@@ -1,6 +1,53 @@
-use binrw::binrw;
+use crate::kafka::types::helper::length::read_bytes;
+use crate::kafka::types::UnsignedVarInt;
+use binrw::meta::{EndianKind, ReadEndian, WriteEndian};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::io::{Read, Seek, Write};
 
-#[binrw]
-#[brw(big, magic = b"\x00")]
-#[derive(Debug, Clone, Copy, Default)]
-pub(crate) struct TagBuffer; // TODO let's assume they are empty for now
\ No newline at end of file
+/// The tagged fields section of a flexible message. Tagged fields this
+/// broker does not understand are skipped on read, none are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TagBuffer;
+
+impl BinRead for TagBuffer {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(reader: &mut R, _endian: Endian, _args: Self::Args<'_>) -> BinResult<Self> {
+        let count = *UnsignedVarInt::read(reader)?;
+        for _ in 0..count {
+            let _tag = UnsignedVarInt::read(reader)?;
+            let size = *UnsignedVarInt::read(reader)?;
+            read_bytes(reader, size as usize)?;
+        }
+        Ok(Self)
+    }
+}
+
+impl BinWrite for TagBuffer {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _endian: Endian, _args: Self::Args<'_>) -> BinResult<()> {
+        UnsignedVarInt(0).write(writer)
+    }
+}
+
+impl ReadEndian for TagBuffer {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+impl WriteEndian for TagBuffer {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_unknown_tags_are_skipped() {
+        let mut reader = Cursor::new(vec![2, 0, 1, 0xAA, 5, 2, 0xBB, 0xCC, 0x42]);
+        TagBuffer::read(&mut reader).unwrap();
+        assert_eq!(u8::read(&mut reader).unwrap(), 0x42);
+    }
+}
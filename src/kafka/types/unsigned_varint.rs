@@ -1,4 +1,3 @@
-use std::cmp::Ordering;
 use binrw::meta::{EndianKind, ReadEndian, WriteEndian};
 use binrw::{BinRead, BinResult, BinWrite, Endian};
 use std::fmt::Debug;
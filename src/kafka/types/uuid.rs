@@ -0,0 +1,73 @@
+use binrw::binrw;
+use std::fmt::{Display, Formatter};
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A 128-bit UUID, written as 16 raw bytes.
+#[binrw]
+#[brw(big)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub(crate) struct Uuid(pub(crate) [u8; 16]);
+
+impl Uuid {
+    /// A random version 4 UUID.
+    pub(crate) fn random() -> Self {
+        let mut bytes = fastrand::u128(..).to_be_bytes();
+        bytes[6] = (bytes[6] & 0x0F) | 0x40;
+        bytes[8] = (bytes[8] & 0x3F) | 0x80;
+        Self(bytes)
+    }
+
+    /// The URL-safe, unpadded base64 form Kafka prints topic ids and
+    /// generated member ids in.
+    pub(crate) fn to_base64(self) -> String {
+        let mut encoded = String::with_capacity(22);
+        for chunk in self.0.chunks(3) {
+            let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, byte)| bits | (*byte as u32) << (16 - 8 * i));
+            for i in 0..=chunk.len() {
+                encoded.push(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+            }
+        }
+        encoded
+    }
+
+    /// Parses the form written by [`Self::to_base64`].
+    pub(crate) fn from_base64(encoded: &str) -> Option<Self> {
+        if encoded.len() != 22 {
+            return None;
+        }
+        let mut bits = 0u128;
+        for (i, c) in encoded.bytes().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|&symbol| symbol == c)? as u128;
+            // 22 symbols carry 132 bits, the last 4 of which are padding.
+            bits = if i < 21 { bits << 6 | value } else { bits << 2 | value >> 4 };
+        }
+        Some(Self(bits.to_be_bytes()))
+    }
+}
+
+impl Display for Uuid {
+    /// The hyphenated form, as used in generated member ids.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                f.write_str("-")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_round_trip() {
+        let uuid = Uuid(*b"\x01\x23\x45\x67\x89\xab\xcd\xef\xfe\xdc\xba\x98\x76\x54\x32\x10");
+        assert_eq!(uuid.to_base64(), "ASNFZ4mrze_-3LqYdlQyEA");
+        assert_eq!(Uuid::from_base64("ASNFZ4mrze_-3LqYdlQyEA"), Some(uuid));
+        assert_eq!(Uuid::from_base64("not a uuid"), None);
+    }
+}
@@ -0,0 +1,156 @@
+use binrw::meta::{EndianKind, ReadEndian, WriteEndian};
+use binrw::{BinRead, BinResult, BinWrite, Endian};
+use std::io::{Read, Seek, Write};
+use std::ops::Deref;
+
+/// Zigzag encoded signed 32-bit varint, as used inside record batches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct VarInt(pub(crate) i32);
+
+/// Zigzag encoded signed 64-bit varint, as used inside record batches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct VarLong(pub(crate) i64);
+
+fn read_unsigned<R: Read + Seek>(reader: &mut R, max_bits: u32) -> BinResult<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+
+    loop {
+        let byte = u8::read(reader)?;
+        value |= ((byte & 0b0_1111111) as u64) << shift;
+
+        if byte & 0b1_0000000 == 0 {
+            return Ok(value);
+        }
+
+        shift += 7;
+        if shift >= max_bits {
+            return Err(binrw::Error::Custom {
+                pos: reader.stream_position()?,
+                err: Box::new("Varint is too long"),
+            });
+        }
+    }
+}
+
+fn write_unsigned<W: Write>(writer: &mut W, mut value: u64) -> BinResult<()> {
+    loop {
+        let mut byte = (value & 0b0_1111111) as u8;
+        value >>= 7;
+
+        if value != 0 {
+            byte |= 0b1_0000000;
+        }
+
+        writer.write_all(&[byte])?;
+
+        if value == 0 {
+            return Ok(());
+        }
+    }
+}
+
+impl BinRead for VarInt {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(reader: &mut R, _endian: Endian, _args: Self::Args<'_>) -> BinResult<Self> {
+        let raw = read_unsigned(reader, 35)? as u32;
+        Ok(Self(((raw >> 1) as i32) ^ -((raw & 1) as i32)))
+    }
+}
+
+impl BinWrite for VarInt {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _endian: Endian, _args: Self::Args<'_>) -> BinResult<()> {
+        write_unsigned(writer, ((self.0 << 1) ^ (self.0 >> 31)) as u32 as u64)
+    }
+}
+
+impl BinRead for VarLong {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(reader: &mut R, _endian: Endian, _args: Self::Args<'_>) -> BinResult<Self> {
+        let raw = read_unsigned(reader, 70)?;
+        Ok(Self(((raw >> 1) as i64) ^ -((raw & 1) as i64)))
+    }
+}
+
+impl BinWrite for VarLong {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(&self, writer: &mut W, _endian: Endian, _args: Self::Args<'_>) -> BinResult<()> {
+        write_unsigned(writer, ((self.0 << 1) ^ (self.0 >> 63)) as u64)
+    }
+}
+
+impl ReadEndian for VarInt {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+impl WriteEndian for VarInt {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+impl ReadEndian for VarLong {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+impl WriteEndian for VarLong {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+impl Deref for VarInt {
+    type Target = i32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Deref for VarLong {
+    type Target = i64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_zigzag_read_write() {
+        let test_cases = [
+            (0, vec![0x00]),
+            (-1, vec![0x01]),
+            (1, vec![0x02]),
+            (-64, vec![0x7f]),
+            (64, vec![0x80, 0x01]),
+            (i32::MAX, vec![0xfe, 0xff, 0xff, 0xff, 0x0f]),
+            (i32::MIN, vec![0xff, 0xff, 0xff, 0xff, 0x0f]),
+        ];
+
+        for (number, expected_bytes) in test_cases {
+            let mut writer = Cursor::new(Vec::new());
+            VarInt(number).write(&mut writer).unwrap();
+            assert_eq!(writer.into_inner(), expected_bytes);
+
+            let mut reader = Cursor::new(expected_bytes);
+            assert_eq!(*VarInt::read(&mut reader).unwrap(), number);
+        }
+    }
+
+    #[test]
+    fn test_varlong_extremes() {
+        for number in [i64::MIN, -1, 0, 1, i64::MAX] {
+            let mut writer = Cursor::new(Vec::new());
+            VarLong(number).write(&mut writer).unwrap();
+
+            let mut reader = Cursor::new(writer.into_inner());
+            assert_eq!(*VarLong::read(&mut reader).unwrap(), number);
+        }
+    }
+}
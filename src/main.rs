@@ -1,101 +1,614 @@
 mod kafka;
 
+use crate::kafka::broker::{Broker, RequestChannel, RequestContext, RequestLog};
 use crate::kafka::codec::KafkaCodec;
-use crate::kafka::proto::ApiVersionsResponse;
-use crate::kafka::request::generic_request::{KafkaRequest, KafkaRequestHeader};
-use crate::kafka::response::KafkaResponseHeaderV0;
-use crate::kafka::response::{KafkaGenericResponse};
+use crate::kafka::config::{DynamicConfigs, Endpoint, LogFormat, ServerConfig};
+use crate::kafka::coordinator::{GroupCoordinator, ProducerIdManager, TransactionCoordinator};
+use crate::kafka::metadata::{BrokerHeartbeatParams, ClusterControl, MetadataEpoch, MetadataImage, RegisterBrokerParams};
+use crate::kafka::metrics::MetricsExporter;
+use crate::kafka::quota::ConnectionSlot;
+use crate::kafka::security::{peer_principal, CredentialStore, SaslSession, SecurityProtocol, TlsContext};
 use crate::kafka::types::ApiKey;
+use crate::kafka::storage::{LogCleaner, LogManager};
+use anyhow::Context;
 use futures::SinkExt;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::{Builder, Handle, Runtime};
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
 use tokio_stream::StreamExt;
 use tokio_util::codec::Framed;
 use tracing::level_filters::LevelFilter;
-use tracing::{error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 use tracing_subscriber::EnvFilter;
 
-#[tokio::main(flavor = "current_thread")]
-async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::builder()
-                .with_default_directive(LevelFilter::INFO.into())
-                .from_env_lossy()
-        )
-        .init();
+fn main() -> anyhow::Result<()> {
+    let config_path = std::env::args().nth(1).map(PathBuf::from);
+    let config = ServerConfig::load(config_path.as_deref())?;
 
-    let listener = TcpListener::bind("127.0.0.1:9092").await?;
-    info!("Listening on: {}", listener.local_addr()?);
+    let subscriber = tracing_subscriber::fmt().with_env_filter(
+        EnvFilter::builder()
+            .with_default_directive(LevelFilter::INFO.into())
+            .from_env_lossy()
+    );
+    match config.logging_format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber.json().flatten_event(true).init(),
+    }
+
+    let (request_handlers, network) = runtimes(&config)?;
+    network.block_on(run(config, request_handlers.handle().clone()))
+}
+
+/// The request handler and network runtimes. Connections are served by the
+/// network threads, requests handled and logs written by the request handler
+/// threads.
+fn runtimes(config: &ServerConfig) -> std::io::Result<(Runtime, Runtime)> {
+    let request_handlers = Builder::new_multi_thread()
+        .worker_threads(config.num_io_threads)
+        .thread_name("kafka-request-handler")
+        .enable_all()
+        .build()?;
+    let network = Builder::new_multi_thread()
+        .worker_threads(config.num_network_threads)
+        .thread_name("kafka-network-thread")
+        .enable_all()
+        .build()?;
+    Ok((request_handlers, network))
+}
+
+async fn run(config: ServerConfig, request_handlers: Handle) -> anyhow::Result<()> {
+    let log_manager = Arc::new(LogManager::startup(config.clone())?);
+    info!(partitions = log_manager.len(), "Log recovery complete");
+    let metadata_epoch = MetadataEpoch::start(&config, &log_manager).context("starting the metadata epoch")?;
+    info!(leader = metadata_epoch.leader_id(), epoch = metadata_epoch.epoch(), "Started metadata log epoch");
+
+    let metadata = MetadataImage::load(&log_manager)?;
+    let dynamic_configs =
+        DynamicConfigs::new(config.clone(), metadata.topic_configs().clone(), metadata.broker_configs().clone());
+    log_manager.update_default_log_config(&dynamic_configs.log_config_overrides());
+    for (topic, overrides) in metadata.topic_configs() {
+        log_manager.update_topic_config(topic, overrides);
+    }
+    request_handlers.spawn(log_manager.clone().run_retention());
+    if config.log_cleaner_enable {
+        LogCleaner::new(log_manager.clone(), &config).start()?;
+    }
+
+    let group_coordinator = Arc::new(GroupCoordinator::new(config.clone(), log_manager.clone()));
+    group_coordinator.load()?;
+    request_handlers.spawn(group_coordinator.clone().run());
+
+    let producer_id_manager = ProducerIdManager::load(&config.log_dirs[0])?;
+    let transaction_coordinator = Arc::new(TransactionCoordinator::new(
+        config.clone(),
+        log_manager.clone(),
+        group_coordinator.clone(),
+        producer_id_manager,
+    ));
+    transaction_coordinator.load()?;
+    request_handlers.spawn(transaction_coordinator.clone().run());
 
+    let mut credentials = match &config.sasl_credentials_file {
+        Some(path) => CredentialStore::load(path)?,
+        None => CredentialStore::default(),
+    };
+    for ((mechanism, username), credential) in metadata.scram_credentials() {
+        credentials.set_scram_credential(*mechanism, username.clone(), credential.clone());
+    }
+
+    let broker = Arc::new(Broker::new(
+        config.clone(),
+        log_manager.clone(),
+        group_coordinator.clone(),
+        transaction_coordinator,
+        credentials,
+        dynamic_configs,
+        &metadata,
+    ));
+    let cluster_control = broker.cluster_control().clone();
+    let broker_epoch = cluster_control
+        .register(RegisterBrokerParams::local(&config), Instant::now())
+        .context("registering the broker")?;
+    // A combined node has caught up with the metadata log once it has been
+    // replayed above, so its first heartbeat unfences it.
+    send_heartbeat(&cluster_control, config.node_id, broker_epoch, false).context("unfencing the broker")?;
+    request_handlers.spawn(cluster_control.clone().run());
+    let heartbeat_interval = Duration::from_millis(config.broker_heartbeat_interval_ms);
+    let heartbeats = send_heartbeats(cluster_control.clone(), config.node_id, broker_epoch, heartbeat_interval);
+    let heartbeats = request_handlers.spawn(heartbeats);
+
+    let requests =
+        RequestChannel::start(broker.clone(), config.queued_max_requests, config.num_io_threads, &request_handlers);
+
+    if let Some(address) = config.metrics_bind_address() {
+        let listener = TcpListener::bind(address).await?;
+        info!("Serving metrics on: http://{}/metrics", listener.local_addr()?);
+        let metrics = broker.metrics().clone();
+        let exporter = MetricsExporter::new(metrics, log_manager.clone(), group_coordinator, request_handlers.clone());
+        let exporter = Arc::new(exporter);
+        tokio::spawn(exporter.run(listener));
+    }
+
+    let protocols: Vec<_> = config
+        .listeners
+        .iter()
+        .map(|endpoint| config.security_protocol(&endpoint.listener_name).expect("listener protocols are validated"))
+        .collect();
+    let tls = if protocols.iter().any(|protocol| protocol.is_tls()) {
+        let tls = Arc::new(TlsContext::load(&config)?);
+        tokio::spawn(tls.clone().run());
+        Some(tls)
+    } else {
+        None
+    };
+
+    // Every listener and connection holds a receiver, so the sender closes
+    // once all of them are done.
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut acceptors = JoinSet::new();
+    for (endpoint, protocol) in config.listeners.iter().zip(protocols) {
+        let listener = TcpListener::bind(endpoint.bind_address()).await?;
+        info!(listener = endpoint.listener_name, ?protocol, "Listening on: {}", listener.local_addr()?);
+        let tls = tls.clone().filter(|_| protocol.is_tls());
+        let (endpoint, broker, requests, shutdown) =
+            (endpoint.clone(), broker.clone(), requests.clone(), shutdown_rx.clone());
+        acceptors.spawn(accept_connections(listener, endpoint, protocol, tls, broker, requests, shutdown));
+    }
+    drop(shutdown_rx);
+
+    let mut result = tokio::select! {
+        signal = shutdown_signal() => {
+            info!(signal = signal?, "Shutting down");
+            Ok(())
+        }
+        Some(accepted) = acceptors.join_next() => {
+            error!("Stopped accepting connections, shutting down");
+            accepted.map_err(anyhow::Error::from).and_then(|result| result)
+        }
+    };
+
+    // A controlled shutdown: clients are sent elsewhere while the in-flight
+    // requests finish.
+    heartbeats.abort();
+    let fenced = send_heartbeat(&cluster_control, config.node_id, broker_epoch, true).context("fencing the broker");
+    result = result.and(fenced);
+    let drained = drain_connections(&shutdown_tx, Duration::from_millis(config.shutdown_timeout_ms)).await;
+    // Flushed even if draining timed out, the requests still in flight are
+    // lost either way.
+    let flushed = log_manager.shutdown().context("flushing logs on shutdown");
+    result.and(drained).and(flushed)
+}
+
+/// Heartbeats the controller every `interval` as the broker of this node.
+async fn send_heartbeats(cluster_control: Arc<ClusterControl>, broker_id: i32, broker_epoch: i64, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
     loop {
-        let (socket, addr) = listener.accept().await?;
-        info!(client = %addr, "Accepted new connection");
-        tokio::spawn(handle_client(socket, addr));
+        interval.tick().await;
+        if let Err(err) = send_heartbeat(&cluster_control, broker_id, broker_epoch, false) {
+            error!(error = %err, "Broker heartbeat failed");
+        }
     }
+}
 
+/// Heartbeats the controller in-process. The broker of this node applies
+/// metadata records as they are written, so it is always caught up, and it
+/// only asks to be fenced when it shuts down.
+fn send_heartbeat(
+    cluster_control: &ClusterControl,
+    broker_id: i32,
+    broker_epoch: i64,
+    want_shut_down: bool,
+) -> anyhow::Result<()> {
+    let params = BrokerHeartbeatParams {
+        broker_id,
+        broker_epoch,
+        current_metadata_offset: cluster_control.metadata_end_offset()?,
+        want_fence: false,
+        want_shut_down,
+    };
+    cluster_control.heartbeat(params, Instant::now())?;
     Ok(())
 }
 
-#[instrument(skip(socket))]
-async fn handle_client(socket: TcpStream, addr: SocketAddr) {
-    let mut framed = Framed::new(socket, KafkaCodec);
+/// Stops the listeners and connections, then waits up to `timeout` for the
+/// connections to finish the requests in flight and close.
+async fn drain_connections(shutdown_tx: &watch::Sender<bool>, timeout: Duration) -> anyhow::Result<()> {
+    shutdown_tx.send_replace(true);
+    if tokio::time::timeout(timeout, shutdown_tx.closed()).await.is_err() {
+        // The listeners are gone, so every receiver left is a connection.
+        let connections = shutdown_tx.receiver_count();
+        warn!(connections, "Connections still open after the shutdown timeout");
+        anyhow::bail!("connections still open after shutdown timeout: {connections}");
+    }
+    Ok(())
+}
+
+/// Resolves to the name of the first SIGTERM or SIGINT received.
+async fn shutdown_signal() -> std::io::Result<&'static str> {
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    Ok(tokio::select! {
+        _ = terminate.recv() => "SIGTERM",
+        _ = interrupt.recv() => "SIGINT",
+    })
+}
+
+async fn accept_connections(
+    listener: TcpListener,
+    endpoint: Endpoint,
+    protocol: SecurityProtocol,
+    tls: Option<Arc<TlsContext>>,
+    broker: Arc<Broker>,
+    requests: RequestChannel,
+    mut shutdown: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    loop {
+        let (socket, addr) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = shutdown.changed() => {
+                info!(listener = endpoint.listener_name, "Stopped accepting connections");
+                return Ok(());
+            }
+        };
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis() as i64);
+        let slot = match broker.connection_quotas().acquire(addr.ip(), now_ms) {
+            Ok(slot) => slot,
+            Err(reason) => {
+                warn!(client = %addr, listener = endpoint.listener_name, %reason, "Rejected connection");
+                broker.metrics().connection_rejected(&endpoint.listener_name, reason);
+                continue;
+            }
+        };
+        info!(client = %addr, listener = endpoint.listener_name, "Accepted new connection");
+        let mut context = RequestContext::new(addr, endpoint.listener_name.clone(), protocol.is_sasl());
+        let acceptor = tls.as_ref().map(|tls| tls.acceptor());
+        let (broker, requests, shutdown) = (broker.clone(), requests.clone(), shutdown.clone());
+        tokio::spawn(async move {
+            match acceptor {
+                Some(acceptor) => {
+                    if let Some(stream) = accept_tls(acceptor, socket, &mut context).await {
+                        handle_client(stream, addr, context, slot, broker, requests, shutdown).await;
+                    }
+                }
+                None => handle_client(socket, addr, context, slot, broker, requests, shutdown).await,
+            }
+        });
+    }
+}
+
+/// Completes the TLS handshake before handling requests, `None` if it
+/// failed. Without SASL, the client certificate decides the principal.
+async fn accept_tls(
+    acceptor: TlsAcceptor,
+    socket: TcpStream,
+    context: &mut RequestContext,
+) -> Option<TlsStream<TcpStream>> {
+    let stream = match acceptor.accept(socket).await {
+        Ok(stream) => stream,
+        Err(err) => {
+            warn!(client = %context.client_addr, error = %err, "TLS handshake failed");
+            return None;
+        }
+    };
+    if context.principal.is_some() {
+        context.principal = Some(peer_principal(stream.get_ref().1));
+    }
+    Some(stream)
+}
+
+/// Handles requests one at a time until the client disconnects or stays idle
+/// for `connections.max.idle.ms`. On shutdown the request being handled is
+/// completed and the connection closed. The connection counts against the
+/// connection limits until its slot is dropped on return.
+#[instrument(skip(socket, context, _slot, broker, requests, shutdown))]
+async fn handle_client<S>(
+    socket: S,
+    addr: SocketAddr,
+    mut context: RequestContext,
+    _slot: ConnectionSlot,
+    broker: Arc<Broker>,
+    requests: RequestChannel,
+    mut shutdown: watch::Receiver<bool>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let config = broker.config();
+    let mut framed = Framed::new(socket, KafkaCodec::new(config.socket_request_max_bytes));
     info!(client = %addr, "Client handler spawned");
+    broker.metrics().connection_opened(&context.listener_name);
 
-    while let Some(request) = framed.next().await {
+    let max_idle = Duration::from_millis(config.connections_max_idle_ms);
+    while !*shutdown.borrow() {
+        // Unauthenticated clients must not make the broker buffer large
+        // requests.
+        let max_request_size = match context.principal {
+            Some(_) => config.socket_request_max_bytes,
+            None => config.sasl_server_max_receive_size.min(config.socket_request_max_bytes),
+        };
+        framed.codec_mut().set_max_request_size(max_request_size);
+        let request = tokio::select! {
+            request = tokio::time::timeout(max_idle, framed.next()) => match request {
+                Ok(request) => request,
+                Err(_) => {
+                    info!(client = %addr, "Closing idle connection");
+                    broker.metrics().idle_connection_closed();
+                    break;
+                }
+            },
+            _ = shutdown.changed() => break,
+        };
+        let Some(request) = request else {
+            break;
+        };
         match request {
             Ok(req) => {
-                info!(client = %addr, request = ?req, "Received request");
-
-                let correlation_id = get_correlation_id(&req);
-                let api_version = get_requests_api_version(&req);
-                let api_key = get_requests_api_key(&req);
-                let response = match api_key {
-                    ApiKey::ApiVersions => {
-                        KafkaGenericResponse::new(
-                            KafkaResponseHeaderV0::new(correlation_id),
-                            ApiVersionsResponse::new(api_version)
-                        )
-                    },
-                    _ => {
-                        error!(client = %addr, api_key = ?api_key, "Unhandled API key");
-                        continue;
-                    }
+                debug!(client = %addr, request = ?req, "Received request");
+                let mut request_log = RequestLog::new(&req);
+                let api_key = req.header.api_key();
+                if context.principal.is_none()
+                    && !matches!(api_key, ApiKey::ApiVersions | ApiKey::SaslHandshake | ApiKey::SaslAuthenticate)
+                {
+                    warn!(client = %addr, ?api_key, "Unexpected request before SASL authentication");
+                    break;
+                }
+
+                context.remote_time = Duration::ZERO;
+                let Some(handled) = requests.handle(context.clone(), req).await else {
+                    break;
                 };
-                if let Err(err) = framed.send(response).await {
-                    error!(client = %addr, error = %err, "Failed to send response");
+                context = handled.context;
+                request_log.queue_time = handled.started.duration_since(request_log.received());
+                request_log.remote_time = context.remote_time;
+                request_log.local_time = handled.handling_time.saturating_sub(context.remote_time);
+                if let Some(response) = handled.response {
+                    let sending = Instant::now();
+                    if let Err(err) = framed.send(response).await {
+                        error!(client = %addr, error = %err, "Failed to send response");
+                    }
+                    request_log.send_time = sending.elapsed();
+                    request_log.response_size = framed.codec().last_response_size();
+                }
+                if let Some(muted_until) = context.muted_until.take() {
+                    let throttling = Instant::now();
+                    tokio::time::sleep_until(muted_until.into()).await;
+                    request_log.throttle_time = throttling.elapsed();
+                }
+                request_log.write(&context);
+                if matches!(context.sasl, SaslSession::Failed) {
+                    break;
                 }
             }
             Err(err) => {
                 error!(client = %addr, error = %err, "Error decoding request");
+                broker.metrics().record_decode_error();
                 break;
             }
         }
     }
 
+    broker.metrics().connection_closed(&context.listener_name);
     info!(client = %addr, "Connection closed");
 }
 
-fn get_requests_api_version(request: &KafkaRequest) -> i16 {
-    match &request.header {
-        KafkaRequestHeader::V2(header) => header.request_api_version,
-        KafkaRequestHeader::V1(header) => header.request_api_version,
-        KafkaRequestHeader::V0(header) => header.request_api_version,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::request::generic_request::KafkaRequest;
+    use crate::kafka::storage::{TopicPartition, CLEAN_SHUTDOWN_FILE, RECOVERY_POINT_CHECKPOINT_FILE};
+    use binrw::BinRead;
+    use std::collections::HashMap;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// A broker on its own log directory, with its coordinators running.
+    fn broker(config: &ServerConfig) -> (Arc<LogManager>, Arc<Broker>) {
+        let log_manager = Arc::new(LogManager::startup(config.clone()).unwrap());
+        let group_coordinator = Arc::new(GroupCoordinator::new(config.clone(), log_manager.clone()));
+        tokio::spawn(group_coordinator.clone().run());
+        let producer_id_manager = ProducerIdManager::load(&config.log_dirs[0]).unwrap();
+        let transaction_coordinator = Arc::new(TransactionCoordinator::new(
+            config.clone(),
+            log_manager.clone(),
+            group_coordinator.clone(),
+            producer_id_manager,
+        ));
+        let broker = Broker::new(
+            config.clone(),
+            log_manager.clone(),
+            group_coordinator,
+            transaction_coordinator,
+            CredentialStore::default(),
+            DynamicConfigs::new(config.clone(), HashMap::new(), HashMap::new()),
+            &MetadataImage::load(&log_manager).unwrap(),
+        );
+        (log_manager, Arc::new(broker))
     }
-}
 
-fn get_requests_api_key(request: &KafkaRequest) -> &ApiKey {
-    match &request.header {
-        KafkaRequestHeader::V2(header) => &header.request_api_key,
-        KafkaRequestHeader::V1(header) => &header.request_api_key,
-        KafkaRequestHeader::V0(header) => &header.request_api_key,
+    fn request(framed: Vec<u8>) -> KafkaRequest {
+        KafkaRequest::read_be(&mut std::io::Cursor::new(framed)).unwrap()
+    }
+
+    fn api_versions_request() -> KafkaRequest {
+        request(vec![0, 0, 0, 10, 0, 18, 0, 0, 0, 0, 0, 9, 0, 0])
+    }
+
+    /// A ListOffsets v1 request for the earliest offset of `foo-0`.
+    fn list_offsets_request() -> KafkaRequest {
+        let mut message = vec![0, 2, 0, 1, 0, 0, 0, 7, 0, 1, b'c'];
+        message.extend_from_slice(&(-1i32).to_be_bytes());
+        message.extend_from_slice(&[0, 0, 0, 1, 0, 3, b'f', b'o', b'o', 0, 0, 0, 1, 0, 0, 0, 0]);
+        message.extend_from_slice(&(-2i64).to_be_bytes());
+        request([(message.len() as i32).to_be_bytes().to_vec(), message].concat())
     }
-}
 
-fn get_correlation_id(request: &KafkaRequest) -> i32 {
-    match &request.header {
-        KafkaRequestHeader::V2(header) => header.correlation_id,
-        KafkaRequestHeader::V1(header) => header.correlation_id,
-        KafkaRequestHeader::V0(header) => header.correlation_id,
+    fn context() -> RequestContext {
+        RequestContext::new("127.0.0.1:50000".parse().unwrap(), "PLAINTEXT".to_owned(), false)
     }
-}
\ No newline at end of file
+
+    /// Holds the lock of the log of `foo-0` on another thread until the
+    /// returned sender is dropped, blocking the requests that need it.
+    fn block_log(log_manager: &LogManager) -> std::sync::mpsc::Sender<()> {
+        let log = log_manager.get_or_create_log(&TopicPartition::new("foo", 0)).unwrap();
+        let (locked_tx, locked) = std::sync::mpsc::channel();
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        std::thread::spawn(move || {
+            let _log = log.lock().unwrap();
+            locked_tx.send(()).unwrap();
+            let _ = released.recv();
+        });
+        locked.recv().unwrap();
+        release
+    }
+
+    /// A JoinGroup v0 request for a new member of group `g`, framed.
+    fn join_group_request() -> Vec<u8> {
+        let mut message = vec![0, 11, 0, 0, 0, 0, 0, 5, 0, 3, b'c', b'l', b'i', 0, 1, b'g'];
+        message.extend_from_slice(&10_000i32.to_be_bytes());
+        message.extend_from_slice(&[0, 0, 0, 8]);
+        message.extend_from_slice(b"consumer");
+        message.extend_from_slice(&[0, 0, 0, 1, 0, 5]);
+        message.extend_from_slice(b"range");
+        message.extend_from_slice(&[0, 0, 0, 0]);
+        [(message.len() as i32).to_be_bytes().to_vec(), message].concat()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_shutdown_drains_in_flight_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig {
+            log_dirs: vec![dir.path().to_path_buf()],
+            group_initial_rebalance_delay_ms: 300,
+            ..ServerConfig::default()
+        };
+        let (log_manager, broker) = broker(&config);
+        let requests = RequestChannel::start(broker.clone(), 1, 1, &Handle::current());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let endpoint = Endpoint { listener_name: "PLAINTEXT".to_owned(), host: String::new(), port: addr.port() };
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let protocol = SecurityProtocol::Plaintext;
+        let accepting = accept_connections(listener, endpoint, protocol, None, broker, requests, shutdown_rx);
+        let acceptor = tokio::spawn(accepting);
+
+        // The join waits out the initial rebalance delay in the coordinator.
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client.write_all(&join_group_request()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drain_connections(&shutdown_tx, Duration::from_secs(5)).await.unwrap();
+        acceptor.await.unwrap().unwrap();
+
+        let size = client.read_i32().await.unwrap();
+        let mut response = vec![0; size as usize];
+        client.read_exact(&mut response).await.unwrap();
+        assert_eq!(response[..6], [0, 0, 0, 5, 0, 0]);
+        assert_eq!(client.read(&mut [0; 1]).await.unwrap(), 0);
+
+        log_manager.shutdown().unwrap();
+        let recovery_points = log_manager.read_checkpoints(RECOVERY_POINT_CHECKPOINT_FILE);
+        for (topic_partition, log) in log_manager.logs() {
+            assert_eq!(recovery_points[&topic_partition], log.lock().unwrap().log_end_offset());
+        }
+        assert!(dir.path().join(CLEAN_SHUTDOWN_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn test_drain_times_out_on_open_connections() {
+        let (shutdown_tx, _connection) = watch::channel(false);
+        let error = drain_connections(&shutdown_tx, Duration::from_millis(10)).await.unwrap_err();
+        assert_eq!(error.to_string(), "connections still open after shutdown timeout: 1");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_full_queue_blocks_handle() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig { log_dirs: vec![dir.path().to_path_buf()], ..ServerConfig::default() };
+        let (log_manager, broker) = broker(&config);
+        let requests = RequestChannel::start(broker, 1, 1, &Handle::current());
+        let release = block_log(&log_manager);
+
+        // The only handler is stuck on the log, the dispatcher waits for it
+        // with the next request and the queue holds the one after.
+        let handling = tokio::spawn({
+            let requests = requests.clone();
+            async move { requests.handle(context(), list_offsets_request()).await }
+        });
+        let mut queued = Vec::new();
+        for _ in 0..2 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            queued.push(tokio::spawn({
+                let requests = requests.clone();
+                async move { requests.handle(context(), api_versions_request()).await }
+            }));
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let blocked = requests.handle(context(), api_versions_request());
+        assert!(tokio::time::timeout(Duration::from_millis(200), blocked).await.is_err());
+
+        drop(release);
+        assert!(handling.await.unwrap().unwrap().response.is_some());
+        for queued in queued {
+            assert!(queued.await.unwrap().unwrap().response.is_some());
+        }
+        assert!(requests.handle(context(), api_versions_request()).await.unwrap().response.is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_requests_waiting_on_a_coordinator_free_their_handler() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig {
+            log_dirs: vec![dir.path().to_path_buf()],
+            group_initial_rebalance_delay_ms: 500,
+            ..ServerConfig::default()
+        };
+        let (_log_manager, broker) = broker(&config);
+        let requests = RequestChannel::start(broker, 1, 1, &Handle::current());
+
+        let joining = tokio::spawn({
+            let requests = requests.clone();
+            async move { requests.handle(context(), request(join_group_request())).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let handled = requests.handle(context(), api_versions_request());
+        let handled = tokio::time::timeout(Duration::from_millis(200), handled).await.unwrap();
+        assert!(handled.unwrap().response.is_some());
+        assert!(!joining.is_finished());
+        assert!(joining.await.unwrap().unwrap().context.remote_time >= Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_blocked_request_handlers_leave_network_threads_free() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig {
+            log_dirs: vec![dir.path().to_path_buf()],
+            num_io_threads: 1,
+            num_network_threads: 1,
+            ..ServerConfig::default()
+        };
+        let (request_handlers, network) = runtimes(&config).unwrap();
+        let (log_manager, broker) = request_handlers.block_on(async { broker(&config) });
+        let requests = RequestChannel::start(broker, 1, 1, request_handlers.handle());
+        let release = block_log(&log_manager);
+
+        let handling = network.spawn({
+            let requests = requests.clone();
+            async move { requests.handle(context(), list_offsets_request()).await }
+        });
+        let thread_name = network.block_on(network.spawn(async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            std::thread::current().name().map(str::to_owned)
+        }));
+        assert_eq!(thread_name.unwrap().as_deref(), Some("kafka-network-thread"));
+        assert!(!handling.is_finished());
+
+        drop(release);
+        assert!(network.block_on(handling).unwrap().unwrap().response.is_some());
+    }
+}